@@ -0,0 +1,659 @@
+// Backtesting engine and grid-search / genetic parameter optimization
+
+use std::collections::HashMap;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use crate::data::{MarketData, Price, PricePoint};
+use crate::error::{Result, StrategyError};
+use crate::performance::{CommissionModel, Money, PerformanceMetrics, Portfolio, Trade};
+use crate::strategy::{Action, Strategy, StrategyEngine, StrategyExecutor, StrategyType, TradingSignal};
+
+/// Result of replaying a strategy bar-by-bar over historical data.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BacktestReport {
+    pub strategy_id: String,
+    pub symbol: String,
+    pub metrics: PerformanceMetrics,
+    /// Portfolio value after each bar the strategy produced a signal for.
+    pub equity_curve: Vec<f64>,
+    pub signals: Vec<TradingSignal>,
+    /// The full simulated trade list, in execution order, so a run can be
+    /// persisted and compared (entry/exit time, fill price, and PnL per trade).
+    pub trades: Vec<Trade>,
+}
+
+/// Replays a price series through a `StrategyEngine`, sizing trades as
+/// all-in/all-out on a single position, and reports the resulting performance.
+pub struct Backtester {
+    initial_capital: f64,
+    commission: f64,
+    slippage_percent: f64,
+}
+
+impl Backtester {
+    /// `slippage_percent` worsens every simulated fill price by that
+    /// percentage (e.g. `0.1` for 0.1%), modeling the gap between a
+    /// strategy's signal price and what a real order would actually fill at.
+    pub fn new(initial_capital: f64, commission: f64, slippage_percent: f64) -> Self {
+        Backtester { initial_capital, commission, slippage_percent }
+    }
+
+    /// Worsen a raw signal price by `slippage_percent` in the direction that
+    /// disadvantages the trader: higher fills when buying/covering a short,
+    /// lower fills when selling/entering a short.
+    fn fill_price(&self, action: &Action, price: f64) -> f64 {
+        let slippage = price * self.slippage_percent / 100.0;
+        match action {
+            Action::Buy | Action::ExitShort => price + slippage,
+            Action::ShortSell | Action::Sell => price - slippage,
+            Action::Hold => price,
+        }
+    }
+
+    /// Feed `price_history` bar-by-bar into `strategy`: each bar becomes
+    /// `market_data`, with every preceding bar as `historical_data`. Buy
+    /// signals invest all available capital; Sell signals liquidate the
+    /// entire position. Bars where the strategy can't yet produce a signal
+    /// (insufficient warm-up history) are skipped.
+    pub async fn run(&self, strategy: Strategy, symbol: &str, price_history: &[PricePoint]) -> Result<BacktestReport> {
+        if price_history.len() < 2 {
+            return Err(StrategyError::InsufficientData.into());
+        }
+
+        let engine = StrategyEngine::new(strategy.clone())?;
+        let mut portfolio = Portfolio::new(strategy.id.clone(), self.initial_capital)
+            .with_commission_model(CommissionModel::Fixed(self.commission));
+
+        let mut equity_curve = Vec::new();
+        let mut signals = Vec::new();
+        let mut returns = Vec::new();
+        let mut peak_value = self.initial_capital;
+        let mut max_drawdown_percent = 0.0_f64;
+        let mut previous_value = self.initial_capital;
+
+        for i in 1..price_history.len() {
+            let historical_data = &price_history[..i];
+            let bar = &price_history[i];
+            let market_data = MarketData::new(symbol.to_string(), bar.close.to_f64(), bar.volume);
+
+            let signal = match engine.execute(&market_data, historical_data).await {
+                Ok(signal) => signal,
+                Err(_) => continue,
+            };
+
+            self.apply_signal(&mut portfolio, &strategy.id, symbol, &signal)?;
+
+            let mut prices = HashMap::new();
+            prices.insert(symbol.to_string(), bar.close.to_f64());
+            portfolio.update_position_prices(&prices)?;
+
+            let current_value = portfolio.total_value()?.to_f64();
+            equity_curve.push(current_value);
+            signals.push(signal);
+
+            if previous_value > 0.0 {
+                returns.push((current_value - previous_value) / previous_value);
+            }
+            previous_value = current_value;
+
+            if current_value > peak_value {
+                peak_value = current_value;
+            }
+            let drawdown_percent = if peak_value > 0.0 {
+                (peak_value - current_value) / peak_value * 100.0
+            } else {
+                0.0
+            };
+            if drawdown_percent > max_drawdown_percent {
+                max_drawdown_percent = drawdown_percent;
+            }
+        }
+
+        let mut metrics = PerformanceMetrics::new(strategy.id.clone(), self.initial_capital);
+        metrics.update_from_trades(&portfolio.trade_history)?;
+        metrics.max_drawdown_percent = max_drawdown_percent;
+        metrics.max_drawdown = Money::from_f64(peak_value * max_drawdown_percent / 100.0);
+        metrics.calculate_sharpe_ratio(&returns, 0.0);
+
+        Ok(BacktestReport {
+            strategy_id: strategy.id,
+            symbol: symbol.to_string(),
+            metrics,
+            equity_curve,
+            signals,
+            trades: portfolio.trade_history,
+        })
+    }
+
+    fn apply_signal(&self, portfolio: &mut Portfolio, strategy_id: &str, symbol: &str, signal: &TradingSignal) -> Result<()> {
+        let fill_price = self.fill_price(&signal.action, signal.price);
+        match signal.action {
+            Action::Buy => {
+                let already_holding = portfolio.positions.get(symbol).is_some_and(|p| !p.is_empty());
+                if already_holding {
+                    return Ok(());
+                }
+                let available = (portfolio.current_capital.to_f64() - self.commission).max(0.0);
+                if available <= 0.0 {
+                    return Ok(());
+                }
+                let quantity = available / fill_price;
+                let trade = Trade::new(strategy_id.to_string(), symbol.to_string(), Action::Buy, quantity, fill_price, signal.explanation.clone(), self.commission);
+                portfolio.execute_trade(trade)?;
+            }
+            Action::Sell => {
+                let shares = portfolio.positions.get(symbol).map(|p| p.shares).unwrap_or(0.0);
+                if shares <= 0.0 {
+                    return Ok(());
+                }
+                let trade = Trade::new(strategy_id.to_string(), symbol.to_string(), Action::Sell, shares, fill_price, signal.explanation.clone(), self.commission);
+                portfolio.execute_trade(trade)?;
+            }
+            Action::ShortSell => {
+                let already_short = portfolio.positions.get(symbol).is_some_and(|p| !p.is_empty());
+                if already_short {
+                    return Ok(());
+                }
+                let available = (portfolio.current_capital.to_f64() - self.commission).max(0.0);
+                if available <= 0.0 {
+                    return Ok(());
+                }
+                let quantity = available / fill_price;
+                let trade = Trade::new(strategy_id.to_string(), symbol.to_string(), Action::ShortSell, quantity, fill_price, signal.explanation.clone(), self.commission);
+                portfolio.execute_trade(trade)?;
+            }
+            Action::ExitShort => {
+                let shares = portfolio.positions.get(symbol).map(|p| -p.shares).unwrap_or(0.0);
+                if shares <= 0.0 {
+                    return Ok(());
+                }
+                let trade = Trade::new(strategy_id.to_string(), symbol.to_string(), Action::ExitShort, shares, fill_price, signal.explanation.clone(), self.commission);
+                portfolio.execute_trade(trade)?;
+            }
+            Action::Hold => {}
+        }
+        Ok(())
+    }
+}
+
+/// Candidate values for each field of a `StrategyType`, mirroring its
+/// variants so a grid search can take the Cartesian product per-variant.
+#[derive(Debug, Clone)]
+pub enum ParameterGrid {
+    PriceDrop {
+        threshold: Vec<f64>,
+    },
+    MovingAverage {
+        short_period: Vec<usize>,
+        long_period: Vec<usize>,
+    },
+    RSI {
+        oversold: Vec<f64>,
+        overbought: Vec<f64>,
+        period: Vec<Option<usize>>,
+    },
+    Confluence {
+        short_period: Vec<usize>,
+        long_period: Vec<usize>,
+        rsi_oversold: Vec<f64>,
+        rsi_overbought: Vec<f64>,
+        stoch_period: Vec<usize>,
+        stoch_oversold: Vec<f64>,
+        stoch_overbought: Vec<f64>,
+    },
+}
+
+impl ParameterGrid {
+    /// Expand this grid into every `StrategyType` combination it describes.
+    pub fn combinations(&self) -> Vec<StrategyType> {
+        match self {
+            ParameterGrid::PriceDrop { threshold } => threshold
+                .iter()
+                .map(|&threshold| StrategyType::PriceDrop { threshold })
+                .collect(),
+            ParameterGrid::MovingAverage { short_period, long_period } => {
+                let mut combinations = Vec::new();
+                for &short_period in short_period {
+                    for &long_period in long_period {
+                        combinations.push(StrategyType::MovingAverage { short_period, long_period });
+                    }
+                }
+                combinations
+            }
+            ParameterGrid::RSI { oversold, overbought, period } => {
+                let mut combinations = Vec::new();
+                for &oversold in oversold {
+                    for &overbought in overbought {
+                        for &period in period {
+                            combinations.push(StrategyType::RSI { oversold, overbought, period });
+                        }
+                    }
+                }
+                combinations
+            }
+            ParameterGrid::Confluence {
+                short_period,
+                long_period,
+                rsi_oversold,
+                rsi_overbought,
+                stoch_period,
+                stoch_oversold,
+                stoch_overbought,
+            } => {
+                let mut combinations = Vec::new();
+                for &short_period in short_period {
+                    for &long_period in long_period {
+                        for &rsi_oversold in rsi_oversold {
+                            for &rsi_overbought in rsi_overbought {
+                                for &stoch_period in stoch_period {
+                                    for &stoch_oversold in stoch_oversold {
+                                        for &stoch_overbought in stoch_overbought {
+                                            combinations.push(StrategyType::Confluence {
+                                                short_period,
+                                                long_period,
+                                                rsi_oversold,
+                                                rsi_overbought,
+                                                stoch_period,
+                                                stoch_oversold,
+                                                stoch_overbought,
+                                            });
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                combinations
+            }
+        }
+    }
+}
+
+/// The metric a grid search should maximize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    TotalReturn,
+    WinRate,
+    SharpeRatio,
+}
+
+impl Objective {
+    fn score(&self, report: &BacktestReport) -> f64 {
+        match self {
+            Objective::TotalReturn => report.metrics.total_return.to_f64(),
+            Objective::WinRate => report.metrics.win_rate,
+            Objective::SharpeRatio => report.metrics.sharpe_ratio.unwrap_or(f64::MIN),
+        }
+    }
+}
+
+/// Best parameter combination found by a grid search, alongside its report.
+#[derive(Debug, Clone)]
+pub struct OptimizationResult {
+    pub best_strategy_type: StrategyType,
+    pub best_report: BacktestReport,
+    pub objective: Objective,
+    pub combinations_evaluated: usize,
+}
+
+/// Runs a `Backtester` across the Cartesian product of a `ParameterGrid`
+/// and keeps the combination that scores highest on the chosen `Objective`.
+pub struct Optimizer {
+    backtester: Backtester,
+}
+
+impl Optimizer {
+    pub fn new(backtester: Backtester) -> Self {
+        Optimizer { backtester }
+    }
+
+    pub async fn grid_search(
+        &self,
+        symbol: &str,
+        price_history: &[PricePoint],
+        grid: ParameterGrid,
+        objective: Objective,
+    ) -> Result<OptimizationResult> {
+        let candidates = grid.combinations();
+        if candidates.is_empty() {
+            return Err(StrategyError::invalid_parameters("Parameter grid produced no candidates").into());
+        }
+
+        let mut best: Option<(StrategyType, BacktestReport, f64)> = None;
+        let mut combinations_evaluated = 0;
+
+        for strategy_type in candidates {
+            let strategy = match Strategy::new(strategy_type.clone(), symbol.to_string()) {
+                Ok(strategy) => strategy,
+                Err(_) => continue,
+            };
+
+            let report = match self.backtester.run(strategy, symbol, price_history).await {
+                Ok(report) => report,
+                Err(_) => continue,
+            };
+
+            combinations_evaluated += 1;
+            let score = objective.score(&report);
+            let is_better = best.as_ref().map_or(true, |(_, _, best_score)| score > *best_score);
+            if is_better {
+                best = Some((strategy_type, report, score));
+            }
+        }
+
+        let (best_strategy_type, best_report, _) = best
+            .ok_or_else(|| StrategyError::invalid_parameters("No parameter combination produced a valid backtest"))?;
+
+        Ok(OptimizationResult {
+            best_strategy_type,
+            best_report,
+            objective,
+            combinations_evaluated,
+        })
+    }
+}
+
+/// Inclusive min/max bounds an individual's parameters are drawn from and
+/// clamped to while evolving under a `GeneticOptimizer`.
+#[derive(Debug, Clone)]
+pub enum ParamRanges {
+    MovingAverage {
+        short_period: (usize, usize),
+        long_period: (usize, usize),
+    },
+    RSI {
+        oversold: (f64, f64),
+        overbought: (f64, f64),
+        period: (usize, usize),
+    },
+    MACD {
+        fast_period: (usize, usize),
+        slow_period: (usize, usize),
+        signal_period: (usize, usize),
+    },
+}
+
+impl ParamRanges {
+    fn random_individual(&self, rng: &mut StdRng) -> StrategyType {
+        match self {
+            ParamRanges::MovingAverage { short_period, long_period } => StrategyType::MovingAverage {
+                short_period: rng.gen_range(short_period.0..=short_period.1),
+                long_period: rng.gen_range(long_period.0..=long_period.1),
+            },
+            ParamRanges::RSI { oversold, overbought, period } => StrategyType::RSI {
+                oversold: rng.gen_range(oversold.0..=oversold.1),
+                overbought: rng.gen_range(overbought.0..=overbought.1),
+                period: Some(rng.gen_range(period.0..=period.1)),
+            },
+            ParamRanges::MACD { fast_period, slow_period, signal_period } => StrategyType::MACD {
+                fast_period: rng.gen_range(fast_period.0..=fast_period.1),
+                slow_period: rng.gen_range(slow_period.0..=slow_period.1),
+                signal_period: rng.gen_range(signal_period.0..=signal_period.1),
+            },
+        }
+    }
+
+    /// Single-point crossover: swap one parameter field between two parents,
+    /// keeping the rest from `a`.
+    fn crossover(&self, a: &StrategyType, b: &StrategyType, rng: &mut StdRng) -> StrategyType {
+        match (a, b) {
+            (
+                StrategyType::MovingAverage { short_period: a_short, long_period: a_long },
+                StrategyType::MovingAverage { short_period: b_short, long_period: b_long },
+            ) => {
+                if rng.gen_bool(0.5) {
+                    StrategyType::MovingAverage { short_period: *b_short, long_period: *a_long }
+                } else {
+                    StrategyType::MovingAverage { short_period: *a_short, long_period: *b_long }
+                }
+            }
+            (
+                StrategyType::RSI { oversold: a_oversold, overbought: a_overbought, period: a_period },
+                StrategyType::RSI { oversold: b_oversold, overbought: b_overbought, period: b_period },
+            ) => match rng.gen_range(0..3) {
+                0 => StrategyType::RSI { oversold: *b_oversold, overbought: *a_overbought, period: *a_period },
+                1 => StrategyType::RSI { oversold: *a_oversold, overbought: *b_overbought, period: *a_period },
+                _ => StrategyType::RSI { oversold: *a_oversold, overbought: *a_overbought, period: *b_period },
+            },
+            (
+                StrategyType::MACD { fast_period: a_fast, slow_period: a_slow, signal_period: a_signal },
+                StrategyType::MACD { fast_period: b_fast, slow_period: b_slow, signal_period: b_signal },
+            ) => match rng.gen_range(0..3) {
+                0 => StrategyType::MACD { fast_period: *b_fast, slow_period: *a_slow, signal_period: *a_signal },
+                1 => StrategyType::MACD { fast_period: *a_fast, slow_period: *b_slow, signal_period: *a_signal },
+                _ => StrategyType::MACD { fast_period: *a_fast, slow_period: *a_slow, signal_period: *b_signal },
+            },
+            _ => a.clone(),
+        }
+    }
+
+    /// Perturb a single parameter field of `individual` to a fresh value
+    /// within its valid range.
+    fn mutate(&self, individual: &StrategyType, rng: &mut StdRng) -> StrategyType {
+        match (self, individual) {
+            (
+                ParamRanges::MovingAverage { short_period, long_period },
+                StrategyType::MovingAverage { short_period: s, long_period: l },
+            ) => {
+                if rng.gen_bool(0.5) {
+                    StrategyType::MovingAverage { short_period: rng.gen_range(short_period.0..=short_period.1), long_period: *l }
+                } else {
+                    StrategyType::MovingAverage { short_period: *s, long_period: rng.gen_range(long_period.0..=long_period.1) }
+                }
+            }
+            (
+                ParamRanges::RSI { oversold, overbought, period },
+                StrategyType::RSI { oversold: o, overbought: ob, period: p },
+            ) => match rng.gen_range(0..3) {
+                0 => StrategyType::RSI { oversold: rng.gen_range(oversold.0..=oversold.1), overbought: *ob, period: *p },
+                1 => StrategyType::RSI { oversold: *o, overbought: rng.gen_range(overbought.0..=overbought.1), period: *p },
+                _ => StrategyType::RSI { oversold: *o, overbought: *ob, period: Some(rng.gen_range(period.0..=period.1)) },
+            },
+            (
+                ParamRanges::MACD { fast_period, slow_period, signal_period },
+                StrategyType::MACD { fast_period: f, slow_period: s, signal_period: sig },
+            ) => match rng.gen_range(0..3) {
+                0 => StrategyType::MACD { fast_period: rng.gen_range(fast_period.0..=fast_period.1), slow_period: *s, signal_period: *sig },
+                1 => StrategyType::MACD { fast_period: *f, slow_period: rng.gen_range(slow_period.0..=slow_period.1), signal_period: *sig },
+                _ => StrategyType::MACD { fast_period: *f, slow_period: *s, signal_period: rng.gen_range(signal_period.0..=signal_period.1) },
+            },
+            _ => individual.clone(),
+        }
+    }
+}
+
+/// Evolves a population of `StrategyType` parameter sets against a
+/// `Backtester`: each generation scores every individual's Sharpe ratio,
+/// carries the fittest individual forward unchanged (elitism), and fills
+/// the rest of the next generation from tournament-selected parents
+/// combined with single-point crossover and occasional mutation.
+pub struct GeneticOptimizer {
+    backtester: Backtester,
+    rng: StdRng,
+    crossover_rate: f64,
+    mutation_rate: f64,
+}
+
+impl GeneticOptimizer {
+    pub fn new(backtester: Backtester) -> Self {
+        GeneticOptimizer { backtester, rng: StdRng::from_entropy(), crossover_rate: 0.7, mutation_rate: 0.1 }
+    }
+
+    /// Construct a `GeneticOptimizer` seeded for deterministic testing.
+    pub fn new_with_seed(backtester: Backtester, seed: u64) -> Self {
+        GeneticOptimizer { backtester, rng: StdRng::seed_from_u64(seed), crossover_rate: 0.7, mutation_rate: 0.1 }
+    }
+
+    async fn evaluate(&self, strategy_type: StrategyType, symbol: &str, price_history: &[PricePoint]) -> Option<(StrategyType, BacktestReport, f64)> {
+        let strategy = Strategy::new(strategy_type.clone(), symbol.to_string()).ok()?;
+        let report = self.backtester.run(strategy, symbol, price_history).await.ok()?;
+        let fitness = report.metrics.sharpe_ratio.unwrap_or(f64::MIN);
+        Some((strategy_type, report, fitness))
+    }
+
+    fn tournament_select<'a>(rng: &mut StdRng, scored: &'a [(StrategyType, BacktestReport, f64)]) -> &'a StrategyType {
+        let a = &scored[rng.gen_range(0..scored.len())];
+        let b = &scored[rng.gen_range(0..scored.len())];
+        if a.2 >= b.2 { &a.0 } else { &b.0 }
+    }
+
+    /// Evolve `population_size` individuals over `generations` within
+    /// `param_ranges`, evaluating each through `backtester` with its
+    /// Sharpe ratio as fitness. Returns every individual from the final
+    /// generation, ranked best first.
+    pub async fn optimize(
+        &mut self,
+        param_ranges: ParamRanges,
+        symbol: &str,
+        price_history: &[PricePoint],
+        generations: usize,
+        population_size: usize,
+    ) -> Result<Vec<(StrategyType, BacktestReport)>> {
+        if population_size == 0 || generations == 0 {
+            return Err(StrategyError::invalid_parameters("Generations and population size must be greater than zero").into());
+        }
+
+        let mut population: Vec<StrategyType> = (0..population_size)
+            .map(|_| param_ranges.random_individual(&mut self.rng))
+            .collect();
+
+        let mut scored = Vec::new();
+        for generation in 0..generations {
+            scored.clear();
+            for individual in population.drain(..) {
+                if let Some(result) = self.evaluate(individual, symbol, price_history).await {
+                    scored.push(result);
+                }
+            }
+            if scored.is_empty() {
+                return Err(StrategyError::invalid_parameters("No individual produced a valid backtest").into());
+            }
+            scored.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap());
+
+            if generation + 1 == generations {
+                break;
+            }
+
+            let mut next_generation = vec![scored[0].0.clone()];
+            while next_generation.len() < population_size {
+                let parent_a = Self::tournament_select(&mut self.rng, &scored);
+                let parent_b = Self::tournament_select(&mut self.rng, &scored);
+                let mut child = if self.rng.gen_bool(self.crossover_rate) {
+                    param_ranges.crossover(parent_a, parent_b, &mut self.rng)
+                } else {
+                    parent_a.clone()
+                };
+                if self.rng.gen_bool(self.mutation_rate) {
+                    child = param_ranges.mutate(&child, &mut self.rng);
+                }
+                next_generation.push(child);
+            }
+            population = next_generation;
+        }
+
+        Ok(scored.into_iter().map(|(strategy_type, report, _)| (strategy_type, report)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn price_points(prices: &[f64]) -> Vec<PricePoint> {
+        prices.iter().enumerate().map(|(i, &price)| {
+            PricePoint {
+                timestamp: Utc::now() - chrono::Duration::days((prices.len() - i - 1) as i64),
+                open: Price::from_f64(price),
+                high: Price::from_f64(price * 1.01),
+                low: Price::from_f64(price * 0.99),
+                close: Price::from_f64(price),
+                volume: 1_000_000,
+                adjusted_close: Some(Price::from_f64(price)),
+                market_status: None,
+            }
+        }).collect()
+    }
+
+    #[tokio::test]
+    async fn test_backtest_reports_trades_and_equity_curve() {
+        let strategy = Strategy::new(
+            StrategyType::MovingAverage { short_period: 2, long_period: 3 },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        // Dips then recovers, forcing a bullish crossover followed by a
+        // bearish one so the backtest exercises both buy and sell trades.
+        let prices = vec![100.0, 98.0, 96.0, 94.0, 98.0, 103.0, 108.0, 100.0, 92.0, 85.0];
+        let history = price_points(&prices);
+
+        let backtester = Backtester::new(10_000.0, 1.0, 0.0);
+        let report = backtester.run(strategy, "AAPL", &history).await.unwrap();
+
+        assert_eq!(report.symbol, "AAPL");
+        assert!(!report.equity_curve.is_empty());
+        assert!(report.metrics.total_trades > 0);
+        assert_eq!(report.trades.len(), report.metrics.total_trades as usize);
+    }
+
+    #[test]
+    fn test_slippage_worsens_fill_price_against_the_trader() {
+        let backtester = Backtester::new(10_000.0, 1.0, 1.0);
+
+        assert_eq!(backtester.fill_price(&Action::Buy, 100.0), 101.0);
+        assert_eq!(backtester.fill_price(&Action::ExitShort, 100.0), 101.0);
+        assert_eq!(backtester.fill_price(&Action::Sell, 100.0), 99.0);
+        assert_eq!(backtester.fill_price(&Action::ShortSell, 100.0), 99.0);
+        assert_eq!(backtester.fill_price(&Action::Hold, 100.0), 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_backtest_trades_reflect_slippage_adjusted_fill_price() {
+        let strategy = Strategy::new(
+            StrategyType::MovingAverage { short_period: 2, long_period: 3 },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let prices = vec![100.0, 98.0, 96.0, 94.0, 98.0, 103.0, 108.0, 100.0, 92.0, 85.0];
+        let history = price_points(&prices);
+
+        let backtester = Backtester::new(10_000.0, 1.0, 1.0);
+        let report = backtester.run(strategy, "AAPL", &history).await.unwrap();
+
+        let buy = report.trades.iter().find(|trade| trade.action == Action::Buy).unwrap();
+        let buy_signal = report.signals.iter().find(|signal| signal.action == Action::Buy).unwrap();
+        assert!(buy.price.to_f64() > buy_signal.price);
+    }
+
+    #[tokio::test]
+    async fn test_insufficient_history_is_an_error() {
+        let strategy = Strategy::new(
+            StrategyType::MovingAverage { short_period: 2, long_period: 3 },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let backtester = Backtester::new(10_000.0, 1.0, 0.0);
+        let result = backtester.run(strategy, "AAPL", &price_points(&[100.0])).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_grid_search_picks_the_best_scoring_combination() {
+        let prices = vec![100.0, 98.0, 96.0, 94.0, 98.0, 103.0, 108.0, 100.0, 92.0, 85.0];
+        let history = price_points(&prices);
+
+        let optimizer = Optimizer::new(Backtester::new(10_000.0, 1.0, 0.0));
+        let grid = ParameterGrid::MovingAverage {
+            short_period: vec![2, 3],
+            long_period: vec![4, 5],
+        };
+
+        let result = optimizer.grid_search("AAPL", &history, grid, Objective::TotalReturn).await.unwrap();
+
+        assert_eq!(result.combinations_evaluated, 4);
+        assert!(matches!(result.best_strategy_type, StrategyType::MovingAverage { .. }));
+    }
+}
@@ -50,6 +50,11 @@ pub struct StrategyConfig {
     pub initial_cash: f64,
     pub max_position_size: f64,
     pub transaction_cost: f64,
+    /// Hard ceiling, in basis points of trade notional, a `Portfolio`'s
+    /// fee model can ever charge a single trade. Validated by `Config::load`
+    /// / `Config::validate` so a misconfigured value can't silently remove
+    /// the guardrail.
+    pub max_fee_bps: f64,
 }
 
 impl Config {
@@ -126,11 +131,28 @@ impl Config {
                 transaction_cost: env::var("TRANSACTION_COST")
                     .unwrap_or_else(|_| "0.01".to_string())
                     .parse()?,
+                max_fee_bps: env::var("MAX_FEE_BPS")
+                    .unwrap_or_else(|_| "100.0".to_string())
+                    .parse()?,
             },
         };
 
+        config.validate()?;
         Ok(config)
     }
+
+    /// Sanity-check values that `parse()` alone can't catch, e.g. a
+    /// `max_fee_bps` of zero or negative that would silently disable the
+    /// fee ceiling instead of enforcing one.
+    pub fn validate(&self) -> Result<()> {
+        if !(self.strategies.max_fee_bps > 0.0 && self.strategies.max_fee_bps <= 10_000.0) {
+            anyhow::bail!(
+                "strategies.max_fee_bps must be in (0, 10000] basis points, got {}",
+                self.strategies.max_fee_bps
+            );
+        }
+        Ok(())
+    }
 }
 
 impl Default for Config {
@@ -166,6 +188,7 @@ impl Default for Config {
                 initial_cash: 10000.0,
                 max_position_size: 0.2,
                 transaction_cost: 0.01,
+                max_fee_bps: 100.0,
             },
         }
     }
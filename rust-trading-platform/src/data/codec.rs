@@ -0,0 +1,291 @@
+// Compact, fixed-layout binary (de)serialization for `MarketData`, for
+// cache storage and WebSocket fan-out where `serde_json`'s payload size
+// and parsing cost are both too high.
+//
+// Every numeric field is little-endian and fixed-width (the symbol is a
+// one-byte length followed by its UTF-8 bytes); prices are scaled to a
+// fixed-point `i64` rather than stored as raw `f64` bits, so re-encoding a
+// decoded value is bit-for-bit stable instead of drifting through float
+// rounding. Optional numeric fields are preceded by a one-byte presence
+// code from `FieldPresence`, which follows the repo's "integer code per
+// enum variant" convention: `0` is never a valid code, reserved so a
+// zeroed or truncated buffer fails loudly instead of silently decoding
+// as `Absent`.
+
+use std::num::NonZeroU8;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::data::{MarketData, Price};
+use crate::error::{Result, TradingPlatformError};
+
+/// Fixed-point scale applied to every price-like field (price, change,
+/// day high/low, previous close, confidence) before it's packed as an
+/// `i64`. Six decimal digits of precision comfortably covers equity and
+/// crypto tick sizes.
+const PRICE_SCALE: f64 = 1_000_000.0;
+
+/// Presence code for an optional numeric field, written as a single byte
+/// ahead of the field itself. `0` is reserved as a decode-error sentinel;
+/// it is never emitted by `encode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldPresence {
+    Absent = 1,
+    Present = 2,
+}
+
+impl FieldPresence {
+    fn code(self) -> NonZeroU8 {
+        NonZeroU8::new(self as u8).expect("FieldPresence variants are never zero")
+    }
+}
+
+impl TryFrom<u8> for FieldPresence {
+    type Error = TradingPlatformError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match NonZeroU8::new(value) {
+            None => Err(TradingPlatformError::codec(
+                "field-presence code 0 is reserved (truncated or corrupt buffer)",
+            )),
+            Some(code) if code.get() == FieldPresence::Absent as u8 => Ok(FieldPresence::Absent),
+            Some(code) if code.get() == FieldPresence::Present as u8 => Ok(FieldPresence::Present),
+            Some(code) => Err(TradingPlatformError::codec(format!(
+                "unknown field-presence code: {code}"
+            ))),
+        }
+    }
+}
+
+/// Encode `market_data` into the codec's fixed binary layout.
+pub fn encode(market_data: &MarketData) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(64 + market_data.symbol.len());
+
+    let symbol_bytes = market_data.symbol.as_bytes();
+    buf.push(symbol_bytes.len() as u8);
+    buf.extend_from_slice(symbol_bytes);
+
+    buf.extend_from_slice(&scale(market_data.price.to_f64()).to_le_bytes());
+    buf.extend_from_slice(&market_data.volume.to_le_bytes());
+    buf.extend_from_slice(&timestamp_millis(market_data.timestamp).to_le_bytes());
+    buf.extend_from_slice(&scale(market_data.change.to_f64()).to_le_bytes());
+    buf.extend_from_slice(&scale(market_data.change_percent).to_le_bytes());
+
+    encode_optional_u64(&mut buf, market_data.market_cap);
+    encode_optional_price(&mut buf, market_data.day_high.map(|p| p.to_f64()));
+    encode_optional_price(&mut buf, market_data.day_low.map(|p| p.to_f64()));
+    encode_optional_price(&mut buf, market_data.previous_close.map(|p| p.to_f64()));
+
+    buf.extend_from_slice(&scale(market_data.confidence).to_le_bytes());
+    buf.extend_from_slice(&timestamp_millis(market_data.publish_time).to_le_bytes());
+
+    buf
+}
+
+/// Decode a buffer produced by `encode` back into a `MarketData`.
+pub fn decode(buf: &[u8]) -> Result<MarketData> {
+    let mut cursor = Cursor::new(buf);
+
+    let symbol_len = cursor.take_u8()? as usize;
+    let symbol = String::from_utf8(cursor.take(symbol_len)?.to_vec())
+        .map_err(|e| TradingPlatformError::codec(format!("symbol is not valid UTF-8: {e}")))?;
+
+    let price = unscale(cursor.take_i64()?);
+    let volume = cursor.take_u64()?;
+    let timestamp = from_timestamp_millis(cursor.take_i64()?)?;
+    let change = unscale(cursor.take_i64()?);
+    let change_percent = unscale(cursor.take_i64()?);
+
+    let market_cap = decode_optional_u64(&mut cursor)?;
+    let day_high = decode_optional_price(&mut cursor)?;
+    let day_low = decode_optional_price(&mut cursor)?;
+    let previous_close = decode_optional_price(&mut cursor)?;
+
+    let confidence = unscale(cursor.take_i64()?);
+    let publish_time = from_timestamp_millis(cursor.take_i64()?)?;
+
+    Ok(MarketData {
+        symbol,
+        price: Price::from_f64(price),
+        volume,
+        timestamp,
+        change: Price::from_f64(change),
+        change_percent,
+        market_cap,
+        day_high: day_high.map(Price::from_f64),
+        day_low: day_low.map(Price::from_f64),
+        previous_close: previous_close.map(Price::from_f64),
+        confidence,
+        publish_time,
+    })
+}
+
+fn scale(value: f64) -> i64 {
+    (value * PRICE_SCALE).round() as i64
+}
+
+fn unscale(value: i64) -> f64 {
+    value as f64 / PRICE_SCALE
+}
+
+fn timestamp_millis(timestamp: DateTime<Utc>) -> i64 {
+    timestamp.timestamp_millis()
+}
+
+fn from_timestamp_millis(millis: i64) -> Result<DateTime<Utc>> {
+    Utc.timestamp_millis_opt(millis)
+        .single()
+        .ok_or_else(|| TradingPlatformError::codec(format!("invalid timestamp: {millis}")))
+}
+
+fn encode_optional_u64(buf: &mut Vec<u8>, value: Option<u64>) {
+    match value {
+        Some(v) => {
+            buf.push(FieldPresence::Present.code().get());
+            buf.extend_from_slice(&v.to_le_bytes());
+        }
+        None => buf.push(FieldPresence::Absent.code().get()),
+    }
+}
+
+fn decode_optional_u64(cursor: &mut Cursor<'_>) -> Result<Option<u64>> {
+    match FieldPresence::try_from(cursor.take_u8()?)? {
+        FieldPresence::Present => Ok(Some(cursor.take_u64()?)),
+        FieldPresence::Absent => Ok(None),
+    }
+}
+
+fn encode_optional_price(buf: &mut Vec<u8>, value: Option<f64>) {
+    match value {
+        Some(v) => {
+            buf.push(FieldPresence::Present.code().get());
+            buf.extend_from_slice(&scale(v).to_le_bytes());
+        }
+        None => buf.push(FieldPresence::Absent.code().get()),
+    }
+}
+
+fn decode_optional_price(cursor: &mut Cursor<'_>) -> Result<Option<f64>> {
+    match FieldPresence::try_from(cursor.take_u8()?)? {
+        FieldPresence::Present => Ok(Some(unscale(cursor.take_i64()?))),
+        FieldPresence::Absent => Ok(None),
+    }
+}
+
+/// A minimal forward-only reader over an encoded buffer, erroring instead of
+/// panicking when a field's bytes run past the end.
+struct Cursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos.checked_add(len).ok_or_else(|| {
+            TradingPlatformError::codec("buffer offset overflow while decoding MarketData")
+        })?;
+        let slice = self.buf.get(self.pos..end).ok_or_else(|| {
+            TradingPlatformError::codec("buffer truncated while decoding MarketData")
+        })?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn take_u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn take_u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn take_i64(&mut self) -> Result<i64> {
+        Ok(i64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Timestamps are whole milliseconds and change_percent has at most six
+    // decimals, the precision the codec stores them at.
+    fn sample_timestamp() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 2, 14, 30, 0).unwrap()
+    }
+
+    fn sample_market_data() -> MarketData {
+        let mut data = MarketData::new("AAPL".to_string(), 189.345678, 1_234_567)
+            .with_change(100.0)
+            .with_day_range(191.2, 187.8)
+            .with_confidence(0.002)
+            .with_publish_time(sample_timestamp());
+        data.timestamp = sample_timestamp();
+        data
+    }
+
+    #[test]
+    fn test_roundtrip_with_all_optional_fields_present() {
+        let original = sample_market_data();
+
+        let decoded = decode(&encode(&original)).unwrap();
+
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn test_roundtrip_with_no_optional_fields_present() {
+        let mut original = MarketData::new("ETH-USD".to_string(), 3123.99, 42)
+            .with_publish_time(sample_timestamp());
+        original.timestamp = sample_timestamp();
+
+        let decoded = decode(&encode(&original)).unwrap();
+
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn test_decode_rejects_truncated_buffer() {
+        let buf = encode(&sample_market_data());
+        let truncated = &buf[..buf.len() - 4];
+
+        let result = decode(truncated);
+
+        assert!(matches!(result, Err(TradingPlatformError::Codec(_))));
+    }
+
+    #[test]
+    fn test_decode_rejects_zero_presence_code() {
+        let mut buf = encode(&sample_market_data());
+        // The byte right after symbol + price + volume + timestamp + change +
+        // change_percent is the market_cap presence code.
+        let presence_offset = 1 + "AAPL".len() + 8 + 8 + 8 + 8 + 8;
+        buf[presence_offset] = 0;
+
+        let result = decode(&buf);
+
+        assert!(matches!(result, Err(TradingPlatformError::Codec(_))));
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_presence_code() {
+        let mut buf = encode(&sample_market_data());
+        let presence_offset = 1 + "AAPL".len() + 8 + 8 + 8 + 8 + 8;
+        buf[presence_offset] = 99;
+
+        let result = decode(&buf);
+
+        assert!(matches!(result, Err(TradingPlatformError::Codec(_))));
+    }
+
+    #[test]
+    fn test_scale_and_unscale_round_trip_within_precision() {
+        let value = 1234.567891;
+
+        assert!((unscale(scale(value)) - value).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,175 @@
+// Back-adjustment of `HistoricalData` closes for splits and cash dividends,
+// matching the auto-adjust/back-adjust behavior of `yfinance`: walk bars
+// newest-to-oldest accumulating a multiplicative factor, and stamp each
+// bar's `adjusted_close` with `close * factor`.
+
+use chrono::{DateTime, Utc};
+
+use crate::data::HistoricalData;
+
+/// A split (ratio-based) or cash dividend (amount-based) corporate action,
+/// applied by [`HistoricalData::apply_corporate_actions`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CorporateAction {
+    /// A forward split of `ratio`-for-1 (e.g. `2.0` for a 2-for-1 split)
+    /// effective on `date`.
+    Split { ratio: f64, date: DateTime<Utc> },
+    /// A cash dividend of `amount` per share, ex-dividend on `date`.
+    Dividend { amount: f64, date: DateTime<Utc> },
+}
+
+impl CorporateAction {
+    fn date(&self) -> DateTime<Utc> {
+        match self {
+            CorporateAction::Split { date, .. } => *date,
+            CorporateAction::Dividend { date, .. } => *date,
+        }
+    }
+
+    /// The multiplicative adjustment this action applies to every bar
+    /// strictly before `self.date()`. `close_on_date` is the unadjusted
+    /// close on the action's date, needed to turn a dividend amount into a
+    /// ratio; splits don't need it.
+    fn factor(&self, close_on_date: f64) -> f64 {
+        match self {
+            CorporateAction::Split { ratio, .. } => {
+                if *ratio > 0.0 {
+                    1.0 / ratio
+                } else {
+                    1.0
+                }
+            }
+            CorporateAction::Dividend { amount, .. } => {
+                if close_on_date <= 0.0 {
+                    1.0
+                } else {
+                    // Clamp so a dividend announced larger than the price it
+                    // was declared against (bad data, or a special
+                    // dividend) can't drive the factor negative.
+                    (1.0 - amount / close_on_date).max(0.0)
+                }
+            }
+        }
+    }
+}
+
+impl HistoricalData {
+    /// Back-adjust every bar's `adjusted_close` for `actions`. Bars with no
+    /// preceding action keep `adjusted_close == close`. Actions on the same
+    /// date compose multiplicatively, and order among same-date actions
+    /// doesn't matter since multiplication commutes.
+    pub fn apply_corporate_actions(&mut self, actions: &[CorporateAction]) {
+        let mut sorted_actions: Vec<&CorporateAction> = actions.iter().collect();
+        sorted_actions.sort_by_key(|a| std::cmp::Reverse(a.date()));
+
+        // Each action's factor is fixed once, against the close on (or just
+        // after) its own date `D`, not whichever prior bar happens to trip
+        // it while walking backwards below.
+        let action_factors: Vec<f64> = sorted_actions
+            .iter()
+            .map(|action| {
+                let close_on_date = self
+                    .data_points
+                    .iter()
+                    .find(|p| p.timestamp >= action.date())
+                    .map(|p| p.close.to_f64())
+                    .unwrap_or(0.0);
+                action.factor(close_on_date)
+            })
+            .collect();
+
+        let mut factor = 1.0;
+        let mut next_action_idx = 0;
+
+        for point in self.data_points.iter_mut().rev() {
+            while next_action_idx < sorted_actions.len() && sorted_actions[next_action_idx].date() > point.timestamp {
+                factor *= action_factors[next_action_idx];
+                next_action_idx += 1;
+            }
+
+            point.adjusted_close = Some(point.close * factor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{Price, PricePoint, TimePeriod};
+    use chrono::{Duration, TimeZone};
+
+    fn bar(days_ago: i64, close: f64) -> PricePoint {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::days(30 - days_ago);
+        PricePoint::new(timestamp, close, close, close, close, 1_000).unwrap()
+    }
+
+    fn series(bars: Vec<PricePoint>) -> HistoricalData {
+        let mut data = HistoricalData::new("TEST".to_string(), TimePeriod::OneMonth);
+        for b in bars {
+            data.add_price_point(b);
+        }
+        data
+    }
+
+    #[test]
+    fn test_no_actions_leaves_adjusted_close_equal_to_close() {
+        let mut data = series(vec![bar(10, 100.0), bar(5, 105.0), bar(0, 110.0)]);
+
+        data.apply_corporate_actions(&[]);
+
+        for point in &data.data_points {
+            assert_eq!(point.adjusted_close, Some(point.close));
+        }
+    }
+
+    #[test]
+    fn test_split_halves_prior_closes() {
+        let mut data = series(vec![bar(10, 100.0), bar(5, 50.0), bar(0, 51.0)]);
+        let split_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::days(22);
+
+        data.apply_corporate_actions(&[CorporateAction::Split { ratio: 2.0, date: split_date }]);
+
+        // Bar before the split (day 10, i.e. index 0 after sort) is halved;
+        // bars on/after the split date are untouched.
+        assert_eq!(data.data_points[0].adjusted_close, Some(Price::from_f64(50.0)));
+        assert_eq!(data.data_points[1].adjusted_close, Some(Price::from_f64(50.0)));
+        assert_eq!(data.data_points[2].adjusted_close, Some(Price::from_f64(51.0)));
+    }
+
+    #[test]
+    fn test_dividend_scales_prior_closes_by_one_minus_amount_over_price() {
+        let mut data = series(vec![bar(10, 100.0), bar(0, 105.0)]);
+        let ex_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::days(25);
+
+        data.apply_corporate_actions(&[CorporateAction::Dividend { amount: 1.0, date: ex_date }]);
+
+        // factor computed against the close on the dividend's own (later)
+        // bar, i.e. the 105.0 bar.
+        let expected_factor = 1.0 - 1.0 / 105.0;
+        assert_eq!(data.data_points[0].adjusted_close, Some(Price::from_f64(100.0 * expected_factor)));
+        assert_eq!(data.data_points[1].adjusted_close, Some(Price::from_f64(105.0)));
+    }
+
+    #[test]
+    fn test_dividend_larger_than_price_clamps_factor_to_zero_not_negative() {
+        let mut data = series(vec![bar(10, 100.0), bar(0, 5.0)]);
+        let ex_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::days(25);
+
+        data.apply_corporate_actions(&[CorporateAction::Dividend { amount: 10.0, date: ex_date }]);
+
+        assert_eq!(data.data_points[0].adjusted_close, Some(Price::ZERO));
+    }
+
+    #[test]
+    fn test_same_date_actions_compose_multiplicatively() {
+        let mut data = series(vec![bar(10, 100.0), bar(0, 50.0)]);
+        let action_date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::days(25);
+
+        data.apply_corporate_actions(&[
+            CorporateAction::Split { ratio: 2.0, date: action_date },
+            CorporateAction::Dividend { amount: 0.0, date: action_date },
+        ]);
+
+        assert_eq!(data.data_points[0].adjusted_close, Some(Price::from_f64(50.0)));
+    }
+}
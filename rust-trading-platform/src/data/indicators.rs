@@ -0,0 +1,177 @@
+// Technical-indicator engine for `HistoricalData`: Wilder-smoothed RSI and
+// Bollinger Bands, plus a `compute_indicators` convenience that fills a
+// `MarketIndicators` in one pass.
+
+use crate::data::{BollingerBands, HistoricalData, MarketIndicators, Price};
+
+/// Default Bollinger Bands width multiplier, per the original Bollinger
+/// formulation.
+const DEFAULT_BOLLINGER_K: f64 = 2.0;
+
+impl HistoricalData {
+    /// Wilder's RSI over every close in the series. Returns `None` if fewer
+    /// than `period + 1` closes exist. Over the first `period` deltas,
+    /// `avg_gain`/`avg_loss` start as the simple average; thereafter each
+    /// is a Wilder-smoothed running average (`(prev*(period-1) + latest) /
+    /// period`).
+    pub fn calculate_rsi(&self, period: usize) -> Option<f64> {
+        if period == 0 || self.data_points.len() < period + 1 {
+            return None;
+        }
+
+        let closes = self.get_closing_prices();
+        let deltas: Vec<f64> = closes.windows(2).map(|w| w[1] - w[0]).collect();
+
+        let seed_gains: f64 = deltas[..period].iter().map(|d| d.max(0.0)).sum();
+        let seed_losses: f64 = deltas[..period].iter().map(|d| (-d).max(0.0)).sum();
+        let mut avg_gain = seed_gains / period as f64;
+        let mut avg_loss = seed_losses / period as f64;
+
+        for delta in &deltas[period..] {
+            let gain = delta.max(0.0);
+            let loss = (-delta).max(0.0);
+            avg_gain = (avg_gain * (period - 1) as f64 + gain) / period as f64;
+            avg_loss = (avg_loss * (period - 1) as f64 + loss) / period as f64;
+        }
+
+        if avg_loss == 0.0 {
+            return Some(100.0);
+        }
+        if avg_gain == 0.0 {
+            return Some(0.0);
+        }
+
+        let rs = avg_gain / avg_loss;
+        Some(100.0 - 100.0 / (1.0 + rs))
+    }
+
+    /// Bollinger Bands over the last `period` closes: `middle` is the SMA,
+    /// `upper`/`lower` are `middle +/- k * sigma` with `sigma` the
+    /// population standard deviation of those closes. Returns `None` if
+    /// fewer than `period` closes exist.
+    pub fn calculate_bollinger_bands(&self, period: usize, k: f64) -> Option<BollingerBands> {
+        let middle = self.calculate_simple_moving_average(period)?;
+
+        let recent: Vec<f64> = self.data_points.iter().rev().take(period).map(|p| p.close.to_f64()).collect();
+        let variance = recent.iter().map(|c| (c - middle).powi(2)).sum::<f64>() / period as f64;
+        let sigma = variance.sqrt();
+
+        Some(BollingerBands {
+            upper: Price::from_f64(middle + k * sigma),
+            middle: Price::from_f64(middle),
+            lower: Price::from_f64(middle - k * sigma),
+        })
+    }
+
+    /// Fill a `MarketIndicators` with a moving average per entry in
+    /// `periods`, plus RSI, Bollinger Bands (using `periods`' largest
+    /// value), and volume average -- all in one pass over `self`.
+    pub fn compute_indicators(&self, periods: &[usize]) -> MarketIndicators {
+        let mut indicators = MarketIndicators::new(self.symbol.clone());
+
+        for &period in periods {
+            if let Some(sma) = self.calculate_simple_moving_average(period) {
+                indicators.add_moving_average(period, sma);
+            }
+        }
+
+        if let Some(&bollinger_period) = periods.iter().max() {
+            indicators.rsi = self.calculate_rsi(bollinger_period);
+            indicators.bollinger_bands = self.calculate_bollinger_bands(bollinger_period, DEFAULT_BOLLINGER_K);
+        }
+
+        let volumes = self.get_volumes();
+        if !volumes.is_empty() {
+            indicators.volume_average = Some(volumes.iter().sum::<u64>() as f64 / volumes.len() as f64);
+        }
+
+        indicators
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{PricePoint, TimePeriod};
+    use chrono::{Duration, Utc};
+
+    fn series(closes: &[f64]) -> HistoricalData {
+        let mut data = HistoricalData::new("TEST".to_string(), TimePeriod::OneMonth);
+        let now = Utc::now();
+        for (i, &close) in closes.iter().enumerate() {
+            let timestamp = now - Duration::days((closes.len() - i) as i64);
+            data.add_price_point(PricePoint::new(timestamp, close, close * 1.01, close * 0.99, close, 1_000).unwrap());
+        }
+        data
+    }
+
+    #[test]
+    fn test_rsi_returns_none_with_insufficient_data() {
+        let data = series(&[100.0, 101.0, 102.0]);
+
+        assert_eq!(data.calculate_rsi(14), None);
+    }
+
+    #[test]
+    fn test_rsi_is_100_when_all_gains() {
+        let closes: Vec<f64> = (0..15).map(|i| 100.0 + i as f64).collect();
+        let data = series(&closes);
+
+        assert_eq!(data.calculate_rsi(14), Some(100.0));
+    }
+
+    #[test]
+    fn test_rsi_is_0_when_all_losses() {
+        let closes: Vec<f64> = (0..15).map(|i| 200.0 - i as f64).collect();
+        let data = series(&closes);
+
+        assert_eq!(data.calculate_rsi(14), Some(0.0));
+    }
+
+    #[test]
+    fn test_rsi_is_between_0_and_100_for_mixed_moves() {
+        let closes = vec![100.0, 102.0, 101.0, 103.0, 102.5, 104.0, 103.0, 105.0, 104.5, 106.0, 105.5, 107.0, 106.0, 108.0, 107.5];
+        let data = series(&closes);
+
+        let rsi = data.calculate_rsi(14).unwrap();
+
+        assert!(rsi > 0.0 && rsi < 100.0);
+    }
+
+    #[test]
+    fn test_bollinger_bands_returns_none_with_insufficient_data() {
+        let data = series(&[100.0, 101.0]);
+
+        assert_eq!(data.calculate_bollinger_bands(20, 2.0), None);
+    }
+
+    #[test]
+    fn test_bollinger_bands_middle_matches_sma_and_widens_with_volatility() {
+        let flat = series(&[100.0; 20]);
+        let flat_bands = flat.calculate_bollinger_bands(20, 2.0).unwrap();
+        assert_eq!(flat_bands.middle.to_f64(), 100.0);
+        assert_eq!(flat_bands.upper.to_f64(), 100.0);
+        assert_eq!(flat_bands.lower.to_f64(), 100.0);
+
+        let mut volatile_closes = vec![100.0; 19];
+        volatile_closes.push(200.0);
+        let volatile = series(&volatile_closes);
+        let volatile_bands = volatile.calculate_bollinger_bands(20, 2.0).unwrap();
+        assert!(volatile_bands.upper > volatile_bands.middle);
+        assert!(volatile_bands.lower < volatile_bands.middle);
+    }
+
+    #[test]
+    fn test_compute_indicators_fills_all_fields() {
+        let closes: Vec<f64> = (0..25).map(|i| 100.0 + i as f64).collect();
+        let data = series(&closes);
+
+        let indicators = data.compute_indicators(&[5, 20]);
+
+        assert!(indicators.get_moving_average(5).is_some());
+        assert!(indicators.get_moving_average(20).is_some());
+        assert!(indicators.rsi.is_some());
+        assert!(indicators.bollinger_bands.is_some());
+        assert!(indicators.volume_average.is_some());
+    }
+}
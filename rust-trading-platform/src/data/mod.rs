@@ -1,33 +1,62 @@
 // Market data provider and management
 
+pub mod codec;
+pub mod corporate_actions;
+pub mod indicators;
+pub mod price;
+pub mod providers;
+pub mod repair;
+pub mod yahoo_download;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use crate::error::{Result, MarketDataError};
+use std::path::Path;
+use crate::error::{Result, MarketDataError, TradingPlatformError};
+
+pub use providers::{
+    AlphaVantageProvider, CachingProvider, DataProviderConfig, FinnhubProvider, MarketDataProvider,
+    TwelveDataProvider, Vendor,
+};
+pub use corporate_actions::CorporateAction;
+pub use price::Price;
+pub use repair::{RepairReport, RepairedBar};
+pub use yahoo_download::{DownloadOptions, Interval, YahooDownloadClient};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MarketData {
     pub symbol: String,
-    pub price: f64,
+    pub price: Price,
     pub volume: u64,
     pub timestamp: DateTime<Utc>,
-    pub change: f64,
+    pub change: Price,
     pub change_percent: f64,
     pub market_cap: Option<u64>,
-    pub day_high: Option<f64>,
-    pub day_low: Option<f64>,
-    pub previous_close: Option<f64>,
+    pub day_high: Option<Price>,
+    pub day_low: Option<Price>,
+    pub previous_close: Option<Price>,
+    /// One-sigma price uncertainty (Pyth/Mango-style confidence band), in the
+    /// same units as `price`. Zero means the provider doesn't model uncertainty.
+    pub confidence: f64,
+    /// When this price was published by the provider. Consumers compare this
+    /// against the current time to detect a stale feed.
+    pub publish_time: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PricePoint {
     pub timestamp: DateTime<Utc>,
-    pub open: f64,
-    pub high: f64,
-    pub low: f64,
-    pub close: f64,
+    pub open: Price,
+    pub high: Price,
+    pub low: Price,
+    pub close: Price,
     pub volume: u64,
-    pub adjusted_close: Option<f64>,
+    pub adjusted_close: Option<Price>,
+    /// Which session this bar was printed in, for fetchers that can include
+    /// pre-market/after-hours bars (see `data::yahoo_download`). `None` when
+    /// the source doesn't distinguish sessions, which is the common case for
+    /// plain end-of-day bars.
+    pub market_status: Option<MarketStatus>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -51,6 +80,19 @@ pub enum TimePeriod {
     Custom { days: u32 },
 }
 
+/// Bar granularity for `MarketDataProvider::get_latest_quotes`, distinct from
+/// `TimePeriod` in that it picks a candle *width* rather than a lookback
+/// *window* — providers pair it with whatever lookback gets a useful number
+/// of recent bars at that width.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum QuoteInterval {
+    OneMinute,
+    FiveMinute,
+    FifteenMinute,
+    OneHour,
+    OneDay,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MarketIndicators {
     pub symbol: String,
@@ -63,16 +105,16 @@ pub struct MarketIndicators {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct BollingerBands {
-    pub upper: f64,
-    pub middle: f64,
-    pub lower: f64,
+    pub upper: Price,
+    pub middle: Price,
+    pub lower: Price,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MarketSummary {
     pub symbol: String,
-    pub current_price: f64,
-    pub daily_change: f64,
+    pub current_price: Price,
+    pub daily_change: Price,
     pub daily_change_percent: f64,
     pub volume: u64,
     pub market_status: MarketStatus,
@@ -93,22 +135,50 @@ impl MarketData {
     pub fn new(symbol: String, price: f64, volume: u64) -> Self {
         MarketData {
             symbol,
-            price,
+            price: Price::from_f64(price),
             volume,
             timestamp: Utc::now(),
-            change: 0.0,
+            change: Price::ZERO,
             change_percent: 0.0,
             market_cap: None,
             day_high: None,
             day_low: None,
             previous_close: None,
+            confidence: 0.0,
+            publish_time: Utc::now(),
         }
     }
 
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        self.confidence = confidence;
+        self
+    }
+
+    pub fn with_publish_time(mut self, publish_time: DateTime<Utc>) -> Self {
+        self.publish_time = publish_time;
+        self
+    }
+
+    /// Check this price against a staleness threshold, erroring if `publish_time`
+    /// is older than `max_age`. Mirrors `HistoricalData::is_stale`, but surfaces
+    /// a typed error so oracle-gating code can propagate it with `?`.
+    pub fn check_staleness(&self, max_age: std::time::Duration) -> Result<()> {
+        let age = Utc::now().signed_duration_since(self.publish_time);
+        if age.to_std().unwrap_or_default() > max_age {
+            return Err(MarketDataError::StalePrice {
+                symbol: self.symbol.clone(),
+                age: age.to_std().unwrap_or(max_age),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
     pub fn with_change(mut self, previous_close: f64) -> Self {
+        let previous_close = Price::from_f64(previous_close);
         self.change = self.price - previous_close;
-        self.change_percent = if previous_close != 0.0 {
-            (self.change / previous_close) * 100.0
+        self.change_percent = if !previous_close.is_zero() {
+            (self.change.to_f64() / previous_close.to_f64()) * 100.0
         } else {
             0.0
         };
@@ -117,8 +187,8 @@ impl MarketData {
     }
 
     pub fn with_day_range(mut self, high: f64, low: f64) -> Self {
-        self.day_high = Some(high);
-        self.day_low = Some(low);
+        self.day_high = Some(Price::from_f64(high));
+        self.day_low = Some(Price::from_f64(low));
         self
     }
 
@@ -127,7 +197,7 @@ impl MarketData {
             return Err(MarketDataError::InvalidFormat.into());
         }
 
-        if self.price <= 0.0 {
+        if self.price <= Price::ZERO {
             return Err(MarketDataError::InvalidFormat.into());
         }
 
@@ -160,30 +230,36 @@ impl PricePoint {
 
         Ok(PricePoint {
             timestamp,
-            open,
-            high,
-            low,
-            close,
+            open: Price::from_f64(open),
+            high: Price::from_f64(high),
+            low: Price::from_f64(low),
+            close: Price::from_f64(close),
             volume,
             adjusted_close: None,
+            market_status: None,
         })
     }
 
     pub fn with_adjusted_close(mut self, adjusted_close: f64) -> Self {
-        self.adjusted_close = Some(adjusted_close);
+        self.adjusted_close = Some(Price::from_f64(adjusted_close));
+        self
+    }
+
+    pub fn with_market_status(mut self, market_status: MarketStatus) -> Self {
+        self.market_status = Some(market_status);
         self
     }
 
     pub fn typical_price(&self) -> f64 {
-        (self.high + self.low + self.close) / 3.0
+        (self.high + self.low + self.close).to_f64() / 3.0
     }
 
     pub fn price_range(&self) -> f64 {
-        self.high - self.low
+        (self.high - self.low).to_f64()
     }
 
     pub fn body_size(&self) -> f64 {
-        (self.close - self.open).abs()
+        (self.close - self.open).to_f64().abs()
     }
 
     pub fn is_bullish(&self) -> bool {
@@ -211,12 +287,79 @@ impl HistoricalData {
         self.last_updated = Utc::now();
     }
 
+    /// Insert `points` in sorted position via binary search, overwriting any
+    /// existing point with the same timestamp instead of duplicating it.
+    /// Unlike `add_price_point`, this doesn't re-sort the whole series: the
+    /// common case of appending newer bars lands at the end of `data_points`
+    /// in O(log n) per point (a binary search plus a push), not O(n log n).
+    pub fn merge(&mut self, points: Vec<PricePoint>) {
+        for point in points {
+            match self.data_points.binary_search_by_key(&point.timestamp, |p| p.timestamp) {
+                Ok(idx) => self.data_points[idx] = point,
+                Err(idx) => self.data_points.insert(idx, point),
+            }
+        }
+        self.last_updated = Utc::now();
+    }
+
+    /// Fetch only the bars newer than `get_latest()`'s timestamp from
+    /// `provider` and merge them in, so refreshing a long-lived series
+    /// doesn't mean re-downloading and re-sorting bars that are already
+    /// held. Returns the whole series with `self`'s existing history
+    /// unchanged if the fetch returns nothing new.
+    pub async fn update_incrementally(&self, provider: &impl MarketDataProvider) -> Result<Self> {
+        let latest_timestamp = self.get_latest().map(|point| point.timestamp);
+        let period = match latest_timestamp {
+            Some(latest) => TimePeriod::Custom {
+                days: Utc::now().signed_duration_since(latest).num_days().max(0) as u32 + 1,
+            },
+            None => self.period,
+        };
+        let fetched = provider.fetch_history(&self.symbol, period).await?;
+
+        let new_points = match latest_timestamp {
+            Some(latest) => fetched
+                .data_points
+                .into_iter()
+                .filter(|point| point.timestamp > latest)
+                .collect(),
+            None => fetched.data_points,
+        };
+
+        let mut updated = self.clone();
+        updated.merge(new_points);
+        Ok(updated)
+    }
+
+    /// Persist this series to `path` as JSON, so a restart can pick up
+    /// where it left off via `load` instead of re-downloading the whole
+    /// history.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string(self).map_err(|e| {
+            TradingPlatformError::internal(format!("failed to serialize historical data for {}: {}", self.symbol, e))
+        })?;
+        std::fs::write(path, json).map_err(|e| {
+            TradingPlatformError::internal(format!("failed to write historical data to {}: {}", path.display(), e))
+        })?;
+        Ok(())
+    }
+
+    /// Load a series previously written by `save`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            TradingPlatformError::internal(format!("failed to read historical data from {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&json).map_err(|e| {
+            TradingPlatformError::internal(format!("corrupt historical data file {}: {}", path.display(), e))
+        })
+    }
+
     pub fn get_latest(&self) -> Option<&PricePoint> {
         self.data_points.last()
     }
 
     pub fn get_closing_prices(&self) -> Vec<f64> {
-        self.data_points.iter().map(|p| p.close).collect()
+        self.data_points.iter().map(|p| p.close.to_f64()).collect()
     }
 
     pub fn get_volumes(&self) -> Vec<u64> {
@@ -232,7 +375,7 @@ impl HistoricalData {
             .iter()
             .rev()
             .take(period)
-            .map(|p| p.close)
+            .map(|p| p.close.to_f64())
             .sum();
 
         Some(sum / period as f64)
@@ -0,0 +1,152 @@
+// Fixed-point decimal type for quote/history prices, eliminating the `f64`
+// drift that crept in wherever this module's indicators, repair pass, and
+// corporate-action adjustments repeatedly rescale or accumulate prices.
+//
+// `Price` mirrors `performance::money::Money`'s representation (an `i128`
+// scaled by `SCALE`, a string-encoded `Serialize`/`Deserialize` so JSON
+// round-trips stay exact) but skips `Money`'s checked arithmetic: this data
+// is read-mostly quote/history data rather than a cash ledger, so overflow
+// and below-zero guards aren't the concern they are for `Money` -- plain
+// operator overloads keep this module's existing arithmetic (rescaling,
+// interpolation, band widths) reading the same as it did over raw `f64`.
+
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Decimal places of precision kept internally (enough for fractional
+/// cents on any realistic share price).
+const SCALE: i128 = 100_000_000; // 1e8
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Price(i128);
+
+impl Price {
+    pub const ZERO: Price = Price(0);
+
+    /// Build from a floating-point amount, rounding to `Price`'s precision.
+    /// This is a lossy edge of the type: it exists so vendor quotes and
+    /// downloaded bars (both naturally `f64`) can enter the data model.
+    /// Addition, subtraction and negation are exact fixed-point arithmetic;
+    /// scaling by an `f64` factor (`Mul<f64>`) rounds back to this precision.
+    pub fn from_f64(value: f64) -> Self {
+        Price((value * SCALE as f64).round() as i128)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / SCALE as f64
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl Add for Price {
+    type Output = Price;
+
+    fn add(self, rhs: Price) -> Price {
+        Price(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Price {
+    type Output = Price;
+
+    fn sub(self, rhs: Price) -> Price {
+        Price(self.0 - rhs.0)
+    }
+}
+
+impl Neg for Price {
+    type Output = Price;
+
+    fn neg(self) -> Price {
+        Price(-self.0)
+    }
+}
+
+/// Scale by a plain factor (e.g. a repair-pass rescale or a corporate-action
+/// adjustment factor), rounding back to `Price`'s precision.
+impl Mul<f64> for Price {
+    type Output = Price;
+
+    fn mul(self, rhs: f64) -> Price {
+        Price::from_f64(self.to_f64() * rhs)
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.4}", self.to_f64())
+    }
+}
+
+impl From<f64> for Price {
+    fn from(value: f64) -> Self {
+        Price::from_f64(value)
+    }
+}
+
+impl From<Price> for f64 {
+    fn from(price: Price) -> Self {
+        price.to_f64()
+    }
+}
+
+// Serialize as the scaled integer string so round-tripping through JSON
+// never touches a float and can't introduce new rounding error.
+impl Serialize for Price {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Price {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse::<i128>().map(Price).map_err(DeError::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_f64_round_trips_through_to_f64() {
+        let price = Price::from_f64(189.345678);
+        assert!((price.to_f64() - 189.345678).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_add_and_sub() {
+        let a = Price::from_f64(100.0);
+        let b = Price::from_f64(40.0);
+        assert!(((a + b).to_f64() - 140.0).abs() < 1e-9);
+        assert!(((a - b).to_f64() - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mul_by_scalar_scales_precisely() {
+        let price = Price::from_f64(100.0);
+        let scaled = price * 0.5;
+        assert!((scaled.to_f64() - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ordering_matches_numeric_order() {
+        let low = Price::from_f64(99.0);
+        let high = Price::from_f64(101.0);
+        assert!(low < high);
+        assert!(high > low);
+    }
+
+    #[test]
+    fn test_serde_round_trip_is_exact() {
+        let price = Price::from_f64(9999.99);
+        let json = serde_json::to_string(&price).unwrap();
+        let back: Price = serde_json::from_str(&json).unwrap();
+        assert_eq!(price, back);
+    }
+}
@@ -0,0 +1,647 @@
+// Pluggable market-data provider abstraction for this module's plain value
+// types (`MarketData`, `HistoricalData`, `MarketSummary`).
+//
+// This is distinct from `crate::market_data::MarketDataProvider`: that trait
+// backs a much richer streaming/backfill/failover provider stack built around
+// Yahoo Finance. This one is scoped to a handful of simple REST quote/history
+// vendors (Alpha Vantage, Finnhub, Twelve Data) selected by a per-vendor
+// API-key config, and wrapped in a TTL cache so callers hitting the same
+// symbol repeatedly don't burn through vendor rate limits.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::data::{HistoricalData, MarketData, MarketStatus, MarketSummary, Price, PricePoint, TimePeriod};
+use crate::error::{MarketDataError, Result, TradingPlatformError};
+
+/// Read-only access to a vendor's quote/history/summary endpoints for this
+/// module's value types.
+#[async_trait]
+pub trait MarketDataProvider: Send + Sync {
+    async fn fetch_quote(&self, symbol: &str) -> Result<MarketData>;
+    async fn fetch_history(&self, symbol: &str, period: TimePeriod) -> Result<HistoricalData>;
+    async fn fetch_summary(&self, symbol: &str) -> Result<MarketSummary>;
+}
+
+/// Per-vendor API keys, mirroring the multi-provider config pattern used
+/// elsewhere in this crate (one field per backend, selected at construction
+/// time rather than via a trait object the caller has to assemble by hand).
+#[derive(Debug, Clone, Default)]
+pub struct DataProviderConfig {
+    pub alpha_vantage_api_key: Option<String>,
+    pub finnhub_api_key: Option<String>,
+    pub twelve_data_api_key: Option<String>,
+    /// How long a `CachingProvider` entry stays fresh before it re-fetches
+    /// from the vendor.
+    pub cache_expire_time: Duration,
+}
+
+impl DataProviderConfig {
+    /// Build the provider for `vendor`, wrapped in a [`CachingProvider`]
+    /// using `cache_expire_time`. Errors if the matching API key is unset.
+    pub fn build(&self, vendor: Vendor) -> Result<CachingProvider> {
+        let inner: Box<dyn MarketDataProvider> = match vendor {
+            Vendor::AlphaVantage => Box::new(AlphaVantageProvider::new(
+                self.alpha_vantage_api_key
+                    .clone()
+                    .ok_or_else(|| TradingPlatformError::config("alpha_vantage_api_key is required"))?,
+            )),
+            Vendor::Finnhub => Box::new(FinnhubProvider::new(
+                self.finnhub_api_key
+                    .clone()
+                    .ok_or_else(|| TradingPlatformError::config("finnhub_api_key is required"))?,
+            )),
+            Vendor::TwelveData => Box::new(TwelveDataProvider::new(
+                self.twelve_data_api_key
+                    .clone()
+                    .ok_or_else(|| TradingPlatformError::config("twelve_data_api_key is required"))?,
+            )),
+        };
+
+        Ok(CachingProvider::new(inner, self.cache_expire_time))
+    }
+}
+
+/// Vendors selectable through [`DataProviderConfig::build`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vendor {
+    AlphaVantage,
+    Finnhub,
+    TwelveData,
+}
+
+/// [Alpha Vantage](https://www.alphavantage.co/documentation/) `GLOBAL_QUOTE`
+/// / `TIME_SERIES_DAILY` provider.
+pub struct AlphaVantageProvider {
+    api_key: String,
+    client: Client,
+    base_url: String,
+}
+
+impl AlphaVantageProvider {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            client: Client::new(),
+            base_url: "https://www.alphavantage.co/query".to_string(),
+        }
+    }
+
+    fn map_error(symbol: &str, err: reqwest::Error) -> TradingPlatformError {
+        if err.is_timeout() {
+            TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable)
+        } else {
+            TradingPlatformError::MarketData(MarketDataError::NoDataAvailable(symbol.to_string()))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AlphaVantageQuoteResponse {
+    #[serde(rename = "Global Quote")]
+    global_quote: Option<AlphaVantageQuote>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AlphaVantageQuote {
+    #[serde(rename = "05. price")]
+    price: String,
+    #[serde(rename = "06. volume")]
+    volume: String,
+    #[serde(rename = "08. previous close")]
+    previous_close: String,
+    #[serde(rename = "09. change")]
+    change: String,
+}
+
+#[async_trait]
+impl MarketDataProvider for AlphaVantageProvider {
+    async fn fetch_quote(&self, symbol: &str) -> Result<MarketData> {
+        let response = self
+            .client
+            .get(&self.base_url)
+            .query(&[
+                ("function", "GLOBAL_QUOTE"),
+                ("symbol", symbol),
+                ("apikey", &self.api_key),
+            ])
+            .send()
+            .await
+            .map_err(|e| Self::map_error(symbol, e))?
+            .json::<AlphaVantageQuoteResponse>()
+            .await
+            .map_err(|e| Self::map_error(symbol, e))?;
+
+        let quote = response
+            .global_quote
+            .ok_or_else(|| MarketDataError::symbol_not_found(symbol))?;
+
+        let price: f64 = quote.price.parse().map_err(|_| MarketDataError::InvalidFormat)?;
+        let volume: u64 = quote.volume.parse().map_err(|_| MarketDataError::InvalidFormat)?;
+        let previous_close: f64 = quote.previous_close.parse().map_err(|_| MarketDataError::InvalidFormat)?;
+        let _: f64 = quote.change.parse().map_err(|_| MarketDataError::InvalidFormat)?;
+
+        Ok(MarketData::new(symbol.to_string(), price, volume).with_change(previous_close))
+    }
+
+    async fn fetch_history(&self, symbol: &str, period: TimePeriod) -> Result<HistoricalData> {
+        #[derive(Debug, Deserialize)]
+        struct DailyBar {
+            #[serde(rename = "1. open")]
+            open: String,
+            #[serde(rename = "2. high")]
+            high: String,
+            #[serde(rename = "3. low")]
+            low: String,
+            #[serde(rename = "4. close")]
+            close: String,
+            #[serde(rename = "5. volume")]
+            volume: String,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct DailyResponse {
+            #[serde(rename = "Time Series (Daily)")]
+            time_series: Option<HashMap<String, DailyBar>>,
+        }
+
+        let response = self
+            .client
+            .get(&self.base_url)
+            .query(&[
+                ("function", "TIME_SERIES_DAILY"),
+                ("symbol", symbol),
+                ("apikey", &self.api_key),
+            ])
+            .send()
+            .await
+            .map_err(|e| Self::map_error(symbol, e))?
+            .json::<DailyResponse>()
+            .await
+            .map_err(|e| Self::map_error(symbol, e))?;
+
+        let time_series = response
+            .time_series
+            .ok_or_else(|| MarketDataError::symbol_not_found(symbol))?;
+
+        let mut historical_data = HistoricalData::new(symbol.to_string(), period);
+
+        for (date, bar) in time_series {
+            let Ok(timestamp) = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc())
+            else {
+                continue;
+            };
+
+            let (Ok(open), Ok(high), Ok(low), Ok(close), Ok(volume)) = (
+                bar.open.parse(),
+                bar.high.parse(),
+                bar.low.parse(),
+                bar.close.parse(),
+                bar.volume.parse(),
+            ) else {
+                continue;
+            };
+
+            if let Ok(point) = PricePoint::new(timestamp, open, high, low, close, volume) {
+                historical_data.add_price_point(point);
+            }
+        }
+
+        if historical_data.data_points.is_empty() {
+            return Err(MarketDataError::InsufficientHistoricalData(symbol.to_string()).into());
+        }
+
+        Ok(historical_data)
+    }
+
+    async fn fetch_summary(&self, symbol: &str) -> Result<MarketSummary> {
+        let quote = self.fetch_quote(symbol).await?;
+        Ok(MarketSummary {
+            symbol: quote.symbol,
+            current_price: quote.price,
+            daily_change: quote.change,
+            daily_change_percent: quote.change_percent,
+            volume: quote.volume,
+            market_status: MarketStatus::Open,
+            last_trade_time: quote.timestamp,
+        })
+    }
+}
+
+/// [Finnhub](https://finnhub.io/docs/api/quote) `/quote` provider.
+pub struct FinnhubProvider {
+    api_key: String,
+    client: Client,
+    base_url: String,
+}
+
+impl FinnhubProvider {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            client: Client::new(),
+            base_url: "https://finnhub.io/api/v1".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FinnhubQuote {
+    c: f64, // current price
+    h: f64, // day high
+    l: f64, // day low
+    pc: f64, // previous close
+    v: Option<u64>,
+}
+
+#[async_trait]
+impl MarketDataProvider for FinnhubProvider {
+    async fn fetch_quote(&self, symbol: &str) -> Result<MarketData> {
+        let url = format!("{}/quote", self.base_url);
+        let quote: FinnhubQuote = self
+            .client
+            .get(&url)
+            .query(&[("symbol", symbol), ("token", &self.api_key)])
+            .send()
+            .await
+            .map_err(|_| MarketDataError::ProviderUnavailable)?
+            .json()
+            .await
+            .map_err(|_| MarketDataError::InvalidFormat)?;
+
+        if quote.c == 0.0 {
+            return Err(MarketDataError::symbol_not_found(symbol).into());
+        }
+
+        Ok(MarketData::new(symbol.to_string(), quote.c, quote.v.unwrap_or(0))
+            .with_day_range(quote.h, quote.l)
+            .with_change(quote.pc))
+    }
+
+    async fn fetch_history(&self, symbol: &str, period: TimePeriod) -> Result<HistoricalData> {
+        #[derive(Debug, Deserialize)]
+        struct Candles {
+            o: Vec<f64>,
+            h: Vec<f64>,
+            l: Vec<f64>,
+            c: Vec<f64>,
+            v: Vec<u64>,
+            t: Vec<i64>,
+            s: String,
+        }
+
+        let to = Utc::now().timestamp();
+        let from = to - (period.to_days() as i64) * 86_400;
+
+        let url = format!("{}/stock/candle", self.base_url);
+        let candles: Candles = self
+            .client
+            .get(&url)
+            .query(&[
+                ("symbol", symbol.to_string()),
+                ("resolution", "D".to_string()),
+                ("from", from.to_string()),
+                ("to", to.to_string()),
+                ("token", self.api_key.clone()),
+            ])
+            .send()
+            .await
+            .map_err(|_| MarketDataError::ProviderUnavailable)?
+            .json()
+            .await
+            .map_err(|_| MarketDataError::InvalidFormat)?;
+
+        if candles.s != "ok" {
+            return Err(MarketDataError::InsufficientHistoricalData(symbol.to_string()).into());
+        }
+
+        let mut historical_data = HistoricalData::new(symbol.to_string(), period);
+
+        for i in 0..candles.t.len() {
+            let Some(timestamp) = chrono::DateTime::from_timestamp(candles.t[i], 0) else {
+                continue;
+            };
+
+            if let Ok(point) = PricePoint::new(timestamp, candles.o[i], candles.h[i], candles.l[i], candles.c[i], candles.v[i]) {
+                historical_data.add_price_point(point);
+            }
+        }
+
+        if historical_data.data_points.is_empty() {
+            return Err(MarketDataError::InsufficientHistoricalData(symbol.to_string()).into());
+        }
+
+        Ok(historical_data)
+    }
+
+    async fn fetch_summary(&self, symbol: &str) -> Result<MarketSummary> {
+        let quote = self.fetch_quote(symbol).await?;
+        Ok(MarketSummary {
+            symbol: quote.symbol,
+            current_price: quote.price,
+            daily_change: quote.change,
+            daily_change_percent: quote.change_percent,
+            volume: quote.volume,
+            market_status: MarketStatus::Open,
+            last_trade_time: quote.timestamp,
+        })
+    }
+}
+
+/// [Twelve Data](https://twelvedata.com/docs#quote) `/quote` provider.
+pub struct TwelveDataProvider {
+    api_key: String,
+    client: Client,
+    base_url: String,
+}
+
+impl TwelveDataProvider {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            client: Client::new(),
+            base_url: "https://api.twelvedata.com".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TwelveDataQuote {
+    close: String,
+    previous_close: String,
+    high: String,
+    low: String,
+    volume: Option<String>,
+}
+
+#[async_trait]
+impl MarketDataProvider for TwelveDataProvider {
+    async fn fetch_quote(&self, symbol: &str) -> Result<MarketData> {
+        let url = format!("{}/quote", self.base_url);
+        let quote: TwelveDataQuote = self
+            .client
+            .get(&url)
+            .query(&[("symbol", symbol), ("apikey", &self.api_key)])
+            .send()
+            .await
+            .map_err(|_| MarketDataError::ProviderUnavailable)?
+            .json()
+            .await
+            .map_err(|_| MarketDataError::InvalidFormat)?;
+
+        let price: f64 = quote.close.parse().map_err(|_| MarketDataError::InvalidFormat)?;
+        let previous_close: f64 = quote.previous_close.parse().map_err(|_| MarketDataError::InvalidFormat)?;
+        let high: f64 = quote.high.parse().map_err(|_| MarketDataError::InvalidFormat)?;
+        let low: f64 = quote.low.parse().map_err(|_| MarketDataError::InvalidFormat)?;
+        let volume: u64 = quote
+            .volume
+            .as_deref()
+            .unwrap_or("0")
+            .parse()
+            .map_err(|_| MarketDataError::InvalidFormat)?;
+
+        Ok(MarketData::new(symbol.to_string(), price, volume)
+            .with_day_range(high, low)
+            .with_change(previous_close))
+    }
+
+    async fn fetch_history(&self, symbol: &str, period: TimePeriod) -> Result<HistoricalData> {
+        #[derive(Debug, Deserialize)]
+        struct TimeSeriesValue {
+            datetime: String,
+            open: String,
+            high: String,
+            low: String,
+            close: String,
+            volume: Option<String>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct TimeSeriesResponse {
+            values: Option<Vec<TimeSeriesValue>>,
+        }
+
+        let url = format!("{}/time_series", self.base_url);
+        let response: TimeSeriesResponse = self
+            .client
+            .get(&url)
+            .query(&[
+                ("symbol", symbol.to_string()),
+                ("interval", "1day".to_string()),
+                ("outputsize", period.to_days().to_string()),
+                ("apikey", self.api_key.clone()),
+            ])
+            .send()
+            .await
+            .map_err(|_| MarketDataError::ProviderUnavailable)?
+            .json()
+            .await
+            .map_err(|_| MarketDataError::InvalidFormat)?;
+
+        let values = response
+            .values
+            .ok_or_else(|| MarketDataError::symbol_not_found(symbol))?;
+
+        let mut historical_data = HistoricalData::new(symbol.to_string(), period);
+
+        for value in values {
+            let Ok(timestamp) = chrono::NaiveDateTime::parse_from_str(&value.datetime, "%Y-%m-%d")
+                .or_else(|_| chrono::NaiveDateTime::parse_from_str(&value.datetime, "%Y-%m-%d %H:%M:%S"))
+                .map(|d| d.and_utc())
+            else {
+                continue;
+            };
+
+            let (Ok(open), Ok(high), Ok(low), Ok(close)) = (
+                value.open.parse(),
+                value.high.parse(),
+                value.low.parse(),
+                value.close.parse(),
+            ) else {
+                continue;
+            };
+            let volume: u64 = value.volume.as_deref().unwrap_or("0").parse().unwrap_or(0);
+
+            if let Ok(point) = PricePoint::new(timestamp, open, high, low, close, volume) {
+                historical_data.add_price_point(point);
+            }
+        }
+
+        if historical_data.data_points.is_empty() {
+            return Err(MarketDataError::InsufficientHistoricalData(symbol.to_string()).into());
+        }
+
+        Ok(historical_data)
+    }
+
+    async fn fetch_summary(&self, symbol: &str) -> Result<MarketSummary> {
+        let quote = self.fetch_quote(symbol).await?;
+        Ok(MarketSummary {
+            symbol: quote.symbol,
+            current_price: quote.price,
+            daily_change: quote.change,
+            daily_change_percent: quote.change_percent,
+            volume: quote.volume,
+            market_status: MarketStatus::Open,
+            last_trade_time: quote.timestamp,
+        })
+    }
+}
+
+/// Cache key for history lookups: a symbol paired with the requested window,
+/// since the same symbol at two different periods isn't interchangeable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct HistoryCacheKey {
+    symbol: String,
+    period_days: u32,
+}
+
+/// Wraps any [`MarketDataProvider`] with a TTL cache, keyed by `(symbol,
+/// TimePeriod)` for history and by `symbol` alone for quotes. Summaries are
+/// not cached: `fetch_summary` always calls the wrapped provider.
+/// Reuses [`HistoricalData::is_stale`] against `cache_expire_time` so a call
+/// inside the window returns the cached series instead of re-hitting the
+/// vendor (and its rate limit).
+pub struct CachingProvider {
+    inner: Box<dyn MarketDataProvider>,
+    cache_expire_time: Duration,
+    quotes: Mutex<HashMap<String, MarketData>>,
+    history: Mutex<HashMap<HistoryCacheKey, HistoricalData>>,
+}
+
+impl CachingProvider {
+    pub fn new(inner: Box<dyn MarketDataProvider>, cache_expire_time: Duration) -> Self {
+        Self {
+            inner,
+            cache_expire_time,
+            quotes: Mutex::new(HashMap::new()),
+            history: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn quote_is_fresh(&self, quote: &MarketData) -> bool {
+        let age = Utc::now().signed_duration_since(quote.timestamp);
+        age.to_std().map(|age| age <= self.cache_expire_time).unwrap_or(false)
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for CachingProvider {
+    async fn fetch_quote(&self, symbol: &str) -> Result<MarketData> {
+        if let Some(cached) = self.quotes.lock().unwrap().get(symbol) {
+            if self.quote_is_fresh(cached) {
+                return Ok(cached.clone());
+            }
+        }
+
+        let quote = self.inner.fetch_quote(symbol).await?;
+        self.quotes.lock().unwrap().insert(symbol.to_string(), quote.clone());
+        Ok(quote)
+    }
+
+    async fn fetch_history(&self, symbol: &str, period: TimePeriod) -> Result<HistoricalData> {
+        let key = HistoryCacheKey { symbol: symbol.to_string(), period_days: period.to_days() };
+
+        if let Some(cached) = self.history.lock().unwrap().get(&key) {
+            if !cached.is_stale(self.cache_expire_time.as_secs() as i64 / 60) {
+                return Ok(cached.clone());
+            }
+        }
+
+        let data = self.inner.fetch_history(symbol, period).await?;
+        self.history.lock().unwrap().insert(key, data.clone());
+        Ok(data)
+    }
+
+    async fn fetch_summary(&self, symbol: &str) -> Result<MarketSummary> {
+        self.inner.fetch_summary(symbol).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    struct CountingProvider {
+        calls: Arc<Mutex<u32>>,
+    }
+
+    #[async_trait]
+    impl MarketDataProvider for CountingProvider {
+        async fn fetch_quote(&self, symbol: &str) -> Result<MarketData> {
+            *self.calls.lock().unwrap() += 1;
+            Ok(MarketData::new(symbol.to_string(), 100.0, 1_000))
+        }
+
+        async fn fetch_history(&self, symbol: &str, period: TimePeriod) -> Result<HistoricalData> {
+            *self.calls.lock().unwrap() += 1;
+            let mut data = HistoricalData::new(symbol.to_string(), period);
+            data.add_price_point(PricePoint::new(Utc::now(), 100.0, 101.0, 99.0, 100.5, 500).unwrap());
+            Ok(data)
+        }
+
+        async fn fetch_summary(&self, symbol: &str) -> Result<MarketSummary> {
+            Ok(MarketSummary {
+                symbol: symbol.to_string(),
+                current_price: Price::from_f64(100.0),
+                daily_change: Price::ZERO,
+                daily_change_percent: 0.0,
+                volume: 1_000,
+                market_status: MarketStatus::Open,
+                last_trade_time: Utc::now(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_caching_provider_reuses_fresh_quote() {
+        let calls = Arc::new(Mutex::new(0));
+        let counting = CountingProvider { calls: calls.clone() };
+        let provider = CachingProvider::new(Box::new(counting), Duration::from_secs(60));
+
+        provider.fetch_quote("AAPL").await.unwrap();
+        provider.fetch_quote("AAPL").await.unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_caching_provider_reuses_fresh_history() {
+        let calls = Arc::new(Mutex::new(0));
+        let counting = CountingProvider { calls: calls.clone() };
+        let provider = CachingProvider::new(Box::new(counting), Duration::from_secs(3600));
+
+        provider.fetch_history("AAPL", TimePeriod::OneMonth).await.unwrap();
+        provider.fetch_history("AAPL", TimePeriod::OneMonth).await.unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_caching_provider_refetches_after_expiry() {
+        let calls = Arc::new(Mutex::new(0));
+        let counting = CountingProvider { calls: calls.clone() };
+        let provider = CachingProvider::new(Box::new(counting), Duration::from_secs(0));
+
+        provider.fetch_quote("AAPL").await.unwrap();
+        provider.fetch_quote("AAPL").await.unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_data_provider_config_build_requires_api_key() {
+        let config = DataProviderConfig::default();
+
+        let result = config.build(Vendor::AlphaVantage);
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,250 @@
+// Bad-price repair pass for `HistoricalData`, importing the data-repair idea
+// from `yfinance`: detect and correct common OHLC anomalies -- 100x/0.01x
+// unit errors, zero/missing prices, and high/low/close/open range
+// violations -- before indicators are computed over the series.
+
+use chrono::{DateTime, Utc};
+
+use crate::data::{HistoricalData, Price, PricePoint};
+
+/// Round factors `yfinance`-style repair checks against: a currency/unit
+/// mixup (cents vs. dollars) or a missed stock-split adjustment.
+const SUSPECT_RATIOS: &[f64] = &[100.0, 0.01, 2.0, 0.5, 3.0, 1.0 / 3.0, 4.0, 0.25];
+
+/// How close an observed ratio must be to a `SUSPECT_RATIOS` entry (as a
+/// fraction of that entry) to be treated as a unit error rather than
+/// genuine volatility.
+const RATIO_TOLERANCE: f64 = 0.03;
+
+/// One correction made by [`HistoricalData::repair`], so callers can audit
+/// what changed instead of having their data silently mutated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairedBar {
+    pub timestamp: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// Every correction [`HistoricalData::repair`] made, in timestamp order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepairReport {
+    pub repairs: Vec<RepairedBar>,
+}
+
+impl RepairReport {
+    pub fn is_clean(&self) -> bool {
+        self.repairs.is_empty()
+    }
+}
+
+impl HistoricalData {
+    /// Detect and correct OHLC anomalies in place, returning a report of
+    /// every bar touched. Three passes, in order: (1) rescale outlier bars
+    /// that are a round multiple away from their neighbors' median; (2)
+    /// interpolate zero/missing prices between adjacent valid bars; (3)
+    /// widen `high`/`low` to include `open`/`close` where the range was
+    /// violated.
+    pub fn repair(&mut self) -> RepairReport {
+        let mut report = RepairReport::default();
+
+        self.repair_unit_errors(&mut report);
+        self.repair_zero_prices(&mut report);
+        self.repair_range_violations(&mut report);
+
+        report
+    }
+
+    fn repair_unit_errors(&mut self, report: &mut RepairReport) {
+        const WINDOW: usize = 5;
+        let closes: Vec<f64> = self.data_points.iter().map(|p| p.close.to_f64()).collect();
+
+        for i in 0..closes.len() {
+            if closes[i] <= 0.0 {
+                continue;
+            }
+
+            let lo = i.saturating_sub(WINDOW);
+            let hi = (i + WINDOW + 1).min(closes.len());
+            let mut neighbors: Vec<f64> = (lo..hi).filter(|&j| j != i && closes[j] > 0.0).map(|j| closes[j]).collect();
+            if neighbors.is_empty() {
+                continue;
+            }
+            neighbors.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let median = neighbors[neighbors.len() / 2];
+            if median <= 0.0 {
+                continue;
+            }
+
+            let ratio = closes[i] / median;
+            if let Some(&suspect) = SUSPECT_RATIOS.iter().find(|&&r| (ratio / r - 1.0).abs() < RATIO_TOLERANCE) {
+                let scale = 1.0 / suspect;
+                let bar = &mut self.data_points[i];
+                bar.open = bar.open * scale;
+                bar.high = bar.high * scale;
+                bar.low = bar.low * scale;
+                bar.close = bar.close * scale;
+                report.repairs.push(RepairedBar {
+                    timestamp: bar.timestamp,
+                    reason: format!("rescaled by {:.4} (ratio to neighbor median was {:.4})", scale, ratio),
+                });
+            }
+        }
+    }
+
+    fn repair_zero_prices(&mut self, report: &mut RepairReport) {
+        let len = self.data_points.len();
+        for i in 0..len {
+            if self.data_points[i].close > Price::ZERO && self.data_points[i].open > Price::ZERO {
+                continue;
+            }
+
+            let prev = (0..i).rev().find(|&j| self.data_points[j].close > Price::ZERO).map(|j| self.data_points[j].close.to_f64());
+            let next = (i + 1..len).find(|&j| self.data_points[j].close > Price::ZERO).map(|j| self.data_points[j].close.to_f64());
+
+            let interpolated = match (prev, next) {
+                (Some(p), Some(n)) => (p + n) / 2.0,
+                (Some(p), None) => p,
+                (None, Some(n)) => n,
+                (None, None) => continue,
+            };
+
+            let bar = &mut self.data_points[i];
+            bar.open = Price::from_f64(interpolated);
+            bar.high = Price::from_f64(interpolated);
+            bar.low = Price::from_f64(interpolated);
+            bar.close = Price::from_f64(interpolated);
+            report.repairs.push(RepairedBar {
+                timestamp: bar.timestamp,
+                reason: "zero/missing price interpolated from adjacent bars".to_string(),
+            });
+        }
+    }
+
+    fn repair_range_violations(&mut self, report: &mut RepairReport) {
+        for bar in self.data_points.iter_mut() {
+            let mut violated = false;
+
+            if bar.high < bar.low {
+                std::mem::swap(&mut bar.high, &mut bar.low);
+                violated = true;
+            }
+            if bar.open > bar.high {
+                bar.high = bar.open;
+                violated = true;
+            }
+            if bar.open < bar.low {
+                bar.low = bar.open;
+                violated = true;
+            }
+            if bar.close > bar.high {
+                bar.high = bar.close;
+                violated = true;
+            }
+            if bar.close < bar.low {
+                bar.low = bar.close;
+                violated = true;
+            }
+
+            if violated {
+                report.repairs.push(RepairedBar {
+                    timestamp: bar.timestamp,
+                    reason: "high/low widened to include open/close".to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// Build a `PricePoint` bypassing `PricePoint::new`'s validation, for tests
+/// that need to construct an already-anomalous bar.
+#[cfg(test)]
+fn unchecked_bar(timestamp: DateTime<Utc>, open: f64, high: f64, low: f64, close: f64) -> PricePoint {
+    PricePoint {
+        timestamp,
+        open: Price::from_f64(open),
+        high: Price::from_f64(high),
+        low: Price::from_f64(low),
+        close: Price::from_f64(close),
+        volume: 1_000,
+        adjusted_close: None,
+        market_status: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::TimePeriod;
+    use chrono::Duration;
+
+    fn series(bars: Vec<PricePoint>) -> HistoricalData {
+        let mut data = HistoricalData { symbol: "TEST".to_string(), data_points: bars, period: TimePeriod::OneMonth, last_updated: Utc::now() };
+        data.data_points.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        data
+    }
+
+    fn ts(day: i64) -> DateTime<Utc> {
+        Utc::now() - Duration::days(30 - day)
+    }
+
+    #[test]
+    fn test_repair_is_a_no_op_on_clean_data() {
+        let mut data = series(vec![
+            unchecked_bar(ts(0), 100.0, 101.0, 99.0, 100.5),
+            unchecked_bar(ts(1), 100.5, 102.0, 100.0, 101.5),
+        ]);
+
+        let report = data.repair();
+
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn test_repair_rescales_100x_outlier() {
+        let mut data = series(vec![
+            unchecked_bar(ts(0), 100.0, 101.0, 99.0, 100.0),
+            unchecked_bar(ts(1), 10_000.0, 10_100.0, 9_900.0, 10_000.0),
+            unchecked_bar(ts(2), 100.0, 101.0, 99.0, 100.0),
+            unchecked_bar(ts(3), 100.0, 101.0, 99.0, 100.0),
+        ]);
+
+        let report = data.repair();
+
+        assert!(!report.is_clean());
+        assert!((data.data_points[1].close.to_f64() - 100.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_repair_interpolates_zero_price() {
+        let mut data = series(vec![
+            unchecked_bar(ts(0), 100.0, 101.0, 99.0, 100.0),
+            unchecked_bar(ts(1), 0.0, 0.0, 0.0, 0.0),
+            unchecked_bar(ts(2), 104.0, 105.0, 103.0, 104.0),
+        ]);
+
+        data.repair();
+
+        assert_eq!(data.data_points[1].close.to_f64(), 102.0);
+    }
+
+    #[test]
+    fn test_repair_widens_range_to_include_open_and_close() {
+        let mut data = series(vec![unchecked_bar(ts(0), 105.0, 101.0, 99.0, 95.0)]);
+
+        let report = data.repair();
+
+        let bar = &data.data_points[0];
+        assert!(bar.high >= bar.open && bar.high >= bar.close);
+        assert!(bar.low <= bar.open && bar.low <= bar.close);
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_repair_swaps_high_and_low_when_inverted() {
+        let mut data = series(vec![unchecked_bar(ts(0), 100.0, 98.0, 102.0, 100.0)]);
+
+        data.repair();
+
+        let bar = &data.data_points[0];
+        assert!(bar.high >= bar.low);
+    }
+}
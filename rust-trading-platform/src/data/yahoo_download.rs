@@ -0,0 +1,263 @@
+// Yahoo-style historical downloader for this module's plain `HistoricalData`,
+// following the query-parameter scheme `yfinance`-style clients use against
+// Yahoo's `v8/finance/chart` endpoint: an explicit `period1`/`period2`
+// epoch-second range rather than a named `range`, plus `includePrePost` and
+// `events` toggles. Complements `data::providers`, which covers vendors that
+// need an API key; this one needs none.
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::data::{HistoricalData, MarketStatus, PricePoint, TimePeriod};
+use crate::error::{MarketDataError, Result, TradingPlatformError};
+
+/// Bar granularity for [`YahooDownloadClient::download`], named after the
+/// `1m`/`5m`/`1h`/`1d`/`1wk`/`1mo` intervals Yahoo's chart endpoint accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interval {
+    OneMinute,
+    FiveMinute,
+    OneHour,
+    OneDay,
+    OneWeek,
+    OneMonth,
+}
+
+impl Interval {
+    fn as_yahoo_str(self) -> &'static str {
+        match self {
+            Interval::OneMinute => "1m",
+            Interval::FiveMinute => "5m",
+            Interval::OneHour => "1h",
+            Interval::OneDay => "1d",
+            Interval::OneWeek => "1wk",
+            Interval::OneMonth => "1mo",
+        }
+    }
+}
+
+/// Options for [`YahooDownloadClient::download`], mirroring the
+/// `yfinance.download(prepost=, actions=)` toggles.
+#[derive(Debug, Clone, Copy)]
+pub struct DownloadOptions {
+    pub interval: Interval,
+    /// Include pre-market/after-hours bars alongside the regular session.
+    pub prepost: bool,
+    /// Request split/dividend `events` alongside price bars. This only
+    /// changes the request Yahoo receives; the `events` block of the
+    /// response is not parsed (corporate actions are applied separately by
+    /// `HistoricalData::apply_corporate_actions` in `data::corporate_actions`).
+    pub actions: bool,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            interval: Interval::OneDay,
+            prepost: false,
+            actions: false,
+        }
+    }
+}
+
+/// Downloads historical bars from Yahoo's `v8/finance/chart` endpoint using
+/// an explicit `period1`/`period2` epoch-second range instead of a named
+/// `range`, so callers can request arbitrary windows.
+pub struct YahooDownloadClient {
+    client: Client,
+    base_url: String,
+}
+
+impl YahooDownloadClient {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            base_url: "https://query1.finance.yahoo.com".to_string(),
+        }
+    }
+
+    /// Download bars for `symbol` between `period1` and `period2` (both
+    /// epoch seconds), at `options.interval` granularity. When
+    /// `options.prepost` is set, returned `PricePoint`s falling outside the
+    /// regular session are tagged `PreMarket`/`AfterHours` via Yahoo's
+    /// `meta.current_trading_period`; bars inside the regular session are
+    /// tagged `Open`.
+    pub async fn download(
+        &self,
+        symbol: &str,
+        period1: i64,
+        period2: i64,
+        options: DownloadOptions,
+    ) -> Result<HistoricalData> {
+        let url = format!("{}/v8/finance/chart/{}", self.base_url, symbol);
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[
+                ("period1", period1.to_string()),
+                ("period2", period2.to_string()),
+                ("interval", options.interval.as_yahoo_str().to_string()),
+                ("includePrePost", options.prepost.to_string()),
+                ("events", if options.actions { "div,splits".to_string() } else { String::new() }),
+            ])
+            .send()
+            .await
+            .map_err(|_| MarketDataError::ProviderUnavailable)?
+            .json::<YahooChartResponse>()
+            .await
+            .map_err(|_| MarketDataError::InvalidFormat)?;
+
+        let result = response
+            .chart
+            .result
+            .into_iter()
+            .next()
+            .ok_or_else(|| MarketDataError::symbol_not_found(symbol))?;
+
+        let sessions = result.meta.current_trading_period;
+        let quote = result
+            .indicators
+            .quote
+            .into_iter()
+            .next()
+            .ok_or_else(|| MarketDataError::InsufficientHistoricalData(symbol.to_string()))?;
+
+        let period = TimePeriod::Custom {
+            days: (((period2 - period1).max(0)) / 86_400) as u32,
+        };
+        let mut historical_data = HistoricalData::new(symbol.to_string(), period);
+
+        for (i, &ts) in result.timestamp.iter().enumerate() {
+            let (Some(open), Some(high), Some(low), Some(close)) = (
+                quote.open.get(i).copied().flatten(),
+                quote.high.get(i).copied().flatten(),
+                quote.low.get(i).copied().flatten(),
+                quote.close.get(i).copied().flatten(),
+            ) else {
+                continue;
+            };
+            let volume = quote.volume.get(i).copied().flatten().unwrap_or(0);
+
+            let Some(timestamp) = DateTime::from_timestamp(ts as i64, 0) else {
+                continue;
+            };
+
+            let status = sessions.as_ref().map(|s| s.classify(ts)).unwrap_or(MarketStatus::Open);
+
+            if let Ok(point) = PricePoint::new(timestamp, open, high, low, close, volume) {
+                historical_data.add_price_point(point.with_market_status(status));
+            }
+        }
+
+        if historical_data.data_points.is_empty() {
+            return Err(MarketDataError::InsufficientHistoricalData(symbol.to_string()).into());
+        }
+
+        Ok(historical_data)
+    }
+}
+
+impl Default for YahooDownloadClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooChartResponse {
+    chart: YahooChart,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooChart {
+    result: Vec<YahooChartResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooChartResult {
+    meta: YahooChartMeta,
+    timestamp: Vec<u64>,
+    indicators: YahooChartIndicators,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct YahooChartMeta {
+    current_trading_period: Option<TradingPeriods>,
+}
+
+/// Yahoo's `pre`/`regular`/`post` session boundaries, each giving `start`
+/// and `end` as epoch seconds.
+#[derive(Debug, Deserialize)]
+struct TradingPeriods {
+    pre: SessionWindow,
+    regular: SessionWindow,
+    post: SessionWindow,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionWindow {
+    start: u64,
+    end: u64,
+}
+
+impl TradingPeriods {
+    /// Classify `timestamp` by time of day. The windows are those of the
+    /// current trading day, so `timestamp` is first shifted by whole days
+    /// onto the day that starts at `pre.start`.
+    fn classify(&self, timestamp: u64) -> MarketStatus {
+        let offset = (timestamp as i64 - self.pre.start as i64).rem_euclid(86_400);
+        let timestamp = self.pre.start + offset as u64;
+        if timestamp >= self.pre.start && timestamp < self.pre.end {
+            MarketStatus::PreMarket
+        } else if timestamp >= self.post.start && timestamp < self.post.end {
+            MarketStatus::AfterHours
+        } else if timestamp >= self.regular.start && timestamp < self.regular.end {
+            MarketStatus::Open
+        } else {
+            MarketStatus::Closed
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooChartIndicators {
+    quote: Vec<YahooChartQuote>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooChartQuote {
+    open: Vec<Option<f64>>,
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    volume: Vec<Option<u64>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trading_periods_classifies_pre_regular_post() {
+        let sessions = TradingPeriods {
+            pre: SessionWindow { start: 100, end: 200 },
+            regular: SessionWindow { start: 200, end: 300 },
+            post: SessionWindow { start: 300, end: 400 },
+        };
+
+        assert_eq!(sessions.classify(150), MarketStatus::PreMarket);
+        assert_eq!(sessions.classify(250), MarketStatus::Open);
+        assert_eq!(sessions.classify(350), MarketStatus::AfterHours);
+        assert_eq!(sessions.classify(50), MarketStatus::Closed);
+    }
+
+    #[test]
+    fn test_interval_maps_to_yahoo_query_strings() {
+        assert_eq!(Interval::OneMinute.as_yahoo_str(), "1m");
+        assert_eq!(Interval::OneWeek.as_yahoo_str(), "1wk");
+        assert_eq!(Interval::OneMonth.as_yahoo_str(), "1mo");
+    }
+}
@@ -1,71 +1,339 @@
-// Database migration utilities
+// Database migration utilities: a small versioned migration framework.
+// Each `Migration` is applied inside its own transaction and recorded in
+// `schema_migrations`, so `migrate`/`rollback_to` always know exactly
+// what has run without relying on table-existence guesswork.
 
-use sqlx::{SqlitePool, Row};
+use sqlx::{Row, Sqlite, SqlitePool, Transaction};
+use crate::database::datetime_to_string;
 use crate::error::Result;
+use chrono::Utc;
+use futures::future::BoxFuture;
+
+/// A Rust-side data transform that runs inside the same transaction as a
+/// migration's SQL `up`/`down` script, for changes plain SQL can't express
+/// (e.g. reserializing a JSON column after a shape change). Plain `fn`s
+/// rather than closures, since migrations are `'static` table entries.
+pub type MigrationFn = for<'c> fn(&'c mut Transaction<'_, Sqlite>) -> BoxFuture<'c, Result<()>>;
+
+/// One schema change: an ascending `version`, a human-readable `name`, and
+/// the statements to apply it (`up`) or revert it (`down`). Each entry is
+/// one complete top-level statement (a `CREATE TRIGGER ... BEGIN ... END`
+/// counts as one, even though its body contains further `;`s) run in order
+/// inside a single transaction. `up_transform`/`down_transform` are optional
+/// Rust-side steps run after `up` (or before `down`) in that same
+/// transaction; most migrations leave these `None`.
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub up: &'static [&'static str],
+    pub down: &'static [&'static str],
+    pub up_transform: Option<MigrationFn>,
+    pub down_transform: Option<MigrationFn>,
+}
 
-/// Initialize the database with all required tables
-pub async fn initialize_database(pool: &SqlitePool) -> Result<()> {
-    // Enable foreign key constraints
-    sqlx::query("PRAGMA foreign_keys = ON")
-        .execute(pool)
-        .await?;
+/// The full migration history, in ascending version order. Schema changes
+/// are appended here as new versions rather than editing an
+/// already-applied migration's SQL.
+pub static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial_schema",
+        up: &[
+            r#"
+            CREATE TABLE strategies (
+                id TEXT PRIMARY KEY,
+                strategy_type TEXT NOT NULL,
+                symbol TEXT NOT NULL,
+                parameters TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                is_active BOOLEAN NOT NULL DEFAULT TRUE
+            )
+            "#,
+            r#"
+            CREATE TABLE trades (
+                id TEXT PRIMARY KEY,
+                strategy_id TEXT NOT NULL,
+                symbol TEXT NOT NULL,
+                action TEXT NOT NULL,
+                quantity REAL NOT NULL,
+                price REAL NOT NULL,
+                timestamp TEXT NOT NULL,
+                explanation TEXT,
+                commission REAL DEFAULT 0.0,
+                realized_pnl REAL,
+                trade_value REAL NOT NULL,
+                FOREIGN KEY (strategy_id) REFERENCES strategies(id) ON DELETE CASCADE
+            )
+            "#,
+            r#"
+            CREATE TABLE performance_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                strategy_id TEXT NOT NULL,
+                total_return REAL NOT NULL,
+                total_trades INTEGER NOT NULL,
+                timestamp TEXT NOT NULL,
+                metrics TEXT NOT NULL,
+                FOREIGN KEY (strategy_id) REFERENCES strategies(id) ON DELETE CASCADE
+            )
+            "#,
+            r#"
+            CREATE TABLE market_data_cache (
+                symbol TEXT NOT NULL,
+                timestamp TEXT NOT NULL,
+                price REAL NOT NULL,
+                volume INTEGER,
+                change_percent REAL,
+                market_cap INTEGER,
+                day_high REAL,
+                day_low REAL,
+                previous_close REAL,
+                PRIMARY KEY (symbol, timestamp)
+            )
+            "#,
+            "CREATE INDEX idx_strategies_symbol ON strategies(symbol)",
+            "CREATE INDEX idx_strategies_active ON strategies(is_active)",
+            "CREATE INDEX idx_strategies_created_at ON strategies(created_at)",
+            "CREATE INDEX idx_trades_strategy_id ON trades(strategy_id)",
+            "CREATE INDEX idx_trades_symbol ON trades(symbol)",
+            "CREATE INDEX idx_trades_timestamp ON trades(timestamp)",
+            "CREATE INDEX idx_trades_action ON trades(action)",
+            "CREATE INDEX idx_performance_strategy_id ON performance_snapshots(strategy_id)",
+            "CREATE INDEX idx_performance_timestamp ON performance_snapshots(timestamp)",
+            "CREATE INDEX idx_market_data_symbol ON market_data_cache(symbol)",
+            "CREATE INDEX idx_market_data_timestamp ON market_data_cache(timestamp)",
+        ],
+        down: &[
+            "DROP TABLE IF EXISTS market_data_cache",
+            "DROP TABLE IF EXISTS performance_snapshots",
+            "DROP TABLE IF EXISTS trades",
+            "DROP TABLE IF EXISTS strategies",
+        ],
+        up_transform: None,
+        down_transform: None,
+    },
+    Migration {
+        version: 2,
+        name: "trade_and_strategy_history",
+        up: &[
+            r#"
+            CREATE TABLE trades_history (
+                history_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                id TEXT NOT NULL,
+                strategy_id TEXT NOT NULL,
+                symbol TEXT NOT NULL,
+                action TEXT NOT NULL,
+                quantity REAL NOT NULL,
+                price REAL NOT NULL,
+                timestamp TEXT NOT NULL,
+                explanation TEXT,
+                commission REAL,
+                realized_pnl REAL,
+                trade_value REAL NOT NULL,
+                operation TEXT NOT NULL,
+                changed_at TEXT NOT NULL
+            )
+            "#,
+            r#"
+            CREATE TABLE strategies_history (
+                history_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                id TEXT NOT NULL,
+                strategy_type TEXT NOT NULL,
+                symbol TEXT NOT NULL,
+                parameters TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                is_active BOOLEAN NOT NULL,
+                operation TEXT NOT NULL,
+                changed_at TEXT NOT NULL
+            )
+            "#,
+            "CREATE INDEX idx_trades_history_id ON trades_history(id)",
+            "CREATE INDEX idx_strategies_history_id ON strategies_history(id)",
+            r#"
+            CREATE TRIGGER trades_history_on_update AFTER UPDATE ON trades
+            BEGIN
+                INSERT INTO trades_history (
+                    id, strategy_id, symbol, action, quantity, price, timestamp,
+                    explanation, commission, realized_pnl, trade_value, operation, changed_at
+                )
+                VALUES (
+                    OLD.id, OLD.strategy_id, OLD.symbol, OLD.action, OLD.quantity, OLD.price, OLD.timestamp,
+                    OLD.explanation, OLD.commission, OLD.realized_pnl, OLD.trade_value, 'update', datetime('now')
+                );
+            END
+            "#,
+            r#"
+            CREATE TRIGGER trades_history_on_delete AFTER DELETE ON trades
+            BEGIN
+                INSERT INTO trades_history (
+                    id, strategy_id, symbol, action, quantity, price, timestamp,
+                    explanation, commission, realized_pnl, trade_value, operation, changed_at
+                )
+                VALUES (
+                    OLD.id, OLD.strategy_id, OLD.symbol, OLD.action, OLD.quantity, OLD.price, OLD.timestamp,
+                    OLD.explanation, OLD.commission, OLD.realized_pnl, OLD.trade_value, 'delete', datetime('now')
+                );
+            END
+            "#,
+            r#"
+            CREATE TRIGGER strategies_history_on_update AFTER UPDATE ON strategies
+            BEGIN
+                INSERT INTO strategies_history (
+                    id, strategy_type, symbol, parameters, created_at, is_active, operation, changed_at
+                )
+                VALUES (
+                    OLD.id, OLD.strategy_type, OLD.symbol, OLD.parameters, OLD.created_at, OLD.is_active, 'update', datetime('now')
+                );
+            END
+            "#,
+            r#"
+            CREATE TRIGGER strategies_history_on_delete AFTER DELETE ON strategies
+            BEGIN
+                INSERT INTO strategies_history (
+                    id, strategy_type, symbol, parameters, created_at, is_active, operation, changed_at
+                )
+                VALUES (
+                    OLD.id, OLD.strategy_type, OLD.symbol, OLD.parameters, OLD.created_at, OLD.is_active, 'delete', datetime('now')
+                );
+            END
+            "#,
+        ],
+        down: &[
+            "DROP TRIGGER IF EXISTS strategies_history_on_delete",
+            "DROP TRIGGER IF EXISTS strategies_history_on_update",
+            "DROP TRIGGER IF EXISTS trades_history_on_delete",
+            "DROP TRIGGER IF EXISTS trades_history_on_update",
+            "DROP TABLE IF EXISTS strategies_history",
+            "DROP TABLE IF EXISTS trades_history",
+        ],
+        up_transform: None,
+        down_transform: None,
+    },
+    Migration {
+        version: 3,
+        name: "strategy_position_and_performance_views",
+        up: &[
+            r#"
+            CREATE VIEW strategy_positions AS
+            SELECT
+                strategy_id,
+                symbol,
+                SUM(CASE
+                    WHEN action IN ('BUY', 'EXIT_SHORT') THEN quantity
+                    WHEN action IN ('SELL', 'SHORT_SELL') THEN -quantity
+                    ELSE 0
+                END) AS net_quantity,
+                SUM(commission) AS total_commission,
+                CASE
+                    WHEN SUM(CASE WHEN action IN ('BUY', 'EXIT_SHORT') THEN quantity WHEN action IN ('SELL', 'SHORT_SELL') THEN -quantity ELSE 0 END) = 0 THEN 0.0
+                    ELSE SUM(CASE WHEN action IN ('BUY', 'EXIT_SHORT') THEN quantity * price WHEN action IN ('SELL', 'SHORT_SELL') THEN -quantity * price ELSE 0 END)
+                         / SUM(CASE WHEN action IN ('BUY', 'EXIT_SHORT') THEN quantity WHEN action IN ('SELL', 'SHORT_SELL') THEN -quantity ELSE 0 END)
+                END AS average_cost
+            FROM trades
+            GROUP BY strategy_id, symbol
+            "#,
+            r#"
+            CREATE VIEW strategy_latest_performance AS
+            SELECT *
+            FROM performance_snapshots ps
+            WHERE ps.timestamp = (
+                SELECT MAX(ps2.timestamp)
+                FROM performance_snapshots ps2
+                WHERE ps2.strategy_id = ps.strategy_id
+            )
+            "#,
+        ],
+        down: &[
+            "DROP VIEW IF EXISTS strategy_latest_performance",
+            "DROP VIEW IF EXISTS strategy_positions",
+        ],
+        up_transform: None,
+        down_transform: None,
+    },
+    Migration {
+        version: 4,
+        name: "market_data_stable_price",
+        up: &[
+            "ALTER TABLE market_data_cache ADD COLUMN stable_price REAL",
+        ],
+        down: &[
+            // SQLite can't drop a column on the versions this platform
+            // targets; reverting this migration just stops writing to it.
+        ],
+        up_transform: None,
+        down_transform: None,
+    },
+];
+
+async fn run_script(tx: &mut Transaction<'_, Sqlite>, statements: &[&str]) -> Result<()> {
+    for statement in statements {
+        sqlx::query(statement).execute(&mut *tx).await?;
+    }
+    Ok(())
+}
 
-    // Create strategies table
+async fn ensure_migrations_table(pool: &SqlitePool) -> Result<()> {
+    sqlx::query("PRAGMA foreign_keys = ON").execute(pool).await?;
     sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS strategies (
-            id TEXT PRIMARY KEY,
-            strategy_type TEXT NOT NULL,
-            symbol TEXT NOT NULL,
-            parameters TEXT NOT NULL,
-            created_at TEXT NOT NULL,
-            is_active BOOLEAN NOT NULL DEFAULT TRUE
-        )
-        "#,
+        "CREATE TABLE IF NOT EXISTS schema_migrations (version INTEGER PRIMARY KEY, applied_at TEXT NOT NULL)"
     )
     .execute(pool)
     .await?;
+    Ok(())
+}
 
-    // Create trades table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS trades (
-            id TEXT PRIMARY KEY,
-            strategy_id TEXT NOT NULL,
-            symbol TEXT NOT NULL,
-            action TEXT NOT NULL,
-            quantity REAL NOT NULL,
-            price REAL NOT NULL,
-            timestamp TEXT NOT NULL,
-            explanation TEXT,
-            commission REAL DEFAULT 0.0,
-            realized_pnl REAL,
-            trade_value REAL NOT NULL,
-            FOREIGN KEY (strategy_id) REFERENCES strategies(id) ON DELETE CASCADE
-        )
-        "#,
-    )
-    .execute(pool)
-    .await?;
+/// The highest migration version recorded as applied, or 0 if none have run.
+pub async fn current_version(pool: &SqlitePool) -> Result<u32> {
+    ensure_migrations_table(pool).await?;
+    let version: Option<i64> = sqlx::query_scalar("SELECT MAX(version) FROM schema_migrations")
+        .fetch_one(pool)
+        .await?;
+    Ok(version.unwrap_or(0) as u32)
+}
 
-    // Create performance snapshots table
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS performance_snapshots (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            strategy_id TEXT NOT NULL,
-            total_return REAL NOT NULL,
-            total_trades INTEGER NOT NULL,
-            timestamp TEXT NOT NULL,
-            metrics TEXT NOT NULL,
-            FOREIGN KEY (strategy_id) REFERENCES strategies(id) ON DELETE CASCADE
-        )
-        "#,
-    )
-    .execute(pool)
-    .await?;
+/// Apply every migration in `(applied, target_version]`, in ascending
+/// order, each inside its own transaction so a failing script leaves the
+/// schema at its last fully-applied version. `target_version` may be below
+/// the latest known version, in which case only the prefix up to it runs.
+pub async fn migrate_to(pool: &SqlitePool, target_version: u32) -> Result<()> {
+    let applied = current_version(pool).await?;
+
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|m| m.version > applied && m.version <= target_version)
+    {
+        let mut tx = pool.begin().await?;
+        run_script(&mut tx, migration.up).await?;
+        if let Some(transform) = migration.up_transform {
+            transform(&mut tx).await?;
+        }
+
+        sqlx::query("INSERT INTO schema_migrations (version, applied_at) VALUES (?, ?)")
+            .bind(migration.version as i64)
+            .bind(datetime_to_string(Utc::now()))
+            .execute(&mut *tx)
+            .await?;
 
-    // Create market data cache table
+        tx.commit().await?;
+    }
+
+    Ok(())
+}
+
+/// Apply every pending migration, bringing the schema to the latest version
+/// in `MIGRATIONS`.
+pub async fn migrate(pool: &SqlitePool) -> Result<()> {
+    let latest = MIGRATIONS.last().map(|m| m.version).unwrap_or(0);
+    migrate_to(pool, latest).await
+}
+
+/// Bootstrap the market-data cache pool, which lives separately from
+/// `MIGRATIONS`'s durable strategies/trades/performance schema (see
+/// [`crate::database::Database::new_with_cache_url`]). It's a single
+/// idempotent `CREATE TABLE IF NOT EXISTS` rather than a versioned
+/// migration: the cache is disposable, so there's no history worth tracking
+/// in a `schema_migrations` row, and dropping its file/`:memory:` pool
+/// wholesale is an intended way to reset it.
+pub async fn migrate_cache_pool(pool: &SqlitePool) -> Result<()> {
     sqlx::query(
         r#"
         CREATE TABLE IF NOT EXISTS market_data_cache (
@@ -78,6 +346,7 @@ pub async fn initialize_database(pool: &SqlitePool) -> Result<()> {
             day_high REAL,
             day_low REAL,
             previous_close REAL,
+            stable_price REAL,
             PRIMARY KEY (symbol, timestamp)
         )
         "#,
@@ -85,58 +354,9 @@ pub async fn initialize_database(pool: &SqlitePool) -> Result<()> {
     .execute(pool)
     .await?;
 
-    // Create indexes for better performance
-    create_indexes(pool).await?;
-
-    Ok(())
-}
-
-/// Create database indexes for better query performance
-async fn create_indexes(pool: &SqlitePool) -> Result<()> {
-    // Index on strategies
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_strategies_symbol ON strategies(symbol)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_strategies_active ON strategies(is_active)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_strategies_created_at ON strategies(created_at)")
-        .execute(pool)
-        .await?;
-
-    // Index on trades
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_trades_strategy_id ON trades(strategy_id)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_trades_symbol ON trades(symbol)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_trades_timestamp ON trades(timestamp)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_trades_action ON trades(action)")
-        .execute(pool)
-        .await?;
-
-    // Index on performance snapshots
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_performance_strategy_id ON performance_snapshots(strategy_id)")
-        .execute(pool)
-        .await?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_performance_timestamp ON performance_snapshots(timestamp)")
-        .execute(pool)
-        .await?;
-
-    // Index on market data cache
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_market_data_symbol ON market_data_cache(symbol)")
         .execute(pool)
         .await?;
-
     sqlx::query("CREATE INDEX IF NOT EXISTS idx_market_data_timestamp ON market_data_cache(timestamp)")
         .execute(pool)
         .await?;
@@ -144,31 +364,56 @@ async fn create_indexes(pool: &SqlitePool) -> Result<()> {
     Ok(())
 }
 
-/// Check if database schema is up to date
-pub async fn check_schema_version(pool: &SqlitePool) -> Result<bool> {
-    // Check if all required tables exist
-    let tables = vec!["strategies", "trades", "performance_snapshots", "market_data_cache"];
-    
-    for table in tables {
-        let count: i64 = sqlx::query_scalar(
-            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name=?"
-        )
-        .bind(table)
-        .fetch_one(pool)
-        .await?;
-
-        if count == 0 {
-            return Ok(false);
+/// Revert every applied migration above `target_version`, running each
+/// migration's `down_transform` (if any) and then its `down` script, in
+/// descending order, each inside its own transaction.
+pub async fn rollback_to(pool: &SqlitePool, target_version: u32) -> Result<()> {
+    let applied = current_version(pool).await?;
+
+    for migration in MIGRATIONS
+        .iter()
+        .rev()
+        .filter(|m| m.version > target_version && m.version <= applied)
+    {
+        let mut tx = pool.begin().await?;
+        if let Some(transform) = migration.down_transform {
+            transform(&mut tx).await?;
         }
+        run_script(&mut tx, migration.down).await?;
+
+        sqlx::query("DELETE FROM schema_migrations WHERE version = ?")
+            .bind(migration.version as i64)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
     }
 
-    Ok(true)
+    Ok(())
+}
+
+/// Initialize the database by applying every pending migration. Kept as a
+/// thin alias over `migrate` for callers that just want "bring this
+/// connection up to the latest schema" without thinking about versions.
+pub async fn initialize_database(pool: &SqlitePool) -> Result<()> {
+    migrate(pool).await
+}
+
+/// Whether the database's applied migration version matches the latest
+/// version in `MIGRATIONS`.
+pub async fn check_schema_version(pool: &SqlitePool) -> Result<bool> {
+    let applied = current_version(pool).await?;
+    let latest = MIGRATIONS.last().map(|m| m.version).unwrap_or(0);
+    Ok(applied == latest)
 }
 
-/// Get database schema information
+/// Get database schema information for every table and view, including the
+/// currently applied migration version.
 pub async fn get_schema_info(pool: &SqlitePool) -> Result<Vec<TableInfo>> {
+    let version = current_version(pool).await?;
+
     let rows = sqlx::query(
-        "SELECT name, sql FROM sqlite_master WHERE type='table' ORDER BY name"
+        "SELECT name, type, sql FROM sqlite_master WHERE type IN ('table', 'view') AND name != 'schema_migrations' ORDER BY type, name"
     )
     .fetch_all(pool)
     .await?;
@@ -176,9 +421,10 @@ pub async fn get_schema_info(pool: &SqlitePool) -> Result<Vec<TableInfo>> {
     let mut tables = Vec::new();
     for row in rows {
         let name: String = row.get("name");
+        let object_type: String = row.get("type");
         let sql: Option<String> = row.get("sql");
-        
-        // Get row count for each table
+
+        // Get row count for each table/view
         let count_query = format!("SELECT COUNT(*) FROM {}", name);
         let row_count: i64 = sqlx::query_scalar(&count_query)
             .fetch_one(pool)
@@ -189,6 +435,8 @@ pub async fn get_schema_info(pool: &SqlitePool) -> Result<Vec<TableInfo>> {
             name,
             sql,
             row_count: row_count as u32,
+            schema_version: version,
+            is_view: object_type == "view",
         });
     }
 
@@ -200,10 +448,20 @@ pub struct TableInfo {
     pub name: String,
     pub sql: Option<String>,
     pub row_count: u32,
+    /// The migration version applied to the database this table was read
+    /// from (the same value on every row, not a per-table version).
+    pub schema_version: u32,
+    /// `true` for a `VIEW` such as `strategy_positions`, `false` for a
+    /// real table.
+    pub is_view: bool,
 }
 
-/// Clean up old data based on retention policies
-pub async fn cleanup_old_data(pool: &SqlitePool, days_to_keep: u32) -> Result<u32> {
+/// Clean up old data based on retention policies. `days_to_keep` governs
+/// operational data (market-data cache, performance snapshots);
+/// `history_days_to_keep` governs the `trades_history`/`strategies_history`
+/// audit trail, which is typically retained far longer for regulatory
+/// review and is pruned on its own schedule.
+pub async fn cleanup_old_data(pool: &SqlitePool, days_to_keep: u32, history_days_to_keep: u32) -> Result<u32> {
     let cutoff_date = chrono::Utc::now() - chrono::Duration::days(days_to_keep as i64);
     let cutoff_str = crate::database::datetime_to_string(cutoff_date);
 
@@ -216,11 +474,11 @@ pub async fn cleanup_old_data(pool: &SqlitePool, days_to_keep: u32) -> Result<u3
     // Clean up old performance snapshots (keep at least one per strategy)
     sqlx::query(
         r#"
-        DELETE FROM performance_snapshots 
-        WHERE timestamp < ? 
+        DELETE FROM performance_snapshots
+        WHERE timestamp < ?
         AND id NOT IN (
-            SELECT MAX(id) 
-            FROM performance_snapshots 
+            SELECT MAX(id)
+            FROM performance_snapshots
             GROUP BY strategy_id
         )
         "#
@@ -229,9 +487,30 @@ pub async fn cleanup_old_data(pool: &SqlitePool, days_to_keep: u32) -> Result<u3
     .execute(pool)
     .await?;
 
+    cleanup_old_history(pool, history_days_to_keep).await?;
+
     Ok(result.rows_affected() as u32)
 }
 
+/// Prune audit rows older than `days_to_keep` from `trades_history` and
+/// `strategies_history`, independent of operational data retention.
+pub async fn cleanup_old_history(pool: &SqlitePool, days_to_keep: u32) -> Result<u32> {
+    let cutoff_date = chrono::Utc::now() - chrono::Duration::days(days_to_keep as i64);
+    let cutoff_str = crate::database::datetime_to_string(cutoff_date);
+
+    let trades_result = sqlx::query("DELETE FROM trades_history WHERE changed_at < ?")
+        .bind(&cutoff_str)
+        .execute(pool)
+        .await?;
+
+    let strategies_result = sqlx::query("DELETE FROM strategies_history WHERE changed_at < ?")
+        .bind(&cutoff_str)
+        .execute(pool)
+        .await?;
+
+    Ok((trades_result.rows_affected() + strategies_result.rows_affected()) as u32)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -241,12 +520,24 @@ mod tests {
         SqlitePool::connect("sqlite::memory:").await.unwrap()
     }
 
+    fn record_marker_up<'c>(tx: &'c mut Transaction<'_, Sqlite>) -> BoxFuture<'c, Result<()>> {
+        Box::pin(async move {
+            sqlx::query("CREATE TABLE IF NOT EXISTS migration_marker (note TEXT NOT NULL)")
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("INSERT INTO migration_marker (note) VALUES ('up')")
+                .execute(&mut *tx)
+                .await?;
+            Ok(())
+        })
+    }
+
     #[tokio::test]
     async fn test_initialize_database() {
         let pool = create_test_db().await;
-        
+
         initialize_database(&pool).await.unwrap();
-        
+
         // Check that all tables were created
         let schema_valid = check_schema_version(&pool).await.unwrap();
         assert!(schema_valid);
@@ -256,27 +547,33 @@ mod tests {
     async fn test_schema_info() {
         let pool = create_test_db().await;
         initialize_database(&pool).await.unwrap();
-        
+
         let tables = get_schema_info(&pool).await.unwrap();
         assert!(tables.len() >= 4); // At least our 4 main tables
-        
+
         let table_names: Vec<String> = tables.iter().map(|t| t.name.clone()).collect();
         assert!(table_names.contains(&"strategies".to_string()));
         assert!(table_names.contains(&"trades".to_string()));
         assert!(table_names.contains(&"performance_snapshots".to_string()));
         assert!(table_names.contains(&"market_data_cache".to_string()));
+        assert!(tables.iter().all(|t| t.schema_version == MIGRATIONS.last().unwrap().version));
+
+        let views: Vec<&TableInfo> = tables.iter().filter(|t| t.is_view).collect();
+        let view_names: Vec<String> = views.iter().map(|t| t.name.clone()).collect();
+        assert!(view_names.contains(&"strategy_positions".to_string()));
+        assert!(view_names.contains(&"strategy_latest_performance".to_string()));
     }
 
     #[tokio::test]
     async fn test_cleanup_old_data() {
         let pool = create_test_db().await;
         initialize_database(&pool).await.unwrap();
-        
+
         // Insert some test data
         let old_timestamp = crate::database::datetime_to_string(
             chrono::Utc::now() - chrono::Duration::days(100)
         );
-        
+
         sqlx::query(
             "INSERT INTO market_data_cache (symbol, timestamp, price, volume, change_percent) VALUES (?, ?, ?, ?, ?)"
         )
@@ -290,7 +587,130 @@ mod tests {
         .unwrap();
 
         // Clean up data older than 30 days
-        let deleted_count = cleanup_old_data(&pool, 30).await.unwrap();
+        let deleted_count = cleanup_old_data(&pool, 30, 365).await.unwrap();
         assert_eq!(deleted_count, 1);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_trade_update_and_delete_are_captured_in_history() {
+        let pool = create_test_db().await;
+        initialize_database(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO strategies (id, strategy_type, symbol, parameters, created_at, is_active) VALUES ('s1', '{}', 'AAPL', '{}', '2024-01-01T00:00:00Z', 1)"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            "INSERT INTO trades (id, strategy_id, symbol, action, quantity, price, timestamp, trade_value) VALUES ('t1', 's1', 'AAPL', 'BUY', 1.0, 100.0, '2024-01-01T00:00:00Z', 100.0)"
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        sqlx::query("UPDATE trades SET price = 105.0 WHERE id = 't1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("DELETE FROM trades WHERE id = 't1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let operations: Vec<String> = sqlx::query_scalar(
+            "SELECT operation FROM trades_history WHERE id = 't1' ORDER BY history_id"
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(operations, vec!["update".to_string(), "delete".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_old_history_prunes_independently_of_operational_data() {
+        let pool = create_test_db().await;
+        initialize_database(&pool).await.unwrap();
+
+        let old_timestamp = crate::database::datetime_to_string(
+            chrono::Utc::now() - chrono::Duration::days(400)
+        );
+
+        sqlx::query(
+            "INSERT INTO trades_history (id, strategy_id, symbol, action, quantity, price, timestamp, trade_value, operation, changed_at) VALUES ('t1', 's1', 'AAPL', 'BUY', 1.0, 100.0, '2024-01-01T00:00:00Z', 100.0, 'delete', ?)"
+        )
+        .bind(&old_timestamp)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let pruned = cleanup_old_history(&pool, 365).await.unwrap();
+        assert_eq!(pruned, 1);
+    }
+
+    #[tokio::test]
+    async fn test_migrate_is_idempotent() {
+        let pool = create_test_db().await;
+        migrate(&pool).await.unwrap();
+        migrate(&pool).await.unwrap();
+
+        assert_eq!(current_version(&pool).await.unwrap(), MIGRATIONS.last().unwrap().version);
+    }
+
+    #[tokio::test]
+    async fn test_rollback_to_zero_drops_tables() {
+        let pool = create_test_db().await;
+        migrate(&pool).await.unwrap();
+
+        rollback_to(&pool, 0).await.unwrap();
+
+        assert_eq!(current_version(&pool).await.unwrap(), 0);
+        assert!(!check_schema_version(&pool).await.unwrap());
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='strategies'"
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_migrate_to_stops_at_the_requested_version() {
+        let pool = create_test_db().await;
+
+        migrate_to(&pool, 2).await.unwrap();
+        assert_eq!(current_version(&pool).await.unwrap(), 2);
+
+        let view_count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type='view' AND name='strategy_positions'"
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(view_count, 0);
+
+        migrate_to(&pool, 4).await.unwrap();
+        assert_eq!(current_version(&pool).await.unwrap(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_migration_transform_runs_in_the_same_transaction() {
+        let pool = create_test_db().await;
+        let mut tx = pool.begin().await.unwrap();
+
+        record_marker_up(&mut tx).await.unwrap();
+
+        let note: String = sqlx::query_scalar("SELECT note FROM migration_marker")
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        assert_eq!(note, "up");
+
+        tx.commit().await.unwrap();
+    }
+}
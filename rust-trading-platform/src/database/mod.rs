@@ -1,39 +1,159 @@
 // Database connection management and repository implementations
 
-use sqlx::SqlitePool;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
+use sqlx::{Sqlite, SqlitePool, Transaction};
 use chrono::{DateTime, Utc};
+use std::future::Future;
 use crate::error::{Result, TradingPlatformError};
 
 pub mod migrations;
+pub mod postgres;
 pub mod repositories;
+pub mod writer;
 
+pub use postgres::PostgresDatabase;
 pub use repositories::*;
+pub use writer::WriteHandle;
 
 /// Database connection pool manager
 #[derive(Debug, Clone)]
 pub struct Database {
     pool: SqlitePool,
+    /// Separate pool backing `market_data_cache`, so high-frequency quote
+    /// writes don't contend with the durable strategies/trades/
+    /// performance_snapshots pool above, and so the cache can be wiped and
+    /// rebuilt (e.g. pointed at a fresh `:memory:` database) without
+    /// touching trade history. See [`Database::new_with_cache_url`].
+    cache_pool: SqlitePool,
+    write_handle: WriteHandle,
 }
 
 impl Database {
-    /// Create a new database connection with the given URL
+    /// Create a new database connection with the given URL, backing the
+    /// market-data cache with an in-memory pool of its own. Use
+    /// [`Database::new_with_cache_url`] to persist the cache across restarts
+    /// instead.
     pub async fn new(database_url: &str) -> Result<Self> {
-        let pool = SqlitePool::connect(database_url).await?;
-        
-        Ok(Database { pool })
+        Self::new_with_cache_url(database_url, "sqlite::memory:").await
+    }
+
+    /// Create a new database connection, with `cache_url` backing the
+    /// `market_data_cache` pool separately from `database_url`'s durable
+    /// strategies/trades/performance_snapshots pool. Both go through
+    /// `DatabaseConfig::create_pool` so WAL journaling and the busy-timeout
+    /// pragma are always applied, not just when the caller builds its own
+    /// `DatabaseConfig`.
+    pub async fn new_with_cache_url(database_url: &str, cache_url: &str) -> Result<Self> {
+        let pool = DatabaseConfig::new(database_url.to_string()).create_pool().await?;
+        let cache_pool = DatabaseConfig::new(cache_url.to_string()).create_pool().await?;
+        let write_handle = writer::spawn(pool.clone(), cache_pool.clone());
+
+        Ok(Database { pool, cache_pool, write_handle })
+    }
+
+    /// A handle to the dedicated write executor, which batches trade,
+    /// snapshot, and market-data writes from every caller into a handful of
+    /// transactions instead of one autocommit write each.
+    pub fn writer(&self) -> WriteHandle {
+        self.write_handle.clone()
     }
 
-    /// Run database migrations
+    /// A [`SqliteStrategyRepository`] whose `create` calls are routed through
+    /// [`Database::writer`] instead of running their own autocommit `INSERT`.
+    pub fn strategy_repository(&self) -> SqliteStrategyRepository {
+        SqliteStrategyRepository::new(self.pool.clone()).with_writer(self.writer())
+    }
+
+    /// A [`SqliteTradeRepository`] whose `create` calls are routed through
+    /// [`Database::writer`]. See [`Database::strategy_repository`].
+    pub fn trade_repository(&self) -> SqliteTradeRepository {
+        SqliteTradeRepository::new(self.pool.clone()).with_writer(self.writer())
+    }
+
+    /// A [`SqliteMarketDataRepository`] backed by [`Database::cache_pool`]
+    /// rather than the durable pool. Its `cache_market_data` calls are not
+    /// routed through [`Database::writer`] -- use [`Database::writer`]'s
+    /// `upsert_market_data`/`upsert_stable_price` directly for the batched
+    /// path instead.
+    pub fn market_data_repository(&self) -> SqliteMarketDataRepository {
+        SqliteMarketDataRepository::new(self.cache_pool.clone())
+    }
+
+    /// Force the write executor to commit whatever batch it's currently
+    /// collecting and wait for that commit to land. Call before the process
+    /// exits so writes queued right before shutdown aren't dropped.
+    pub async fn shutdown(&self) -> Result<()> {
+        self.write_handle.flush().await
+    }
+
+    /// Apply every pending migration in `migrations::MIGRATIONS` to the
+    /// durable pool, in ascending version order, then bootstrap
+    /// `market_data_cache` on the cache pool via
+    /// [`migrations::migrate_cache_pool`].
     pub async fn migrate(&self) -> Result<()> {
-        sqlx::migrate!("./migrations").run(&self.pool).await?;
-        Ok(())
+        migrations::migrate(&self.pool).await?;
+        migrations::migrate_cache_pool(&self.cache_pool).await
+    }
+
+    /// Apply every pending migration up to (and including) `version`,
+    /// rather than the latest in `migrations::MIGRATIONS`. Only touches the
+    /// durable pool.
+    pub async fn migrate_to(&self, version: u32) -> Result<()> {
+        migrations::migrate_to(&self.pool, version).await
+    }
+
+    /// Revert applied migrations down to (but not including) `version`,
+    /// running their `down` scripts (and any `down_transform`) in
+    /// descending order. Only touches the durable pool --
+    /// `market_data_cache`'s bootstrap isn't versioned.
+    pub async fn rollback_to(&self, version: u32) -> Result<()> {
+        migrations::rollback_to(&self.pool, version).await
     }
 
-    /// Get a reference to the connection pool
+    /// Get a reference to the durable connection pool.
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
 
+    /// Get a reference to the `market_data_cache` connection pool. See
+    /// [`Database::market_data_repository`].
+    pub fn cache_pool(&self) -> &SqlitePool {
+        &self.cache_pool
+    }
+
+    /// Start a [`UnitOfWork`]: a single `sqlx::Transaction` that hands out
+    /// transaction-scoped repository views, so writes made through it either
+    /// all commit together or all roll back. Prefer [`Database::transaction`]
+    /// for one-shot closures; use this when the writes need to be threaded
+    /// through calling code across an `await` boundary the closure form
+    /// can't express.
+    pub async fn begin(&self) -> Result<UnitOfWork<'_>> {
+        Ok(UnitOfWork { tx: self.pool.begin().await? })
+    }
+
+    /// Run `f` against a single transaction, committing on `Ok` and rolling
+    /// back on any `Err` — e.g. a strategy tick that records a trade, updates
+    /// its performance snapshot, and refreshes the market-data cache either
+    /// all land or none do.
+    pub async fn transaction<F, Fut, T>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Transaction<'_, Sqlite>) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut tx = self.pool.begin().await?;
+
+        match f(&mut tx).await {
+            Ok(value) => {
+                tx.commit().await?;
+                Ok(value)
+            }
+            Err(e) => {
+                tx.rollback().await.ok();
+                Err(e)
+            }
+        }
+    }
+
     /// Check database health
     pub async fn health_check(&self) -> Result<()> {
         sqlx::query("SELECT 1")
@@ -58,19 +178,173 @@ impl Database {
         .fetch_one(&self.pool)
         .await?;
 
+        let schema_version = migrations::current_version(&self.pool).await?;
+
         Ok(DatabaseStats {
             total_strategies: strategies_count as u32,
             total_trades: trades_count as u32,
             active_strategies: active_strategies_count as u32,
+            schema_version,
         })
     }
 }
 
+/// Groups several repository writes into one `sqlx::Transaction` so a
+/// strategy update, a trade insert, and a performance snapshot either all
+/// commit or all roll back, instead of each repository's `create`/`update`
+/// running its own implicit autocommit transaction. Built via
+/// [`Database::begin`]:
+///
+/// ```ignore
+/// let mut uow = db.begin().await?;
+/// uow.trades().create(&trade).await?;
+/// uow.performance().create_snapshot(&trade.strategy_id, &metrics).await?;
+/// uow.commit().await?;
+/// ```
+///
+/// Dropping a `UnitOfWork` without calling `commit` rolls back, same as a
+/// bare `sqlx::Transaction`.
+pub struct UnitOfWork<'c> {
+    tx: Transaction<'c, Sqlite>,
+}
+
+impl<'c> UnitOfWork<'c> {
+    /// A transaction-scoped [`StrategyRepository`]-shaped view.
+    pub fn strategies(&mut self) -> repositories::TransactionStrategyRepository<'_, 'c> {
+        repositories::TransactionStrategyRepository::new(&mut self.tx)
+    }
+
+    /// A transaction-scoped [`TradeRepository`]-shaped view.
+    pub fn trades(&mut self) -> repositories::TransactionTradeRepository<'_, 'c> {
+        repositories::TransactionTradeRepository::new(&mut self.tx)
+    }
+
+    /// A transaction-scoped [`PerformanceRepository`]-shaped view.
+    pub fn performance(&mut self) -> repositories::TransactionPerformanceRepository<'_, 'c> {
+        repositories::TransactionPerformanceRepository::new(&mut self.tx)
+    }
+
+    /// Commit every write made through this unit of work.
+    pub async fn commit(self) -> Result<()> {
+        self.tx.commit().await?;
+        Ok(())
+    }
+
+    /// Roll back every write made through this unit of work. Equivalent to
+    /// dropping it, but lets the caller observe the error.
+    pub async fn rollback(self) -> Result<()> {
+        self.tx.rollback().await?;
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DatabaseStats {
     pub total_strategies: u32,
     pub total_trades: u32,
     pub active_strategies: u32,
+    /// Highest applied migration version, per `migrations::current_version`.
+    /// Always `0` for a Postgres-backed [`PostgresDatabase`], which doesn't
+    /// track schema version yet.
+    pub schema_version: u32,
+}
+
+/// Picks between the SQLite-backed [`Database`] and the Postgres-backed
+/// [`PostgresDatabase`] based on a connection URL's scheme, so `main` can
+/// select a backend from `config.database.url` without an `if`/`else` at
+/// every call site. Delegates `migrate`/`health_check`/`get_stats`/
+/// `shutdown` to whichever variant it holds; reach through to
+/// [`DatabaseHandle::as_sqlite`]/[`DatabaseHandle::as_postgres`] for
+/// backend-specific repository access (e.g. [`Database::strategy_repository`]'s
+/// batched-writer routing, which has no Postgres equivalent yet).
+#[derive(Debug, Clone)]
+pub enum DatabaseHandle {
+    Sqlite(Database),
+    Postgres(PostgresDatabase),
+}
+
+impl DatabaseHandle {
+    /// Connect to `url`, dispatching on [`DatabaseBackend::from_url`].
+    pub async fn connect(url: &str) -> Result<Self> {
+        match DatabaseBackend::from_url(url)? {
+            DatabaseBackend::Sqlite => Ok(Self::Sqlite(Database::new(url).await?)),
+            DatabaseBackend::Postgres => Ok(Self::Postgres(PostgresDatabase::new(url).await?)),
+        }
+    }
+
+    pub fn as_sqlite(&self) -> Option<&Database> {
+        match self {
+            Self::Sqlite(db) => Some(db),
+            Self::Postgres(_) => None,
+        }
+    }
+
+    pub fn as_postgres(&self) -> Option<&PostgresDatabase> {
+        match self {
+            Self::Sqlite(_) => None,
+            Self::Postgres(db) => Some(db),
+        }
+    }
+
+    pub async fn migrate(&self) -> Result<()> {
+        match self {
+            Self::Sqlite(db) => db.migrate().await,
+            Self::Postgres(db) => db.migrate().await,
+        }
+    }
+
+    pub async fn health_check(&self) -> Result<()> {
+        match self {
+            Self::Sqlite(db) => db.health_check().await,
+            Self::Postgres(db) => db.health_check().await,
+        }
+    }
+
+    pub async fn get_stats(&self) -> Result<DatabaseStats> {
+        match self {
+            Self::Sqlite(db) => db.get_stats().await,
+            Self::Postgres(db) => db.get_stats().await,
+        }
+    }
+
+    pub async fn shutdown(&self) -> Result<()> {
+        match self {
+            Self::Sqlite(db) => db.shutdown().await,
+            Self::Postgres(db) => db.shutdown().await,
+        }
+    }
+}
+
+/// Which database engine a `DatabaseConfig::url` points at, so callers can
+/// branch on dialect-specific behaviour (DDL, upsert syntax, portable
+/// statistics queries) without re-parsing the URL themselves. Used by
+/// [`DatabaseHandle::connect`] to pick between the `Sqlite`-backed
+/// [`Database`] and the `Postgres`-backed [`PostgresDatabase`].
+///
+/// [`DatabaseBackend::Sqlite`] remains the more complete of the two --
+/// `migrations`, `writer`'s batched-write executor, and the `trades_history`
+/// audit trail are all SQLite-specific. [`postgres::migrate`] creates a bare
+/// `strategies`/`trades`/`performance_snapshots` schema with no versioning,
+/// views, or triggers; bring it to parity before relying on it in production.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseBackend {
+    Sqlite,
+    Postgres,
+}
+
+impl DatabaseBackend {
+    /// Infer the backend from a connection URL's scheme, e.g. `sqlite:...`
+    /// vs `postgres://...`/`postgresql://...`.
+    pub fn from_url(url: &str) -> Result<Self> {
+        match url.split_once(':').map(|(scheme, _)| scheme) {
+            Some("sqlite") => Ok(DatabaseBackend::Sqlite),
+            Some("postgres") | Some("postgresql") => Ok(DatabaseBackend::Postgres),
+            _ => Err(TradingPlatformError::config(format!(
+                "unrecognized database URL scheme in '{}'; expected sqlite:, postgres:, or postgresql:",
+                url
+            ))),
+        }
+    }
 }
 
 /// Database connection configuration
@@ -81,9 +355,22 @@ pub struct DatabaseConfig {
     pub min_connections: u32,
     pub connect_timeout: u64,
     pub idle_timeout: u64,
+    /// SQLite journal mode, e.g. `"WAL"` or `"DELETE"`. WAL lets readers and
+    /// a single writer proceed concurrently instead of blocking each other.
+    pub journal_mode: String,
+    /// SQLite `synchronous` pragma, e.g. `"NORMAL"` or `"FULL"`.
+    pub synchronous: String,
+    /// How long a connection waits on `SQLITE_BUSY` before giving up,
+    /// instead of immediately erroring with "database is locked".
+    pub busy_timeout_ms: u64,
 }
 
 impl DatabaseConfig {
+    /// Which engine this config's `url` points at. See [`DatabaseBackend`].
+    pub fn backend(&self) -> Result<DatabaseBackend> {
+        DatabaseBackend::from_url(&self.url)
+    }
+
     pub fn new(url: String) -> Self {
         DatabaseConfig {
             url,
@@ -91,16 +378,68 @@ impl DatabaseConfig {
             min_connections: 1,
             connect_timeout: 30,
             idle_timeout: 600,
+            journal_mode: "WAL".to_string(),
+            synchronous: "NORMAL".to_string(),
+            busy_timeout_ms: 5000,
         }
     }
 
+    /// Build a `DatabaseConfig` from `DATABASE_URL` plus the `DB_*` env
+    /// vars (`DB_MAX_CONNECTIONS`, `DB_MIN_CONNECTIONS`, `DB_JOURNAL_MODE`,
+    /// `DB_SYNCHRONOUS`, `DB_BUSY_TIMEOUT_MS`), falling back to `new`'s
+    /// defaults for anything unset or unparsable.
+    pub fn from_env() -> Self {
+        let url = std::env::var("DATABASE_URL")
+            .unwrap_or_else(|_| "sqlite:trading_platform.db".to_string());
+        let mut config = DatabaseConfig::new(url);
+
+        if let Some(value) = std::env::var("DB_MAX_CONNECTIONS").ok().and_then(|v| v.parse().ok()) {
+            config.max_connections = value;
+        }
+        if let Some(value) = std::env::var("DB_MIN_CONNECTIONS").ok().and_then(|v| v.parse().ok()) {
+            config.min_connections = value;
+        }
+        if let Ok(value) = std::env::var("DB_JOURNAL_MODE") {
+            config.journal_mode = value;
+        }
+        if let Ok(value) = std::env::var("DB_SYNCHRONOUS") {
+            config.synchronous = value;
+        }
+        if let Some(value) = std::env::var("DB_BUSY_TIMEOUT_MS").ok().and_then(|v| v.parse().ok()) {
+            config.busy_timeout_ms = value;
+        }
+
+        config
+    }
+
+    /// Open a pool with WAL journaling, the configured `synchronous` level,
+    /// foreign keys enabled, and a busy-timeout, so concurrent readers and a
+    /// single writer coexist instead of failing with "database is locked".
+    ///
+    /// Only `sqlite:` URLs are supported here; a `postgres:`/`postgresql:`
+    /// URL is recognized by [`DatabaseConfig::backend`] but rejected --
+    /// go through [`DatabaseHandle::connect`] instead, which routes those to
+    /// [`postgres::create_pool`].
     pub async fn create_pool(&self) -> Result<SqlitePool> {
-        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        if self.backend()? != DatabaseBackend::Sqlite {
+            return Err(TradingPlatformError::config(
+                "create_pool only opens sqlite: URLs; connect to postgres through DatabaseHandle::connect",
+            ));
+        }
+
+        let connect_options: SqliteConnectOptions = self.url.parse()?;
+        let connect_options = connect_options
+            .journal_mode(parse_journal_mode(&self.journal_mode))
+            .synchronous(parse_synchronous(&self.synchronous))
+            .foreign_keys(true)
+            .busy_timeout(std::time::Duration::from_millis(self.busy_timeout_ms));
+
+        let pool = SqlitePoolOptions::new()
             .max_connections(self.max_connections)
             .min_connections(self.min_connections)
             .acquire_timeout(std::time::Duration::from_secs(self.connect_timeout))
             .idle_timeout(std::time::Duration::from_secs(self.idle_timeout))
-            .connect(&self.url)
+            .connect_with(connect_options)
             .await?;
 
         Ok(pool)
@@ -113,6 +452,26 @@ impl Default for DatabaseConfig {
     }
 }
 
+fn parse_journal_mode(mode: &str) -> SqliteJournalMode {
+    match mode.to_ascii_uppercase().as_str() {
+        "DELETE" => SqliteJournalMode::Delete,
+        "TRUNCATE" => SqliteJournalMode::Truncate,
+        "PERSIST" => SqliteJournalMode::Persist,
+        "MEMORY" => SqliteJournalMode::Memory,
+        "OFF" => SqliteJournalMode::Off,
+        _ => SqliteJournalMode::Wal,
+    }
+}
+
+fn parse_synchronous(mode: &str) -> SqliteSynchronous {
+    match mode.to_ascii_uppercase().as_str() {
+        "OFF" => SqliteSynchronous::Off,
+        "FULL" => SqliteSynchronous::Full,
+        "EXTRA" => SqliteSynchronous::Extra,
+        _ => SqliteSynchronous::Normal,
+    }
+}
+
 // Helper functions for database operations
 pub fn serialize_json<T: serde::Serialize>(value: &T) -> Result<String> {
     serde_json::to_string(value).map_err(TradingPlatformError::from)
@@ -135,7 +494,194 @@ pub fn string_to_datetime(s: &str) -> Result<DateTime<Utc>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::database::repositories::{insert_snapshot, insert_trade, StrategyRepository, SqliteStrategyRepository};
+    use crate::performance::{Money, PerformanceMetrics, Position, Trade};
+    use crate::strategy::{Action, Strategy, StrategyParameters, StrategyType};
+    use uuid::Uuid;
+
+    async fn test_db() -> Database {
+        let db = Database::new("sqlite::memory:").await.unwrap();
+        migrations::initialize_database(db.pool()).await.unwrap();
+        db
+    }
+
+    fn test_strategy() -> Strategy {
+        Strategy {
+            id: Uuid::new_v4().to_string(),
+            strategy_type: StrategyType::PriceDrop { threshold: 5.0 },
+            symbol: "AAPL".to_string(),
+            parameters: StrategyParameters::default(),
+            created_at: Utc::now(),
+            is_active: true,
+        }
+    }
+
+    fn test_trade(strategy_id: &str) -> Trade {
+        Trade {
+            id: Uuid::new_v4().to_string(),
+            strategy_id: strategy_id.to_string(),
+            symbol: "AAPL".to_string(),
+            action: Action::Buy,
+            quantity: 10.0,
+            price: Money::from_f64(150.0),
+            timestamp: Utc::now(),
+            explanation: "test buy".to_string(),
+            commission: Money::from_f64(1.0),
+            realized_pnl: None,
+            trade_value: Money::from_f64(1500.0),
+        }
+    }
+
+    fn test_metrics(strategy_id: &str) -> PerformanceMetrics {
+        PerformanceMetrics {
+            strategy_id: strategy_id.to_string(),
+            total_return: Money::from_f64(50.0),
+            total_return_percent: 5.0,
+            total_trades: 1,
+            winning_trades: 1,
+            losing_trades: 0,
+            current_position: Position {
+                symbol: "AAPL".to_string(),
+                shares: 10.0,
+                average_price: Money::from_f64(150.0),
+                current_price: Money::from_f64(155.0),
+                current_value: Money::from_f64(1550.0),
+                unrealized_pnl: Money::from_f64(50.0),
+                unrealized_pnl_percent: 3.3,
+                cost_basis: Money::from_f64(1500.0),
+                last_updated: Utc::now(),
+                lots: Vec::new(),
+            },
+            max_drawdown: Money::ZERO,
+            max_drawdown_percent: 0.0,
+            sharpe_ratio: None,
+            win_rate: 1.0,
+            average_win: Money::from_f64(50.0),
+            average_loss: Money::ZERO,
+            profit_factor: 1.0,
+            initial_capital: Money::from_f64(10000.0),
+            current_capital: Money::from_f64(10050.0),
+            last_updated: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transaction_commits_trade_and_snapshot_together() {
+        let db = test_db().await;
+        let strategy = test_strategy();
+        SqliteStrategyRepository::new(db.pool().clone())
+            .create(&strategy)
+            .await
+            .unwrap();
 
+        let trade = test_trade(&strategy.id);
+        let metrics = test_metrics(&strategy.id);
+
+        db.transaction(|tx| {
+            let trade = trade.clone();
+            let metrics = metrics.clone();
+            Box::pin(async move {
+                insert_trade(&mut *tx, &trade).await?;
+                insert_snapshot(&mut *tx, &trade.strategy_id, &metrics).await?;
+                Ok(())
+            })
+        })
+        .await
+        .unwrap();
+
+        let trade_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM trades")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(trade_count, 1);
+
+        let snapshot_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM performance_snapshots")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(snapshot_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_rolls_back_trade_on_later_failure() {
+        let db = test_db().await;
+        let strategy = test_strategy();
+        SqliteStrategyRepository::new(db.pool().clone())
+            .create(&strategy)
+            .await
+            .unwrap();
+
+        let trade = test_trade(&strategy.id);
+
+        let result = db
+            .transaction(|tx| {
+                let trade = trade.clone();
+                Box::pin(async move {
+                    insert_trade(&mut *tx, &trade).await?;
+                    Err(TradingPlatformError::internal("downstream step failed"))
+                })
+            })
+            .await;
+        assert!(result.is_err());
+
+        let trade_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM trades")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(trade_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_unit_of_work_commits_trade_and_snapshot_together() {
+        let db = test_db().await;
+        let strategy = test_strategy();
+        SqliteStrategyRepository::new(db.pool().clone())
+            .create(&strategy)
+            .await
+            .unwrap();
+
+        let trade = test_trade(&strategy.id);
+        let metrics = test_metrics(&strategy.id);
+
+        let mut uow = db.begin().await.unwrap();
+        uow.trades().create(&trade).await.unwrap();
+        uow.performance().create_snapshot(&trade.strategy_id, &metrics).await.unwrap();
+        uow.commit().await.unwrap();
+
+        let trade_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM trades")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(trade_count, 1);
+
+        let snapshot_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM performance_snapshots")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(snapshot_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_unit_of_work_rolls_back_on_explicit_rollback() {
+        let db = test_db().await;
+        let strategy = test_strategy();
+        SqliteStrategyRepository::new(db.pool().clone())
+            .create(&strategy)
+            .await
+            .unwrap();
+
+        let trade = test_trade(&strategy.id);
+
+        let mut uow = db.begin().await.unwrap();
+        uow.trades().create(&trade).await.unwrap();
+        uow.rollback().await.unwrap();
+
+        let trade_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM trades")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(trade_count, 0);
+    }
 
     #[tokio::test]
     async fn test_database_creation() {
@@ -144,12 +690,104 @@ mod tests {
         assert!(db.health_check().await.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_migrate_bootstraps_cache_pool_separately_from_durable_pool() {
+        let db = Database::new("sqlite::memory:").await.unwrap();
+        db.migrate().await.unwrap();
+
+        let cache_tables: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'market_data_cache'"
+        )
+        .fetch_one(db.cache_pool())
+        .await
+        .unwrap();
+        assert_eq!(cache_tables, 1);
+
+        let trade_tables_on_cache_pool: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'trades'"
+        )
+        .fetch_one(db.cache_pool())
+        .await
+        .unwrap();
+        assert_eq!(trade_tables_on_cache_pool, 0);
+    }
+
+    #[tokio::test]
+    async fn test_market_data_repository_writes_land_on_cache_pool_not_durable_pool() {
+        use crate::data::MarketData;
+        use crate::database::repositories::MarketDataRepository;
+
+        let db = Database::new("sqlite::memory:").await.unwrap();
+        db.migrate().await.unwrap();
+
+        db.market_data_repository()
+            .cache_market_data(&MarketData::new("AAPL".to_string(), 150.0, 1_000_000))
+            .await
+            .unwrap();
+
+        let cached_rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM market_data_cache")
+            .fetch_one(db.cache_pool())
+            .await
+            .unwrap();
+        assert_eq!(cached_rows, 1);
+
+        let rows_on_durable_pool: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM market_data_cache")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(rows_on_durable_pool, 0);
+    }
+
     #[tokio::test]
     async fn test_database_config() {
         let config = DatabaseConfig::default();
         assert_eq!(config.url, "sqlite:trading_platform.db");
         assert_eq!(config.max_connections, 10);
         assert_eq!(config.min_connections, 1);
+        assert_eq!(config.journal_mode, "WAL");
+        assert_eq!(config.synchronous, "NORMAL");
+        assert_eq!(config.busy_timeout_ms, 5000);
+    }
+
+    #[tokio::test]
+    async fn test_create_pool_applies_pragmas() {
+        let config = DatabaseConfig::new("sqlite::memory:".to_string());
+        let pool = config.create_pool().await.unwrap();
+
+        let synchronous: i64 = sqlx::query_scalar("PRAGMA synchronous")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(synchronous, 1); // NORMAL
+
+        let foreign_keys: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(foreign_keys, 1);
+    }
+
+    #[test]
+    fn test_database_backend_from_url() {
+        assert_eq!(DatabaseBackend::from_url("sqlite:trading_platform.db").unwrap(), DatabaseBackend::Sqlite);
+        assert_eq!(DatabaseBackend::from_url("sqlite::memory:").unwrap(), DatabaseBackend::Sqlite);
+        assert_eq!(DatabaseBackend::from_url("postgres://localhost/db").unwrap(), DatabaseBackend::Postgres);
+        assert_eq!(DatabaseBackend::from_url("postgresql://localhost/db").unwrap(), DatabaseBackend::Postgres);
+        assert!(DatabaseBackend::from_url("mysql://localhost/db").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_pool_rejects_postgres_url_for_now() {
+        let config = DatabaseConfig::new("postgres://localhost/trading".to_string());
+        assert!(config.create_pool().await.is_err());
+    }
+
+    #[test]
+    fn test_parse_journal_mode_and_synchronous_fall_back_to_defaults() {
+        assert!(matches!(parse_journal_mode("wal"), SqliteJournalMode::Wal));
+        assert!(matches!(parse_journal_mode("bogus"), SqliteJournalMode::Wal));
+        assert!(matches!(parse_synchronous("full"), SqliteSynchronous::Full));
+        assert!(matches!(parse_synchronous("bogus"), SqliteSynchronous::Normal));
     }
 
     #[test]
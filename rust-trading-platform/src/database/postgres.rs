@@ -0,0 +1,665 @@
+// Postgres-backed repository implementations, parallel to `repositories.rs`'s
+// SQLite family. `DatabaseHandle::connect` picks between the two based on
+// `DatabaseConfig::backend`, so a caller using the trait objects (or the
+// repository traits directly) doesn't need to know which backend it's on.
+//
+// Differences from the SQLite repositories are confined to this module:
+// - placeholders are `$1, $2, ...` instead of `?`
+// - `is_active` is a native `BOOLEAN` (no `is_active = TRUE` string quirk)
+// - timestamps are native `TIMESTAMPTZ`, bound/read as `DateTime<Utc>`
+//   directly instead of round-tripping through `datetime_to_string`/
+//   `string_to_datetime`
+//
+// Row mapping uses `sqlx::FromRow` (via `query_as`) rather than the manual
+// `row_to_strategy`/`row_to_trade` helpers in `repositories.rs`. SQLite keeps
+// its manual mapping because of the string/TEXT quirks above; Postgres's
+// native types let `FromRow` do the whole job.
+
+use async_trait::async_trait;
+use sqlx::postgres::{PgPoolOptions, PgRow};
+use sqlx::{FromRow, PgPool, Row};
+use chrono::{DateTime, Utc};
+
+use crate::error::{Result, TradingPlatformError};
+use crate::strategy::{Strategy, StrategyType, Action};
+use crate::performance::{Money, Trade, PerformanceMetrics};
+use crate::database::{serialize_json, deserialize_json, DatabaseStats};
+use crate::database::repositories::{
+    StrategyRepository, TradeRepository, PerformanceRepository, TradeHistoryEntry,
+    SymbolFilter, TradeScanFilter,
+};
+
+impl<'r> FromRow<'r, PgRow> for Strategy {
+    fn from_row(row: &'r PgRow) -> std::result::Result<Self, sqlx::Error> {
+        let strategy_type_json: String = row.try_get("strategy_type")?;
+        let parameters_json: String = row.try_get("parameters")?;
+
+        Ok(Strategy {
+            id: row.try_get("id")?,
+            strategy_type: deserialize_json(&strategy_type_json).map_err(decode_err)?,
+            symbol: row.try_get("symbol")?,
+            parameters: deserialize_json(&parameters_json).map_err(decode_err)?,
+            created_at: row.try_get("created_at")?,
+            is_active: row.try_get("is_active")?,
+        })
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for Trade {
+    fn from_row(row: &'r PgRow) -> std::result::Result<Self, sqlx::Error> {
+        let action_str: String = row.try_get("action")?;
+        let action = match action_str.as_str() {
+            "BUY" => Action::Buy,
+            "SELL" => Action::Sell,
+            "HOLD" => Action::Hold,
+            "SHORT_SELL" => Action::ShortSell,
+            "EXIT_SHORT" => Action::ExitShort,
+            _ => return Err(decode_err(TradingPlatformError::internal(format!("Invalid action: {}", action_str)))),
+        };
+
+        let commission: Option<f64> = row.try_get("commission")?;
+        let realized_pnl: Option<f64> = row.try_get("realized_pnl")?;
+
+        Ok(Trade {
+            id: row.try_get("id")?,
+            strategy_id: row.try_get("strategy_id")?,
+            symbol: row.try_get("symbol")?,
+            action,
+            quantity: row.try_get("quantity")?,
+            price: Money::from_f64(row.try_get("price")?),
+            timestamp: row.try_get("timestamp")?,
+            explanation: row.try_get::<Option<String>, _>("explanation")?.unwrap_or_default(),
+            commission: Money::from_f64(commission.unwrap_or(0.0)),
+            realized_pnl: realized_pnl.map(Money::from_f64),
+            trade_value: Money::from_f64(row.try_get("trade_value")?),
+        })
+    }
+}
+
+fn decode_err(e: impl std::error::Error + Send + Sync + 'static) -> sqlx::Error {
+    sqlx::Error::Decode(Box::new(e))
+}
+
+/// Create a Postgres connection pool for `url`. No WAL/busy-timeout pragmas
+/// to set here -- Postgres handles concurrent writers itself -- so this is
+/// just `PgPoolOptions::connect` with the same connection-count defaults as
+/// [`crate::database::DatabaseConfig`].
+pub async fn create_pool(url: &str) -> Result<PgPool> {
+    PgPoolOptions::new()
+        .max_connections(10)
+        .min_connections(1)
+        .connect(url)
+        .await
+        .map_err(TradingPlatformError::from)
+}
+
+/// The minimal set of tables the three implemented repositories need. Unlike
+/// [`crate::database::migrations`]'s versioned SQLite framework, this is a
+/// single idempotent `CREATE TABLE IF NOT EXISTS` pass -- there's no
+/// production Postgres deployment to track schema history for yet. Bring
+/// this up to parity with the SQLite migrations (views, triggers, history
+/// tables) before relying on it for anything beyond local testing.
+pub async fn migrate(pool: &PgPool) -> Result<()> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS strategies (
+            id TEXT PRIMARY KEY,
+            strategy_type TEXT NOT NULL,
+            symbol TEXT NOT NULL,
+            parameters TEXT NOT NULL,
+            created_at TIMESTAMPTZ NOT NULL,
+            is_active BOOLEAN NOT NULL DEFAULT TRUE
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS trades (
+            id TEXT PRIMARY KEY,
+            strategy_id TEXT NOT NULL REFERENCES strategies(id),
+            symbol TEXT NOT NULL,
+            action TEXT NOT NULL,
+            quantity DOUBLE PRECISION NOT NULL,
+            price DOUBLE PRECISION NOT NULL,
+            timestamp TIMESTAMPTZ NOT NULL,
+            explanation TEXT,
+            commission DOUBLE PRECISION,
+            realized_pnl DOUBLE PRECISION,
+            trade_value DOUBLE PRECISION NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS performance_snapshots (
+            id BIGSERIAL PRIMARY KEY,
+            strategy_id TEXT NOT NULL REFERENCES strategies(id),
+            total_return DOUBLE PRECISION NOT NULL,
+            total_trades BIGINT NOT NULL,
+            timestamp TIMESTAMPTZ NOT NULL,
+            metrics TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub struct PostgresStrategyRepository {
+    pool: PgPool,
+}
+
+impl PostgresStrategyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl StrategyRepository for PostgresStrategyRepository {
+    async fn create(&self, strategy: &Strategy) -> Result<()> {
+        let strategy_type_json = serialize_json(&strategy.strategy_type)?;
+        let parameters_json = serialize_json(&strategy.parameters)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO strategies (id, strategy_type, symbol, parameters, created_at, is_active)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(&strategy.id)
+        .bind(&strategy_type_json)
+        .bind(&strategy.symbol)
+        .bind(&parameters_json)
+        .bind(strategy.created_at)
+        .bind(strategy.is_active)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_by_id(&self, id: &str) -> Result<Option<Strategy>> {
+        let strategy = sqlx::query_as::<_, Strategy>(
+            "SELECT id, strategy_type, symbol, parameters, created_at, is_active FROM strategies WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(strategy)
+    }
+
+    async fn get_all(&self) -> Result<Vec<Strategy>> {
+        let strategies = sqlx::query_as::<_, Strategy>(
+            "SELECT id, strategy_type, symbol, parameters, created_at, is_active FROM strategies ORDER BY created_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(strategies)
+    }
+
+    async fn get_active(&self) -> Result<Vec<Strategy>> {
+        let strategies = sqlx::query_as::<_, Strategy>(
+            "SELECT id, strategy_type, symbol, parameters, created_at, is_active FROM strategies WHERE is_active ORDER BY created_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(strategies)
+    }
+
+    async fn get_by_symbol(&self, symbol: &str) -> Result<Vec<Strategy>> {
+        let strategies = sqlx::query_as::<_, Strategy>(
+            "SELECT id, strategy_type, symbol, parameters, created_at, is_active FROM strategies WHERE symbol = $1 ORDER BY created_at DESC",
+        )
+        .bind(symbol)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(strategies)
+    }
+
+    async fn update(&self, strategy: &Strategy) -> Result<()> {
+        let strategy_type_json = serialize_json(&strategy.strategy_type)?;
+        let parameters_json = serialize_json(&strategy.parameters)?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE strategies
+            SET strategy_type = $1, symbol = $2, parameters = $3, is_active = $4
+            WHERE id = $5
+            "#,
+        )
+        .bind(&strategy_type_json)
+        .bind(&strategy.symbol)
+        .bind(&parameters_json)
+        .bind(strategy.is_active)
+        .bind(&strategy.id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TradingPlatformError::internal("Strategy not found for update"));
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let result = sqlx::query("DELETE FROM strategies WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TradingPlatformError::internal("Strategy not found for deletion"));
+        }
+
+        Ok(())
+    }
+
+    async fn set_active(&self, id: &str, active: bool) -> Result<()> {
+        let result = sqlx::query("UPDATE strategies SET is_active = $1 WHERE id = $2")
+            .bind(active)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TradingPlatformError::internal("Strategy not found"));
+        }
+
+        Ok(())
+    }
+}
+
+pub struct PostgresTradeRepository {
+    pool: PgPool,
+}
+
+impl PostgresTradeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TradeRepository for PostgresTradeRepository {
+    async fn create(&self, trade: &Trade) -> Result<()> {
+        let action_str = trade.action.to_string();
+
+        sqlx::query(
+            r#"
+            INSERT INTO trades (id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(&trade.id)
+        .bind(&trade.strategy_id)
+        .bind(&trade.symbol)
+        .bind(&action_str)
+        .bind(trade.quantity)
+        .bind(trade.price.to_f64())
+        .bind(trade.timestamp)
+        .bind(&trade.explanation)
+        .bind(trade.commission.to_f64())
+        .bind(trade.realized_pnl.map(|pnl| pnl.to_f64()))
+        .bind(trade.trade_value.to_f64())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_batch(&self, trades: &[Trade]) -> Result<()> {
+        if trades.is_empty() {
+            return Ok(());
+        }
+
+        let mut builder = sqlx::QueryBuilder::new(
+            "INSERT INTO trades (id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value) "
+        );
+
+        builder.push_values(trades, |mut row, trade| {
+            row.push_bind(trade.id.clone())
+                .push_bind(trade.strategy_id.clone())
+                .push_bind(trade.symbol.clone())
+                .push_bind(trade.action.to_string())
+                .push_bind(trade.quantity)
+                .push_bind(trade.price.to_f64())
+                .push_bind(trade.timestamp)
+                .push_bind(trade.explanation.clone())
+                .push_bind(trade.commission.to_f64())
+                .push_bind(trade.realized_pnl.map(|pnl| pnl.to_f64()))
+                .push_bind(trade.trade_value.to_f64());
+        });
+
+        builder.build().execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn get_by_id(&self, id: &str) -> Result<Option<Trade>> {
+        let trade = sqlx::query_as::<_, Trade>(
+            "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(trade)
+    }
+
+    async fn get_by_strategy(&self, strategy_id: &str) -> Result<Vec<Trade>> {
+        let trades = sqlx::query_as::<_, Trade>(
+            "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades WHERE strategy_id = $1 ORDER BY timestamp DESC",
+        )
+        .bind(strategy_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(trades)
+    }
+
+    async fn get_by_symbol(&self, symbol: &str) -> Result<Vec<Trade>> {
+        let trades = sqlx::query_as::<_, Trade>(
+            "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades WHERE symbol = $1 ORDER BY timestamp DESC",
+        )
+        .bind(symbol)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(trades)
+    }
+
+    async fn get_recent(&self, limit: u32) -> Result<Vec<Trade>> {
+        self.scan(&TradeScanFilter {
+            limit: Some(limit),
+            reverse: true,
+            ..Default::default()
+        })
+        .await
+    }
+
+    async fn get_by_date_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Trade>> {
+        self.scan(&TradeScanFilter {
+            start: Some(start),
+            end: Some(end),
+            reverse: true,
+            ..Default::default()
+        })
+        .await
+    }
+
+    async fn scan(&self, filter: &TradeScanFilter) -> Result<Vec<Trade>> {
+        if let SymbolFilter::Set(symbols) = &filter.symbols {
+            if symbols.is_empty() {
+                return Ok(Vec::new());
+            }
+        }
+
+        let mut builder = sqlx::QueryBuilder::new(
+            "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades WHERE 1 = 1"
+        );
+
+        match &filter.symbols {
+            SymbolFilter::Any => {}
+            SymbolFilter::Set(symbols) => {
+                builder.push(" AND symbol IN (");
+                let mut separated = builder.separated(", ");
+                for symbol in symbols {
+                    separated.push_bind(symbol.clone());
+                }
+                separated.push_unseparated(")");
+            }
+            SymbolFilter::Prefix(prefix) => {
+                let escaped = prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+                builder.push(" AND symbol LIKE ");
+                builder.push_bind(format!("{}%", escaped));
+                builder.push(" ESCAPE '\\'");
+            }
+        }
+
+        if let Some(strategy_id) = &filter.strategy_id {
+            builder.push(" AND strategy_id = ");
+            builder.push_bind(strategy_id.clone());
+        }
+        if let Some(start) = filter.start {
+            builder.push(" AND timestamp >= ");
+            builder.push_bind(start);
+        }
+        if let Some(end) = filter.end {
+            builder.push(" AND timestamp <= ");
+            builder.push_bind(end);
+        }
+
+        builder.push(" ORDER BY timestamp ");
+        builder.push(if filter.reverse { "DESC" } else { "ASC" });
+
+        if let Some(limit) = filter.limit {
+            builder.push(" LIMIT ");
+            builder.push_bind(limit as i64);
+        }
+
+        let trades: Vec<Trade> = builder.build_query_as().fetch_all(&self.pool).await?;
+        Ok(trades)
+    }
+
+    async fn update(&self, trade: &Trade) -> Result<()> {
+        let action_str = trade.action.to_string();
+
+        let result = sqlx::query(
+            r#"
+            UPDATE trades
+            SET strategy_id = $1, symbol = $2, action = $3, quantity = $4, price = $5, timestamp = $6, explanation = $7, commission = $8, realized_pnl = $9, trade_value = $10
+            WHERE id = $11
+            "#,
+        )
+        .bind(&trade.strategy_id)
+        .bind(&trade.symbol)
+        .bind(&action_str)
+        .bind(trade.quantity)
+        .bind(trade.price.to_f64())
+        .bind(trade.timestamp)
+        .bind(&trade.explanation)
+        .bind(trade.commission.to_f64())
+        .bind(trade.realized_pnl.map(|pnl| pnl.to_f64()))
+        .bind(trade.trade_value.to_f64())
+        .bind(&trade.id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TradingPlatformError::internal("Trade not found for update"));
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let result = sqlx::query("DELETE FROM trades WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(TradingPlatformError::internal("Trade not found for deletion"));
+        }
+
+        Ok(())
+    }
+
+    /// Postgres has no trigger-based `trades_history` table yet (see
+    /// [`migrate`]'s doc comment), so there's nothing to read.
+    async fn get_trade_history(&self, _strategy_id: &str) -> Result<Vec<TradeHistoryEntry>> {
+        Ok(Vec::new())
+    }
+}
+
+pub struct PostgresPerformanceRepository {
+    pool: PgPool,
+}
+
+impl PostgresPerformanceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PerformanceRepository for PostgresPerformanceRepository {
+    async fn create_snapshot(&self, strategy_id: &str, metrics: &PerformanceMetrics) -> Result<()> {
+        let metrics_json = serialize_json(metrics)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO performance_snapshots (strategy_id, total_return, total_trades, timestamp, metrics)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(strategy_id)
+        .bind(metrics.total_return.to_f64())
+        .bind(metrics.total_trades as i64)
+        .bind(Utc::now())
+        .bind(&metrics_json)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_latest(&self, strategy_id: &str) -> Result<Option<PerformanceMetrics>> {
+        let row = sqlx::query(
+            "SELECT metrics FROM performance_snapshots WHERE strategy_id = $1 ORDER BY timestamp DESC LIMIT 1",
+        )
+        .bind(strategy_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => {
+                let metrics_json: String = row.try_get("metrics")?;
+                Ok(Some(deserialize_json(&metrics_json)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn get_history(&self, strategy_id: &str, limit: u32) -> Result<Vec<PerformanceMetrics>> {
+        let rows = sqlx::query(
+            "SELECT metrics FROM performance_snapshots WHERE strategy_id = $1 ORDER BY timestamp DESC LIMIT $2",
+        )
+        .bind(strategy_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut history = Vec::new();
+        for row in rows {
+            let metrics_json: String = row.try_get("metrics")?;
+            history.push(deserialize_json(&metrics_json)?);
+        }
+
+        Ok(history)
+    }
+
+    async fn delete_old_snapshots(&self, strategy_id: &str, keep_count: u32) -> Result<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM performance_snapshots
+            WHERE strategy_id = $1
+            AND id NOT IN (
+                SELECT id FROM performance_snapshots
+                WHERE strategy_id = $1
+                ORDER BY timestamp DESC
+                LIMIT $2
+            )
+            "#,
+        )
+        .bind(strategy_id)
+        .bind(keep_count as i64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// A Postgres-backed counterpart to [`crate::database::Database`], holding a
+/// `PgPool` instead of a `SqlitePool`. There's no dedicated write executor
+/// here -- [`crate::database::writer`]'s batching is SQLite-specific --
+/// repository `create` calls just run an autocommit `INSERT` directly.
+#[derive(Debug, Clone)]
+pub struct PostgresDatabase {
+    pool: PgPool,
+}
+
+impl PostgresDatabase {
+    pub async fn new(database_url: &str) -> Result<Self> {
+        let pool = create_pool(database_url).await?;
+        Ok(Self { pool })
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    pub fn strategy_repository(&self) -> PostgresStrategyRepository {
+        PostgresStrategyRepository::new(self.pool.clone())
+    }
+
+    pub fn trade_repository(&self) -> PostgresTradeRepository {
+        PostgresTradeRepository::new(self.pool.clone())
+    }
+
+    pub fn performance_repository(&self) -> PostgresPerformanceRepository {
+        PostgresPerformanceRepository::new(self.pool.clone())
+    }
+
+    pub async fn migrate(&self) -> Result<()> {
+        migrate(&self.pool).await
+    }
+
+    pub async fn health_check(&self) -> Result<()> {
+        sqlx::query("SELECT 1").fetch_one(&self.pool).await?;
+        Ok(())
+    }
+
+    pub async fn get_stats(&self) -> Result<DatabaseStats> {
+        let strategies_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM strategies")
+            .fetch_one(&self.pool)
+            .await?;
+
+        let trades_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM trades")
+            .fetch_one(&self.pool)
+            .await?;
+
+        let active_strategies_count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM strategies WHERE is_active")
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(DatabaseStats {
+            total_strategies: strategies_count as u32,
+            total_trades: trades_count as u32,
+            active_strategies: active_strategies_count as u32,
+            schema_version: 0,
+        })
+    }
+
+    /// No batched writer to flush here; kept for symmetry with
+    /// [`crate::database::Database::shutdown`] so callers don't need to
+    /// branch on backend just to shut down.
+    pub async fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+}
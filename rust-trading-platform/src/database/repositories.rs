@@ -1,14 +1,15 @@
 // Repository implementations for database operations
 
 use async_trait::async_trait;
-use sqlx::{SqlitePool, Row};
+use sqlx::{Sqlite, SqlitePool, Row, Transaction};
 use chrono::{DateTime, Utc};
 
 use crate::error::{Result, TradingPlatformError};
 use crate::strategy::{Strategy, StrategyType, Action};
-use crate::performance::{Trade, PerformanceMetrics};
-use crate::data::MarketData;
+use crate::performance::{Money, Trade, PerformanceMetrics};
+use crate::data::{MarketData, Price};
 use crate::database::{serialize_json, deserialize_json, datetime_to_string, string_to_datetime};
+use crate::database::writer::WriteHandle;
 
 // Repository traits
 #[async_trait]
@@ -26,13 +27,23 @@ pub trait StrategyRepository {
 #[async_trait]
 pub trait TradeRepository {
     async fn create(&self, trade: &Trade) -> Result<()>;
+    /// Insert every trade in `trades` via one multi-row `INSERT`, instead of
+    /// one `create` call per trade.
+    async fn insert_batch(&self, trades: &[Trade]) -> Result<()>;
     async fn get_by_id(&self, id: &str) -> Result<Option<Trade>>;
     async fn get_by_strategy(&self, strategy_id: &str) -> Result<Vec<Trade>>;
     async fn get_by_symbol(&self, symbol: &str) -> Result<Vec<Trade>>;
     async fn get_recent(&self, limit: u32) -> Result<Vec<Trade>>;
     async fn get_by_date_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Trade>>;
+    /// A structured, windowed read over `trades` in one round-trip. See
+    /// [`TradeScanFilter`]; `get_recent`/`get_by_date_range` are wrappers
+    /// over this for the common cases.
+    async fn scan(&self, filter: &TradeScanFilter) -> Result<Vec<Trade>>;
     async fn update(&self, trade: &Trade) -> Result<()>;
     async fn delete(&self, id: &str) -> Result<()>;
+    /// The audit trail of every `update`/`delete` captured for this
+    /// strategy's trades by the `trades_history` triggers, most recent first.
+    async fn get_trade_history(&self, strategy_id: &str) -> Result<Vec<TradeHistoryEntry>>;
 }
 
 #[async_trait]
@@ -46,331 +57,1156 @@ pub trait PerformanceRepository {
 #[async_trait]
 pub trait MarketDataRepository {
     async fn cache_market_data(&self, data: &MarketData) -> Result<()>;
+    /// Upsert every row in `data` via one multi-row statement, instead of
+    /// one `cache_market_data` call per row.
+    async fn insert_batch(&self, data: &[MarketData]) -> Result<()>;
     async fn get_cached_data(&self, symbol: &str, limit: u32) -> Result<Vec<MarketData>>;
     async fn get_latest_cached(&self, symbol: &str) -> Result<Option<MarketData>>;
+    /// A structured, windowed read over `market_data_cache` in one
+    /// round-trip. See [`MarketDataScanFilter`]; `get_cached_data` is a
+    /// wrapper over this for the common case.
+    async fn scan(&self, filter: &MarketDataScanFilter) -> Result<Vec<MarketData>>;
     async fn cleanup_old_cache(&self, older_than: DateTime<Utc>) -> Result<u32>;
 }
 
-// Repository implementations
-pub struct SqliteStrategyRepository {
-    pool: SqlitePool,
+// Executor-generic write helpers: each takes anything implementing
+// `sqlx::Executor` (a `&SqlitePool` for a standalone autocommit write, or a
+// `&mut Transaction<'_, Sqlite>` to group it with other writes into one
+// atomic unit via `Database::transaction`). Repository methods and the
+// batched writer delegate to these instead of duplicating the SQL.
+pub async fn insert_trade<'e, E>(executor: E, trade: &Trade) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let timestamp = datetime_to_string(trade.timestamp);
+    let action_str = trade.action.to_string();
+
+    sqlx::query(
+        r#"
+        INSERT INTO trades (id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#
+    )
+    .bind(&trade.id)
+    .bind(&trade.strategy_id)
+    .bind(&trade.symbol)
+    .bind(&action_str)
+    .bind(trade.quantity)
+    .bind(trade.price.to_f64())
+    .bind(&timestamp)
+    .bind(&trade.explanation)
+    .bind(trade.commission.to_f64())
+    .bind(trade.realized_pnl.map(|pnl| pnl.to_f64()))
+    .bind(trade.trade_value.to_f64())
+    .execute(executor)
+    .await?;
+
+    Ok(())
 }
 
-impl SqliteStrategyRepository {
-    pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+/// Insert every trade in `trades` via one multi-row `INSERT ... VALUES`
+/// statement instead of `trades.len()` separate round-trips. A no-op on an
+/// empty slice.
+pub async fn insert_trades<'e, E>(executor: E, trades: &[Trade]) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    if trades.is_empty() {
+        return Ok(());
     }
+
+    let mut builder = sqlx::QueryBuilder::new(
+        "INSERT INTO trades (id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value) "
+    );
+
+    builder.push_values(trades, |mut row, trade| {
+        row.push_bind(trade.id.clone())
+            .push_bind(trade.strategy_id.clone())
+            .push_bind(trade.symbol.clone())
+            .push_bind(trade.action.to_string())
+            .push_bind(trade.quantity)
+            .push_bind(trade.price.to_f64())
+            .push_bind(datetime_to_string(trade.timestamp))
+            .push_bind(trade.explanation.clone())
+            .push_bind(trade.commission.to_f64())
+            .push_bind(trade.realized_pnl.map(|pnl| pnl.to_f64()))
+            .push_bind(trade.trade_value.to_f64());
+    });
+
+    builder.build().execute(executor).await?;
+    Ok(())
 }
 
-#[async_trait]
-impl StrategyRepository for SqliteStrategyRepository {
-    async fn create(&self, strategy: &Strategy) -> Result<()> {
-        let strategy_type_json = serialize_json(&strategy.strategy_type)?;
-        let parameters_json = serialize_json(&strategy.parameters)?;
-        let created_at = datetime_to_string(strategy.created_at);
-
-        sqlx::query(
-            r#"
-            INSERT INTO strategies (id, strategy_type, symbol, parameters, created_at, is_active)
-            VALUES (?, ?, ?, ?, ?, ?)
-            "#
-        )
-        .bind(&strategy.id)
-        .bind(&strategy_type_json)
-        .bind(&strategy.symbol)
-        .bind(&parameters_json)
-        .bind(&created_at)
-        .bind(strategy.is_active)
-        .execute(&self.pool)
+/// How [`TradeRepository::scan`]/[`MarketDataRepository::scan`] match rows
+/// against a symbol: no filter, an explicit set, or a prefix (e.g. sweeping
+/// every `BRK`-class share line).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SymbolFilter {
+    #[default]
+    Any,
+    Set(Vec<String>),
+    Prefix(String),
+}
+
+/// Structured range filter for [`TradeRepository::scan`]: an optional
+/// symbol/strategy match, a `[start, end]` timestamp window, a result cap,
+/// and a scan direction. `get_recent`/`get_by_date_range` are thin wrappers
+/// over this.
+#[derive(Debug, Clone, Default)]
+pub struct TradeScanFilter {
+    pub symbols: SymbolFilter,
+    pub strategy_id: Option<String>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+    /// `false` scans oldest-first; `true` scans newest-first.
+    pub reverse: bool,
+}
+
+fn push_symbol_filter(builder: &mut sqlx::QueryBuilder<'_, Sqlite>, symbols: &SymbolFilter) {
+    match symbols {
+        SymbolFilter::Any => {}
+        SymbolFilter::Set(symbols) => {
+            builder.push(" AND symbol IN (");
+            let mut separated = builder.separated(", ");
+            for symbol in symbols {
+                separated.push_bind(symbol.clone());
+            }
+            separated.push_unseparated(")");
+        }
+        SymbolFilter::Prefix(prefix) => {
+            let escaped = prefix.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            builder.push(" AND symbol LIKE ");
+            builder.push_bind(format!("{}%", escaped));
+            builder.push(" ESCAPE '\\'");
+        }
+    }
+}
+
+/// One round-trip, structured-filter read over `trades`. See
+/// [`TradeScanFilter`].
+pub async fn scan_trades<'e, E>(executor: E, filter: &TradeScanFilter) -> Result<Vec<Trade>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    if let SymbolFilter::Set(symbols) = &filter.symbols {
+        if symbols.is_empty() {
+            return Ok(Vec::new());
+        }
+    }
+
+    let mut builder = sqlx::QueryBuilder::new(
+        "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades WHERE 1 = 1"
+    );
+
+    push_symbol_filter(&mut builder, &filter.symbols);
+
+    if let Some(strategy_id) = &filter.strategy_id {
+        builder.push(" AND strategy_id = ");
+        builder.push_bind(strategy_id.clone());
+    }
+    if let Some(start) = filter.start {
+        builder.push(" AND timestamp >= ");
+        builder.push_bind(datetime_to_string(start));
+    }
+    if let Some(end) = filter.end {
+        builder.push(" AND timestamp <= ");
+        builder.push_bind(datetime_to_string(end));
+    }
+
+    builder.push(" ORDER BY timestamp ");
+    builder.push(if filter.reverse { "DESC" } else { "ASC" });
+
+    if let Some(limit) = filter.limit {
+        builder.push(" LIMIT ");
+        builder.push_bind(limit as i64);
+    }
+
+    let rows = builder.build().fetch_all(executor).await?;
+    rows.into_iter().map(row_to_trade).collect()
+}
+
+/// Insert a performance snapshot, mirroring [`insert_trade`]'s executor-generic shape.
+pub async fn insert_snapshot<'e, E>(
+    executor: E,
+    strategy_id: &str,
+    metrics: &PerformanceMetrics,
+) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let metrics_json = serialize_json(metrics)?;
+    let timestamp = datetime_to_string(Utc::now());
+
+    sqlx::query(
+        r#"
+        INSERT INTO performance_snapshots (strategy_id, total_return, total_trades, timestamp, metrics)
+        VALUES (?, ?, ?, ?, ?)
+        "#
+    )
+    .bind(strategy_id)
+    .bind(metrics.total_return.to_f64())
+    .bind(metrics.total_trades as i64)
+    .bind(&timestamp)
+    .bind(&metrics_json)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Upsert a market-data cache row, mirroring [`insert_trade`]'s executor-generic shape.
+pub async fn upsert_market_data<'e, E>(executor: E, data: &MarketData) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let timestamp = datetime_to_string(data.timestamp);
+
+    sqlx::query(
+        r#"
+        INSERT INTO market_data_cache (symbol, timestamp, price, volume, change_percent, market_cap, day_high, day_low, previous_close)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(symbol, timestamp) DO UPDATE SET
+            price = excluded.price,
+            volume = excluded.volume,
+            change_percent = excluded.change_percent,
+            market_cap = excluded.market_cap,
+            day_high = excluded.day_high,
+            day_low = excluded.day_low,
+            previous_close = excluded.previous_close
+        "#
+    )
+    .bind(&data.symbol)
+    .bind(&timestamp)
+    .bind(data.price.to_f64())
+    .bind(data.volume as i64)
+    .bind(data.change_percent)
+    .bind(data.market_cap.map(|v| v as i64))
+    .bind(data.day_high.map(|p| p.to_f64()))
+    .bind(data.day_low.map(|p| p.to_f64()))
+    .bind(data.previous_close.map(|p| p.to_f64()))
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Upsert every row in `data` via one multi-row `INSERT ... ON CONFLICT`
+/// statement instead of `data.len()` separate round-trips. A no-op on an
+/// empty slice.
+pub async fn upsert_market_data_batch<'e, E>(executor: E, data: &[MarketData]) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    if data.is_empty() {
+        return Ok(());
+    }
+
+    let mut builder = sqlx::QueryBuilder::new(
+        "INSERT INTO market_data_cache (symbol, timestamp, price, volume, change_percent, market_cap, day_high, day_low, previous_close) "
+    );
+
+    builder.push_values(data, |mut row, item| {
+        row.push_bind(item.symbol.clone())
+            .push_bind(datetime_to_string(item.timestamp))
+            .push_bind(item.price.to_f64())
+            .push_bind(item.volume as i64)
+            .push_bind(item.change_percent)
+            .push_bind(item.market_cap.map(|v| v as i64))
+            .push_bind(item.day_high.map(|p| p.to_f64()))
+            .push_bind(item.day_low.map(|p| p.to_f64()))
+            .push_bind(item.previous_close.map(|p| p.to_f64()));
+    });
+
+    builder.push(
+        " ON CONFLICT(symbol, timestamp) DO UPDATE SET
+            price = excluded.price,
+            volume = excluded.volume,
+            change_percent = excluded.change_percent,
+            market_cap = excluded.market_cap,
+            day_high = excluded.day_high,
+            day_low = excluded.day_low,
+            previous_close = excluded.previous_close"
+    );
+
+    builder.build().execute(executor).await?;
+    Ok(())
+}
+
+/// Structured range filter for [`MarketDataRepository::scan`]. See
+/// [`TradeScanFilter`].
+#[derive(Debug, Clone, Default)]
+pub struct MarketDataScanFilter {
+    pub symbols: SymbolFilter,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+    /// `false` scans oldest-first; `true` scans newest-first.
+    pub reverse: bool,
+}
+
+/// One round-trip, structured-filter read over `market_data_cache`. See
+/// [`MarketDataScanFilter`]; [`get_cached_market_data`] is a wrapper over
+/// this for the common "latest N for one symbol" case.
+pub async fn scan_market_data<'e, E>(executor: E, filter: &MarketDataScanFilter) -> Result<Vec<MarketData>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    if let SymbolFilter::Set(symbols) = &filter.symbols {
+        if symbols.is_empty() {
+            return Ok(Vec::new());
+        }
+    }
+
+    let mut builder = sqlx::QueryBuilder::new(
+        "SELECT symbol, timestamp, price, volume, change_percent, market_cap, day_high, day_low, previous_close FROM market_data_cache WHERE 1 = 1"
+    );
+
+    push_symbol_filter(&mut builder, &filter.symbols);
+
+    if let Some(start) = filter.start {
+        builder.push(" AND timestamp >= ");
+        builder.push_bind(datetime_to_string(start));
+    }
+    if let Some(end) = filter.end {
+        builder.push(" AND timestamp <= ");
+        builder.push_bind(datetime_to_string(end));
+    }
+
+    builder.push(" ORDER BY timestamp ");
+    builder.push(if filter.reverse { "DESC" } else { "ASC" });
+
+    if let Some(limit) = filter.limit {
+        builder.push(" LIMIT ");
+        builder.push_bind(limit as i64);
+    }
+
+    let rows = builder.build().fetch_all(executor).await?;
+    rows.into_iter().map(row_to_market_data).collect()
+}
+
+/// The `limit` most recent cached rows for `symbol`, newest first.
+pub async fn get_cached_market_data<'e, E>(executor: E, symbol: &str, limit: u32) -> Result<Vec<MarketData>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    scan_market_data(executor, &MarketDataScanFilter {
+        symbols: SymbolFilter::Set(vec![symbol.to_string()]),
+        limit: Some(limit),
+        reverse: true,
+        ..Default::default()
+    })
+    .await
+}
+
+/// The single most recently cached row for `symbol`, or `None` if nothing
+/// has been cached for it yet.
+pub async fn get_latest_cached_market_data<'e, E>(executor: E, symbol: &str) -> Result<Option<MarketData>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let row = sqlx::query(
+        "SELECT symbol, timestamp, price, volume, change_percent, market_cap, day_high, day_low, previous_close FROM market_data_cache WHERE symbol = ? ORDER BY timestamp DESC LIMIT 1"
+    )
+    .bind(symbol)
+    .fetch_optional(executor)
+    .await?;
+
+    row.map(row_to_market_data).transpose()
+}
+
+/// Delete every cached row older than `older_than`, returning how many rows
+/// were removed.
+pub async fn delete_cached_market_data_older_than<'e, E>(executor: E, older_than: DateTime<Utc>) -> Result<u32>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM market_data_cache WHERE timestamp < ?")
+        .bind(datetime_to_string(older_than))
+        .execute(executor)
+        .await?;
+
+    Ok(result.rows_affected() as u32)
+}
+
+/// Insert a strategy, mirroring [`insert_trade`]'s executor-generic shape.
+pub async fn insert_strategy<'e, E>(executor: E, strategy: &Strategy) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let strategy_type_json = serialize_json(&strategy.strategy_type)?;
+    let parameters_json = serialize_json(&strategy.parameters)?;
+    let created_at = datetime_to_string(strategy.created_at);
+
+    sqlx::query(
+        r#"
+        INSERT INTO strategies (id, strategy_type, symbol, parameters, created_at, is_active)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#
+    )
+    .bind(&strategy.id)
+    .bind(&strategy_type_json)
+    .bind(&strategy.symbol)
+    .bind(&parameters_json)
+    .bind(&created_at)
+    .bind(strategy.is_active)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Persist `symbol`'s `StablePriceModel`-computed reference price onto the
+/// `market_data_cache` row for `timestamp`, so it survives a restart instead
+/// of the model re-seeding at the next live quote. Assumes a row for
+/// `(symbol, timestamp)` already exists, e.g. from an `upsert_market_data`
+/// call for the same tick.
+pub async fn upsert_stable_price<'e, E>(
+    executor: E,
+    symbol: &str,
+    timestamp: DateTime<Utc>,
+    stable_price: f64,
+) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query("UPDATE market_data_cache SET stable_price = ? WHERE symbol = ? AND timestamp = ?")
+        .bind(stable_price)
+        .bind(symbol)
+        .bind(datetime_to_string(timestamp))
+        .execute(executor)
         .await?;
 
-        Ok(())
+    Ok(())
+}
+
+/// The most recently persisted stable price for `symbol`, or `None` if it
+/// has never been cached -- e.g. right after a fresh `market_data_cache`
+/// migration, before any `upsert_stable_price` call.
+pub async fn get_latest_stable_price<'e, E>(executor: E, symbol: &str) -> Result<Option<f64>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let row = sqlx::query(
+        r#"
+        SELECT stable_price FROM market_data_cache
+        WHERE symbol = ? AND stable_price IS NOT NULL
+        ORDER BY timestamp DESC
+        LIMIT 1
+        "#
+    )
+    .bind(symbol)
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(row.map(|row| row.get::<f64, _>("stable_price")))
+}
+
+/// The strategy with `id`, or `None` if it doesn't exist. Executor-generic
+/// like [`insert_strategy`] so it can run against a pool or a transaction.
+pub async fn get_strategy_by_id<'e, E>(executor: E, id: &str) -> Result<Option<Strategy>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let row = sqlx::query(
+        "SELECT id, strategy_type, symbol, parameters, created_at, is_active FROM strategies WHERE id = ?"
+    )
+    .bind(id)
+    .fetch_optional(executor)
+    .await?;
+
+    row.map(row_to_strategy).transpose()
+}
+
+pub async fn get_all_strategies<'e, E>(executor: E) -> Result<Vec<Strategy>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let rows = sqlx::query(
+        "SELECT id, strategy_type, symbol, parameters, created_at, is_active FROM strategies ORDER BY created_at DESC"
+    )
+    .fetch_all(executor)
+    .await?;
+
+    rows.into_iter().map(row_to_strategy).collect()
+}
+
+pub async fn get_active_strategies<'e, E>(executor: E) -> Result<Vec<Strategy>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let rows = sqlx::query(
+        "SELECT id, strategy_type, symbol, parameters, created_at, is_active FROM strategies WHERE is_active = TRUE ORDER BY created_at DESC"
+    )
+    .fetch_all(executor)
+    .await?;
+
+    rows.into_iter().map(row_to_strategy).collect()
+}
+
+pub async fn get_strategies_by_symbol<'e, E>(executor: E, symbol: &str) -> Result<Vec<Strategy>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let rows = sqlx::query(
+        "SELECT id, strategy_type, symbol, parameters, created_at, is_active FROM strategies WHERE symbol = ? ORDER BY created_at DESC"
+    )
+    .bind(symbol)
+    .fetch_all(executor)
+    .await?;
+
+    rows.into_iter().map(row_to_strategy).collect()
+}
+
+pub async fn update_strategy<'e, E>(executor: E, strategy: &Strategy) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let strategy_type_json = serialize_json(&strategy.strategy_type)?;
+    let parameters_json = serialize_json(&strategy.parameters)?;
+
+    let result = sqlx::query(
+        r#"
+        UPDATE strategies
+        SET strategy_type = ?, symbol = ?, parameters = ?, is_active = ?
+        WHERE id = ?
+        "#
+    )
+    .bind(&strategy_type_json)
+    .bind(&strategy.symbol)
+    .bind(&parameters_json)
+    .bind(strategy.is_active)
+    .bind(&strategy.id)
+    .execute(executor)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(TradingPlatformError::internal("Strategy not found for update"));
     }
 
-    async fn get_by_id(&self, id: &str) -> Result<Option<Strategy>> {
-        let row = sqlx::query(
-            "SELECT id, strategy_type, symbol, parameters, created_at, is_active FROM strategies WHERE id = ?"
-        )
+    Ok(())
+}
+
+pub async fn delete_strategy<'e, E>(executor: E, id: &str) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM strategies WHERE id = ?")
         .bind(id)
-        .fetch_optional(&self.pool)
+        .execute(executor)
         .await?;
 
-        match row {
-            Some(row) => {
-                let strategy = row_to_strategy(row)?;
-                Ok(Some(strategy))
-            }
-            None => Ok(None),
-        }
+    if result.rows_affected() == 0 {
+        return Err(TradingPlatformError::internal("Strategy not found for deletion"));
     }
 
-    async fn get_all(&self) -> Result<Vec<Strategy>> {
-        let rows = sqlx::query(
-            "SELECT id, strategy_type, symbol, parameters, created_at, is_active FROM strategies ORDER BY created_at DESC"
-        )
-        .fetch_all(&self.pool)
+    Ok(())
+}
+
+pub async fn set_strategy_active<'e, E>(executor: E, id: &str, active: bool) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let result = sqlx::query("UPDATE strategies SET is_active = ? WHERE id = ?")
+        .bind(active)
+        .bind(id)
+        .execute(executor)
         .await?;
 
-        let mut strategies = Vec::new();
-        for row in rows {
-            strategies.push(row_to_strategy(row)?);
-        }
+    if result.rows_affected() == 0 {
+        return Err(TradingPlatformError::internal("Strategy not found"));
+    }
 
-        Ok(strategies)
+    Ok(())
+}
+
+pub async fn get_trade_by_id<'e, E>(executor: E, id: &str) -> Result<Option<Trade>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let row = sqlx::query(
+        "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades WHERE id = ?"
+    )
+    .bind(id)
+    .fetch_optional(executor)
+    .await?;
+
+    row.map(row_to_trade).transpose()
+}
+
+pub async fn get_trades_by_strategy<'e, E>(executor: E, strategy_id: &str) -> Result<Vec<Trade>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let rows = sqlx::query(
+        "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades WHERE strategy_id = ? ORDER BY timestamp DESC"
+    )
+    .bind(strategy_id)
+    .fetch_all(executor)
+    .await?;
+
+    rows.into_iter().map(row_to_trade).collect()
+}
+
+pub async fn get_trades_by_symbol<'e, E>(executor: E, symbol: &str) -> Result<Vec<Trade>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let rows = sqlx::query(
+        "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades WHERE symbol = ? ORDER BY timestamp DESC"
+    )
+    .bind(symbol)
+    .fetch_all(executor)
+    .await?;
+
+    rows.into_iter().map(row_to_trade).collect()
+}
+
+pub async fn get_recent_trades<'e, E>(executor: E, limit: u32) -> Result<Vec<Trade>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    scan_trades(executor, &TradeScanFilter {
+        limit: Some(limit),
+        reverse: true,
+        ..Default::default()
+    })
+    .await
+}
+
+pub async fn get_trades_by_date_range<'e, E>(
+    executor: E,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<Trade>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    scan_trades(executor, &TradeScanFilter {
+        start: Some(start),
+        end: Some(end),
+        reverse: true,
+        ..Default::default()
+    })
+    .await
+}
+
+pub async fn update_trade<'e, E>(executor: E, trade: &Trade) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let timestamp = datetime_to_string(trade.timestamp);
+    let action_str = trade.action.to_string();
+
+    let result = sqlx::query(
+        r#"
+        UPDATE trades
+        SET strategy_id = ?, symbol = ?, action = ?, quantity = ?, price = ?, timestamp = ?, explanation = ?, commission = ?, realized_pnl = ?, trade_value = ?
+        WHERE id = ?
+        "#
+    )
+    .bind(&trade.strategy_id)
+    .bind(&trade.symbol)
+    .bind(&action_str)
+    .bind(trade.quantity)
+    .bind(trade.price.to_f64())
+    .bind(&timestamp)
+    .bind(&trade.explanation)
+    .bind(trade.commission.to_f64())
+    .bind(trade.realized_pnl.map(|pnl| pnl.to_f64()))
+    .bind(trade.trade_value.to_f64())
+    .bind(&trade.id)
+    .execute(executor)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(TradingPlatformError::internal("Trade not found for update"));
     }
 
-    async fn get_active(&self) -> Result<Vec<Strategy>> {
-        let rows = sqlx::query(
-            "SELECT id, strategy_type, symbol, parameters, created_at, is_active FROM strategies WHERE is_active = TRUE ORDER BY created_at DESC"
-        )
-        .fetch_all(&self.pool)
-        .await?;
+    Ok(())
+}
 
-        let mut strategies = Vec::new();
-        for row in rows {
-            strategies.push(row_to_strategy(row)?);
-        }
+pub async fn delete_trade<'e, E>(executor: E, id: &str) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let result = sqlx::query("DELETE FROM trades WHERE id = ?")
+        .bind(id)
+        .execute(executor)
+        .await?;
 
-        Ok(strategies)
+    if result.rows_affected() == 0 {
+        return Err(TradingPlatformError::internal("Trade not found for deletion"));
     }
 
-    async fn get_by_symbol(&self, symbol: &str) -> Result<Vec<Strategy>> {
-        let rows = sqlx::query(
-            "SELECT id, strategy_type, symbol, parameters, created_at, is_active FROM strategies WHERE symbol = ? ORDER BY created_at DESC"
-        )
-        .bind(symbol)
-        .fetch_all(&self.pool)
+    Ok(())
+}
+
+pub async fn get_trade_history<'e, E>(executor: E, strategy_id: &str) -> Result<Vec<TradeHistoryEntry>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let rows = sqlx::query(
+        "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value, operation, changed_at FROM trades_history WHERE strategy_id = ? ORDER BY history_id DESC"
+    )
+    .bind(strategy_id)
+    .fetch_all(executor)
+    .await?;
+
+    rows.into_iter().map(row_to_trade_history).collect()
+}
+
+/// Reads the `strategy_latest_performance` view instead of issuing a
+/// `MAX(timestamp)`-per-strategy query itself.
+pub async fn get_latest_performance<'e, E>(executor: E, strategy_id: &str) -> Result<Option<PerformanceMetrics>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let row = sqlx::query("SELECT metrics FROM strategy_latest_performance WHERE strategy_id = ?")
+        .bind(strategy_id)
+        .fetch_optional(executor)
         .await?;
 
-        let mut strategies = Vec::new();
-        for row in rows {
-            strategies.push(row_to_strategy(row)?);
+    match row {
+        Some(row) => {
+            let metrics_json: String = row.get("metrics");
+            Ok(Some(deserialize_json(&metrics_json)?))
         }
+        None => Ok(None),
+    }
+}
 
-        Ok(strategies)
+pub async fn get_performance_history<'e, E>(
+    executor: E,
+    strategy_id: &str,
+    limit: u32,
+) -> Result<Vec<PerformanceMetrics>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    let rows = sqlx::query(
+        "SELECT metrics FROM performance_snapshots WHERE strategy_id = ? ORDER BY timestamp DESC LIMIT ?"
+    )
+    .bind(strategy_id)
+    .bind(limit as i64)
+    .fetch_all(executor)
+    .await?;
+
+    let mut history = Vec::new();
+    for row in rows {
+        let metrics_json: String = row.get("metrics");
+        history.push(deserialize_json(&metrics_json)?);
     }
 
-    async fn update(&self, strategy: &Strategy) -> Result<()> {
-        let strategy_type_json = serialize_json(&strategy.strategy_type)?;
-        let parameters_json = serialize_json(&strategy.parameters)?;
-
-        let result = sqlx::query(
-            r#"
-            UPDATE strategies 
-            SET strategy_type = ?, symbol = ?, parameters = ?, is_active = ?
-            WHERE id = ?
-            "#
+    Ok(history)
+}
+
+pub async fn delete_old_performance_snapshots<'e, E>(executor: E, strategy_id: &str, keep_count: u32) -> Result<()>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query(
+        r#"
+        DELETE FROM performance_snapshots
+        WHERE strategy_id = ?
+        AND id NOT IN (
+            SELECT id FROM performance_snapshots
+            WHERE strategy_id = ?
+            ORDER BY timestamp DESC
+            LIMIT ?
         )
-        .bind(&strategy_type_json)
-        .bind(&strategy.symbol)
-        .bind(&parameters_json)
-        .bind(strategy.is_active)
-        .bind(&strategy.id)
-        .execute(&self.pool)
-        .await?;
+        "#
+    )
+    .bind(strategy_id)
+    .bind(strategy_id)
+    .bind(keep_count as i64)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
 
-        if result.rows_affected() == 0 {
-            return Err(TradingPlatformError::internal("Strategy not found for update"));
-        }
+// Repository implementations
+pub struct SqliteStrategyRepository {
+    pool: SqlitePool,
+    write_handle: Option<WriteHandle>,
+}
 
-        Ok(())
+impl SqliteStrategyRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool, write_handle: None }
     }
 
-    async fn delete(&self, id: &str) -> Result<()> {
-        let result = sqlx::query("DELETE FROM strategies WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+    /// Route `create` through `write_handle`'s batched executor instead of
+    /// running its own autocommit `INSERT`, so bursts of strategy creation
+    /// coalesce with concurrent trade/snapshot writes into fewer fsyncs.
+    pub fn with_writer(mut self, write_handle: WriteHandle) -> Self {
+        self.write_handle = Some(write_handle);
+        self
+    }
+}
 
-        if result.rows_affected() == 0 {
-            return Err(TradingPlatformError::internal("Strategy not found for deletion"));
+#[async_trait]
+impl StrategyRepository for SqliteStrategyRepository {
+    async fn create(&self, strategy: &Strategy) -> Result<()> {
+        match &self.write_handle {
+            Some(handle) => handle.insert_strategy(strategy.clone()).await,
+            None => insert_strategy(&self.pool, strategy).await,
         }
+    }
 
-        Ok(())
+    async fn get_by_id(&self, id: &str) -> Result<Option<Strategy>> {
+        get_strategy_by_id(&self.pool, id).await
     }
 
-    async fn set_active(&self, id: &str, active: bool) -> Result<()> {
-        let result = sqlx::query("UPDATE strategies SET is_active = ? WHERE id = ?")
-            .bind(active)
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
-
-        if result.rows_affected() == 0 {
-            return Err(TradingPlatformError::internal("Strategy not found"));
-        }
+    async fn get_all(&self) -> Result<Vec<Strategy>> {
+        get_all_strategies(&self.pool).await
+    }
+
+    async fn get_active(&self) -> Result<Vec<Strategy>> {
+        get_active_strategies(&self.pool).await
+    }
+
+    async fn get_by_symbol(&self, symbol: &str) -> Result<Vec<Strategy>> {
+        get_strategies_by_symbol(&self.pool, symbol).await
+    }
+
+    async fn update(&self, strategy: &Strategy) -> Result<()> {
+        update_strategy(&self.pool, strategy).await
+    }
 
-        Ok(())
+    async fn delete(&self, id: &str) -> Result<()> {
+        delete_strategy(&self.pool, id).await
+    }
+
+    async fn set_active(&self, id: &str, active: bool) -> Result<()> {
+        set_strategy_active(&self.pool, id, active).await
     }
 }
 
 pub struct SqliteTradeRepository {
     pool: SqlitePool,
+    write_handle: Option<WriteHandle>,
 }
 
 impl SqliteTradeRepository {
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self { pool, write_handle: None }
+    }
+
+    /// Route `create` through `write_handle`'s batched executor instead of
+    /// running its own autocommit `INSERT`. See
+    /// [`SqliteStrategyRepository::with_writer`].
+    pub fn with_writer(mut self, write_handle: WriteHandle) -> Self {
+        self.write_handle = Some(write_handle);
+        self
     }
 }
 
 #[async_trait]
 impl TradeRepository for SqliteTradeRepository {
     async fn create(&self, trade: &Trade) -> Result<()> {
-        let timestamp = datetime_to_string(trade.timestamp);
-        let action_str = trade.action.to_string();
-
-        sqlx::query(
-            r#"
-            INSERT INTO trades (id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#
-        )
-        .bind(&trade.id)
-        .bind(&trade.strategy_id)
-        .bind(&trade.symbol)
-        .bind(&action_str)
-        .bind(trade.quantity)
-        .bind(trade.price)
-        .bind(&timestamp)
-        .bind(&trade.explanation)
-        .bind(trade.commission)
-        .bind(trade.realized_pnl)
-        .bind(trade.trade_value)
-        .execute(&self.pool)
-        .await?;
+        match &self.write_handle {
+            Some(handle) => handle.insert_trade(trade.clone()).await,
+            None => insert_trade(&self.pool, trade).await,
+        }
+    }
 
-        Ok(())
+    async fn insert_batch(&self, trades: &[Trade]) -> Result<()> {
+        insert_trades(&self.pool, trades).await
     }
 
     async fn get_by_id(&self, id: &str) -> Result<Option<Trade>> {
-        let row = sqlx::query(
-            "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades WHERE id = ?"
-        )
-        .bind(id)
-        .fetch_optional(&self.pool)
-        .await?;
-
-        match row {
-            Some(row) => {
-                let trade = row_to_trade(row)?;
-                Ok(Some(trade))
-            }
-            None => Ok(None),
-        }
+        get_trade_by_id(&self.pool, id).await
     }
 
     async fn get_by_strategy(&self, strategy_id: &str) -> Result<Vec<Trade>> {
+        get_trades_by_strategy(&self.pool, strategy_id).await
+    }
+
+    async fn get_by_symbol(&self, symbol: &str) -> Result<Vec<Trade>> {
+        get_trades_by_symbol(&self.pool, symbol).await
+    }
+
+    async fn get_recent(&self, limit: u32) -> Result<Vec<Trade>> {
+        get_recent_trades(&self.pool, limit).await
+    }
+
+    async fn get_by_date_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Trade>> {
+        get_trades_by_date_range(&self.pool, start, end).await
+    }
+
+    async fn scan(&self, filter: &TradeScanFilter) -> Result<Vec<Trade>> {
+        scan_trades(&self.pool, filter).await
+    }
+
+    async fn update(&self, trade: &Trade) -> Result<()> {
+        update_trade(&self.pool, trade).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        delete_trade(&self.pool, id).await
+    }
+
+    async fn get_trade_history(&self, strategy_id: &str) -> Result<Vec<TradeHistoryEntry>> {
+        get_trade_history(&self.pool, strategy_id).await
+    }
+}
+
+/// A single audit entry from `trades_history`: the trade's state just
+/// before the mutation, plus which mutation it was and when it happened.
+#[derive(Debug, Clone)]
+pub struct TradeHistoryEntry {
+    pub trade: Trade,
+    pub operation: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl SqliteTradeRepository {
+    /// Net quantity, cost basis, and commission per symbol for a strategy,
+    /// read from the `strategy_positions` view instead of reassembling it
+    /// from `trades` in application code.
+    pub async fn get_positions(&self, strategy_id: &str) -> Result<Vec<StrategyPosition>> {
         let rows = sqlx::query(
-            "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades WHERE strategy_id = ? ORDER BY timestamp DESC"
+            "SELECT strategy_id, symbol, net_quantity, total_commission, average_cost FROM strategy_positions WHERE strategy_id = ?"
         )
         .bind(strategy_id)
         .fetch_all(&self.pool)
         .await?;
 
-        let mut trades = Vec::new();
+        let mut positions = Vec::new();
         for row in rows {
-            trades.push(row_to_trade(row)?);
+            positions.push(StrategyPosition {
+                strategy_id: row.get("strategy_id"),
+                symbol: row.get("symbol"),
+                net_quantity: row.get("net_quantity"),
+                total_commission: row.get("total_commission"),
+                average_cost: row.get("average_cost"),
+            });
         }
 
-        Ok(trades)
+        Ok(positions)
     }
+}
 
-    async fn get_by_symbol(&self, symbol: &str) -> Result<Vec<Trade>> {
-        let rows = sqlx::query(
-            "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades WHERE symbol = ? ORDER BY timestamp DESC"
-        )
-        .bind(symbol)
-        .fetch_all(&self.pool)
-        .await?;
+/// A row from the `strategy_positions` view: the net position a strategy
+/// holds in a symbol, derived server-side from summed `trades`.
+#[derive(Debug, Clone)]
+pub struct StrategyPosition {
+    pub strategy_id: String,
+    pub symbol: String,
+    pub net_quantity: f64,
+    pub total_commission: f64,
+    pub average_cost: f64,
+}
 
-        let mut trades = Vec::new();
-        for row in rows {
-            trades.push(row_to_trade(row)?);
-        }
+pub struct SqlitePerformanceRepository {
+    pool: SqlitePool,
+}
 
-        Ok(trades)
+impl SqlitePerformanceRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
     }
+}
 
-    async fn get_recent(&self, limit: u32) -> Result<Vec<Trade>> {
-        let rows = sqlx::query(
-            "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades ORDER BY timestamp DESC LIMIT ?"
-        )
-        .bind(limit as i64)
-        .fetch_all(&self.pool)
-        .await?;
+#[async_trait]
+impl PerformanceRepository for SqlitePerformanceRepository {
+    async fn create_snapshot(&self, strategy_id: &str, metrics: &PerformanceMetrics) -> Result<()> {
+        insert_snapshot(&self.pool, strategy_id, metrics).await
+    }
 
-        let mut trades = Vec::new();
-        for row in rows {
-            trades.push(row_to_trade(row)?);
-        }
+    /// Reads the `strategy_latest_performance` view instead of issuing a
+    /// `MAX(timestamp)`-per-strategy query itself.
+    async fn get_latest(&self, strategy_id: &str) -> Result<Option<PerformanceMetrics>> {
+        get_latest_performance(&self.pool, strategy_id).await
+    }
 
-        Ok(trades)
+    async fn get_history(&self, strategy_id: &str, limit: u32) -> Result<Vec<PerformanceMetrics>> {
+        get_performance_history(&self.pool, strategy_id, limit).await
     }
 
-    async fn get_by_date_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Trade>> {
-        let start_str = datetime_to_string(start);
-        let end_str = datetime_to_string(end);
+    async fn delete_old_snapshots(&self, strategy_id: &str, keep_count: u32) -> Result<()> {
+        delete_old_performance_snapshots(&self.pool, strategy_id, keep_count).await
+    }
+}
 
-        let rows = sqlx::query(
-            "SELECT id, strategy_id, symbol, action, quantity, price, timestamp, explanation, commission, realized_pnl, trade_value FROM trades WHERE timestamp BETWEEN ? AND ? ORDER BY timestamp DESC"
-        )
-        .bind(&start_str)
-        .bind(&end_str)
-        .fetch_all(&self.pool)
-        .await?;
+/// Backed by [`crate::database::Database::cache_pool`] rather than the
+/// durable pool the other `Sqlite*Repository` types use, so high-frequency
+/// cache writes and [`MarketDataRepository::cleanup_old_cache`] sweeps never
+/// contend with the strategy/trade-recording path.
+pub struct SqliteMarketDataRepository {
+    pool: SqlitePool,
+}
 
-        let mut trades = Vec::new();
-        for row in rows {
-            trades.push(row_to_trade(row)?);
-        }
+impl SqliteMarketDataRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
 
-        Ok(trades)
+#[async_trait]
+impl MarketDataRepository for SqliteMarketDataRepository {
+    async fn cache_market_data(&self, data: &MarketData) -> Result<()> {
+        upsert_market_data(&self.pool, data).await
     }
 
-    async fn update(&self, trade: &Trade) -> Result<()> {
-        let timestamp = datetime_to_string(trade.timestamp);
-        let action_str = trade.action.to_string();
-
-        let result = sqlx::query(
-            r#"
-            UPDATE trades 
-            SET strategy_id = ?, symbol = ?, action = ?, quantity = ?, price = ?, timestamp = ?, explanation = ?, commission = ?, realized_pnl = ?, trade_value = ?
-            WHERE id = ?
-            "#
-        )
-        .bind(&trade.strategy_id)
-        .bind(&trade.symbol)
-        .bind(&action_str)
-        .bind(trade.quantity)
-        .bind(trade.price)
-        .bind(&timestamp)
-        .bind(&trade.explanation)
-        .bind(trade.commission)
-        .bind(trade.realized_pnl)
-        .bind(trade.trade_value)
-        .bind(&trade.id)
-        .execute(&self.pool)
-        .await?;
+    async fn insert_batch(&self, data: &[MarketData]) -> Result<()> {
+        upsert_market_data_batch(&self.pool, data).await
+    }
 
-        if result.rows_affected() == 0 {
-            return Err(TradingPlatformError::internal("Trade not found for update"));
-        }
+    async fn get_cached_data(&self, symbol: &str, limit: u32) -> Result<Vec<MarketData>> {
+        get_cached_market_data(&self.pool, symbol, limit).await
+    }
 
-        Ok(())
+    async fn get_latest_cached(&self, symbol: &str) -> Result<Option<MarketData>> {
+        get_latest_cached_market_data(&self.pool, symbol).await
     }
 
-    async fn delete(&self, id: &str) -> Result<()> {
-        let result = sqlx::query("DELETE FROM trades WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+    async fn scan(&self, filter: &MarketDataScanFilter) -> Result<Vec<MarketData>> {
+        scan_market_data(&self.pool, filter).await
+    }
 
-        if result.rows_affected() == 0 {
-            return Err(TradingPlatformError::internal("Trade not found for deletion"));
-        }
+    async fn cleanup_old_cache(&self, older_than: DateTime<Utc>) -> Result<u32> {
+        delete_cached_market_data_older_than(&self.pool, older_than).await
+    }
+}
+
+// Transaction-scoped repository views handed out by
+// `crate::database::UnitOfWork`. Each mirrors its `Sqlite*Repository`
+// counterpart's methods but delegates to the same executor-generic free
+// functions with `&mut Transaction` instead of `&SqlitePool`, so the writes
+// only land when the enclosing transaction commits. Methods take `&mut
+// self` rather than `&self` because a transaction can only be borrowed
+// mutably one statement at a time, so these are plain inherent impls rather
+// than implementations of the `&self`-based `StrategyRepository` /
+// `TradeRepository` / `PerformanceRepository` traits.
+
+/// A [`StrategyRepository`]-shaped view scoped to one `sqlx::Transaction`.
+/// See [`crate::database::UnitOfWork::strategies`].
+pub struct TransactionStrategyRepository<'a, 'c> {
+    tx: &'a mut Transaction<'c, Sqlite>,
+}
+
+impl<'a, 'c> TransactionStrategyRepository<'a, 'c> {
+    pub(crate) fn new(tx: &'a mut Transaction<'c, Sqlite>) -> Self {
+        Self { tx }
+    }
+
+    pub async fn create(&mut self, strategy: &Strategy) -> Result<()> {
+        insert_strategy(&mut *self.tx, strategy).await
+    }
+
+    pub async fn get_by_id(&mut self, id: &str) -> Result<Option<Strategy>> {
+        get_strategy_by_id(&mut *self.tx, id).await
+    }
 
-        Ok(())
+    pub async fn get_all(&mut self) -> Result<Vec<Strategy>> {
+        get_all_strategies(&mut *self.tx).await
+    }
+
+    pub async fn get_active(&mut self) -> Result<Vec<Strategy>> {
+        get_active_strategies(&mut *self.tx).await
+    }
+
+    pub async fn get_by_symbol(&mut self, symbol: &str) -> Result<Vec<Strategy>> {
+        get_strategies_by_symbol(&mut *self.tx, symbol).await
+    }
+
+    pub async fn update(&mut self, strategy: &Strategy) -> Result<()> {
+        update_strategy(&mut *self.tx, strategy).await
+    }
+
+    pub async fn delete(&mut self, id: &str) -> Result<()> {
+        delete_strategy(&mut *self.tx, id).await
+    }
+
+    pub async fn set_active(&mut self, id: &str, active: bool) -> Result<()> {
+        set_strategy_active(&mut *self.tx, id, active).await
+    }
+}
+
+/// A [`TradeRepository`]-shaped view scoped to one `sqlx::Transaction`.
+/// See [`crate::database::UnitOfWork::trades`].
+pub struct TransactionTradeRepository<'a, 'c> {
+    tx: &'a mut Transaction<'c, Sqlite>,
+}
+
+impl<'a, 'c> TransactionTradeRepository<'a, 'c> {
+    pub(crate) fn new(tx: &'a mut Transaction<'c, Sqlite>) -> Self {
+        Self { tx }
+    }
+
+    pub async fn create(&mut self, trade: &Trade) -> Result<()> {
+        insert_trade(&mut *self.tx, trade).await
+    }
+
+    pub async fn insert_batch(&mut self, trades: &[Trade]) -> Result<()> {
+        insert_trades(&mut *self.tx, trades).await
+    }
+
+    pub async fn get_by_id(&mut self, id: &str) -> Result<Option<Trade>> {
+        get_trade_by_id(&mut *self.tx, id).await
+    }
+
+    pub async fn get_by_strategy(&mut self, strategy_id: &str) -> Result<Vec<Trade>> {
+        get_trades_by_strategy(&mut *self.tx, strategy_id).await
+    }
+
+    pub async fn get_by_symbol(&mut self, symbol: &str) -> Result<Vec<Trade>> {
+        get_trades_by_symbol(&mut *self.tx, symbol).await
+    }
+
+    pub async fn get_recent(&mut self, limit: u32) -> Result<Vec<Trade>> {
+        get_recent_trades(&mut *self.tx, limit).await
+    }
+
+    pub async fn get_by_date_range(&mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Trade>> {
+        get_trades_by_date_range(&mut *self.tx, start, end).await
+    }
+
+    pub async fn scan(&mut self, filter: &TradeScanFilter) -> Result<Vec<Trade>> {
+        scan_trades(&mut *self.tx, filter).await
+    }
+
+    pub async fn update(&mut self, trade: &Trade) -> Result<()> {
+        update_trade(&mut *self.tx, trade).await
+    }
+
+    pub async fn delete(&mut self, id: &str) -> Result<()> {
+        delete_trade(&mut *self.tx, id).await
+    }
+
+    pub async fn get_trade_history(&mut self, strategy_id: &str) -> Result<Vec<TradeHistoryEntry>> {
+        get_trade_history(&mut *self.tx, strategy_id).await
+    }
+}
+
+/// A [`PerformanceRepository`]-shaped view scoped to one `sqlx::Transaction`.
+/// See [`crate::database::UnitOfWork::performance`].
+pub struct TransactionPerformanceRepository<'a, 'c> {
+    tx: &'a mut Transaction<'c, Sqlite>,
+}
+
+impl<'a, 'c> TransactionPerformanceRepository<'a, 'c> {
+    pub(crate) fn new(tx: &'a mut Transaction<'c, Sqlite>) -> Self {
+        Self { tx }
+    }
+
+    pub async fn create_snapshot(&mut self, strategy_id: &str, metrics: &PerformanceMetrics) -> Result<()> {
+        insert_snapshot(&mut *self.tx, strategy_id, metrics).await
+    }
+
+    pub async fn get_latest(&mut self, strategy_id: &str) -> Result<Option<PerformanceMetrics>> {
+        get_latest_performance(&mut *self.tx, strategy_id).await
+    }
+
+    pub async fn get_history(&mut self, strategy_id: &str, limit: u32) -> Result<Vec<PerformanceMetrics>> {
+        get_performance_history(&mut *self.tx, strategy_id, limit).await
+    }
+
+    pub async fn delete_old_snapshots(&mut self, strategy_id: &str, keep_count: u32) -> Result<()> {
+        delete_old_performance_snapshots(&mut *self.tx, strategy_id, keep_count).await
     }
 }
 
@@ -414,6 +1250,8 @@ fn row_to_trade(row: sqlx::sqlite::SqliteRow) -> Result<Trade> {
         "BUY" => Action::Buy,
         "SELL" => Action::Sell,
         "HOLD" => Action::Hold,
+        "SHORT_SELL" => Action::ShortSell,
+        "EXIT_SHORT" => Action::ExitShort,
         _ => return Err(TradingPlatformError::internal(format!("Invalid action: {}", action_str))),
     };
 
@@ -425,12 +1263,57 @@ fn row_to_trade(row: sqlx::sqlite::SqliteRow) -> Result<Trade> {
         symbol,
         action,
         quantity,
-        price,
+        price: Money::from_f64(price),
         timestamp,
         explanation: explanation.unwrap_or_default(),
-        commission: commission.unwrap_or(0.0),
-        realized_pnl,
-        trade_value,
+        commission: Money::from_f64(commission.unwrap_or(0.0)),
+        realized_pnl: realized_pnl.map(Money::from_f64),
+        trade_value: Money::from_f64(trade_value),
+    })
+}
+
+fn row_to_trade_history(row: sqlx::sqlite::SqliteRow) -> Result<TradeHistoryEntry> {
+    let operation: String = row.get("operation");
+    let changed_at_str: String = row.get("changed_at");
+    let changed_at = string_to_datetime(&changed_at_str)?;
+
+    Ok(TradeHistoryEntry {
+        trade: row_to_trade(row)?,
+        operation,
+        changed_at,
+    })
+}
+
+/// `change`, `confidence` and `publish_time` aren't persisted in
+/// `market_data_cache` (see [`upsert_market_data`]): `change` and
+/// `confidence` come back as zero, as [`MarketData::new`] sets them, and
+/// `publish_time` comes back as the row's `timestamp`.
+fn row_to_market_data(row: sqlx::sqlite::SqliteRow) -> Result<MarketData> {
+    let symbol: String = row.get("symbol");
+    let timestamp_str: String = row.get("timestamp");
+    let price: f64 = row.get("price");
+    let volume: Option<i64> = row.get("volume");
+    let change_percent: Option<f64> = row.get("change_percent");
+    let market_cap: Option<i64> = row.get("market_cap");
+    let day_high: Option<f64> = row.get("day_high");
+    let day_low: Option<f64> = row.get("day_low");
+    let previous_close: Option<f64> = row.get("previous_close");
+
+    let timestamp = string_to_datetime(&timestamp_str)?;
+
+    Ok(MarketData {
+        symbol,
+        price: Price::from_f64(price),
+        volume: volume.unwrap_or(0) as u64,
+        timestamp,
+        change: Price::ZERO,
+        change_percent: change_percent.unwrap_or(0.0),
+        market_cap: market_cap.map(|v| v as u64),
+        day_high: day_high.map(Price::from_f64),
+        day_low: day_low.map(Price::from_f64),
+        previous_close: previous_close.map(Price::from_f64),
+        confidence: 0.0,
+        publish_time: timestamp,
     })
 }
 
@@ -439,12 +1322,13 @@ mod tests {
     use super::*;
     use crate::strategy::{StrategyType, StrategyParameters};
     use crate::strategy::Action;
+    use crate::performance::Position;
 
     use uuid::Uuid;
 
     async fn create_test_db() -> SqlitePool {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
-        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        crate::database::migrations::migrate(&pool).await.unwrap();
         pool
     }
 
@@ -490,6 +1374,50 @@ mod tests {
         assert!(retrieved.is_none());
     }
 
+    #[tokio::test]
+    async fn test_get_latest_stable_price_returns_most_recent_persisted_value() {
+        let pool = create_test_db().await;
+        assert_eq!(get_latest_stable_price(&pool, "AAPL").await.unwrap(), None);
+
+        let first = crate::data::MarketData::new("AAPL".to_string(), 150.0, 1_000_000);
+        let first_timestamp = first.timestamp;
+        upsert_market_data(&pool, &first).await.unwrap();
+        upsert_stable_price(&pool, "AAPL", first_timestamp, 149.0).await.unwrap();
+
+        let mut second = crate::data::MarketData::new("AAPL".to_string(), 160.0, 1_000_000);
+        second.timestamp = first_timestamp + chrono::Duration::seconds(1);
+        upsert_market_data(&pool, &second).await.unwrap();
+        upsert_stable_price(&pool, "AAPL", second.timestamp, 150.5).await.unwrap();
+
+        assert_eq!(get_latest_stable_price(&pool, "AAPL").await.unwrap(), Some(150.5));
+    }
+
+    #[tokio::test]
+    async fn test_market_data_repository_caches_and_expires_rows() {
+        let pool = create_test_db().await;
+        let repo = SqliteMarketDataRepository::new(pool);
+
+        assert!(repo.get_latest_cached("AAPL").await.unwrap().is_none());
+
+        let mut old = crate::data::MarketData::new("AAPL".to_string(), 150.0, 1_000_000);
+        old.timestamp = Utc::now() - chrono::Duration::days(100);
+        repo.cache_market_data(&old).await.unwrap();
+
+        let mut recent = crate::data::MarketData::new("AAPL".to_string(), 160.0, 2_000_000);
+        recent.timestamp = Utc::now();
+        repo.cache_market_data(&recent).await.unwrap();
+
+        let latest = repo.get_latest_cached("AAPL").await.unwrap().unwrap();
+        assert_eq!(latest.price.to_f64(), 160.0);
+
+        let cached = repo.get_cached_data("AAPL", 10).await.unwrap();
+        assert_eq!(cached.len(), 2);
+
+        let deleted = repo.cleanup_old_cache(Utc::now() - chrono::Duration::days(30)).await.unwrap();
+        assert_eq!(deleted, 1);
+        assert_eq!(repo.get_cached_data("AAPL", 10).await.unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_trade_repository() {
         let pool = create_test_db().await;
@@ -514,12 +1442,12 @@ mod tests {
             symbol: "AAPL".to_string(),
             action: Action::Buy,
             quantity: 100.0,
-            price: 150.0,
+            price: Money::from_f64(150.0),
             timestamp: Utc::now(),
             explanation: "Test trade".to_string(),
-            commission: 5.0,
-            realized_pnl: Some(50.0),
-            trade_value: 15000.0,
+            commission: Money::from_f64(5.0),
+            realized_pnl: Some(Money::from_f64(50.0)),
+            trade_value: Money::from_f64(15000.0),
         };
 
         // Test create
@@ -539,9 +1467,286 @@ mod tests {
         let recent_trades = trade_repo.get_recent(10).await.unwrap();
         assert_eq!(recent_trades.len(), 1);
 
+        // Test update, which the trades_history trigger should capture
+        let mut updated_trade = trade.clone();
+        updated_trade.price = Money::from_f64(151.0);
+        trade_repo.update(&updated_trade).await.unwrap();
+
         // Test delete
         trade_repo.delete(&trade.id).await.unwrap();
         let retrieved = trade_repo.get_by_id(&trade.id).await.unwrap();
         assert!(retrieved.is_none());
+
+        // Test get trade history: most recent (the delete) first
+        let history = trade_repo.get_trade_history(&trade.strategy_id).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].operation, "delete");
+        assert_eq!(history[1].operation, "update");
+        assert_eq!(history[1].trade.price.to_f64(), 150.0); // pre-update state
+    }
+
+    #[tokio::test]
+    async fn test_insert_batch_inserts_every_trade_in_one_round_trip() {
+        let pool = create_test_db().await;
+        let trade_repo = SqliteTradeRepository::new(pool.clone());
+        let strategy_repo = SqliteStrategyRepository::new(pool);
+
+        let strategy = Strategy {
+            id: Uuid::new_v4().to_string(),
+            strategy_type: StrategyType::PriceDrop { threshold: 5.0 },
+            symbol: "AAPL".to_string(),
+            parameters: StrategyParameters::default(),
+            created_at: Utc::now(),
+            is_active: true,
+        };
+        strategy_repo.create(&strategy).await.unwrap();
+
+        let trades: Vec<Trade> = (0..5)
+            .map(|i| Trade {
+                id: Uuid::new_v4().to_string(),
+                strategy_id: strategy.id.clone(),
+                symbol: "AAPL".to_string(),
+                action: Action::Buy,
+                quantity: 10.0,
+                price: Money::from_f64(100.0 + i as f64),
+                timestamp: Utc::now() + chrono::Duration::seconds(i),
+                explanation: "batch trade".to_string(),
+                commission: Money::from_f64(1.0),
+                realized_pnl: None,
+                trade_value: Money::from_f64(1000.0),
+            })
+            .collect();
+
+        trade_repo.insert_batch(&trades).await.unwrap();
+
+        let all = trade_repo.get_by_strategy(&strategy.id).await.unwrap();
+        assert_eq!(all.len(), 5);
+
+        // insert_batch on an empty slice is a no-op, not an error.
+        trade_repo.insert_batch(&[]).await.unwrap();
+        assert_eq!(trade_repo.get_by_strategy(&strategy.id).await.unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_scan_applies_symbol_set_and_timestamp_window_with_direction() {
+        let pool = create_test_db().await;
+        let trade_repo = SqliteTradeRepository::new(pool.clone());
+        let strategy_repo = SqliteStrategyRepository::new(pool);
+
+        let strategy = Strategy {
+            id: Uuid::new_v4().to_string(),
+            strategy_type: StrategyType::PriceDrop { threshold: 5.0 },
+            symbol: "AAPL".to_string(),
+            parameters: StrategyParameters::default(),
+            created_at: Utc::now(),
+            is_active: true,
+        };
+        strategy_repo.create(&strategy).await.unwrap();
+
+        let base = Utc::now();
+        let symbols = ["AAPL", "AAPL", "MSFT"];
+        let trades: Vec<Trade> = symbols
+            .iter()
+            .enumerate()
+            .map(|(i, symbol)| Trade {
+                id: Uuid::new_v4().to_string(),
+                strategy_id: strategy.id.clone(),
+                symbol: symbol.to_string(),
+                action: Action::Buy,
+                quantity: 10.0,
+                price: Money::from_f64(100.0),
+                timestamp: base + chrono::Duration::seconds(i as i64),
+                explanation: "scan trade".to_string(),
+                commission: Money::from_f64(1.0),
+                realized_pnl: None,
+                trade_value: Money::from_f64(1000.0),
+            })
+            .collect();
+        trade_repo.insert_batch(&trades).await.unwrap();
+
+        let aapl_only = trade_repo
+            .scan(&TradeScanFilter {
+                symbols: SymbolFilter::Set(vec!["AAPL".to_string()]),
+                reverse: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(aapl_only.len(), 2);
+        assert!(aapl_only.iter().all(|t| t.symbol == "AAPL"));
+        assert!(aapl_only[0].timestamp > aapl_only[1].timestamp); // reverse = newest first
+
+        let windowed = trade_repo
+            .scan(&TradeScanFilter {
+                start: Some(base + chrono::Duration::seconds(1)),
+                end: Some(base + chrono::Duration::seconds(2)),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(windowed.len(), 2);
+        assert!(windowed[0].timestamp < windowed[1].timestamp); // ascending by default
+    }
+
+    #[tokio::test]
+    async fn test_market_data_insert_batch_and_scan_by_symbol_prefix() {
+        let pool = create_test_db().await;
+        let repo = SqliteMarketDataRepository::new(pool);
+
+        let base = Utc::now();
+        let data = vec![
+            {
+                let mut d = crate::data::MarketData::new("BRK.A".to_string(), 500_000.0, 10);
+                d.timestamp = base;
+                d
+            },
+            {
+                let mut d = crate::data::MarketData::new("BRK.B".to_string(), 350.0, 1_000);
+                d.timestamp = base + chrono::Duration::seconds(1);
+                d
+            },
+            {
+                let mut d = crate::data::MarketData::new("AAPL".to_string(), 150.0, 1_000_000);
+                d.timestamp = base + chrono::Duration::seconds(2);
+                d
+            },
+        ];
+
+        repo.insert_batch(&data).await.unwrap();
+
+        let berkshire = repo
+            .scan(&MarketDataScanFilter {
+                symbols: SymbolFilter::Prefix("BRK".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(berkshire.len(), 2);
+        assert!(berkshire.iter().all(|d| d.symbol.starts_with("BRK")));
+
+        // insert_batch on an empty slice is a no-op, not an error.
+        repo.insert_batch(&[]).await.unwrap();
+        assert_eq!(repo.get_cached_data("AAPL", 10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_strategy_positions_view_nets_buys_and_sells() {
+        let pool = create_test_db().await;
+        let trade_repo = SqliteTradeRepository::new(pool.clone());
+        let strategy_repo = SqliteStrategyRepository::new(pool);
+
+        let strategy = Strategy {
+            id: Uuid::new_v4().to_string(),
+            strategy_type: StrategyType::PriceDrop { threshold: 5.0 },
+            symbol: "AAPL".to_string(),
+            parameters: StrategyParameters::default(),
+            created_at: Utc::now(),
+            is_active: true,
+        };
+        strategy_repo.create(&strategy).await.unwrap();
+
+        trade_repo.create(&Trade {
+            id: Uuid::new_v4().to_string(),
+            strategy_id: strategy.id.clone(),
+            symbol: "AAPL".to_string(),
+            action: Action::Buy,
+            quantity: 10.0,
+            price: Money::from_f64(100.0),
+            timestamp: Utc::now(),
+            explanation: "open".to_string(),
+            commission: Money::from_f64(1.0),
+            realized_pnl: None,
+            trade_value: Money::from_f64(1000.0),
+        }).await.unwrap();
+
+        trade_repo.create(&Trade {
+            id: Uuid::new_v4().to_string(),
+            strategy_id: strategy.id.clone(),
+            symbol: "AAPL".to_string(),
+            action: Action::Sell,
+            quantity: 4.0,
+            price: Money::from_f64(110.0),
+            timestamp: Utc::now(),
+            explanation: "trim".to_string(),
+            commission: Money::from_f64(1.0),
+            realized_pnl: Some(Money::from_f64(40.0)),
+            trade_value: Money::from_f64(440.0),
+        }).await.unwrap();
+
+        let positions = trade_repo.get_positions(&strategy.id).await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].symbol, "AAPL");
+        assert_eq!(positions[0].net_quantity, 6.0);
+        assert_eq!(positions[0].total_commission, 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_performance_repository_latest_uses_view() {
+        let pool = create_test_db().await;
+        let strategy_repo = SqliteStrategyRepository::new(pool.clone());
+        let performance_repo = SqlitePerformanceRepository::new(pool);
+
+        let strategy = Strategy {
+            id: Uuid::new_v4().to_string(),
+            strategy_type: StrategyType::PriceDrop { threshold: 5.0 },
+            symbol: "AAPL".to_string(),
+            parameters: StrategyParameters::default(),
+            created_at: Utc::now(),
+            is_active: true,
+        };
+        strategy_repo.create(&strategy).await.unwrap();
+
+        assert!(performance_repo.get_latest(&strategy.id).await.unwrap().is_none());
+
+        let mut metrics = test_performance_metrics(&strategy.id);
+        metrics.total_return = Money::from_f64(10.0);
+        performance_repo.create_snapshot(&strategy.id, &metrics).await.unwrap();
+
+        metrics.total_return = Money::from_f64(25.0);
+        performance_repo.create_snapshot(&strategy.id, &metrics).await.unwrap();
+
+        let latest = performance_repo.get_latest(&strategy.id).await.unwrap().unwrap();
+        assert_eq!(latest.total_return.to_f64(), 25.0);
+
+        let history = performance_repo.get_history(&strategy.id, 10).await.unwrap();
+        assert_eq!(history.len(), 2);
+
+        performance_repo.delete_old_snapshots(&strategy.id, 1).await.unwrap();
+        let history = performance_repo.get_history(&strategy.id, 10).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].total_return.to_f64(), 25.0);
+    }
+
+    fn test_performance_metrics(strategy_id: &str) -> PerformanceMetrics {
+        PerformanceMetrics {
+            strategy_id: strategy_id.to_string(),
+            total_return: Money::ZERO,
+            total_return_percent: 0.0,
+            total_trades: 1,
+            winning_trades: 1,
+            losing_trades: 0,
+            current_position: Position {
+                symbol: "AAPL".to_string(),
+                shares: 10.0,
+                average_price: Money::from_f64(100.0),
+                current_price: Money::from_f64(100.0),
+                current_value: Money::from_f64(1000.0),
+                unrealized_pnl: Money::ZERO,
+                unrealized_pnl_percent: 0.0,
+                cost_basis: Money::from_f64(1000.0),
+                last_updated: Utc::now(),
+                lots: Vec::new(),
+            },
+            max_drawdown: Money::ZERO,
+            max_drawdown_percent: 0.0,
+            sharpe_ratio: None,
+            win_rate: 1.0,
+            average_win: Money::ZERO,
+            average_loss: Money::ZERO,
+            profit_factor: 1.0,
+            initial_capital: Money::from_f64(10000.0),
+            current_capital: Money::from_f64(10000.0),
+            last_updated: Utc::now(),
+        }
     }
 }
\ No newline at end of file
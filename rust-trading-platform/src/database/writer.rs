@@ -0,0 +1,380 @@
+// Serialized write executor: one task batches trade/snapshot/market-data
+// writes from many concurrent callers into a few transactions, instead of
+// every caller running its own autocommit write. Each batch commits on a
+// connection taken from the pool for that commit; the task holds no
+// connection between batches.
+//
+// Trade/strategy/snapshot writes and market-data writes land in the same
+// batch but commit as two separate transactions against two separate pools
+// (see `Database::cache_pool`): high-frequency market-data ticks shouldn't
+// hold up, or be held up by, the durable trade history they share a flush
+// cadence with.
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{Duration, Instant};
+
+use crate::data::MarketData;
+use crate::database::repositories::{
+    insert_snapshot, insert_strategy, insert_trade, upsert_market_data, upsert_stable_price,
+};
+use crate::error::{Result, TradingPlatformError};
+use crate::performance::{PerformanceMetrics, Trade};
+use crate::strategy::Strategy;
+
+/// Writes queued for a batch are flushed once this many have accumulated...
+const MAX_BATCH_SIZE: usize = 64;
+/// ...or once this long has passed since the first write in the batch arrived.
+const FLUSH_INTERVAL: Duration = Duration::from_millis(20);
+
+enum WriteRequest {
+    InsertTrade(Trade, oneshot::Sender<Result<()>>),
+    InsertStrategy(Strategy, oneshot::Sender<Result<()>>),
+    InsertSnapshot {
+        strategy_id: String,
+        metrics: PerformanceMetrics,
+        reply: oneshot::Sender<Result<()>>,
+    },
+    UpsertMarketData(MarketData, oneshot::Sender<Result<()>>),
+    UpsertStablePrice {
+        symbol: String,
+        timestamp: DateTime<Utc>,
+        stable_price: f64,
+        reply: oneshot::Sender<Result<()>>,
+    },
+    /// Not a write itself: forces the executor to commit whatever batch is
+    /// currently being collected right away, instead of waiting out
+    /// `FLUSH_INTERVAL` or for `MAX_BATCH_SIZE` to fill up. Used for clean
+    /// shutdown, where remaining work must land before the process exits.
+    Flush(oneshot::Sender<()>),
+}
+
+impl WriteRequest {
+    /// Whether this request belongs on `cache_pool` (market-data/stable-price)
+    /// rather than the durable `pool` (trade/strategy/snapshot).
+    fn is_cache_write(&self) -> bool {
+        matches!(self, WriteRequest::UpsertMarketData(..) | WriteRequest::UpsertStablePrice { .. })
+    }
+
+    fn reply(self, result: &Result<()>) {
+        let resend = |tx: oneshot::Sender<Result<()>>| {
+            let _ = tx.send(match result {
+                Ok(()) => Ok(()),
+                Err(e) => Err(TradingPlatformError::internal(format!("batched write failed: {}", e))),
+            });
+        };
+
+        match self {
+            WriteRequest::InsertTrade(_, reply) => resend(reply),
+            WriteRequest::InsertStrategy(_, reply) => resend(reply),
+            WriteRequest::InsertSnapshot { reply, .. } => resend(reply),
+            WriteRequest::UpsertMarketData(_, reply) => resend(reply),
+            WriteRequest::UpsertStablePrice { reply, .. } => resend(reply),
+            WriteRequest::Flush(_) => unreachable!("Flush requests are drained before reaching a batch"),
+        }
+    }
+}
+
+/// A cheaply-cloneable handle to `Database`'s write executor. Each method
+/// enqueues a write and awaits the executor's acknowledgement that it was
+/// committed as part of a batch.
+#[derive(Debug, Clone)]
+pub struct WriteHandle {
+    sender: mpsc::Sender<WriteRequest>,
+}
+
+impl WriteHandle {
+    async fn send(&self, build: impl FnOnce(oneshot::Sender<Result<()>>) -> WriteRequest) -> Result<()> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.sender
+            .send(build(reply_tx))
+            .await
+            .map_err(|_| TradingPlatformError::internal("write executor has shut down"))?;
+
+        reply_rx
+            .await
+            .map_err(|_| TradingPlatformError::internal("write executor dropped the reply"))?
+    }
+
+    pub async fn insert_trade(&self, trade: Trade) -> Result<()> {
+        self.send(|reply| WriteRequest::InsertTrade(trade, reply)).await
+    }
+
+    pub async fn insert_strategy(&self, strategy: Strategy) -> Result<()> {
+        self.send(|reply| WriteRequest::InsertStrategy(strategy, reply)).await
+    }
+
+    pub async fn insert_snapshot(&self, strategy_id: String, metrics: PerformanceMetrics) -> Result<()> {
+        self.send(|reply| WriteRequest::InsertSnapshot { strategy_id, metrics, reply }).await
+    }
+
+    pub async fn upsert_market_data(&self, data: MarketData) -> Result<()> {
+        self.send(|reply| WriteRequest::UpsertMarketData(data, reply)).await
+    }
+
+    /// Persist a `StablePriceModel`-computed reference price onto the
+    /// `market_data_cache` row for `(symbol, timestamp)` so it survives a
+    /// restart. Call after `upsert_market_data` has cached that tick.
+    pub async fn upsert_stable_price(&self, symbol: String, timestamp: DateTime<Utc>, stable_price: f64) -> Result<()> {
+        self.send(|reply| WriteRequest::UpsertStablePrice { symbol, timestamp, stable_price, reply }).await
+    }
+
+    /// Force the executor to commit its current in-flight batch immediately
+    /// and wait for that commit to land, instead of waiting out
+    /// `FLUSH_INTERVAL`. Call before shutdown so queued writes aren't lost.
+    pub async fn flush(&self) -> Result<()> {
+        let (ack_tx, ack_rx) = oneshot::channel();
+        self.sender
+            .send(WriteRequest::Flush(ack_tx))
+            .await
+            .map_err(|_| TradingPlatformError::internal("write executor has shut down"))?;
+
+        ack_rx
+            .await
+            .map_err(|_| TradingPlatformError::internal("write executor dropped the flush ack"))
+    }
+}
+
+/// Spawn the write-executor task and return a handle to it. Each batch's
+/// trade/strategy/snapshot writes commit on a connection taken from `pool`,
+/// and its market-data/stable-price writes on one taken from `cache_pool`;
+/// neither connection is held between batches. The task runs until every
+/// `WriteHandle` (and its queue) is dropped.
+pub fn spawn(pool: SqlitePool, cache_pool: SqlitePool) -> WriteHandle {
+    let (sender, receiver) = mpsc::channel(256);
+    tokio::spawn(run(pool, cache_pool, receiver));
+    WriteHandle { sender }
+}
+
+async fn run(pool: SqlitePool, cache_pool: SqlitePool, mut receiver: mpsc::Receiver<WriteRequest>) {
+    while let Some(first) = receiver.recv().await {
+        let mut batch = Vec::new();
+        let mut flush_acks = Vec::new();
+        collect(first, &mut batch, &mut flush_acks);
+
+        let deadline = Instant::now() + FLUSH_INTERVAL;
+        while batch.len() < MAX_BATCH_SIZE && flush_acks.is_empty() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            match tokio::time::timeout(remaining, receiver.recv()).await {
+                Ok(Some(request)) => collect(request, &mut batch, &mut flush_acks),
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        if !batch.is_empty() {
+            let (durable_result, cache_result) = flush_batch(&pool, &cache_pool, &batch).await;
+            for request in batch {
+                let result = if request.is_cache_write() { &cache_result } else { &durable_result };
+                request.reply(result);
+            }
+        }
+        for ack in flush_acks {
+            let _ = ack.send(());
+        }
+    }
+}
+
+/// Route an incoming request either into the pending write batch, or (for
+/// `Flush`) onto the list of acks to fire once that batch commits.
+fn collect(request: WriteRequest, batch: &mut Vec<WriteRequest>, flush_acks: &mut Vec<oneshot::Sender<()>>) {
+    match request {
+        WriteRequest::Flush(ack) => flush_acks.push(ack),
+        other => batch.push(other),
+    }
+}
+
+/// Commit `batch`'s durable (trade/strategy/snapshot) writes against `pool`
+/// and its market-data/stable-price writes against `cache_pool`, each as its
+/// own single transaction, so N queued writes of either kind cost one fsync
+/// instead of N. The two transactions commit independently: a cache-pool
+/// failure doesn't roll back trades that landed in the same batch, and vice
+/// versa.
+async fn flush_batch(pool: &SqlitePool, cache_pool: &SqlitePool, batch: &[WriteRequest]) -> (Result<()>, Result<()>) {
+    let durable_result = flush_durable(pool, batch).await;
+    let cache_result = flush_cache(cache_pool, batch).await;
+    (durable_result, cache_result)
+}
+
+async fn flush_durable(pool: &SqlitePool, batch: &[WriteRequest]) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    for request in batch {
+        match request {
+            WriteRequest::InsertTrade(trade, _) => {
+                insert_trade(&mut *tx, trade).await?;
+            }
+            WriteRequest::InsertStrategy(strategy, _) => {
+                insert_strategy(&mut *tx, strategy).await?;
+            }
+            WriteRequest::InsertSnapshot { strategy_id, metrics, .. } => {
+                insert_snapshot(&mut *tx, strategy_id, metrics).await?;
+            }
+            WriteRequest::UpsertMarketData(..) | WriteRequest::UpsertStablePrice { .. } => {}
+            WriteRequest::Flush(_) => unreachable!("Flush requests are diverted before reaching a batch"),
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn flush_cache(cache_pool: &SqlitePool, batch: &[WriteRequest]) -> Result<()> {
+    let mut tx = cache_pool.begin().await?;
+
+    for request in batch {
+        match request {
+            WriteRequest::UpsertMarketData(data, _) => {
+                upsert_market_data(&mut *tx, data).await?;
+            }
+            WriteRequest::UpsertStablePrice { symbol, timestamp, stable_price, .. } => {
+                upsert_stable_price(&mut *tx, symbol, *timestamp, *stable_price).await?;
+            }
+            WriteRequest::InsertTrade(..) | WriteRequest::InsertStrategy(..) | WriteRequest::InsertSnapshot { .. } => {}
+            WriteRequest::Flush(_) => unreachable!("Flush requests are diverted before reaching a batch"),
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::migrations::initialize_database;
+    use crate::performance::Money;
+    use crate::strategy::Action;
+
+    async fn test_pool() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        initialize_database(&pool).await.unwrap();
+        pool
+    }
+
+    fn test_trade() -> Trade {
+        Trade {
+            id: "trade-1".to_string(),
+            strategy_id: "strategy-1".to_string(),
+            symbol: "AAPL".to_string(),
+            action: Action::Buy,
+            quantity: 10.0,
+            price: Money::from_f64(150.0),
+            timestamp: Utc::now(),
+            explanation: "test buy".to_string(),
+            commission: Money::from_f64(1.0),
+            realized_pnl: None,
+            trade_value: Money::from_f64(1500.0),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_insert_trade_commits_and_acknowledges() {
+        let pool = test_pool().await;
+        let handle = spawn(pool.clone(), pool.clone());
+
+        // trades.strategy_id references strategies(id), so the strategy goes first.
+        insert_strategy(&pool, &test_strategy()).await.unwrap();
+        handle.insert_trade(test_trade()).await.unwrap();
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM trades")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_inserts_batch_into_one_flush() {
+        let pool = test_pool().await;
+        let handle = spawn(pool.clone(), pool.clone());
+
+        insert_strategy(&pool, &test_strategy()).await.unwrap();
+        let mut tasks = Vec::new();
+        for i in 0..20 {
+            let handle = handle.clone();
+            let mut trade = test_trade();
+            trade.id = format!("trade-{}", i);
+            tasks.push(tokio::spawn(async move { handle.insert_trade(trade).await }));
+        }
+
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM trades")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 20);
+    }
+
+    #[tokio::test]
+    async fn test_upsert_stable_price_persists_onto_the_cached_tick() {
+        let pool = test_pool().await;
+        let handle = spawn(pool.clone(), pool.clone());
+        let data = MarketData::new("AAPL".to_string(), 150.0, 1_000_000);
+        let timestamp = data.timestamp;
+
+        handle.upsert_market_data(data).await.unwrap();
+        handle.upsert_stable_price("AAPL".to_string(), timestamp, 148.5).await.unwrap();
+
+        let stable_price: f64 = sqlx::query_scalar(
+            "SELECT stable_price FROM market_data_cache WHERE symbol = ? AND timestamp = ?"
+        )
+        .bind("AAPL")
+        .bind(crate::database::datetime_to_string(timestamp))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(stable_price, 148.5);
+    }
+
+    fn test_strategy() -> Strategy {
+        Strategy {
+            id: "strategy-1".to_string(),
+            strategy_type: crate::strategy::StrategyType::PriceDrop { threshold: 5.0 },
+            symbol: "AAPL".to_string(),
+            parameters: crate::strategy::StrategyParameters::default(),
+            created_at: Utc::now(),
+            is_active: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_insert_strategy_commits_and_acknowledges() {
+        let pool = test_pool().await;
+        let handle = spawn(pool.clone(), pool.clone());
+
+        handle.insert_strategy(test_strategy()).await.unwrap();
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM strategies")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_flush_commits_immediately_without_waiting_for_flush_interval() {
+        let pool = test_pool().await;
+        let handle = spawn(pool.clone(), pool.clone());
+
+        insert_strategy(&pool, &test_strategy()).await.unwrap();
+        let insert_handle = handle.clone();
+        let insert = tokio::spawn(async move { insert_handle.insert_trade(test_trade()).await });
+
+        handle.flush().await.unwrap();
+        insert.await.unwrap().unwrap();
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM trades")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+}
@@ -34,6 +34,9 @@ pub enum TradingPlatformError {
     
     #[error("Internal error: {0}")]
     Internal(String),
+
+    #[error("Codec error: {0}")]
+    Codec(String),
 }
 
 #[derive(Debug, Error)]
@@ -76,6 +79,15 @@ pub enum MarketDataError {
     
     #[error("Historical data insufficient for symbol: {0}")]
     InsufficientHistoricalData(String),
+
+    #[error("Invalid correlation matrix: {0}")]
+    InvalidCorrelationMatrix(String),
+
+    #[error("Stale price for {symbol}: last published {age:?} ago")]
+    StalePrice { symbol: String, age: std::time::Duration },
+
+    #[error("Rate limit exceeded, retry after {retry_after:?}")]
+    RateLimited { retry_after: std::time::Duration },
 }
 
 pub type Result<T> = std::result::Result<T, TradingPlatformError>;
@@ -95,6 +107,10 @@ impl TradingPlatformError {
     pub fn internal<T: std::fmt::Display>(msg: T) -> Self {
         TradingPlatformError::Internal(msg.to_string())
     }
+
+    pub fn codec<T: std::fmt::Display>(msg: T) -> Self {
+        TradingPlatformError::Codec(msg.to_string())
+    }
 }
 
 impl StrategyError {
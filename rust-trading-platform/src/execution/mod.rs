@@ -0,0 +1,421 @@
+// Hybrid execution router: `Portfolio::execute_trade` assumes every `Trade`
+// fills instantly and in full at a single given price, which is fine once a
+// fill price is known but says nothing about *how* to get one. This module
+// routes an `Order` through two fill paths in one pass: an immediate "take"
+// against the current market price (priced through a `SlippageModel`), and,
+// for whatever a `Limit` order couldn't take immediately, a resting order
+// that fills later, when a `MarketData` update crosses its limit price.
+// Every fill still produces a `Trade` the normal way, via the configured
+// `FeeModel`, ready to hand to `Portfolio::execute_trade`.
+
+use crate::data::MarketData;
+use crate::error::{Result, TradingPlatformError};
+use crate::performance::{FeeModel, Trade};
+use crate::strategy::Action;
+
+/// How an `Order` should be filled: immediately at the market price, or
+/// only once the market trades at or better than `price`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderType {
+    Market,
+    Limit { price: f64 },
+}
+
+/// A request to buy or sell `quantity` of `symbol`, routed by an
+/// `ExecutionRouter`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Order {
+    pub strategy_id: String,
+    pub symbol: String,
+    pub action: Action,
+    pub quantity: f64,
+    pub order_type: OrderType,
+}
+
+impl Order {
+    pub fn new(strategy_id: String, symbol: String, action: Action, quantity: f64, order_type: OrderType) -> Self {
+        Order { strategy_id, symbol, action, quantity, order_type }
+    }
+}
+
+/// Prices the execution a taker gets for a `quantity`-sized fill against
+/// `reference_price`, e.g. a fixed spread or an impact that grows with
+/// order size. Buys/short-covers pay up from the reference price; sells and
+/// new shorts get hit down from it, matching how a real book would move
+/// against the taker's own size.
+pub trait SlippageModel: Send + Sync {
+    fn execution_price(&self, reference_price: f64, quantity: f64, action: Action) -> f64;
+}
+
+/// Fills exactly at the reference price, no impact -- the baseline for
+/// tests and for symbols deep enough that impact isn't worth modeling.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoSlippage;
+
+impl SlippageModel for NoSlippage {
+    fn execution_price(&self, reference_price: f64, _quantity: f64, _action: Action) -> f64 {
+        reference_price
+    }
+}
+
+/// Linear price impact: `bps_per_unit` of `reference_price`, per unit of
+/// `quantity`, moving the execution price against the taker.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearImpact {
+    pub bps_per_unit: f64,
+}
+
+impl SlippageModel for LinearImpact {
+    fn execution_price(&self, reference_price: f64, quantity: f64, action: Action) -> f64 {
+        let impact = reference_price * self.bps_per_unit * quantity.abs() / 10_000.0;
+        reference_price + direction(action) * impact
+    }
+}
+
+/// The unfilled remainder of a `Limit` order, waiting for a `MarketData`
+/// tick on its symbol that crosses `limit_price`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RestingOrder {
+    pub strategy_id: String,
+    pub symbol: String,
+    pub action: Action,
+    pub limit_price: f64,
+    pub remaining_quantity: f64,
+}
+
+/// One execution -- immediate or resting -- already turned into a `Trade`
+/// via the router's `FeeModel`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Execution {
+    pub trade: Trade,
+    pub quantity: f64,
+    pub price: f64,
+}
+
+/// The result of routing an `Order`: every execution it produced so far,
+/// plus how much quantity is still open (zero once fully filled). Callers
+/// can cancel or re-quote whatever `remaining_quantity` is left resting.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Fill {
+    pub executions: Vec<Execution>,
+    pub remaining_quantity: f64,
+}
+
+impl Fill {
+    pub fn is_fully_filled(&self) -> bool {
+        self.remaining_quantity <= 0.0
+    }
+}
+
+/// +1.0 for a buy/short-cover (pays up against the taker), -1.0 for a
+/// sell/new-short (gets hit down), 0.0 for `Hold`.
+fn direction(action: Action) -> f64 {
+    match action {
+        Action::Buy | Action::ExitShort => 1.0,
+        Action::Sell | Action::ShortSell => -1.0,
+        Action::Hold => 0.0,
+    }
+}
+
+/// Whether `market_price` has reached a point where `action` at
+/// `limit_price` could fill: at or below the limit for a buy/cover, at or
+/// above it for a sell/new short.
+fn crosses(action: Action, market_price: f64, limit_price: f64) -> bool {
+    match action {
+        Action::Buy | Action::ExitShort => market_price <= limit_price,
+        Action::Sell | Action::ShortSell => market_price >= limit_price,
+        Action::Hold => false,
+    }
+}
+
+/// Share of a tick's traded volume a single order is allowed to take,
+/// modeling that a real book only has so much liquidity resting at any
+/// price on a given tick -- the rest of `desired` stays unfilled (or
+/// resting, for a `Limit` order) for a later tick.
+const MAX_PARTICIPATION_OF_VOLUME: f64 = 0.1;
+
+/// Cap `desired` quantity at this tick's available liquidity.
+fn fillable_quantity(desired: f64, tick_volume: u64) -> f64 {
+    desired.min(tick_volume as f64 * MAX_PARTICIPATION_OF_VOLUME)
+}
+
+/// Routes `Order`s against the live market, blending an immediate take with
+/// resting limit orders that fill as later `MarketData` ticks cross their
+/// limit price. Holds its own book of unfilled resting orders between
+/// calls, so one router should live for as long as the strategy it serves.
+pub struct ExecutionRouter<S: SlippageModel, F: FeeModel> {
+    slippage: S,
+    fee_model: F,
+    resting_orders: Vec<RestingOrder>,
+}
+
+impl<S: SlippageModel, F: FeeModel> ExecutionRouter<S, F> {
+    pub fn new(slippage: S, fee_model: F) -> Self {
+        ExecutionRouter { slippage, fee_model, resting_orders: Vec::new() }
+    }
+
+    /// Route a new `order` against `market_data`: take what can be filled
+    /// immediately (all or part of a `Market` order, or a `Limit` order
+    /// whose price the market has already crossed, capped at the tick's
+    /// available liquidity), and rest whatever's left of a `Limit` order
+    /// for a later `on_market_data` call.
+    pub fn route(&mut self, order: Order, market_data: &MarketData) -> Result<Fill> {
+        if order.quantity <= 0.0 {
+            return Err(TradingPlatformError::internal("order quantity must be positive"));
+        }
+
+        let market_price = market_data.price.to_f64();
+        let mut executions = Vec::new();
+
+        let desired = match order.order_type {
+            OrderType::Market => order.quantity,
+            OrderType::Limit { price } if crosses(order.action, market_price, price) => order.quantity,
+            OrderType::Limit { .. } => 0.0,
+        };
+        let takeable = fillable_quantity(desired, market_data.volume);
+
+        if takeable > 0.0 {
+            let fill_price = self.slippage.execution_price(market_price, takeable, order.action);
+            let trade = self.book_trade(&order.strategy_id, &order.symbol, order.action, takeable, fill_price)?;
+            executions.push(Execution { trade, quantity: takeable, price: fill_price });
+        }
+
+        let remaining_quantity = order.quantity - takeable;
+        if remaining_quantity > 0.0 {
+            if let OrderType::Limit { price } = order.order_type {
+                self.resting_orders.push(RestingOrder {
+                    strategy_id: order.strategy_id,
+                    symbol: order.symbol,
+                    action: order.action,
+                    limit_price: price,
+                    remaining_quantity,
+                });
+            }
+        }
+
+        Ok(Fill { executions, remaining_quantity })
+    }
+
+    /// Check every resting order against a new `data` tick: any whose
+    /// symbol matches and whose limit price `data.price` has crossed fills
+    /// up to this tick's available liquidity, shrinking `remaining_quantity`
+    /// and leaving the rest resting for a later tick (fully filling and
+    /// removing the order once nothing remains).
+    pub fn on_market_data(&mut self, data: &MarketData) -> Result<Vec<Execution>> {
+        let mut executions = Vec::new();
+        let mut still_resting = Vec::new();
+
+        for mut resting in std::mem::take(&mut self.resting_orders) {
+            let fills = resting.symbol == data.symbol && crosses(resting.action, data.price.to_f64(), resting.limit_price);
+            if fills {
+                let takeable = fillable_quantity(resting.remaining_quantity, data.volume);
+                let fill_price = self.slippage.execution_price(data.price.to_f64(), takeable, resting.action);
+                let trade = self.book_trade(&resting.strategy_id, &resting.symbol, resting.action, takeable, fill_price)?;
+                executions.push(Execution { trade, quantity: takeable, price: fill_price });
+
+                resting.remaining_quantity -= takeable;
+                if resting.remaining_quantity > 0.0 {
+                    still_resting.push(resting);
+                }
+            } else {
+                still_resting.push(resting);
+            }
+        }
+
+        self.resting_orders = still_resting;
+        Ok(executions)
+    }
+
+    /// Total quantity still resting for `symbol` across all open limit orders.
+    pub fn resting_quantity(&self, symbol: &str) -> f64 {
+        self.resting_orders.iter().filter(|r| r.symbol == symbol).map(|r| r.remaining_quantity).sum()
+    }
+
+    /// Cancel every resting order for `symbol`, returning how much
+    /// quantity was withdrawn.
+    pub fn cancel(&mut self, symbol: &str) -> f64 {
+        let mut cancelled = 0.0;
+        self.resting_orders.retain(|r| {
+            if r.symbol == symbol {
+                cancelled += r.remaining_quantity;
+                false
+            } else {
+                true
+            }
+        });
+        cancelled
+    }
+
+    fn book_trade(&self, strategy_id: &str, symbol: &str, action: Action, quantity: f64, price: f64) -> Result<Trade> {
+        let trade_value = price * quantity;
+        let commission = self.fee_model.calculate(quantity, trade_value, 0.0)?;
+        Ok(Trade::new(
+            strategy_id.to_string(),
+            symbol.to_string(),
+            action,
+            quantity,
+            price,
+            format!("Routed {:?} order", action),
+            commission.to_f64(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::performance::FlatFee;
+
+    fn router() -> ExecutionRouter<NoSlippage, FlatFee> {
+        ExecutionRouter::new(NoSlippage, FlatFee(0.0))
+    }
+
+    /// A tick with enough volume (1_000, so a 10% participation cap allows
+    /// 100 shares) that it doesn't itself constrain the small order sizes
+    /// most of these tests route.
+    fn tick(price: f64) -> MarketData {
+        MarketData::new("AAPL".to_string(), price, 1_000)
+    }
+
+    #[test]
+    fn test_market_order_fills_immediately_in_full() {
+        let mut router = router();
+        let order = Order::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 10.0, OrderType::Market);
+
+        let fill = router.route(order, &tick(100.0)).unwrap();
+
+        assert!(fill.is_fully_filled());
+        assert_eq!(fill.executions.len(), 1);
+        assert_eq!(fill.executions[0].quantity, 10.0);
+        assert_eq!(fill.executions[0].price, 100.0);
+    }
+
+    #[test]
+    fn test_limit_order_fills_immediately_when_already_crossed() {
+        let mut router = router();
+        let order = Order::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 10.0, OrderType::Limit { price: 101.0 });
+
+        let fill = router.route(order, &tick(100.0)).unwrap();
+
+        assert!(fill.is_fully_filled());
+        assert_eq!(fill.executions[0].price, 100.0);
+    }
+
+    #[test]
+    fn test_limit_order_rests_when_not_yet_crossed() {
+        let mut router = router();
+        let order = Order::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 10.0, OrderType::Limit { price: 99.0 });
+
+        let fill = router.route(order, &tick(100.0)).unwrap();
+
+        assert!(fill.executions.is_empty());
+        assert_eq!(fill.remaining_quantity, 10.0);
+        assert_eq!(router.resting_quantity("AAPL"), 10.0);
+    }
+
+    #[test]
+    fn test_resting_order_fills_once_market_data_crosses_limit() {
+        let mut router = router();
+        let order = Order::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 10.0, OrderType::Limit { price: 99.0 });
+        router.route(order, &tick(100.0)).unwrap();
+
+        let still_above = MarketData::new("AAPL".to_string(), 99.5, 1_000);
+        assert!(router.on_market_data(&still_above).unwrap().is_empty());
+        assert_eq!(router.resting_quantity("AAPL"), 10.0);
+
+        let crossed = MarketData::new("AAPL".to_string(), 98.0, 1_000);
+        let executions = router.on_market_data(&crossed).unwrap();
+
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0].quantity, 10.0);
+        assert_eq!(executions[0].price, 98.0);
+        assert_eq!(router.resting_quantity("AAPL"), 0.0);
+    }
+
+    #[test]
+    fn test_sell_limit_order_crosses_when_price_rises_to_limit() {
+        let mut router = router();
+        let order = Order::new("s1".to_string(), "AAPL".to_string(), Action::Sell, 5.0, OrderType::Limit { price: 110.0 });
+        router.route(order, &tick(100.0)).unwrap();
+
+        let crossed = MarketData::new("AAPL".to_string(), 112.0, 1_000);
+        let executions = router.on_market_data(&crossed).unwrap();
+
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0].price, 112.0);
+    }
+
+    #[test]
+    fn test_cancel_withdraws_resting_quantity() {
+        let mut router = router();
+        let order = Order::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 10.0, OrderType::Limit { price: 99.0 });
+        router.route(order, &tick(100.0)).unwrap();
+
+        let cancelled = router.cancel("AAPL");
+
+        assert_eq!(cancelled, 10.0);
+        assert_eq!(router.resting_quantity("AAPL"), 0.0);
+    }
+
+    #[test]
+    fn test_linear_impact_moves_price_against_the_taker() {
+        let mut router = ExecutionRouter::new(LinearImpact { bps_per_unit: 10.0 }, FlatFee(0.0));
+        let buy = Order::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 5.0, OrderType::Market);
+        let sell = Order::new("s1".to_string(), "AAPL".to_string(), Action::Sell, 5.0, OrderType::Market);
+
+        let buy_fill = router.route(buy, &tick(100.0)).unwrap();
+        let sell_fill = router.route(sell, &tick(100.0)).unwrap();
+
+        // 10 bps/unit * 5 units = 50 bps = 0.5% of 100.0 = 0.5
+        assert_eq!(buy_fill.executions[0].price, 100.5);
+        assert_eq!(sell_fill.executions[0].price, 99.5);
+    }
+
+    #[test]
+    fn test_route_rejects_non_positive_quantity() {
+        let mut router = router();
+        let order = Order::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 0.0, OrderType::Market);
+
+        assert!(router.route(order, &tick(100.0)).is_err());
+    }
+
+    #[test]
+    fn test_market_order_partially_fills_when_order_exceeds_tick_volume() {
+        let mut router = router();
+        // Cap is 10% of volume: a 1_000-share order against a 1_000-volume
+        // tick can only take 100 shares this tick.
+        let order = Order::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 1_000.0, OrderType::Market);
+
+        let fill = router.route(order, &tick(100.0)).unwrap();
+
+        assert!(!fill.is_fully_filled());
+        assert_eq!(fill.executions[0].quantity, 100.0);
+        assert_eq!(fill.remaining_quantity, 900.0);
+    }
+
+    #[test]
+    fn test_resting_order_partially_fills_across_multiple_ticks() {
+        let mut router = router();
+        let order = Order::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 250.0, OrderType::Limit { price: 99.0 });
+        router.route(order, &tick(100.0)).unwrap();
+
+        let crossed = MarketData::new("AAPL".to_string(), 98.0, 1_000);
+        let first = router.on_market_data(&crossed).unwrap();
+
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].quantity, 100.0);
+        assert_eq!(router.resting_quantity("AAPL"), 150.0);
+
+        let second = router.on_market_data(&crossed).unwrap();
+
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].quantity, 100.0);
+        assert_eq!(router.resting_quantity("AAPL"), 50.0);
+
+        let third = router.on_market_data(&crossed).unwrap();
+
+        assert_eq!(third.len(), 1);
+        assert_eq!(third[0].quantity, 50.0);
+        assert_eq!(router.resting_quantity("AAPL"), 0.0);
+    }
+}
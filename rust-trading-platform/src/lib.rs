@@ -1,8 +1,10 @@
 pub mod api;
+pub mod backtest;
 pub mod config;
 pub mod data;
 pub mod database;
 pub mod error;
+pub mod execution;
 pub mod market_data;
 pub mod performance;
 pub mod strategy;
@@ -32,6 +34,18 @@ mod tests {
         assert_eq!(config.server.static_port, 8050);
         assert_eq!(config.database.url, "sqlite:trading_platform.db");
         assert_eq!(config.strategies.initial_cash, 10000.0);
+        assert_eq!(config.strategies.max_fee_bps, 100.0);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_config_validate_rejects_out_of_range_max_fee_bps() {
+        let mut config = Config::default();
+        config.strategies.max_fee_bps = 0.0;
+        assert!(config.validate().is_err());
+
+        config.strategies.max_fee_bps = 10_001.0;
+        assert!(config.validate().is_err());
     }
 
     #[test]
@@ -54,7 +68,7 @@ mod tests {
         assert!(invalid_ma_strategy.validate().is_err());
 
         // Test RSI validation
-        let invalid_rsi = StrategyType::RSI { oversold: 80.0, overbought: 20.0 };
+        let invalid_rsi = StrategyType::RSI { oversold: 80.0, overbought: 20.0, period: None };
         let invalid_rsi_strategy = Strategy::new(invalid_rsi, "AAPL".to_string()).unwrap();
         assert!(invalid_rsi_strategy.validate().is_err());
     }
@@ -168,41 +182,42 @@ mod tests {
             5.0,
         ).with_realized_pnl(-20.0));
 
-        metrics.update_from_trades(&trades);
-        
+        metrics.update_from_trades(&trades).unwrap();
+
         assert_eq!(metrics.total_trades, 2);
         assert_eq!(metrics.winning_trades, 1);
         assert_eq!(metrics.losing_trades, 1);
-        assert_eq!(metrics.total_return, 30.0);
+        assert_eq!(metrics.total_return.to_f64(), 20.0); // 50 - 20 realized, net of 5 + 5 commission
         assert_eq!(metrics.win_rate, 50.0);
     }
 
     #[test]
     fn test_position_management() {
         let mut position = Position::new("AAPL".to_string(), 100.0, 50.0);
-        assert_eq!(position.cost_basis, 5000.0);
-        assert_eq!(position.average_price, 50.0);
+        assert_eq!(position.cost_basis.to_f64(), 5000.0);
+        assert_eq!(position.average_price.to_f64(), 50.0);
 
         // Update price
-        position.update_price(55.0);
-        assert_eq!(position.unrealized_pnl, 500.0);
+        position.update_price(55.0).unwrap();
+        assert_eq!(position.unrealized_pnl.to_f64(), 500.0);
         assert_eq!(position.unrealized_pnl_percent, 10.0);
 
         // Add more shares
-        position.add_shares(50.0, 60.0);
+        position.add_shares(50.0, 60.0).unwrap();
         assert_eq!(position.shares, 150.0);
-        assert!((position.average_price - 53.33).abs() < 0.01);
+        assert!((position.average_price.to_f64() - 53.33).abs() < 0.01);
 
         // Remove shares
-        let realized_pnl = position.remove_shares(50.0, 65.0).unwrap();
-        assert!(realized_pnl > 0.0);
+        let disposal = position.remove_shares(50.0, 65.0, CostBasisMethod::AverageCost).unwrap();
+        assert!(disposal.realized_pnl.to_f64() > 0.0);
         assert_eq!(position.shares, 100.0);
     }
 
     #[test]
     fn test_portfolio_operations() {
-        let mut portfolio = Portfolio::new("strategy-123".to_string(), 10000.0);
-        
+        let mut portfolio = Portfolio::new("strategy-123".to_string(), 10000.0)
+            .with_commission_model(CommissionModel::Fixed(5.0));
+
         let buy_trade = Trade::new(
             "strategy-123".to_string(),
             "AAPL".to_string(),
@@ -214,16 +229,16 @@ mod tests {
         );
 
         portfolio.execute_trade(buy_trade).unwrap();
-        assert_eq!(portfolio.current_capital, 8995.0); // 10000 - 1000 - 5
+        assert_eq!(portfolio.current_capital.to_f64(), 8995.0); // 10000 - 1000 - 5
         assert_eq!(portfolio.positions.len(), 1);
 
         // Update position prices
         let mut prices = HashMap::new();
         prices.insert("AAPL".to_string(), 110.0);
-        portfolio.update_position_prices(&prices);
+        portfolio.update_position_prices(&prices).unwrap();
 
-        assert_eq!(portfolio.total_value(), 10095.0); // 8995 + 1100
-        assert_eq!(portfolio.total_unrealized_pnl(), 100.0);
+        assert_eq!(portfolio.total_value().unwrap().to_f64(), 10095.0); // 8995 + 1100
+        assert_eq!(portfolio.total_unrealized_pnl().unwrap().to_f64(), 100.0);
     }
 
     #[test]
@@ -494,7 +509,7 @@ mod tests {
         // Test current price
         let market_data = provider.get_current_price("AAPL").await.unwrap();
         assert_eq!(market_data.symbol, "AAPL");
-        assert!(market_data.price > 0.0);
+        assert!(market_data.price.to_f64() > 0.0);
         assert!(market_data.volume > 0);
 
         // Test historical data
@@ -574,6 +589,7 @@ mod tests {
             base_delay: Duration::from_millis(1),
             max_delay: Duration::from_millis(10),
             backoff_multiplier: 2.0,
+            ..Default::default()
         };
 
         let mut call_count = 0;
@@ -616,4 +632,216 @@ mod tests {
         assert_eq!(info.current_usage, 0);
         assert!(info.reset_time.is_none());
     }
+
+    fn sample_explanation_context() -> ExplanationContext {
+        let strategy_type = StrategyType::PriceDrop { threshold: 5.0 };
+        let market_data = MarketData::new("AAPL".to_string(), 150.0, 1000000);
+        let mut strategy_data = HashMap::new();
+        strategy_data.insert("price_change".to_string(), -5.2);
+
+        ExplanationContext::new(strategy_type, Action::Buy, market_data, strategy_data)
+    }
+
+    #[tokio::test]
+    async fn test_rule_based_renderer_produces_explanation() {
+        let renderer = RuleBasedRenderer::new();
+        let context = sample_explanation_context();
+
+        let explanation = renderer.render(&context).await.unwrap();
+
+        assert!(explanation.summary.contains("AAPL"));
+        assert!(explanation.detailed_reasoning.contains("price_change"));
+    }
+
+    struct MockLlmService {
+        response: String,
+    }
+
+    #[async_trait::async_trait]
+    impl LlmService for MockLlmService {
+        async fn complete(&self, _prompt: String) -> Result<String> {
+            Ok(self.response.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_llm_renderer_parses_structured_response() {
+        let llm = MockLlmService {
+            response: "SUMMARY: Buy AAPL on a sharp drop\n\
+                       REASONING: Price fell below the strategy threshold.\n\
+                       RISKS: Market volatility, Thin liquidity\n\
+                       SCENARIOS: Continued decline|0.3|Price keeps falling|Additional losses\n"
+                .to_string(),
+        };
+        let renderer = LlmExplanationRenderer::new(llm);
+        let context = sample_explanation_context();
+
+        let explanation = renderer.render(&context).await.unwrap();
+
+        assert_eq!(explanation.summary, "Buy AAPL on a sharp drop");
+        assert_eq!(explanation.risk_factors.len(), 2);
+        assert_eq!(explanation.alternative_scenarios.len(), 1);
+        assert_eq!(explanation.alternative_scenarios[0].scenario_name, "Continued decline");
+    }
+
+    #[test]
+    fn test_analyze_support_resistance_flags_breakout() {
+        let mut context = MarketContext::new();
+        let ohlcv = vec![
+            (101.0, 99.0, 100.0),
+            (102.0, 100.0, 101.0),
+            (103.0, 101.0, 102.0),
+            (105.0, 102.0, 104.8),
+        ];
+
+        let indicators = context.analyze_support_resistance(&ohlcv, 3);
+
+        let sr = context.support_resistance.as_ref().unwrap();
+        assert_eq!(sr.resistance_level, 105.0);
+        assert_eq!(sr.current_position, PricePosition::NearResistance);
+        assert_eq!(indicators.len(), 2);
+    }
+
+    #[test]
+    fn test_analyze_support_resistance_sigma_shrinks_band() {
+        let mut context = MarketContext::new();
+        let ohlcv = vec![(110.0, 90.0, 100.0)];
+
+        context.analyze_support_resistance_with_sigma(&ohlcv, 1, 0.5);
+
+        let sr = context.support_resistance.as_ref().unwrap();
+        assert_eq!(sr.resistance_level, 105.0);
+        assert_eq!(sr.support_level, 95.0);
+    }
+
+    #[test]
+    fn test_analyze_sentiment_flags_overbought() {
+        let mut context = MarketContext::new();
+        let mut price_changes = vec![1.0; 14];
+        price_changes.extend(vec![2.0; 10]);
+
+        let indicators = context.analyze_sentiment(&price_changes, 14);
+
+        assert_eq!(context.market_sentiment, MarketSentiment::VeryBullish);
+        assert_eq!(indicators.len(), 1);
+        assert_eq!(indicators[0].significance, IndicatorSignificance::Critical);
+    }
+
+    #[test]
+    fn test_analyze_sentiment_neutral_when_insufficient_data() {
+        let mut context = MarketContext::new();
+        let price_changes = vec![1.0; 5];
+
+        let indicators = context.analyze_sentiment(&price_changes, 14);
+
+        assert_eq!(context.market_sentiment, MarketSentiment::Neutral);
+        assert!(indicators.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_volatility_log_returns_annualizes_and_buckets() {
+        let mut context = MarketContext::new();
+        let prices = vec![100.0, 101.0, 99.0, 102.0, 98.0, 103.0];
+
+        let annualized = context.analyze_volatility_log_returns(&prices, 252.0);
+
+        assert!(annualized > 0.0);
+        assert_eq!(context.annualized_volatility, Some(annualized));
+    }
+
+    #[test]
+    fn test_is_tradeable_gates_on_volatility_band() {
+        let mut context = MarketContext::new();
+        context.analyze_volatility_log_returns(&[100.0, 100.1, 99.9, 100.2], 252.0);
+
+        assert!(!context.is_tradeable(10.0, 20.0));
+
+        let fresh_context = MarketContext::new();
+        assert!(fresh_context.is_tradeable(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_analyze_vsa_flags_distribution() {
+        let mut context = MarketContext::new();
+        let mut bars = vec![(100.0, 101.0, 99.0, 100.5, 1_000_000.0); 10];
+        // Wide spread, ultra-high volume, close near the low of the bar.
+        bars.push((105.0, 110.0, 95.0, 96.0, 5_000_000.0));
+
+        let indicators = context.analyze_vsa(&bars);
+
+        assert_eq!(indicators.len(), 1);
+        assert_eq!(indicators[0].significance, IndicatorSignificance::Critical);
+        assert_eq!(context.volume_analysis, VolumeAnalysis::Unusual);
+    }
+
+    #[test]
+    fn test_analyze_vsa_flags_absorption() {
+        let mut context = MarketContext::new();
+        let mut bars = vec![(100.0, 103.0, 97.0, 100.5, 1_000_000.0); 10];
+        // Narrow spread, above-average volume, down move that closes high in range.
+        bars.push((101.0, 101.0, 99.5, 100.8, 1_700_000.0));
+
+        let indicators = context.analyze_vsa(&bars);
+
+        assert_eq!(indicators.len(), 1);
+        assert_eq!(indicators[0].significance, IndicatorSignificance::Important);
+        assert_eq!(context.volume_analysis, VolumeAnalysis::Unusual);
+    }
+
+    #[test]
+    fn test_build_scenarios_continuation_when_signals_align() {
+        let mut bars = Vec::new();
+        let mut price = 100.0;
+        for _ in 0..25 {
+            let open = price;
+            let close = price + 1.0;
+            bars.push((open, close + 0.5, open - 0.5, close, 1_000_000.0));
+            price = close;
+        }
+
+        let engine = ScenarioEngine::new(bars);
+        let context = sample_explanation_context();
+
+        let scenarios = engine.build_scenarios(&context);
+
+        assert_eq!(scenarios.len(), 1);
+        assert_eq!(scenarios[0].scenario_name, "Continuation");
+        assert_eq!(scenarios[0].probability, 0.75);
+    }
+
+    #[test]
+    fn test_build_scenarios_competing_when_signals_diverge() {
+        let mut bars = Vec::new();
+        let mut price = 100.0;
+        for i in 0..25 {
+            let open = price;
+            let close = if i % 2 == 0 { price + 1.5 } else { price - 1.0 };
+            // Price drifts up, but heavier volume on the down bars makes volume delta bearish.
+            let volume = if i % 2 == 0 { 1_000_000.0 } else { 2_000_000.0 };
+            bars.push((open, open.max(close) + 0.5, open.min(close) - 0.5, close, volume));
+            price = close;
+        }
+
+        let engine = ScenarioEngine::new(bars);
+        let context = sample_explanation_context();
+
+        let scenarios = engine.build_scenarios(&context);
+
+        assert_eq!(scenarios.len(), 2);
+        let total_probability: f64 = scenarios.iter().map(|s| s.probability).sum();
+        assert!((total_probability - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_llm_renderer_falls_back_on_unparseable_response() {
+        let llm = MockLlmService {
+            response: "not in the expected format".to_string(),
+        };
+        let renderer = LlmExplanationRenderer::new(llm);
+        let context = sample_explanation_context();
+
+        let explanation = renderer.render(&context).await.unwrap();
+
+        assert!(explanation.summary.contains("AAPL"));
+    }
 }
\ No newline at end of file
@@ -12,7 +12,7 @@ mod strategy;
 mod xai;
 
 use config::Config;
-use database::Database;
+use database::DatabaseHandle;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -33,7 +33,7 @@ async fn main() -> Result<()> {
 
     // Initialize database
     info!("🗄️ Initializing database...");
-    let database = Database::new(&config.database.url).await?;
+    let database = DatabaseHandle::connect(&config.database.url).await?;
     database.migrate().await?;
     
     // Check database health
@@ -57,5 +57,7 @@ async fn main() -> Result<()> {
     tokio::signal::ctrl_c().await?;
     info!("👋 Shutting down Trading Platform");
 
+    database.shutdown().await?;
+
     Ok(())
 }
@@ -0,0 +1,331 @@
+// Durable historical-data backfill subsystem, separate from the in-memory
+// `HistoricalData` returned by `MarketDataProvider::get_historical_data`.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tracing::{debug, info, warn};
+
+use crate::data::{PricePoint, TimePeriod};
+use crate::error::{Result, TradingPlatformError};
+use super::MarketDataProvider;
+
+/// Durable storage for OHLCV bars, pluggable so the backfill subsystem isn't
+/// tied to one persistence mechanism (newline-delimited JSON to start;
+/// SQLite or anything else can implement the same trait).
+#[async_trait]
+pub trait CandleStore: Send + Sync {
+    /// The timestamp of the most recently stored bar for `symbol`, or `None`
+    /// if nothing has been stored yet. `Backfiller::backfill` resumes from
+    /// here instead of re-downloading history that's already durable.
+    async fn latest_timestamp(&self, symbol: &str) -> Result<Option<DateTime<Utc>>>;
+
+    /// Append `bars` for `symbol`. Callers always pass bars sorted ascending
+    /// by timestamp and strictly after `latest_timestamp`.
+    async fn append_bars(&self, symbol: &str, bars: &[PricePoint]) -> Result<()>;
+}
+
+/// One newline-delimited JSON file per symbol under `base_dir`. Simple and
+/// dependency-free; swap in a SQLite-backed `CandleStore` for concurrent
+/// access or querying by range.
+pub struct NdjsonCandleStore {
+    base_dir: PathBuf,
+}
+
+impl NdjsonCandleStore {
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        Self { base_dir: base_dir.into() }
+    }
+
+    fn path_for(&self, symbol: &str) -> PathBuf {
+        self.base_dir.join(format!("{}.ndjson", symbol))
+    }
+}
+
+#[async_trait]
+impl CandleStore for NdjsonCandleStore {
+    async fn latest_timestamp(&self, symbol: &str) -> Result<Option<DateTime<Utc>>> {
+        let path = self.path_for(symbol);
+        let file = match tokio::fs::File::open(&path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(TradingPlatformError::internal(
+                format!("failed to open candle store {}: {}", path.display(), e)
+            )),
+        };
+
+        let mut lines = BufReader::new(file).lines();
+        let mut last: Option<PricePoint> = None;
+        while let Some(line) = lines.next_line().await.map_err(|e| TradingPlatformError::internal(
+            format!("failed to read candle store {}: {}", path.display(), e)
+        ))? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let bar: PricePoint = serde_json::from_str(&line).map_err(|e| TradingPlatformError::internal(
+                format!("corrupt candle store {}: {}", path.display(), e)
+            ))?;
+            last = Some(bar);
+        }
+
+        Ok(last.map(|bar| bar.timestamp))
+    }
+
+    async fn append_bars(&self, symbol: &str, bars: &[PricePoint]) -> Result<()> {
+        if bars.is_empty() {
+            return Ok(());
+        }
+
+        tokio::fs::create_dir_all(&self.base_dir).await.map_err(|e| TradingPlatformError::internal(
+            format!("failed to create candle store dir {}: {}", self.base_dir.display(), e)
+        ))?;
+
+        let path = self.path_for(symbol);
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await
+            .map_err(|e| TradingPlatformError::internal(
+                format!("failed to open candle store {}: {}", path.display(), e)
+            ))?;
+
+        let mut buf = String::new();
+        for bar in bars {
+            let line = serde_json::to_string(bar).map_err(|e| TradingPlatformError::internal(
+                format!("failed to serialize candle: {}", e)
+            ))?;
+            buf.push_str(&line);
+            buf.push('\n');
+        }
+
+        file.write_all(buf.as_bytes()).await.map_err(|e| TradingPlatformError::internal(
+            format!("failed to append to candle store {}: {}", path.display(), e)
+        ))?;
+
+        Ok(())
+    }
+}
+
+/// A contiguous run of expected bar slots that no returned bar covers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gap {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Outcome of one `Backfiller::backfill` or `Backfiller::append_latest` call.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BackfillReport {
+    pub bars_written: usize,
+    pub gaps: Vec<Gap>,
+}
+
+/// Pages historical bars from a `MarketDataProvider` into a `CandleStore`.
+/// Keeps the one-shot historical backfill (`backfill`) and the incremental
+/// "append whatever's new since last time" path (`append_latest`) separate --
+/// they have different resume semantics and run on different schedules
+/// (once at startup vs. every tick).
+pub struct Backfiller<P, S> {
+    provider: Arc<P>,
+    store: Arc<S>,
+}
+
+impl<P: MarketDataProvider, S: CandleStore> Backfiller<P, S> {
+    pub fn new(provider: Arc<P>, store: Arc<S>) -> Self {
+        Self { provider, store }
+    }
+
+    /// One-shot historical fill of `symbol` from `start` to `end`, assuming
+    /// bars are spaced `interval` apart. Resumes from the store's last
+    /// timestamp rather than re-downloading already-durable history, paging
+    /// through successive `TimePeriod`-bucketed requests when the remaining
+    /// span exceeds what a single one covers.
+    pub async fn backfill(
+        &self,
+        symbol: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        interval: ChronoDuration,
+    ) -> Result<BackfillReport> {
+        let mut cursor = match self.store.latest_timestamp(symbol).await? {
+            Some(last) if last > start => last,
+            _ => start,
+        };
+
+        if cursor >= end {
+            debug!("{} already backfilled through {}", symbol, end);
+            return Ok(BackfillReport::default());
+        }
+
+        let mut report = BackfillReport::default();
+
+        while cursor < end {
+            let period = Self::period_for_span(end - cursor);
+            let historical = self.provider.get_historical_data(symbol, period).await?;
+
+            let mut bars: Vec<PricePoint> = historical.data_points.into_iter()
+                .filter(|bar| bar.timestamp > cursor && bar.timestamp <= end)
+                .collect();
+            bars.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+            if bars.is_empty() {
+                warn!("No new bars for {} after {}, stopping backfill short of {}", symbol, cursor, end);
+                break;
+            }
+
+            report.gaps.extend(Self::detect_gaps(cursor, &bars, interval));
+
+            let advanced_to = bars.last().unwrap().timestamp;
+            self.store.append_bars(symbol, &bars).await?;
+            report.bars_written += bars.len();
+
+            if advanced_to <= cursor {
+                // The provider isn't returning anything past our cursor;
+                // bail rather than loop forever re-requesting the same page.
+                break;
+            }
+            cursor = advanced_to;
+        }
+
+        info!("Backfilled {} bars for {} ({} gaps detected)", report.bars_written, symbol, report.gaps.len());
+        Ok(report)
+    }
+
+    /// Fetch whatever bars have landed since the store's last timestamp and
+    /// append them. Unlike `backfill`, this doesn't page or take an end
+    /// bound -- it's meant to run on a tight loop alongside live trading.
+    pub async fn append_latest(&self, symbol: &str, interval: ChronoDuration) -> Result<BackfillReport> {
+        let resume_from = self.store.latest_timestamp(symbol).await?;
+        let historical = self.provider.get_historical_data(symbol, TimePeriod::OneWeek).await?;
+
+        let mut bars: Vec<PricePoint> = historical.data_points.into_iter()
+            .filter(|bar| resume_from.map_or(true, |last| bar.timestamp > last))
+            .collect();
+        bars.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+        let mut report = BackfillReport::default();
+        if bars.is_empty() {
+            return Ok(report);
+        }
+
+        if let Some(last) = resume_from {
+            report.gaps.extend(Self::detect_gaps(last, &bars, interval));
+        }
+
+        self.store.append_bars(symbol, &bars).await?;
+        report.bars_written = bars.len();
+        Ok(report)
+    }
+
+    /// Compare each bar's timestamp against the expected `interval`-spaced
+    /// slot since `since`, recording a `Gap` for any expected slot with no
+    /// matching bar (Yahoo sometimes omits bars around holidays/outages).
+    fn detect_gaps(since: DateTime<Utc>, bars: &[PricePoint], interval: ChronoDuration) -> Vec<Gap> {
+        let mut gaps = Vec::new();
+        let mut expected = since + interval;
+
+        for bar in bars {
+            if bar.timestamp > expected {
+                gaps.push(Gap { start: expected, end: bar.timestamp });
+            }
+            expected = bar.timestamp + interval;
+        }
+
+        gaps
+    }
+
+    /// Pick the smallest `TimePeriod` bucket that comfortably covers `span`,
+    /// since Yahoo's chart endpoint returns a whole named range rather than
+    /// an explicit start/end window.
+    fn period_for_span(span: ChronoDuration) -> TimePeriod {
+        let days = span.num_days().max(1);
+        match days {
+            0..=7 => TimePeriod::OneWeek,
+            8..=30 => TimePeriod::OneMonth,
+            31..=90 => TimePeriod::ThreeMonths,
+            91..=180 => TimePeriod::SixMonths,
+            181..=365 => TimePeriod::OneYear,
+            366..=730 => TimePeriod::TwoYears,
+            _ => TimePeriod::FiveYears,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    #[derive(Default)]
+    struct InMemoryCandleStore {
+        bars: StdMutex<std::collections::HashMap<String, Vec<PricePoint>>>,
+    }
+
+    #[async_trait]
+    impl CandleStore for InMemoryCandleStore {
+        async fn latest_timestamp(&self, symbol: &str) -> Result<Option<DateTime<Utc>>> {
+            Ok(self.bars.lock().unwrap().get(symbol).and_then(|bars| bars.last()).map(|bar| bar.timestamp))
+        }
+
+        async fn append_bars(&self, symbol: &str, bars: &[PricePoint]) -> Result<()> {
+            self.bars.lock().unwrap().entry(symbol.to_string()).or_default().extend_from_slice(bars);
+            Ok(())
+        }
+    }
+
+    fn bar_at(timestamp: DateTime<Utc>) -> PricePoint {
+        PricePoint::new(timestamp, 100.0, 101.0, 99.0, 100.5, 1_000).unwrap()
+    }
+
+    #[test]
+    fn test_period_for_span_buckets_by_days() {
+        assert_eq!(Backfiller::<crate::market_data::MockMarketDataProvider, InMemoryCandleStore>::period_for_span(ChronoDuration::days(3)), TimePeriod::OneWeek);
+        assert_eq!(Backfiller::<crate::market_data::MockMarketDataProvider, InMemoryCandleStore>::period_for_span(ChronoDuration::days(400)), TimePeriod::TwoYears);
+        assert_eq!(Backfiller::<crate::market_data::MockMarketDataProvider, InMemoryCandleStore>::period_for_span(ChronoDuration::days(4000)), TimePeriod::FiveYears);
+    }
+
+    #[test]
+    fn test_detect_gaps_flags_missing_bar() {
+        let since = Utc::now();
+        let interval = ChronoDuration::minutes(1);
+        let bars = vec![
+            bar_at(since + ChronoDuration::minutes(1)),
+            // minute 2 missing
+            bar_at(since + ChronoDuration::minutes(3)),
+        ];
+
+        let gaps = Backfiller::<crate::market_data::MockMarketDataProvider, InMemoryCandleStore>::detect_gaps(since, &bars, interval);
+
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].start, since + ChronoDuration::minutes(2));
+        assert_eq!(gaps[0].end, since + ChronoDuration::minutes(3));
+    }
+
+    #[test]
+    fn test_detect_gaps_none_when_contiguous() {
+        let since = Utc::now();
+        let interval = ChronoDuration::minutes(1);
+        let bars = vec![
+            bar_at(since + ChronoDuration::minutes(1)),
+            bar_at(since + ChronoDuration::minutes(2)),
+        ];
+
+        let gaps = Backfiller::<crate::market_data::MockMarketDataProvider, InMemoryCandleStore>::detect_gaps(since, &bars, interval);
+
+        assert!(gaps.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_candle_store_round_trip() {
+        let store = InMemoryCandleStore::default();
+        assert_eq!(store.latest_timestamp("AAPL").await.unwrap(), None);
+
+        let now = Utc::now();
+        store.append_bars("AAPL", &[bar_at(now)]).await.unwrap();
+
+        assert_eq!(store.latest_timestamp("AAPL").await.unwrap(), Some(now));
+    }
+}
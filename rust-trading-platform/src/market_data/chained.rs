@@ -0,0 +1,359 @@
+// Sequential failover across a prioritized chain of market data providers.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use futures::Stream;
+
+use crate::data::{HistoricalData, MarketData, QuoteInterval, TimePeriod};
+use crate::error::{Result, TradingPlatformError};
+use super::{default_classify, MarketDataProvider, RateLimitInfo, RetryClassification};
+
+/// How many calls run against the cached `active` provider before
+/// `ChainedMarketDataProvider` re-probes higher-priority providers to see
+/// if they've recovered.
+const DEFAULT_REPROBE_INTERVAL: usize = 20;
+
+struct ChainState {
+    /// Index into `providers` of the last-known-good link. Calls start
+    /// here instead of always retrying from the top of the chain.
+    active: usize,
+    calls_since_reprobe: usize,
+}
+
+/// Tries a prioritized chain of `MarketDataProvider`s in order, falling
+/// forward to the next link on a `Transient`/`Throttling` failure
+/// (`default_classify`'s bucket for timeouts, `ProviderUnavailable`, and
+/// `RateLimitExceeded`) and giving up immediately on a `Permanent` one,
+/// since a bad symbol or malformed request fails identically everywhere in
+/// the chain. The last-known-good index is cached so a degraded primary
+/// doesn't re-pay its own timeout on every subsequent call; `active`
+/// periodically re-probes the providers ahead of it via `health_check` so a
+/// recovered primary is used again instead of being abandoned forever.
+pub struct ChainedMarketDataProvider {
+    /// `providers[0]` is the highest-priority link.
+    providers: Vec<Arc<dyn MarketDataProvider>>,
+    state: Mutex<ChainState>,
+    reprobe_interval: usize,
+    /// One composed "chain + currently-selected link" name per possible
+    /// `active` index, computed once here and leaked to `'static` so
+    /// `provider_name()` can return a `&str` that reflects live failover
+    /// state. Bounded by `providers.len()` — leaked once per provider, not
+    /// once per failover.
+    names: Vec<&'static str>,
+}
+
+impl ChainedMarketDataProvider {
+    pub fn new(providers: Vec<Arc<dyn MarketDataProvider>>) -> Self {
+        Self::with_reprobe_interval(providers, DEFAULT_REPROBE_INTERVAL)
+    }
+
+    pub fn with_reprobe_interval(providers: Vec<Arc<dyn MarketDataProvider>>, reprobe_interval: usize) -> Self {
+        assert!(!providers.is_empty(), "ChainedMarketDataProvider needs at least one provider");
+
+        let chain_desc = providers.iter().map(|p| p.provider_name()).collect::<Vec<_>>().join(" -> ");
+        let names: Vec<&'static str> = providers.iter()
+            .map(|p| {
+                let name = format!("Chain[{}] (active: {})", chain_desc, p.provider_name());
+                let leaked: &'static str = Box::leak(name.into_boxed_str());
+                leaked
+            })
+            .collect();
+
+        Self {
+            providers,
+            state: Mutex::new(ChainState { active: 0, calls_since_reprobe: 0 }),
+            reprobe_interval,
+            names,
+        }
+    }
+
+    fn active(&self) -> usize {
+        self.state.lock().unwrap().active
+    }
+
+    fn set_active(&self, idx: usize) {
+        self.state.lock().unwrap().active = idx;
+    }
+
+    /// Every `reprobe_interval` calls, health-check the providers ahead of
+    /// `active` in priority order and jump back to the first healthy one.
+    /// A no-op while `active` is already the top of the chain.
+    async fn maybe_reprobe(&self) {
+        let active = {
+            let mut state = self.state.lock().unwrap();
+            if state.active == 0 {
+                return;
+            }
+            state.calls_since_reprobe += 1;
+            if state.calls_since_reprobe < self.reprobe_interval {
+                return;
+            }
+            state.calls_since_reprobe = 0;
+            state.active
+        };
+
+        for idx in 0..active {
+            if self.providers[idx].health_check().await.is_ok() {
+                self.set_active(idx);
+                break;
+            }
+        }
+    }
+
+    /// Try each provider from `active` onward, advancing past any
+    /// `Transient`/`Throttling` failure. Stops and returns immediately on a
+    /// `Permanent` one, or once every remaining provider has failed.
+    async fn execute<T, F, Fut>(&self, request: F) -> Result<T>
+    where
+        F: Fn(Arc<dyn MarketDataProvider>) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        self.maybe_reprobe().await;
+
+        let start = self.active();
+        let mut last_error = None;
+
+        for idx in start..self.providers.len() {
+            match request(self.providers[idx].clone()).await {
+                Ok(value) => {
+                    self.set_active(idx);
+                    return Ok(value);
+                }
+                Err(error) => {
+                    let advance = default_classify(&error) != RetryClassification::Permanent;
+                    last_error = Some(error);
+                    if !advance {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            TradingPlatformError::internal("Chained provider failed without a recorded error")
+        }))
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for ChainedMarketDataProvider {
+    async fn get_current_price(&self, symbol: &str) -> Result<MarketData> {
+        self.execute(|provider| async move { provider.get_current_price(symbol).await }).await
+    }
+
+    async fn get_historical_data(&self, symbol: &str, period: TimePeriod) -> Result<HistoricalData> {
+        self.execute(|provider| async move { provider.get_historical_data(symbol, period).await }).await
+    }
+
+    async fn get_latest_quotes(&self, symbol: &str, interval: QuoteInterval) -> Result<HistoricalData> {
+        self.execute(|provider| async move { provider.get_latest_quotes(symbol, interval).await }).await
+    }
+
+    async fn get_multiple_prices(&self, symbols: &[String]) -> Result<HashMap<String, MarketData>> {
+        self.execute(|provider| async move { provider.get_multiple_prices(symbols).await }).await
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        self.providers[self.active()].health_check().await
+    }
+
+    async fn subscribe_quotes(&self, symbols: &[String]) -> Result<Pin<Box<dyn Stream<Item = MarketData> + Send>>> {
+        self.providers[self.active()].subscribe_quotes(symbols).await
+    }
+
+    fn provider_name(&self) -> &str {
+        self.names[self.active()]
+    }
+
+    fn rate_limit_info(&self) -> RateLimitInfo {
+        self.providers[self.active()].rate_limit_info()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Price;
+    use crate::error::MarketDataError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A `MarketDataProvider` that returns a fixed scripted result on every
+    /// call and counts how many times it was asked.
+    struct ScriptedProvider {
+        name: &'static str,
+        healthy: bool,
+        result: Result<MarketData>,
+        calls: AtomicUsize,
+    }
+
+    impl ScriptedProvider {
+        fn new(name: &'static str, healthy: bool, result: Result<MarketData>) -> Self {
+            Self { name, healthy, result, calls: AtomicUsize::new(0) }
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    fn test_market_data(symbol: &str) -> MarketData {
+        let now = chrono::Utc::now();
+        MarketData {
+            symbol: symbol.to_string(),
+            price: Price::from_f64(100.0),
+            volume: 0,
+            timestamp: now,
+            change: Price::ZERO,
+            change_percent: 0.0,
+            market_cap: None,
+            day_high: None,
+            day_low: None,
+            previous_close: None,
+            confidence: 0.0,
+            publish_time: now,
+        }
+    }
+
+    fn clone_result(result: &Result<MarketData>) -> Result<MarketData> {
+        match result {
+            Ok(data) => Ok(data.clone()),
+            Err(TradingPlatformError::MarketData(MarketDataError::SymbolNotFound(s))) => {
+                Err(TradingPlatformError::MarketData(MarketDataError::SymbolNotFound(s.clone())))
+            }
+            Err(_) => Err(TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable)),
+        }
+    }
+
+    #[async_trait]
+    impl MarketDataProvider for ScriptedProvider {
+        async fn get_current_price(&self, _symbol: &str) -> Result<MarketData> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            clone_result(&self.result)
+        }
+
+        async fn get_historical_data(&self, _symbol: &str, _period: TimePeriod) -> Result<HistoricalData> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        async fn get_latest_quotes(&self, _symbol: &str, _interval: QuoteInterval) -> Result<HistoricalData> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        async fn get_multiple_prices(&self, _symbols: &[String]) -> Result<HashMap<String, MarketData>> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        async fn health_check(&self) -> Result<()> {
+            if self.healthy {
+                Ok(())
+            } else {
+                Err(TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable))
+            }
+        }
+
+        async fn subscribe_quotes(&self, _symbols: &[String]) -> Result<Pin<Box<dyn Stream<Item = MarketData> + Send>>> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        fn provider_name(&self) -> &str {
+            self.name
+        }
+
+        fn rate_limit_info(&self) -> RateLimitInfo {
+            RateLimitInfo::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_falls_forward_on_transient_error() {
+        let primary = Arc::new(ScriptedProvider::new(
+            "primary",
+            true,
+            Err(TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable)),
+        ));
+        let backup = Arc::new(ScriptedProvider::new("backup", true, Ok(test_market_data("AAPL"))));
+
+        let chain = ChainedMarketDataProvider::new(vec![primary.clone(), backup.clone()]);
+
+        let result = chain.get_current_price("AAPL").await;
+
+        assert!(result.is_ok());
+        assert_eq!(primary.calls(), 1);
+        assert_eq!(backup.calls(), 1);
+        assert_eq!(chain.active(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_caches_active_provider_across_calls() {
+        let primary = Arc::new(ScriptedProvider::new(
+            "primary",
+            true,
+            Err(TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable)),
+        ));
+        let backup = Arc::new(ScriptedProvider::new("backup", true, Ok(test_market_data("AAPL"))));
+
+        let chain = ChainedMarketDataProvider::with_reprobe_interval(
+            vec![primary.clone(), backup.clone()],
+            1000, // effectively disabled for this test
+        );
+
+        chain.get_current_price("AAPL").await.unwrap();
+        chain.get_current_price("AAPL").await.unwrap();
+
+        // Second call starts at the cached `active` (backup), never re-pays
+        // the primary's failure.
+        assert_eq!(primary.calls(), 1);
+        assert_eq!(backup.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_error_does_not_advance_to_next_provider() {
+        let primary = Arc::new(ScriptedProvider::new(
+            "primary",
+            true,
+            Err(TradingPlatformError::MarketData(MarketDataError::SymbolNotFound("NOPE".to_string()))),
+        ));
+        let backup = Arc::new(ScriptedProvider::new("backup", true, Ok(test_market_data("NOPE"))));
+
+        let chain = ChainedMarketDataProvider::new(vec![primary.clone(), backup.clone()]);
+
+        let result = chain.get_current_price("NOPE").await;
+
+        assert!(result.is_err());
+        assert_eq!(primary.calls(), 1);
+        assert_eq!(backup.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_reprobe_jumps_back_to_healthy_primary() {
+        let primary = Arc::new(ScriptedProvider::new("primary", true, Ok(test_market_data("AAPL"))));
+        let backup = Arc::new(ScriptedProvider::new("backup", true, Ok(test_market_data("AAPL"))));
+
+        let chain = ChainedMarketDataProvider::with_reprobe_interval(
+            vec![primary.clone(), backup.clone()],
+            2,
+        );
+        chain.set_active(1); // simulate having already failed over to backup
+
+        chain.get_current_price("AAPL").await.unwrap(); // 1st call on backup, counts toward reprobe
+        assert_eq!(chain.active(), 1);
+        chain.get_current_price("AAPL").await.unwrap(); // 2nd call triggers the reprobe
+
+        assert_eq!(chain.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_provider_name_reflects_active_link() {
+        let primary = Arc::new(ScriptedProvider::new("primary", true, Ok(test_market_data("AAPL"))));
+        let backup = Arc::new(ScriptedProvider::new("backup", true, Ok(test_market_data("AAPL"))));
+
+        let chain = ChainedMarketDataProvider::new(vec![primary, backup]);
+        assert!(chain.provider_name().contains("active: primary"));
+
+        chain.set_active(1);
+        assert!(chain.provider_name().contains("active: backup"));
+    }
+}
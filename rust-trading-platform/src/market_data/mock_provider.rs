@@ -2,20 +2,199 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc, Duration as ChronoDuration};
+use futures::Stream;
 use rand::{Rng, SeedableRng};
 use rand::rngs::StdRng;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_stream::wrappers::ReceiverStream;
 
-use crate::data::{MarketData, PricePoint, HistoricalData, TimePeriod};
+use crate::data::{MarketData, PricePoint, HistoricalData, QuoteInterval, TimePeriod};
 use crate::error::{Result, TradingPlatformError, MarketDataError};
 use super::{MarketDataProvider, RateLimitInfo};
 
+/// Default interval between ticks on a `subscribe_quotes` stream.
+const DEFAULT_TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Default rate limits advertised by the mock provider, matching the old
+/// static `rate_limit_info()` values.
+const DEFAULT_REQUESTS_PER_MINUTE: u32 = 1000;
+const DEFAULT_REQUESTS_PER_HOUR: u32 = 60000;
+
+/// Tracks request timestamps in a sliding window so `get_*` calls can be
+/// throttled like a real rate-limited API.
+struct RateLimitState {
+    requests_per_minute: u32,
+    requests_per_hour: u32,
+    timestamps: VecDeque<DateTime<Utc>>,
+}
+
+impl RateLimitState {
+    fn new() -> Self {
+        Self {
+            requests_per_minute: DEFAULT_REQUESTS_PER_MINUTE,
+            requests_per_hour: DEFAULT_REQUESTS_PER_HOUR,
+            timestamps: VecDeque::new(),
+        }
+    }
+}
+
+/// Trading days per year, used to convert wall-clock intervals into the
+/// fraction of a year a GBM step should advance.
+const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+/// Default annualized drift (mu) and volatility (sigma) applied to any
+/// symbol that hasn't been given an explicit value via `set_drift`/`set_volatility`.
+const DEFAULT_DRIFT: f64 = 0.08;
+const DEFAULT_VOLATILITY: f64 = 0.20;
+
+/// A Cholesky-decomposed correlation matrix used to derive correlated return
+/// shocks for a fixed set of symbols, so multi-asset ticks co-move realistically.
+struct CorrelationModel {
+    symbols: Vec<String>,
+    /// Lower-triangular factor `L` such that `C = L * L^T`
+    lower: Vec<Vec<f64>>,
+}
+
+impl CorrelationModel {
+    /// Build the model, validating that `matrix` is square, symmetric, has a
+    /// unit diagonal, and is positive-definite (i.e. Cholesky succeeds).
+    fn new(symbols: &[String], matrix: Vec<Vec<f64>>) -> Result<Self> {
+        let n = symbols.len();
+
+        if matrix.len() != n || matrix.iter().any(|row| row.len() != n) {
+            return Err(TradingPlatformError::MarketData(
+                MarketDataError::InvalidCorrelationMatrix(
+                    "correlation matrix must be square and match the symbol count".to_string(),
+                ),
+            ));
+        }
+
+        for i in 0..n {
+            if (matrix[i][i] - 1.0).abs() > 1e-9 {
+                return Err(TradingPlatformError::MarketData(
+                    MarketDataError::InvalidCorrelationMatrix(
+                        "correlation matrix must have a unit diagonal".to_string(),
+                    ),
+                ));
+            }
+            for j in 0..n {
+                if (matrix[i][j] - matrix[j][i]).abs() > 1e-9 {
+                    return Err(TradingPlatformError::MarketData(
+                        MarketDataError::InvalidCorrelationMatrix(
+                            "correlation matrix must be symmetric".to_string(),
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let lower = cholesky(&matrix).ok_or_else(|| {
+            TradingPlatformError::MarketData(MarketDataError::InvalidCorrelationMatrix(
+                "correlation matrix is not positive-definite".to_string(),
+            ))
+        })?;
+
+        Ok(Self { symbols: symbols.to_vec(), lower })
+    }
+}
+
+/// Lower-triangular Cholesky factorization `C = L * L^T`. Returns `None` if
+/// `matrix` is not positive-definite (a diagonal pivot goes non-positive).
+fn cholesky(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let mut l = vec![vec![0.0; n]; n];
+
+    for i in 0..n {
+        for j in 0..=i {
+            let mut sum = matrix[i][j];
+            for k in 0..j {
+                sum -= l[i][k] * l[j][k];
+            }
+
+            if i == j {
+                if sum <= 0.0 {
+                    return None;
+                }
+                l[i][j] = sum.sqrt();
+            } else {
+                l[i][j] = sum / l[j][j];
+            }
+        }
+    }
+
+    Some(l)
+}
+
+/// A discrete price shock: at tick `step_index`, the generated price is
+/// multiplied by `multiplier` (e.g. `0.8` for a single-bar -20% crash).
+#[derive(Debug, Clone, Copy)]
+pub struct JumpEvent {
+    pub step_index: u64,
+    pub multiplier: f64,
+}
+
+/// A named market regime used to drive `MockMarketDataProvider` for
+/// property-based tests: a fixed drift/volatility pair, plus optional
+/// discrete jump events layered on top of the GBM path.
+#[derive(Debug, Clone)]
+pub struct MarketScenario {
+    drift: f64,
+    volatility: f64,
+    jumps: Vec<JumpEvent>,
+}
+
+impl MarketScenario {
+    /// Steady upward drift with below-average volatility.
+    pub fn bull_trend() -> Self {
+        Self { drift: 0.25, volatility: 0.15, jumps: Vec::new() }
+    }
+
+    /// Sustained negative drift with elevated volatility.
+    pub fn bear_crash() -> Self {
+        Self { drift: -0.35, volatility: 0.30, jumps: Vec::new() }
+    }
+
+    /// Flat drift with a single sharp drop on the first tick.
+    pub fn flash_crash() -> Self {
+        Self { drift: 0.0, volatility: 0.20, jumps: Vec::new() }
+            .with_jump(0, 0.8)
+    }
+
+    /// Flat drift with volatility far above any other regime.
+    pub fn high_volatility() -> Self {
+        Self { drift: 0.0, volatility: 0.80, jumps: Vec::new() }
+    }
+
+    /// Near-zero drift and volatility: prices barely move.
+    pub fn sideways() -> Self {
+        Self { drift: 0.0, volatility: 0.05, jumps: Vec::new() }
+    }
+
+    /// Schedule a discrete jump at `step_index`, applied on top of the GBM
+    /// step for that tick.
+    pub fn with_jump(mut self, step_index: u64, multiplier: f64) -> Self {
+        self.jumps.push(JumpEvent { step_index, multiplier });
+        self
+    }
+}
+
 /// Mock market data provider for testing and development
+#[derive(Clone)]
 pub struct MockMarketDataProvider {
     rng: Arc<Mutex<StdRng>>,
     base_prices: Arc<Mutex<HashMap<String, f64>>>,
     health_status: Arc<Mutex<bool>>,
+    drift: Arc<Mutex<HashMap<String, f64>>>,
+    volatility: Arc<Mutex<HashMap<String, f64>>>,
+    correlation: Arc<Mutex<Option<CorrelationModel>>>,
+    staleness: Arc<Mutex<HashMap<String, ChronoDuration>>>,
+    tick_interval: Arc<Mutex<Duration>>,
+    rate_limit: Arc<Mutex<RateLimitState>>,
+    scenario_jumps: Arc<Mutex<Vec<JumpEvent>>>,
+    step: Arc<Mutex<u64>>,
 }
 
 impl MockMarketDataProvider {
@@ -37,6 +216,14 @@ impl MockMarketDataProvider {
             rng: Arc::new(Mutex::new(StdRng::from_entropy())),
             base_prices: Arc::new(Mutex::new(base_prices)),
             health_status: Arc::new(Mutex::new(true)),
+            drift: Arc::new(Mutex::new(HashMap::new())),
+            volatility: Arc::new(Mutex::new(HashMap::new())),
+            correlation: Arc::new(Mutex::new(None)),
+            staleness: Arc::new(Mutex::new(HashMap::new())),
+            tick_interval: Arc::new(Mutex::new(DEFAULT_TICK_INTERVAL)),
+            rate_limit: Arc::new(Mutex::new(RateLimitState::new())),
+            scenario_jumps: Arc::new(Mutex::new(Vec::new())),
+            step: Arc::new(Mutex::new(0)),
         }
     }
 
@@ -46,12 +233,174 @@ impl MockMarketDataProvider {
         base_prices.insert("AAPL".to_string(), 150.0);
         base_prices.insert("GOOGL".to_string(), 2800.0);
         base_prices.insert("MSFT".to_string(), 300.0);
-        
+
         Self {
             rng: Arc::new(Mutex::new(StdRng::seed_from_u64(seed))),
             base_prices: Arc::new(Mutex::new(base_prices)),
             health_status: Arc::new(Mutex::new(true)),
+            drift: Arc::new(Mutex::new(HashMap::new())),
+            volatility: Arc::new(Mutex::new(HashMap::new())),
+            correlation: Arc::new(Mutex::new(None)),
+            staleness: Arc::new(Mutex::new(HashMap::new())),
+            tick_interval: Arc::new(Mutex::new(DEFAULT_TICK_INTERVAL)),
+            rate_limit: Arc::new(Mutex::new(RateLimitState::new())),
+            scenario_jumps: Arc::new(Mutex::new(Vec::new())),
+            step: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    /// Build a provider seeded for deterministic replay and pre-configured to
+    /// follow `scenario` on every symbol, so an entire price path — including
+    /// any scheduled jump events — is reproducible and shrinkable under proptest.
+    pub fn with_scenario(scenario: MarketScenario, seed: u64) -> Self {
+        let provider = Self::new_with_seed(seed);
+
+        let symbols: Vec<String> = provider.base_prices.lock().unwrap().keys().cloned().collect();
+        for symbol in &symbols {
+            provider.set_drift(symbol, scenario.drift);
+            provider.set_volatility(symbol, scenario.volatility);
         }
+        *provider.scenario_jumps.lock().unwrap() = scenario.jumps;
+
+        provider
+    }
+
+    /// Set the annualized drift (mu) used by the GBM price process for a symbol
+    pub fn set_drift(&self, symbol: &str, drift: f64) {
+        self.drift.lock().unwrap().insert(symbol.to_string(), drift);
+    }
+
+    /// Set the annualized volatility (sigma) used by the GBM price process for a symbol
+    pub fn set_volatility(&self, symbol: &str, volatility: f64) {
+        self.volatility.lock().unwrap().insert(symbol.to_string(), volatility);
+    }
+
+    /// Configure correlated returns for a fixed set of symbols. `matrix` must be
+    /// square, symmetric, have a unit diagonal, and be positive-definite.
+    /// Symbols requested elsewhere that aren't part of `symbols` fall back to
+    /// independent shocks.
+    pub fn set_correlation(&self, symbols: &[String], matrix: Vec<Vec<f64>>) -> Result<()> {
+        let model = CorrelationModel::new(symbols, matrix)?;
+        *self.correlation.lock().unwrap() = Some(model);
+        Ok(())
+    }
+
+    /// Draw one correlated shock per configured symbol: `epsilon = L * z` where
+    /// `z` is a vector of independent standard normals.
+    fn correlated_shocks(&self) -> Option<HashMap<String, f64>> {
+        let correlation = self.correlation.lock().unwrap();
+        let model = correlation.as_ref()?;
+        let n = model.symbols.len();
+
+        let z: Vec<f64> = (0..n).map(|_| self.next_standard_normal()).collect();
+        let mut shocks = HashMap::with_capacity(n);
+        for (i, symbol) in model.symbols.iter().enumerate() {
+            let mut eps = 0.0;
+            for j in 0..=i {
+                eps += model.lower[i][j] * z[j];
+            }
+            shocks.insert(symbol.clone(), eps);
+        }
+
+        Some(shocks)
+    }
+
+    fn drift_for(&self, symbol: &str) -> f64 {
+        self.drift.lock().unwrap().get(symbol).copied().unwrap_or(DEFAULT_DRIFT)
+    }
+
+    fn volatility_for(&self, symbol: &str) -> f64 {
+        self.volatility.lock().unwrap().get(symbol).copied().unwrap_or(DEFAULT_VOLATILITY)
+    }
+
+    /// Simulate an oracle feed that has gone stale: prices for `symbol` will
+    /// report a `publish_time` this far in the past until cleared or changed.
+    pub fn set_staleness(&self, symbol: &str, age: std::time::Duration) {
+        let age = ChronoDuration::from_std(age).unwrap_or_else(|_| ChronoDuration::zero());
+        self.staleness.lock().unwrap().insert(symbol.to_string(), age);
+    }
+
+    fn staleness_for(&self, symbol: &str) -> ChronoDuration {
+        self.staleness.lock().unwrap().get(symbol).copied().unwrap_or_else(ChronoDuration::zero)
+    }
+
+    /// Set how often `subscribe_quotes` advances the price state and emits a tick.
+    pub fn set_tick_interval(&self, interval: Duration) {
+        *self.tick_interval.lock().unwrap() = interval;
+    }
+
+    /// Override the rate limits enforced by `record_request`, so tests can
+    /// drive the throttled path deterministically.
+    pub fn set_rate_limit(&self, requests_per_minute: u32, requests_per_hour: u32) {
+        let mut state = self.rate_limit.lock().unwrap();
+        state.requests_per_minute = requests_per_minute;
+        state.requests_per_hour = requests_per_hour;
+    }
+
+    /// Record a `get_*` call against the sliding window, rejecting it with
+    /// `MarketDataError::RateLimited` once either limit is exceeded.
+    fn record_request(&self) -> Result<()> {
+        let mut state = self.rate_limit.lock().unwrap();
+        let now = Utc::now();
+
+        while let Some(&front) = state.timestamps.front() {
+            if now.signed_duration_since(front) > ChronoDuration::hours(1) {
+                state.timestamps.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        let minute_ago = now - ChronoDuration::minutes(1);
+        let minute_count = state.timestamps.iter().filter(|&&t| t > minute_ago).count() as u32;
+
+        if minute_count >= state.requests_per_minute {
+            let oldest_in_minute = state.timestamps.iter().find(|&&t| t > minute_ago).copied().unwrap_or(now);
+            let retry_after = (oldest_in_minute + ChronoDuration::minutes(1)) - now;
+            return Err(TradingPlatformError::MarketData(MarketDataError::RateLimited {
+                retry_after: retry_after.to_std().unwrap_or(Duration::from_secs(1)),
+            }));
+        }
+
+        if state.timestamps.len() as u32 >= state.requests_per_hour {
+            let oldest = *state.timestamps.front().unwrap();
+            let retry_after = (oldest + ChronoDuration::hours(1)) - now;
+            return Err(TradingPlatformError::MarketData(MarketDataError::RateLimited {
+                retry_after: retry_after.to_std().unwrap_or(Duration::from_secs(1)),
+            }));
+        }
+
+        state.timestamps.push_back(now);
+        Ok(())
+    }
+
+    /// One-sigma price uncertainty for a single tick, scaled from the symbol's
+    /// configured volatility the same way a GBM step scales its own shock.
+    fn confidence_for(&self, symbol: &str, price: f64) -> f64 {
+        let dt = 1.0 / TRADING_DAYS_PER_YEAR / 24.0 / 12.0;
+        price * self.volatility_for(symbol) * dt.sqrt()
+    }
+
+    /// Draw a standard normal sample from the shared RNG via Box-Muller
+    fn next_standard_normal(&self) -> f64 {
+        let mut rng = self.rng.lock().unwrap();
+        let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+        let u2: f64 = rng.gen_range(0.0..1.0);
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+
+    /// Advance a price one geometric Brownian motion step:
+    /// `S_{t+1} = S_t * exp((mu - sigma^2/2)*dt + sigma*sqrt(dt)*Z)`
+    fn gbm_step(&self, current_price: f64, drift: f64, volatility: f64, dt: f64) -> f64 {
+        let z = self.next_standard_normal();
+        self.gbm_step_with_z(current_price, drift, volatility, dt, z)
+    }
+
+    /// Same GBM step as `gbm_step`, but with the standard normal shock `z`
+    /// supplied by the caller (e.g. a correlated shock from `correlated_shocks`).
+    fn gbm_step_with_z(&self, current_price: f64, drift: f64, volatility: f64, dt: f64, z: f64) -> f64 {
+        let exponent = (drift - volatility * volatility / 2.0) * dt + volatility * dt.sqrt() * z;
+        (current_price * exponent.exp()).max(0.01)
     }
 
     /// Set the health status for testing error scenarios
@@ -64,15 +413,49 @@ impl MockMarketDataProvider {
         self.base_prices.lock().unwrap().insert(symbol.to_string(), price);
     }
 
-    /// Generate a realistic price variation
-    fn generate_price_variation(&self, base_price: f64) -> f64 {
-        let mut rng = self.rng.lock().unwrap();
-        
-        // Generate a price variation between -5% and +5%
-        let variation_percent = rng.gen_range(-0.05..0.05);
-        let variation = base_price * variation_percent;
-        
-        (base_price + variation).max(0.01) // Ensure price is always positive
+    /// Generate a realistic price variation using a single GBM step.
+    /// Treated as a ~5-minute tick when no explicit `TimePeriod` interval applies.
+    fn generate_price_variation(&self, symbol: &str, base_price: f64) -> f64 {
+        let dt = 1.0 / TRADING_DAYS_PER_YEAR / 24.0 / 12.0;
+        let price = self.gbm_step(base_price, self.drift_for(symbol), self.volatility_for(symbol), dt);
+        self.apply_scheduled_jump(price)
+    }
+
+    /// Same as `generate_price_variation`, but driven by an externally supplied
+    /// shock `z` (used to apply correlated multi-asset shocks).
+    fn generate_price_variation_with_z(&self, symbol: &str, base_price: f64, z: f64) -> f64 {
+        let dt = 1.0 / TRADING_DAYS_PER_YEAR / 24.0 / 12.0;
+        let price = self.gbm_step_with_z(base_price, self.drift_for(symbol), self.volatility_for(symbol), dt, z);
+        self.apply_scheduled_jump(price)
+    }
+
+    /// Advance the scenario's tick counter and apply any `JumpEvent` scheduled
+    /// for this step on top of the already-generated GBM price.
+    fn apply_scheduled_jump(&self, price: f64) -> f64 {
+        let mut step = self.step.lock().unwrap();
+        let current_step = *step;
+        *step += 1;
+
+        match self.scenario_jumps.lock().unwrap().iter().find(|j| j.step_index == current_step) {
+            Some(jump) => price * jump.multiplier,
+            None => price,
+        }
+    }
+
+    /// Build a `MarketData` snapshot from an already-generated current price
+    fn build_market_data(&self, symbol: &str, current_price: f64, previous_close: f64) -> MarketData {
+        let volume = self.generate_volume();
+        let mut market_data = MarketData::new(symbol.to_string(), current_price, volume);
+        market_data = market_data.with_change(previous_close);
+
+        let day_high = current_price * 1.02;
+        let day_low = current_price * 0.98;
+        market_data = market_data.with_day_range(day_high, day_low);
+
+        let publish_time = Utc::now() - self.staleness_for(symbol);
+        market_data
+            .with_confidence(self.confidence_for(symbol, current_price))
+            .with_publish_time(publish_time)
     }
 
     /// Generate realistic volume
@@ -83,9 +466,8 @@ impl MockMarketDataProvider {
 
     /// Generate historical price points
     fn generate_historical_prices(&self, symbol: &str, base_price: f64, period: TimePeriod) -> Vec<PricePoint> {
-        let mut rng = self.rng.lock().unwrap();
         let mut prices = Vec::new();
-        
+
         let (num_points, interval) = match period {
             TimePeriod::OneDay => (24 * 4, ChronoDuration::minutes(15)), // 15-minute intervals
             TimePeriod::OneWeek => (7 * 24, ChronoDuration::hours(1)),   // Hourly intervals
@@ -105,28 +487,86 @@ impl MockMarketDataProvider {
             std::time::Duration::from_secs(interval.num_seconds() as u64 * num_points as u64)
         ).unwrap_or(ChronoDuration::days(1));
 
+        // Fraction of a trading year this interval represents, for the GBM step
+        let dt = (interval.num_seconds() as f64) / (TRADING_DAYS_PER_YEAR * 24.0 * 3600.0);
+        let drift = self.drift_for(symbol);
+        let volatility = self.volatility_for(symbol);
+
         let mut current_price = base_price;
-        
+
         for i in 0..num_points {
             let timestamp = start_time + interval * i as i32;
-            
-            // Generate realistic OHLC data
-            let price_change = rng.gen_range(-0.02..0.02); // ±2% change per period
-            current_price *= 1.0 + price_change;
-            current_price = current_price.max(0.01);
-            
-            let volatility = rng.gen_range(0.005..0.02); // 0.5% to 2% volatility
-            let high = current_price * (1.0 + volatility);
-            let low = current_price * (1.0 - volatility);
-            let open = current_price * rng.gen_range(0.995..1.005);
+
+            // Evolve the close via a GBM step, then derive realistic OHLC around it
+            current_price = self.gbm_step(current_price, drift, volatility, dt);
+
+            let intrabar_vol = {
+                let mut rng = self.rng.lock().unwrap();
+                rng.gen_range(0.005..0.02)
+            };
+            let high = current_price * (1.0 + intrabar_vol);
+            let low = current_price * (1.0 - intrabar_vol);
+            let open = {
+                let mut rng = self.rng.lock().unwrap();
+                current_price * rng.gen_range(0.995..1.005)
+            };
             let close = current_price;
             let volume = self.generate_volume();
-            
+
             if let Ok(price_point) = PricePoint::new(timestamp, open, high, low, close, volume) {
                 prices.push(price_point);
             }
         }
-        
+
+        prices
+    }
+
+    /// Generate synthetic intraday candles at `interval` granularity, mirroring
+    /// `generate_historical_prices` but sized for a short, fresh lookback
+    /// instead of a full `TimePeriod` window.
+    fn generate_latest_quote_prices(&self, symbol: &str, base_price: f64, interval: QuoteInterval) -> Vec<PricePoint> {
+        let (num_points, bar_width) = match interval {
+            QuoteInterval::OneMinute => (60, ChronoDuration::minutes(1)),
+            QuoteInterval::FiveMinute => (60, ChronoDuration::minutes(5)),
+            QuoteInterval::FifteenMinute => (48, ChronoDuration::minutes(15)),
+            QuoteInterval::OneHour => (48, ChronoDuration::hours(1)),
+            QuoteInterval::OneDay => (30, ChronoDuration::days(1)),
+        };
+
+        let start_time = Utc::now() - ChronoDuration::from_std(
+            std::time::Duration::from_secs(bar_width.num_seconds() as u64 * num_points as u64)
+        ).unwrap_or(ChronoDuration::hours(1));
+
+        let dt = (bar_width.num_seconds() as f64) / (TRADING_DAYS_PER_YEAR * 24.0 * 3600.0);
+        let drift = self.drift_for(symbol);
+        let volatility = self.volatility_for(symbol);
+
+        let mut current_price = base_price;
+        let mut prices = Vec::new();
+
+        for i in 0..num_points {
+            let timestamp = start_time + bar_width * i as i32;
+
+            current_price = self.gbm_step(current_price, drift, volatility, dt);
+
+            let intrabar_vol = {
+                let mut rng = self.rng.lock().unwrap();
+                rng.gen_range(0.002..0.01)
+            };
+            let high = current_price * (1.0 + intrabar_vol);
+            let low = current_price * (1.0 - intrabar_vol);
+            let open = {
+                let mut rng = self.rng.lock().unwrap();
+                current_price * rng.gen_range(0.998..1.002)
+            };
+            let close = current_price;
+            let volume = self.generate_volume();
+
+            if let Ok(price_point) = PricePoint::new(timestamp, open, high, low, close, volume) {
+                prices.push(price_point);
+            }
+        }
+
         prices
     }
 }
@@ -140,9 +580,11 @@ impl Default for MockMarketDataProvider {
 #[async_trait]
 impl MarketDataProvider for MockMarketDataProvider {
     async fn get_current_price(&self, symbol: &str) -> Result<MarketData> {
+        self.record_request()?;
+
         // Simulate network delay
         tokio::time::sleep(std::time::Duration::from_millis(50)).await;
-        
+
         // Check health status
         if !*self.health_status.lock().unwrap() {
             return Err(TradingPlatformError::MarketData(
@@ -163,25 +605,17 @@ impl MarketDataProvider for MockMarketDataProvider {
             }
         };
 
-        let current_price = self.generate_price_variation(base_price);
-        let volume = self.generate_volume();
-        let previous_close = base_price;
+        let current_price = self.generate_price_variation(symbol, base_price);
 
-        let mut market_data = MarketData::new(symbol.to_string(), current_price, volume);
-        market_data = market_data.with_change(previous_close);
-        
-        // Add some realistic day range
-        let day_high = current_price * 1.02;
-        let day_low = current_price * 0.98;
-        market_data = market_data.with_day_range(day_high, day_low);
-
-        Ok(market_data)
+        Ok(self.build_market_data(symbol, current_price, base_price))
     }
 
     async fn get_historical_data(&self, symbol: &str, period: TimePeriod) -> Result<HistoricalData> {
+        self.record_request()?;
+
         // Simulate network delay
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-        
+
         // Check health status
         if !*self.health_status.lock().unwrap() {
             return Err(TradingPlatformError::MarketData(
@@ -218,19 +652,79 @@ impl MarketDataProvider for MockMarketDataProvider {
         Ok(historical_data)
     }
 
+    async fn get_latest_quotes(&self, symbol: &str, interval: QuoteInterval) -> Result<HistoricalData> {
+        self.record_request()?;
+
+        // Simulate network delay
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        if !*self.health_status.lock().unwrap() {
+            return Err(TradingPlatformError::MarketData(
+                MarketDataError::ProviderUnavailable
+            ));
+        }
+
+        let base_price = {
+            let prices = self.base_prices.lock().unwrap();
+            match prices.get(symbol) {
+                Some(&price) => price,
+                None => {
+                    return Err(TradingPlatformError::MarketData(
+                        MarketDataError::SymbolNotFound(symbol.to_string())
+                    ));
+                }
+            }
+        };
+
+        let price_points = self.generate_latest_quote_prices(symbol, base_price, interval);
+
+        if price_points.is_empty() {
+            return Err(TradingPlatformError::MarketData(
+                MarketDataError::InsufficientHistoricalData(symbol.to_string())
+            ));
+        }
+
+        let mut historical_data = HistoricalData::new(symbol.to_string(), TimePeriod::OneDay);
+        for price_point in price_points {
+            historical_data.add_price_point(price_point);
+        }
+
+        Ok(historical_data)
+    }
+
     async fn get_multiple_prices(&self, symbols: &[String]) -> Result<HashMap<String, MarketData>> {
+        self.record_request()?;
+
+        // Simulate network delay
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        if !*self.health_status.lock().unwrap() {
+            return Err(TradingPlatformError::MarketData(
+                MarketDataError::ProviderUnavailable
+            ));
+        }
+
+        // Draw one correlated shock per configured symbol up front so that
+        // co-moving symbols in this tick share the same underlying factors.
+        let shocks = self.correlated_shocks();
+
         let mut results = HashMap::new();
-        
+
         for symbol in symbols {
-            match self.get_current_price(symbol).await {
-                Ok(market_data) => {
-                    results.insert(symbol.clone(), market_data);
+            let base_price = {
+                let prices = self.base_prices.lock().unwrap();
+                match prices.get(symbol) {
+                    Some(&price) => price,
+                    None => continue,
                 }
-                Err(_) => {
-                    // Continue with other symbols even if one fails
-                    continue;
-                }
-            }
+            };
+
+            let current_price = match shocks.as_ref().and_then(|s| s.get(symbol)) {
+                Some(&z) => self.generate_price_variation_with_z(symbol, base_price, z),
+                None => self.generate_price_variation(symbol, base_price),
+            };
+
+            results.insert(symbol.clone(), self.build_market_data(symbol, current_price, base_price));
         }
 
         if results.is_empty() {
@@ -260,12 +754,74 @@ impl MarketDataProvider for MockMarketDataProvider {
     }
 
     fn rate_limit_info(&self) -> RateLimitInfo {
+        let mut state = self.rate_limit.lock().unwrap();
+        let now = Utc::now();
+
+        while let Some(&front) = state.timestamps.front() {
+            if now.signed_duration_since(front) > ChronoDuration::hours(1) {
+                state.timestamps.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        let minute_ago = now - ChronoDuration::minutes(1);
+        let current_usage = state.timestamps.iter().filter(|&&t| t > minute_ago).count() as u32;
+        let reset_time = state.timestamps.front().map(|&t| t + ChronoDuration::hours(1));
+
         RateLimitInfo {
-            requests_per_minute: 1000, // Very high limits for testing
-            requests_per_hour: 60000,
-            current_usage: 0,
-            reset_time: None,
+            requests_per_minute: state.requests_per_minute,
+            requests_per_hour: state.requests_per_hour,
+            current_usage,
+            reset_time,
+        }
+    }
+
+    async fn subscribe_quotes(&self, symbols: &[String]) -> Result<Pin<Box<dyn Stream<Item = MarketData> + Send>>> {
+        if !*self.health_status.lock().unwrap() {
+            return Err(TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable));
         }
+
+        let (tx, rx) = tokio::sync::mpsc::channel(64);
+        let provider = self.clone();
+        let symbols = symbols.to_vec();
+        let tick_interval = *self.tick_interval.lock().unwrap();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tick_interval);
+            loop {
+                interval.tick().await;
+
+                if !*provider.health_status.lock().unwrap() {
+                    return;
+                }
+
+                let shocks = provider.correlated_shocks();
+
+                for symbol in &symbols {
+                    let base_price = {
+                        let prices = provider.base_prices.lock().unwrap();
+                        match prices.get(symbol) {
+                            Some(&price) => price,
+                            None => continue,
+                        }
+                    };
+
+                    let current_price = match shocks.as_ref().and_then(|s| s.get(symbol)) {
+                        Some(&z) => provider.generate_price_variation_with_z(symbol, base_price, z),
+                        None => provider.generate_price_variation(symbol, base_price),
+                    };
+                    provider.base_prices.lock().unwrap().insert(symbol.clone(), current_price);
+
+                    let market_data = provider.build_market_data(symbol, current_price, base_price);
+                    if tx.send(market_data).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(Box::pin(ReceiverStream::new(rx)))
     }
 }
 
@@ -282,7 +838,7 @@ mod tests {
         
         let market_data = result.unwrap();
         assert_eq!(market_data.symbol, "AAPL");
-        assert!(market_data.price > 0.0);
+        assert!(market_data.price.to_f64() > 0.0);
         assert!(market_data.volume > 0);
     }
 
@@ -389,9 +945,188 @@ mod tests {
         let provider2 = MockMarketDataProvider::new_with_seed(42);
         
         // Both providers should generate the same price variation for the same symbol
-        let price1 = provider1.generate_price_variation(100.0);
-        let price2 = provider2.generate_price_variation(100.0);
-        
+        let price1 = provider1.generate_price_variation("AAPL", 100.0);
+        let price2 = provider2.generate_price_variation("AAPL", 100.0);
+
+        assert_eq!(price1, price2);
+    }
+
+    #[test]
+    fn test_gbm_prices_stay_positive_under_high_volatility() {
+        let provider = MockMarketDataProvider::new_with_seed(7);
+        provider.set_volatility("AAPL", 5.0);
+        provider.set_drift("AAPL", 0.0);
+
+        let mut price = 100.0;
+        for _ in 0..1000 {
+            price = provider.generate_price_variation("AAPL", price);
+            assert!(price > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_set_drift_and_volatility_are_applied() {
+        let provider = MockMarketDataProvider::new_with_seed(1);
+        provider.set_drift("AAPL", 0.5);
+        provider.set_volatility("AAPL", 0.01);
+
+        assert_eq!(provider.drift_for("AAPL"), 0.5);
+        assert_eq!(provider.volatility_for("AAPL"), 0.01);
+        assert_eq!(provider.drift_for("UNCONFIGURED"), DEFAULT_DRIFT);
+    }
+
+    #[test]
+    fn test_set_correlation_rejects_non_square_matrix() {
+        let provider = MockMarketDataProvider::new();
+        let symbols = vec!["AAPL".to_string(), "MSFT".to_string()];
+        let matrix = vec![vec![1.0, 0.5, 0.0], vec![0.5, 1.0, 0.0]];
+
+        let result = provider.set_correlation(&symbols, matrix);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_set_correlation_rejects_non_unit_diagonal() {
+        let provider = MockMarketDataProvider::new();
+        let symbols = vec!["AAPL".to_string(), "MSFT".to_string()];
+        let matrix = vec![vec![2.0, 0.5], vec![0.5, 1.0]];
+
+        let result = provider.set_correlation(&symbols, matrix);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_set_correlation_rejects_non_positive_definite() {
+        let provider = MockMarketDataProvider::new();
+        let symbols = vec!["AAPL".to_string(), "MSFT".to_string(), "GOOGL".to_string()];
+        // AAPL~MSFT and MSFT~GOOGL at +0.99 but AAPL~GOOGL at -0.99 are mutually inconsistent
+        let matrix = vec![
+            vec![1.0, 0.99, -0.99],
+            vec![0.99, 1.0, 0.99],
+            vec![-0.99, 0.99, 1.0],
+        ];
+
+        let result = provider.set_correlation(&symbols, matrix);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_set_correlation_accepts_valid_matrix() {
+        let provider = MockMarketDataProvider::new();
+        let symbols = vec!["AAPL".to_string(), "MSFT".to_string()];
+        let matrix = vec![vec![1.0, 0.5], vec![0.5, 1.0]];
+
+        assert!(provider.set_correlation(&symbols, matrix).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_current_price_carries_confidence_from_volatility() {
+        let provider = MockMarketDataProvider::new_with_seed(11);
+        provider.set_volatility("AAPL", 0.5);
+
+        let market_data = provider.get_current_price("AAPL").await.unwrap();
+        assert!(market_data.confidence > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_set_staleness_pushes_publish_time_into_the_past() {
+        let provider = MockMarketDataProvider::new_with_seed(12);
+        provider.set_staleness("AAPL", std::time::Duration::from_secs(3600));
+
+        let market_data = provider.get_current_price("AAPL").await.unwrap();
+        let age = Utc::now().signed_duration_since(market_data.publish_time);
+        assert!(age >= ChronoDuration::minutes(59));
+
+        let result = market_data.check_staleness(std::time::Duration::from_secs(60));
+        assert!(matches!(
+            result.unwrap_err(),
+            TradingPlatformError::MarketData(MarketDataError::StalePrice { .. })
+        ));
+    }
+
+    #[test]
+    fn test_with_scenario_applies_drift_and_volatility_to_all_symbols() {
+        let provider = MockMarketDataProvider::with_scenario(MarketScenario::bear_crash(), 5);
+
+        assert_eq!(provider.drift_for("AAPL"), -0.35);
+        assert_eq!(provider.volatility_for("AAPL"), 0.30);
+    }
+
+    #[test]
+    fn test_flash_crash_scenario_is_deterministic_and_drops_on_first_tick() {
+        let provider1 = MockMarketDataProvider::with_scenario(MarketScenario::flash_crash(), 99);
+        let provider2 = MockMarketDataProvider::with_scenario(MarketScenario::flash_crash(), 99);
+
+        let price1 = provider1.generate_price_variation("AAPL", 100.0);
+        let price2 = provider2.generate_price_variation("AAPL", 100.0);
+
         assert_eq!(price1, price2);
+        assert!(price1 <= 100.0 * 0.85);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_enforced_after_minute_quota_exhausted() {
+        let provider = MockMarketDataProvider::new();
+        provider.set_rate_limit(2, 1000);
+
+        assert!(provider.get_current_price("AAPL").await.is_ok());
+        assert!(provider.get_current_price("AAPL").await.is_ok());
+
+        let result = provider.get_current_price("AAPL").await;
+        match result.unwrap_err() {
+            TradingPlatformError::MarketData(MarketDataError::RateLimited { .. }) => {}
+            other => panic!("Expected RateLimited error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_info_reflects_live_usage() {
+        let provider = MockMarketDataProvider::new();
+        provider.set_rate_limit(1000, 60000);
+
+        provider.get_current_price("AAPL").await.unwrap();
+        provider.get_current_price("AAPL").await.unwrap();
+
+        let info = provider.rate_limit_info();
+        assert_eq!(info.current_usage, 2);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_quotes_emits_ticks_for_known_symbols() {
+        use tokio_stream::StreamExt;
+
+        let provider = MockMarketDataProvider::new_with_seed(21);
+        provider.set_tick_interval(Duration::from_millis(10));
+
+        let symbols = vec!["AAPL".to_string(), "GOOGL".to_string()];
+        let mut stream = provider.subscribe_quotes(&symbols).await.unwrap();
+
+        let first = stream.next().await.unwrap();
+        assert!(symbols.contains(&first.symbol));
+        let second = stream.next().await.unwrap();
+        assert!(symbols.contains(&second.symbol));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_quotes_rejects_when_unhealthy() {
+        let provider = MockMarketDataProvider::new();
+        provider.set_health_status(false);
+
+        let result = provider.subscribe_quotes(&["AAPL".to_string()]).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_correlated_multiple_prices_falls_back_for_unknown_symbol() {
+        let provider = MockMarketDataProvider::new_with_seed(3);
+        let symbols = vec!["AAPL".to_string(), "MSFT".to_string()];
+        provider.set_correlation(&symbols, vec![vec![1.0, 0.8], vec![0.8, 1.0]]).unwrap();
+
+        let requested = vec!["AAPL".to_string(), "GOOGL".to_string()];
+        let result = provider.get_multiple_prices(&requested).await.unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert!(result.contains_key("AAPL"));
+        assert!(result.contains_key("GOOGL"));
     }
 }
\ No newline at end of file
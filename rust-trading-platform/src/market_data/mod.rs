@@ -2,17 +2,32 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use futures::Stream;
+use rand::Rng;
 use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tracing::warn;
 
-use crate::data::{MarketData, HistoricalData, TimePeriod};
-use crate::error::{Result, TradingPlatformError};
+use crate::data::{MarketData, HistoricalData, QuoteInterval, TimePeriod};
+use crate::error::{MarketDataError, Result, TradingPlatformError};
 
 pub mod yahoo_finance;
+pub mod yahoo_market_data;
 pub mod mock_provider;
+pub mod backfill;
+pub mod stable_price;
+pub mod speculative;
+pub mod chained;
 
-pub use yahoo_finance::YahooFinanceProvider;
-pub use mock_provider::MockMarketDataProvider;
+pub use yahoo_finance::{YahooFinanceProvider, AdjustType};
+pub use yahoo_market_data::YahooMarketDataProvider;
+pub use mock_provider::{MockMarketDataProvider, MarketScenario, JumpEvent};
+pub use backfill::{Backfiller, CandleStore, NdjsonCandleStore, BackfillReport, Gap};
+pub use stable_price::StablePriceModel;
+pub use speculative::SpeculativeMarketDataProvider;
+pub use chained::ChainedMarketDataProvider;
 
 /// Trait for market data providers
 #[async_trait]
@@ -22,13 +37,23 @@ pub trait MarketDataProvider: Send + Sync {
     
     /// Get historical price data for a symbol
     async fn get_historical_data(&self, symbol: &str, period: TimePeriod) -> Result<HistoricalData>;
-    
+
+    /// Get the most recent intraday candles for a symbol at `interval`
+    /// granularity, without pulling a whole `TimePeriod` window. Meant for
+    /// strategies that want fresher bars than `get_historical_data` offers.
+    async fn get_latest_quotes(&self, symbol: &str, interval: QuoteInterval) -> Result<HistoricalData>;
+
     /// Get current prices for multiple symbols
     async fn get_multiple_prices(&self, symbols: &[String]) -> Result<HashMap<String, MarketData>>;
     
     /// Check if the provider is healthy and responsive
     async fn health_check(&self) -> Result<()>;
-    
+
+    /// Subscribe to a push-based stream of live quotes for `symbols`, rather
+    /// than polling `get_current_price`. The stream ends (or yields errors
+    /// through out-of-band health checks) once the provider goes unhealthy.
+    async fn subscribe_quotes(&self, symbols: &[String]) -> Result<Pin<Box<dyn Stream<Item = MarketData> + Send>>>;
+
     /// Get provider-specific information
     fn provider_name(&self) -> &str;
     
@@ -66,7 +91,23 @@ pub struct MarketDataConfig {
     pub max_retries: u32,
     pub retry_delay_ms: u64,
     pub rate_limit_delay_ms: u64,
-    pub cache_ttl_seconds: u64,
+    /// How long a cached `get_current_price` result stays fresh. Short,
+    /// since quotes move within seconds.
+    pub quote_cache_ttl_seconds: u64,
+    /// How long a cached `get_historical_data` result (keyed on symbol +
+    /// period) stays fresh. Long, since a day-old bar doesn't change.
+    pub historical_cache_ttl_seconds: u64,
+    /// Starting (and max) size of the provider's shared `RetryTokenBucket`.
+    /// Bounds how many retries can be in flight across *all* concurrent
+    /// callers before the provider abandons further retries and fails fast.
+    pub retry_token_bucket_capacity: usize,
+    /// Tokens a retry after a timeout/transient failure withdraws from the
+    /// bucket.
+    pub retry_cost_timeout: usize,
+    /// Tokens a retry after a throttling (rate-limit) response withdraws
+    /// from the bucket. Higher than `retry_cost_timeout` since a provider
+    /// that's already throttling us is the case retry storms hurt most.
+    pub retry_cost_throttle: usize,
 }
 
 impl Default for MarketDataConfig {
@@ -79,9 +120,57 @@ impl Default for MarketDataConfig {
             max_retries: 3,
             retry_delay_ms: 1000,
             rate_limit_delay_ms: 100,
-            cache_ttl_seconds: 60,
+            quote_cache_ttl_seconds: 5,
+            historical_cache_ttl_seconds: 3600,
+            retry_token_bucket_capacity: 500,
+            retry_cost_timeout: 10,
+            retry_cost_throttle: 20,
+        }
+    }
+}
+
+/// Shared backpressure gate for `RetryPolicy::execute_with_retry`, modeled
+/// on the Smithy standard retry strategy's retry quota: retries draw down a
+/// shared pool of tokens instead of each caller retrying independently, so
+/// when a provider degrades, concurrent callers don't pile more load onto
+/// it on the way down. One bucket is created per provider (by the
+/// provider's constructor or the factory) and shared via `Arc` across every
+/// `RetryPolicy` clone that talks to it.
+pub struct RetryTokenBucket {
+    capacity: usize,
+    tokens: Mutex<usize>,
+}
+
+impl RetryTokenBucket {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tokens: Mutex::new(capacity),
+        }
+    }
+
+    /// Withdraw `cost` tokens if available. Returns `false` (and withdraws
+    /// nothing) if the bucket can't cover the cost.
+    fn try_acquire(&self, cost: usize) -> bool {
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        if *tokens >= cost {
+            *tokens -= cost;
+            true
+        } else {
+            false
         }
     }
+
+    /// Return `amount` tokens to the bucket, capped at capacity.
+    fn refund(&self, amount: usize) {
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        *tokens = (*tokens + amount).min(self.capacity);
+    }
+
+    /// Tokens currently available. Exposed for monitoring/tests.
+    pub fn available(&self) -> usize {
+        *self.tokens.lock().unwrap_or_else(|e| e.into_inner())
+    }
 }
 
 /// Factory for creating market data providers
@@ -95,6 +184,10 @@ impl MarketDataProviderFactory {
                 let provider = YahooFinanceProvider::new(config.clone())?;
                 Ok(Box::new(provider))
             }
+            "yahoo_finance_live" => {
+                let provider = YahooMarketDataProvider::new()?;
+                Ok(Box::new(provider))
+            }
             "mock" => {
                 let provider = MockMarketDataProvider::new();
                 Ok(Box::new(provider))
@@ -105,14 +198,96 @@ impl MarketDataProviderFactory {
             ))),
         }
     }
+
+    /// Build a `ChainedMarketDataProvider` from an ordered list of provider
+    /// configs (e.g. `["yahoo_finance", "mock"]` as a fallback), so a caller
+    /// gets graceful degradation across providers without rewriting its own
+    /// call sites around a chain. `configs[0]` is the highest-priority link.
+    pub fn create_chained_provider(configs: &[MarketDataConfig]) -> Result<ChainedMarketDataProvider> {
+        if configs.is_empty() {
+            return Err(TradingPlatformError::Config(
+                "Provider chain requires at least one MarketDataConfig".to_string()
+            ));
+        }
+
+        let providers = configs.iter()
+            .map(|config| Self::create_provider(config).map(Arc::from))
+            .collect::<Result<Vec<Arc<dyn MarketDataProvider>>>>()?;
+
+        Ok(ChainedMarketDataProvider::new(providers))
+    }
+}
+
+/// How `calculate_delay_with_jitter` randomizes the pure exponential delay,
+/// so concurrent clients that started retrying around the same time don't
+/// stay in lockstep and hammer the provider in synchronized waves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Jitter {
+    /// No randomization; the exact exponential delay.
+    None,
+    /// Sample uniformly in `[0, computed_delay]` (AWS's "full jitter").
+    Full,
+    /// `delay / 2 + rand(0, delay / 2)`: never waits less than half the
+    /// computed delay, unlike `Full`.
+    Equal,
+}
+
+/// How `RetryPolicy::classify` (or a caller-supplied override) buckets a
+/// failed attempt, so `execute_with_retry` only spends the backoff schedule
+/// on errors that have a chance of succeeding next time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryClassification {
+    /// Likely to clear up on its own (timeout, connection failure, 5xx,
+    /// provider unavailable) — retry with the standard backoff/cost.
+    Transient,
+    /// The provider is actively telling us to slow down — retryable, but
+    /// charged `retry_cost_throttle` against the token bucket instead of
+    /// `retry_cost_timeout`.
+    Throttling,
+    /// Will never succeed by retrying (bad input, malformed response,
+    /// misconfiguration) — `execute_with_retry` returns it immediately
+    /// instead of burning the rest of the backoff schedule.
+    Permanent,
+}
+
+/// Default `classify` used when `RetryPolicy::classifier` is `None`. Also
+/// used by `SpeculativeMarketDataProvider` to decide whether a failed
+/// backup frees up a speculative launch immediately.
+pub(crate) fn default_classify(error: &TradingPlatformError) -> RetryClassification {
+    match error {
+        TradingPlatformError::MarketData(MarketDataError::RateLimitExceeded)
+        | TradingPlatformError::MarketData(MarketDataError::RateLimited { .. }) => {
+            RetryClassification::Throttling
+        }
+        TradingPlatformError::MarketData(MarketDataError::SymbolNotFound(_))
+        | TradingPlatformError::MarketData(MarketDataError::InvalidFormat)
+        | TradingPlatformError::Config(_)
+        | TradingPlatformError::Serialization(_)
+        | TradingPlatformError::Parse(_) => RetryClassification::Permanent,
+        // Network timeouts/connection failures, `ProviderUnavailable`, and
+        // any other 5xx-shaped failure we haven't special-cased: worth
+        // another attempt.
+        _ => RetryClassification::Transient,
+    }
 }
 
 /// Retry logic with exponential backoff
+#[derive(Clone)]
 pub struct RetryPolicy {
     pub max_retries: u32,
     pub base_delay: Duration,
     pub max_delay: Duration,
     pub backoff_multiplier: f64,
+    pub jitter: Jitter,
+    /// Shared gate a retry must draw tokens from before it's issued. `None`
+    /// (the default) retries unconditionally, matching the old behavior.
+    pub token_bucket: Option<Arc<RetryTokenBucket>>,
+    pub retry_cost_timeout: usize,
+    pub retry_cost_throttle: usize,
+    /// Overrides `default_classify` for this policy, e.g. a provider with
+    /// its own error types or retry-worthiness rules. `None` uses
+    /// `default_classify`.
+    pub classifier: Option<Arc<dyn Fn(&TradingPlatformError) -> RetryClassification + Send + Sync>>,
 }
 
 impl Default for RetryPolicy {
@@ -122,46 +297,133 @@ impl Default for RetryPolicy {
             base_delay: Duration::from_millis(1000),
             max_delay: Duration::from_secs(30),
             backoff_multiplier: 2.0,
+            jitter: Jitter::None,
+            token_bucket: None,
+            retry_cost_timeout: 10,
+            retry_cost_throttle: 20,
+            classifier: None,
         }
     }
 }
 
 impl RetryPolicy {
-    /// Calculate delay for a given retry attempt
+    /// Calculate the pure exponential delay for a given retry attempt, with
+    /// no jitter applied.
     pub fn calculate_delay(&self, attempt: u32) -> Duration {
         if attempt == 0 {
             return Duration::from_millis(0);
         }
-        
-        let delay_ms = (self.base_delay.as_millis() as f64) 
+
+        let delay_ms = (self.base_delay.as_millis() as f64)
             * self.backoff_multiplier.powi((attempt - 1) as i32);
-        
+
         let delay = Duration::from_millis(delay_ms as u64);
         std::cmp::min(delay, self.max_delay)
     }
-    
-    /// Execute a function with retry logic
+
+    /// `calculate_delay`, randomized per `self.jitter` using `rng`. Takes an
+    /// injectable RNG (rather than reaching for `rand::thread_rng()`
+    /// internally) so callers can keep jitter tests deterministic. `attempt
+    /// == 0` always returns zero, same as `calculate_delay` — there's
+    /// nothing to jitter before the first retry. `max_delay` capping is
+    /// re-applied after jitter, since `Full`/`Equal` can't push the delay
+    /// above what `calculate_delay` already returned but a future jitter
+    /// variant might.
+    pub fn calculate_delay_with_jitter(&self, attempt: u32, rng: &mut impl Rng) -> Duration {
+        let delay = self.calculate_delay(attempt);
+        if attempt == 0 {
+            return delay;
+        }
+
+        let jittered = match self.jitter {
+            Jitter::None => delay,
+            Jitter::Full => Duration::from_millis(rng.gen_range(0..=delay.as_millis() as u64)),
+            Jitter::Equal => {
+                let half = delay.as_millis() as u64 / 2;
+                Duration::from_millis(half + rng.gen_range(0..=half))
+            }
+        };
+
+        std::cmp::min(jittered, self.max_delay)
+    }
+
+    /// Classify `error` via `self.classifier` if set, falling back to
+    /// `default_classify`.
+    fn classify(&self, error: &TradingPlatformError) -> RetryClassification {
+        self.classifier.as_ref().map_or_else(
+            || default_classify(error),
+            |classifier| classifier(error),
+        )
+    }
+
+    /// Tokens a retry classified as `classification` should withdraw from
+    /// `token_bucket`. Throttling responses cost more than a plain
+    /// transient failure, since they're the strongest signal that adding
+    /// load is making things worse.
+    fn retry_cost(&self, classification: RetryClassification) -> usize {
+        match classification {
+            RetryClassification::Throttling => self.retry_cost_throttle,
+            _ => self.retry_cost_timeout,
+        }
+    }
+
+    /// Execute a function with retry logic. Errors `self.classify`es as
+    /// `Permanent` are returned immediately without consuming a retry or
+    /// sleeping — they have no chance of succeeding on a second attempt. For
+    /// everything else: if a `token_bucket` is set, every retry (but not the
+    /// initial attempt) must first withdraw tokens from it; if the bucket
+    /// can't cover the cost, the retry is abandoned and the triggering error
+    /// is returned immediately instead of sleeping and trying again. Tokens
+    /// spent on retries that eventually succeed are refunded in full; a
+    /// first-try success refunds a small flat amount, letting the bucket
+    /// slowly recover during healthy periods.
     pub async fn execute_with_retry<F, Fut, T>(&self, mut operation: F) -> Result<T>
     where
         F: FnMut() -> Fut,
         Fut: std::future::Future<Output = Result<T>>,
     {
+        const FIRST_TRY_SUCCESS_REFUND: usize = 1;
+
         let mut last_error = None;
-        
+        let mut tokens_spent: usize = 0;
+
         for attempt in 0..=self.max_retries {
             match operation().await {
-                Ok(result) => return Ok(result),
+                Ok(result) => {
+                    if let Some(bucket) = &self.token_bucket {
+                        bucket.refund(if tokens_spent == 0 {
+                            FIRST_TRY_SUCCESS_REFUND
+                        } else {
+                            tokens_spent
+                        });
+                    }
+                    return Ok(result);
+                }
                 Err(error) => {
-                    last_error = Some(error);
-                    
+                    let classification = self.classify(&error);
+                    if classification == RetryClassification::Permanent {
+                        return Err(error);
+                    }
+
                     if attempt < self.max_retries {
-                        let delay = self.calculate_delay(attempt + 1);
+                        if let Some(bucket) = &self.token_bucket {
+                            let cost = self.retry_cost(classification);
+                            if !bucket.try_acquire(cost) {
+                                warn!("Retry token bucket exhausted, abandoning retry");
+                                return Err(error);
+                            }
+                            tokens_spent += cost;
+                        }
+
+                        let delay = self.calculate_delay_with_jitter(attempt + 1, &mut rand::thread_rng());
                         tokio::time::sleep(delay).await;
                     }
+
+                    last_error = Some(error);
                 }
             }
         }
-        
+
         Err(last_error.unwrap_or_else(|| {
             TradingPlatformError::internal("Retry policy failed without error")
         }))
@@ -171,6 +433,7 @@ impl RetryPolicy {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
 
     #[test]
     fn test_retry_policy_delay_calculation() {
@@ -182,6 +445,59 @@ mod tests {
         assert_eq!(policy.calculate_delay(3), Duration::from_millis(4000));
     }
 
+    #[test]
+    fn test_calculate_delay_with_jitter_none_matches_pure_delay() {
+        let policy = RetryPolicy { jitter: Jitter::None, ..Default::default() };
+        let mut rng = StdRng::seed_from_u64(42);
+
+        assert_eq!(policy.calculate_delay_with_jitter(0, &mut rng), Duration::from_millis(0));
+        assert_eq!(policy.calculate_delay_with_jitter(2, &mut rng), policy.calculate_delay(2));
+    }
+
+    #[test]
+    fn test_calculate_delay_with_jitter_full_stays_in_range() {
+        let policy = RetryPolicy { jitter: Jitter::Full, ..Default::default() };
+        let mut rng = StdRng::seed_from_u64(7);
+        let upper_bound = policy.calculate_delay(2);
+
+        for _ in 0..50 {
+            let delay = policy.calculate_delay_with_jitter(2, &mut rng);
+            assert!(delay <= upper_bound);
+        }
+    }
+
+    #[test]
+    fn test_calculate_delay_with_jitter_equal_never_below_half() {
+        let policy = RetryPolicy { jitter: Jitter::Equal, ..Default::default() };
+        let mut rng = StdRng::seed_from_u64(7);
+        let pure_delay = policy.calculate_delay(2);
+        let half = pure_delay / 2;
+
+        for _ in 0..50 {
+            let delay = policy.calculate_delay_with_jitter(2, &mut rng);
+            assert!(delay >= half);
+            assert!(delay <= pure_delay);
+        }
+    }
+
+    #[test]
+    fn test_calculate_delay_with_jitter_respects_max_delay() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_millis(1000),
+            max_delay: Duration::from_millis(1500),
+            backoff_multiplier: 2.0,
+            jitter: Jitter::Full,
+            ..Default::default()
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for _ in 0..50 {
+            let delay = policy.calculate_delay_with_jitter(5, &mut rng);
+            assert!(delay <= Duration::from_millis(1500));
+        }
+    }
+
     #[test]
     fn test_retry_policy_max_delay() {
         let policy = RetryPolicy {
@@ -189,6 +505,7 @@ mod tests {
             base_delay: Duration::from_millis(1000),
             max_delay: Duration::from_secs(5),
             backoff_multiplier: 2.0,
+            ..Default::default()
         };
         
         // Should cap at max_delay
@@ -233,6 +550,7 @@ mod tests {
             base_delay: Duration::from_millis(1),
             max_delay: Duration::from_millis(10),
             backoff_multiplier: 2.0,
+            ..Default::default()
         };
         
         let mut call_count = 0;
@@ -260,6 +578,7 @@ mod tests {
             base_delay: Duration::from_millis(1),
             max_delay: Duration::from_millis(10),
             backoff_multiplier: 2.0,
+            ..Default::default()
         };
         
         let mut call_count = 0;
@@ -272,4 +591,142 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(call_count, 3); // Initial attempt + 2 retries
     }
+
+    #[test]
+    fn test_default_classify() {
+        assert_eq!(
+            default_classify(&TradingPlatformError::MarketData(MarketDataError::RateLimitExceeded)),
+            RetryClassification::Throttling
+        );
+        assert_eq!(
+            default_classify(&TradingPlatformError::MarketData(MarketDataError::SymbolNotFound("AAPL".into()))),
+            RetryClassification::Permanent
+        );
+        assert_eq!(
+            default_classify(&TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable)),
+            RetryClassification::Transient
+        );
+        assert_eq!(
+            default_classify(&TradingPlatformError::Config("bad config".into())),
+            RetryClassification::Permanent
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_retry_returns_immediately_on_permanent_error() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+            backoff_multiplier: 2.0,
+            ..Default::default()
+        };
+
+        let mut call_count = 0;
+
+        let result = policy.execute_with_retry(|| {
+            call_count += 1;
+            async {
+                Err::<i32, TradingPlatformError>(TradingPlatformError::MarketData(
+                    MarketDataError::SymbolNotFound("AAPL".to_string()),
+                ))
+            }
+        }).await;
+
+        assert!(result.is_err());
+        assert_eq!(call_count, 1); // No retries burned on an unretryable error
+    }
+
+    #[tokio::test]
+    async fn test_execute_with_retry_honors_custom_classifier() {
+        // Override the classifier so this policy treats `Internal` errors
+        // (normally transient) as permanent instead.
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+            backoff_multiplier: 2.0,
+            classifier: Some(Arc::new(|_: &TradingPlatformError| RetryClassification::Permanent)),
+            ..Default::default()
+        };
+
+        let mut call_count = 0;
+
+        let result = policy.execute_with_retry(|| {
+            call_count += 1;
+            async { Err::<i32, TradingPlatformError>(TradingPlatformError::internal("Always fails")) }
+        }).await;
+
+        assert!(result.is_err());
+        assert_eq!(call_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_token_bucket_abandons_retry_when_exhausted() {
+        let bucket = Arc::new(RetryTokenBucket::new(5));
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+            backoff_multiplier: 1.0,
+            jitter: Jitter::None,
+            token_bucket: Some(bucket.clone()),
+            retry_cost_timeout: 3,
+            retry_cost_throttle: 3,
+            classifier: None,
+        };
+
+        let mut call_count = 0;
+
+        let result = policy.execute_with_retry(|| {
+            call_count += 1;
+            async { Err::<i32, TradingPlatformError>(TradingPlatformError::internal("Always fails")) }
+        }).await;
+
+        assert!(result.is_err());
+        // Only 5 / 3 = 1 retry can afford its cost before the bucket runs
+        // dry, so the retry is abandoned instead of running all 5.
+        assert_eq!(call_count, 2);
+        assert_eq!(bucket.available(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_token_bucket_refunds_on_success() {
+        let bucket = Arc::new(RetryTokenBucket::new(10));
+        let policy = RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+            backoff_multiplier: 1.0,
+            token_bucket: Some(bucket.clone()),
+            retry_cost_timeout: 4,
+            retry_cost_throttle: 4,
+            ..Default::default()
+        };
+
+        let mut call_count = 0;
+
+        let result = policy.execute_with_retry(|| {
+            call_count += 1;
+            async move {
+                if call_count < 2 {
+                    Err(TradingPlatformError::internal("Temporary failure"))
+                } else {
+                    Ok::<i32, TradingPlatformError>(42)
+                }
+            }
+        }).await;
+
+        assert!(result.is_ok());
+        // Cost of the one retry is refunded in full once it succeeds.
+        assert_eq!(bucket.available(), 10);
+    }
+
+    #[test]
+    fn test_retry_token_bucket_refund_caps_at_capacity() {
+        let bucket = RetryTokenBucket::new(5);
+        assert!(bucket.try_acquire(5));
+        bucket.refund(100);
+        assert_eq!(bucket.available(), 5);
+    }
 }
\ No newline at end of file
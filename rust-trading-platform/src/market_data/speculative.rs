@@ -0,0 +1,382 @@
+// Speculative execution across backup market data providers, to hide the
+// tail latency of a single slow endpoint.
+
+use async_trait::async_trait;
+use futures::stream::FuturesUnordered;
+use futures::{Stream, StreamExt};
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::data::{HistoricalData, MarketData, QuoteInterval, TimePeriod};
+use crate::error::{Result, TradingPlatformError};
+use super::{default_classify, MarketDataProvider, RateLimitInfo, RetryClassification};
+
+/// Races a primary `MarketDataProvider` against an ordered list of backups
+/// to shorten tail latency, modeled on a speculative-execution retry
+/// policy. The primary is always fired first; if nothing has answered
+/// within `retry_interval`, the next backup is launched *in parallel*
+/// rather than waiting for the primary to fail. The first `Ok` wins and
+/// every other in-flight request is dropped.
+///
+/// This is distinct from `RetryPolicy`, which retries the *same* provider
+/// after it fails. `SpeculativeMarketDataProvider` races *different*
+/// providers concurrently so one slow backend doesn't set the pace for
+/// every caller.
+pub struct SpeculativeMarketDataProvider {
+    /// `providers[0]` is the primary; the rest are backups in the order
+    /// they're tried.
+    providers: Vec<Arc<dyn MarketDataProvider>>,
+    /// How many backups beyond the primary may be launched speculatively
+    /// for a single request. A backup launched to replace one that just
+    /// returned a `Permanent` error doesn't count against this.
+    max_speculative_count: usize,
+    /// How long to wait for the currently in-flight request(s) to answer
+    /// before launching the next backup.
+    retry_interval: Duration,
+}
+
+impl SpeculativeMarketDataProvider {
+    pub fn new(
+        primary: Arc<dyn MarketDataProvider>,
+        backups: Vec<Arc<dyn MarketDataProvider>>,
+        max_speculative_count: usize,
+        retry_interval: Duration,
+    ) -> Self {
+        let mut providers = Vec::with_capacity(1 + backups.len());
+        providers.push(primary);
+        providers.extend(backups);
+
+        Self {
+            providers,
+            max_speculative_count,
+            retry_interval,
+        }
+    }
+
+    fn primary(&self) -> &Arc<dyn MarketDataProvider> {
+        &self.providers[0]
+    }
+
+    /// Race every provider (primary first) against `request`, launching the
+    /// next backup every `retry_interval` while budget remains. A
+    /// `Permanent` error (per `default_classify`) frees up an immediate
+    /// launch of the next backup without spending that budget, since the
+    /// failure tells us nothing about how loaded the *next* provider is.
+    async fn race<T, F, Fut>(&self, request: F) -> Result<T>
+    where
+        F: Fn(Arc<dyn MarketDataProvider>) -> Fut,
+        Fut: std::future::Future<Output = Result<T>> + Send,
+        T: Send,
+    {
+        let mut remaining: VecDeque<Arc<dyn MarketDataProvider>> =
+            self.providers.iter().skip(1).cloned().collect();
+
+        let mut in_flight = FuturesUnordered::new();
+        in_flight.push(request(self.providers[0].clone()));
+
+        let mut speculative_remaining = self.max_speculative_count;
+        let mut last_error: Option<TradingPlatformError> = None;
+
+        loop {
+            let can_launch_on_timer = speculative_remaining > 0 && !remaining.is_empty();
+
+            if in_flight.is_empty() {
+                match remaining.pop_front() {
+                    Some(provider) => {
+                        in_flight.push(request(provider));
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+
+            enum RaceEvent<T> {
+                Answered(Option<Result<T>>),
+                RetryIntervalElapsed,
+            }
+
+            let event = if can_launch_on_timer {
+                tokio::select! {
+                    biased;
+                    result = in_flight.next() => RaceEvent::Answered(result),
+                    _ = tokio::time::sleep(self.retry_interval) => RaceEvent::RetryIntervalElapsed,
+                }
+            } else {
+                RaceEvent::Answered(in_flight.next().await)
+            };
+
+            match event {
+                RaceEvent::Answered(Some(Ok(value))) => return Ok(value),
+                RaceEvent::Answered(Some(Err(error))) => {
+                    let permanent = default_classify(&error) == RetryClassification::Permanent;
+                    last_error = Some(error);
+                    if permanent {
+                        if let Some(provider) = remaining.pop_front() {
+                            debug!("Speculative backup returned a permanent error, launching next backup immediately");
+                            in_flight.push(request(provider));
+                        }
+                    }
+                }
+                RaceEvent::Answered(None) => break,
+                RaceEvent::RetryIntervalElapsed => {
+                    if let Some(provider) = remaining.pop_front() {
+                        debug!("Speculative retry interval elapsed, launching next backup");
+                        in_flight.push(request(provider));
+                        speculative_remaining -= 1;
+                    }
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            TradingPlatformError::internal("Speculative provider race produced no result")
+        }))
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for SpeculativeMarketDataProvider {
+    async fn get_current_price(&self, symbol: &str) -> Result<MarketData> {
+        let symbol = symbol.to_string();
+        self.race(move |provider| {
+            let symbol = symbol.clone();
+            async move { provider.get_current_price(&symbol).await }
+        }).await
+    }
+
+    async fn get_historical_data(&self, symbol: &str, period: TimePeriod) -> Result<HistoricalData> {
+        // Historical requests aren't latency-sensitive the way a live quote
+        // is, so they go to the primary only.
+        self.primary().get_historical_data(symbol, period).await
+    }
+
+    async fn get_latest_quotes(&self, symbol: &str, interval: QuoteInterval) -> Result<HistoricalData> {
+        // Same reasoning as get_historical_data: a batch of candles isn't
+        // worth racing backups for.
+        self.primary().get_latest_quotes(symbol, interval).await
+    }
+
+    async fn get_multiple_prices(&self, symbols: &[String]) -> Result<HashMap<String, MarketData>> {
+        let symbols = symbols.to_vec();
+        self.race(move |provider| {
+            let symbols = symbols.clone();
+            async move { provider.get_multiple_prices(&symbols).await }
+        }).await
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        self.primary().health_check().await
+    }
+
+    async fn subscribe_quotes(&self, symbols: &[String]) -> Result<Pin<Box<dyn Stream<Item = MarketData> + Send>>> {
+        self.primary().subscribe_quotes(symbols).await
+    }
+
+    fn provider_name(&self) -> &str {
+        "Speculative Provider"
+    }
+
+    fn rate_limit_info(&self) -> RateLimitInfo {
+        self.primary().rate_limit_info()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Price;
+    use crate::error::MarketDataError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// A `MarketDataProvider` whose `get_current_price` behavior is
+    /// scripted per call, for exercising the race without a real network.
+    struct ScriptedProvider {
+        name: &'static str,
+        delay: Duration,
+        result: Mutex<Option<Result<MarketData>>>,
+        calls: AtomicUsize,
+    }
+
+    impl ScriptedProvider {
+        fn new(name: &'static str, delay: Duration, result: Result<MarketData>) -> Self {
+            Self {
+                name,
+                delay,
+                result: Mutex::new(Some(result)),
+                calls: AtomicUsize::new(0),
+            }
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    fn test_market_data(symbol: &str) -> MarketData {
+        let now = chrono::Utc::now();
+        MarketData {
+            symbol: symbol.to_string(),
+            price: Price::from_f64(100.0),
+            volume: 0,
+            timestamp: now,
+            change: Price::ZERO,
+            change_percent: 0.0,
+            market_cap: None,
+            day_high: None,
+            day_low: None,
+            previous_close: None,
+            confidence: 0.0,
+            publish_time: now,
+        }
+    }
+
+    #[async_trait]
+    impl MarketDataProvider for ScriptedProvider {
+        async fn get_current_price(&self, _symbol: &str) -> Result<MarketData> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.result.lock().unwrap().take().expect("ScriptedProvider called more than once")
+        }
+
+        async fn get_historical_data(&self, _symbol: &str, _period: TimePeriod) -> Result<HistoricalData> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        async fn get_latest_quotes(&self, _symbol: &str, _interval: QuoteInterval) -> Result<HistoricalData> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        async fn get_multiple_prices(&self, _symbols: &[String]) -> Result<HashMap<String, MarketData>> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        async fn health_check(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn subscribe_quotes(&self, _symbols: &[String]) -> Result<Pin<Box<dyn Stream<Item = MarketData> + Send>>> {
+            unimplemented!("not exercised by these tests")
+        }
+
+        fn provider_name(&self) -> &str {
+            self.name
+        }
+
+        fn rate_limit_info(&self) -> RateLimitInfo {
+            RateLimitInfo::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fast_primary_wins_without_launching_backups() {
+        let primary = Arc::new(ScriptedProvider::new(
+            "primary",
+            Duration::from_millis(1),
+            Ok(test_market_data("AAPL")),
+        ));
+        let backup = Arc::new(ScriptedProvider::new(
+            "backup",
+            Duration::from_millis(1),
+            Ok(test_market_data("AAPL")),
+        ));
+
+        let provider = SpeculativeMarketDataProvider::new(
+            primary.clone(),
+            vec![backup.clone()],
+            1,
+            Duration::from_millis(50),
+        );
+
+        let result = provider.get_current_price("AAPL").await;
+
+        assert!(result.is_ok());
+        assert_eq!(primary.calls(), 1);
+        assert_eq!(backup.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_slow_primary_loses_to_speculative_backup() {
+        let primary = Arc::new(ScriptedProvider::new(
+            "primary",
+            Duration::from_millis(200),
+            Ok(test_market_data("AAPL")),
+        ));
+        let backup = Arc::new(ScriptedProvider::new(
+            "backup",
+            Duration::from_millis(1),
+            Ok(test_market_data("AAPL")),
+        ));
+
+        let provider = SpeculativeMarketDataProvider::new(
+            primary.clone(),
+            vec![backup.clone()],
+            1,
+            Duration::from_millis(10),
+        );
+
+        let result = provider.get_current_price("AAPL").await;
+
+        assert!(result.is_ok());
+        assert_eq!(backup.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_error_launches_next_backup_without_waiting() {
+        let primary = Arc::new(ScriptedProvider::new(
+            "primary",
+            Duration::from_millis(1),
+            Err(TradingPlatformError::MarketData(MarketDataError::SymbolNotFound("AAPL".to_string()))),
+        ));
+        let backup = Arc::new(ScriptedProvider::new(
+            "backup",
+            Duration::from_millis(1),
+            Ok(test_market_data("AAPL")),
+        ));
+
+        // A retry_interval far longer than the test timeout: if the
+        // permanent error didn't trigger an immediate launch, this would
+        // hang instead of resolving quickly.
+        let provider = SpeculativeMarketDataProvider::new(
+            primary.clone(),
+            vec![backup.clone()],
+            1,
+            Duration::from_secs(60),
+        );
+
+        let result = tokio::time::timeout(Duration::from_secs(5), provider.get_current_price("AAPL"))
+            .await
+            .expect("permanent error should free a launch immediately, not wait out retry_interval");
+
+        assert!(result.is_ok());
+        assert_eq!(backup.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_all_providers_failing_returns_last_error() {
+        let primary = Arc::new(ScriptedProvider::new(
+            "primary",
+            Duration::from_millis(1),
+            Err(TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable)),
+        ));
+        let backup = Arc::new(ScriptedProvider::new(
+            "backup",
+            Duration::from_millis(1),
+            Err(TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable)),
+        ));
+
+        let provider = SpeculativeMarketDataProvider::new(
+            primary.clone(),
+            vec![backup.clone()],
+            1,
+            Duration::from_millis(5),
+        );
+
+        let result = provider.get_current_price("AAPL").await;
+
+        assert!(result.is_err());
+    }
+}
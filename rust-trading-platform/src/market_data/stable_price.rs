@@ -0,0 +1,145 @@
+// Manipulation-resistant reference prices, separate from the raw
+// instantaneous `MarketData::price` a provider returns. Strategy evaluation
+// and the health engine react to a single `MarketDataProvider` tick, which
+// makes both vulnerable to a one-off spike or a stale/erroneous quote.
+// `StablePriceModel` tracks a slowly-adjusting price per symbol that can
+// only move toward the live price by a bounded rate, so callers can value
+// assets at `conservative_asset_price` (min of live/stable) and
+// liabilities or thresholds at `conservative_liability_price` (max of
+// live/stable) to keep a transient tick from instantly tripping a
+// `PriceDrop` signal or a margin call.
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StableState {
+    price: f64,
+    updated_at: DateTime<Utc>,
+}
+
+/// Maintains a per-symbol stable price, moving toward each `update`'s live
+/// price by at most `max_change_rate` (a fraction of the stable price, per
+/// second elapsed) rather than snapping to it.
+#[derive(Debug, Clone)]
+pub struct StablePriceModel {
+    max_change_rate: f64,
+    states: HashMap<String, StableState>,
+}
+
+impl StablePriceModel {
+    pub fn new(max_change_rate: f64) -> Self {
+        Self { max_change_rate, states: HashMap::new() }
+    }
+
+    /// Seed (or overwrite) `symbol`'s stable price without passing through
+    /// the rate limit, e.g. restoring a value persisted in
+    /// `market_data_cache` at startup so the model doesn't snap to the
+    /// first live quote after a restart.
+    pub fn seed(&mut self, symbol: impl Into<String>, price: f64, as_of: DateTime<Utc>) {
+        self.states.insert(symbol.into(), StableState { price, updated_at: as_of });
+    }
+
+    /// Advance `symbol`'s stable price toward `live` by at most
+    /// `max_change_rate * elapsed_seconds` of its current value, and return
+    /// the result. A symbol with no prior state is seeded at `live` with no
+    /// smoothing -- there's nothing stale to guard against yet.
+    pub fn update(&mut self, symbol: &str, live: f64, now: DateTime<Utc>) -> f64 {
+        let stable = match self.states.get(symbol) {
+            None => live,
+            Some(state) => {
+                let elapsed_seconds = (now - state.updated_at).num_milliseconds().max(0) as f64 / 1000.0;
+                let allowed = state.price.abs() * self.max_change_rate * elapsed_seconds;
+                let delta = (live - state.price).clamp(-allowed, allowed);
+                state.price + delta
+            }
+        };
+
+        self.states.insert(symbol.to_string(), StableState { price: stable, updated_at: now });
+        stable
+    }
+
+    pub fn stable_price(&self, symbol: &str) -> Option<f64> {
+        self.states.get(symbol).map(|state| state.price)
+    }
+
+    /// The conservative price for valuing an asset: the lesser of `live`
+    /// and the tracked stable price, so an upward spike can't inflate its
+    /// value. Falls back to `live` for a symbol with no stable price yet.
+    pub fn conservative_asset_price(&self, symbol: &str, live: f64) -> f64 {
+        self.stable_price(symbol).map_or(live, |stable| live.min(stable))
+    }
+
+    /// The conservative price for a liability or a drop/liquidation
+    /// threshold: the greater of `live` and the tracked stable price, so a
+    /// downward spike can't understate exposure. Falls back to `live` for a
+    /// symbol with no stable price yet.
+    pub fn conservative_liability_price(&self, symbol: &str, live: f64) -> f64 {
+        self.stable_price(symbol).map_or(live, |stable| live.max(stable))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_update_seeds_first_observation_without_smoothing() {
+        let mut model = StablePriceModel::new(0.1);
+        let now = Utc::now();
+
+        assert_eq!(model.update("AAPL", 150.0, now), 150.0);
+    }
+
+    #[test]
+    fn test_update_clamps_move_toward_live_by_elapsed_rate() {
+        let mut model = StablePriceModel::new(0.1); // 10%/second
+        let t0 = Utc::now();
+        model.seed("AAPL", 100.0, t0);
+
+        // 1 second later, a spike to 200 can only move the stable price by
+        // 10% of 100.0 = 10.0, not all the way to the live price.
+        let stable = model.update("AAPL", 200.0, t0 + Duration::seconds(1));
+
+        assert_eq!(stable, 110.0);
+    }
+
+    #[test]
+    fn test_update_does_not_overshoot_when_allowed_move_exceeds_gap() {
+        let mut model = StablePriceModel::new(0.5); // 50%/second
+        let t0 = Utc::now();
+        model.seed("AAPL", 100.0, t0);
+
+        // Allowed move is 50.0, but the live price is only 5.0 away.
+        let stable = model.update("AAPL", 105.0, t0 + Duration::seconds(1));
+
+        assert_eq!(stable, 105.0);
+    }
+
+    #[test]
+    fn test_conservative_asset_price_takes_the_lesser_of_live_and_stable() {
+        let mut model = StablePriceModel::new(0.1);
+        model.seed("AAPL", 100.0, Utc::now());
+
+        assert_eq!(model.conservative_asset_price("AAPL", 120.0), 100.0);
+        assert_eq!(model.conservative_asset_price("AAPL", 90.0), 90.0);
+    }
+
+    #[test]
+    fn test_conservative_liability_price_takes_the_greater_of_live_and_stable() {
+        let mut model = StablePriceModel::new(0.1);
+        model.seed("AAPL", 100.0, Utc::now());
+
+        assert_eq!(model.conservative_liability_price("AAPL", 120.0), 120.0);
+        assert_eq!(model.conservative_liability_price("AAPL", 90.0), 100.0);
+    }
+
+    #[test]
+    fn test_conservative_prices_fall_back_to_live_when_unseeded() {
+        let model = StablePriceModel::new(0.1);
+
+        assert_eq!(model.conservative_asset_price("AAPL", 120.0), 120.0);
+        assert_eq!(model.conservative_liability_price("AAPL", 120.0), 120.0);
+    }
+}
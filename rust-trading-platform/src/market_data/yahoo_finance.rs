@@ -1,26 +1,49 @@
 // Yahoo Finance API implementation
 
 use async_trait::async_trait;
-use chrono::{Utc, TimeZone};
+use chrono::{DateTime, Utc, TimeZone};
+use futures::{Stream, SinkExt, StreamExt};
 use reqwest::Client;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{mpsc, Mutex};
 use std::time::{Duration, SystemTime};
 use tokio::time::sleep;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
 use tracing::{debug, error, info, warn};
 
-use crate::data::{MarketData, PricePoint, HistoricalData, TimePeriod};
+use crate::data::{MarketData, PricePoint, HistoricalData, QuoteInterval, TimePeriod};
 use crate::error::{Result, TradingPlatformError, MarketDataError};
-use super::{MarketDataProvider, MarketDataConfig, RateLimitInfo, RetryPolicy};
+use super::{MarketDataProvider, MarketDataConfig, RateLimitInfo, RetryPolicy, RetryTokenBucket, Jitter};
+
+/// Yahoo's streaming quote endpoint. Modeled on how Alpaca's data stream
+/// works: one persistent WebSocket per process, with symbols added or
+/// removed via small JSON control frames instead of one HTTP GET per
+/// symbol per tick.
+const YAHOO_STREAM_URL: &str = "wss://streamer.finance.yahoo.com/";
+
+/// Max symbols per `v7/finance/quote` request. Yahoo doesn't publish a hard
+/// cap; 50 keeps the URL well under common proxy/query-string length limits.
+const BATCH_QUOTE_CHUNK_SIZE: usize = 50;
+
+/// Advertised Yahoo Finance rate limits, enforced by `RateLimiter` and
+/// surfaced in `rate_limit_info()`.
+const YAHOO_REQUESTS_PER_MINUTE: u32 = 60;
+const YAHOO_REQUESTS_PER_HOUR: u32 = 2000;
 
 /// Yahoo Finance API provider
+#[derive(Clone)]
 pub struct YahooFinanceProvider {
     client: Client,
     config: MarketDataConfig,
     retry_policy: RetryPolicy,
     rate_limiter: Arc<Mutex<RateLimiter>>,
+    crumb_manager: Arc<Mutex<CrumbManager>>,
+    cache: Arc<Cache>,
 }
 
 impl YahooFinanceProvider {
@@ -32,25 +55,81 @@ impl YahooFinanceProvider {
             .build()
             .map_err(|e| TradingPlatformError::internal(format!("Failed to create HTTP client: {}", e)))?;
 
+        // Shared across every retry this provider ever issues, so a
+        // degraded Yahoo endpoint throttles retries system-wide instead of
+        // each concurrent caller retrying independently and piling on more
+        // load.
+        let retry_token_bucket = Arc::new(RetryTokenBucket::new(config.retry_token_bucket_capacity));
+
         let retry_policy = RetryPolicy {
             max_retries: config.max_retries,
             base_delay: Duration::from_millis(config.retry_delay_ms),
             max_delay: Duration::from_secs(30),
             backoff_multiplier: 2.0,
+            // Full jitter: many `YahooFinanceProvider` instances can start
+            // retrying at roughly the same moment (a deploy, a shared
+            // outage), and without jitter they'd all retry in lockstep.
+            jitter: Jitter::Full,
+            token_bucket: Some(retry_token_bucket),
+            retry_cost_timeout: config.retry_cost_timeout,
+            retry_cost_throttle: config.retry_cost_throttle,
+            // `default_classify` already treats 404/`SymbolNotFound`,
+            // malformed JSON, and config/serialization errors as
+            // permanent; Yahoo's shape doesn't need anything beyond that.
+            classifier: None,
         };
 
         let rate_limiter = Arc::new(Mutex::new(RateLimiter::new(
-            config.rate_limit_delay_ms,
+            YAHOO_REQUESTS_PER_MINUTE,
+            YAHOO_REQUESTS_PER_HOUR,
         )));
 
+        let crumb_manager = Arc::new(Mutex::new(CrumbManager::new()));
+
+        let cache = Arc::new(Cache::new(
+            Duration::from_secs(config.quote_cache_ttl_seconds),
+            Duration::from_secs(config.historical_cache_ttl_seconds),
+        ));
+
         Ok(Self {
             client,
             config,
             retry_policy,
             rate_limiter,
+            crumb_manager,
+            cache,
         })
     }
 
+    /// Drop every cached quote/historical entry for `symbol`. Call this after
+    /// any action that should invalidate what's currently cached (e.g. a
+    /// manual refresh request).
+    pub fn invalidate(&self, symbol: &str) {
+        self.cache.invalidate(symbol);
+    }
+
+    /// Drop the entire cache.
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
+    /// Append `&crumb=...` to `url` and attach the session cookie header,
+    /// fetching both from Yahoo (once, cached) if we don't already have them.
+    async fn authenticated_request(&self, url: &str) -> Result<reqwest::RequestBuilder> {
+        let (cookie, crumb) = self.crumb_manager.lock().await.get_or_refresh(&self.client).await?;
+        let url = format!("{}&crumb={}", url, crumb);
+        Ok(self.client.get(&url).header(reqwest::header::COOKIE, cookie))
+    }
+
+    /// Build and send a crumb/cookie-authenticated GET. Network failures are
+    /// folded into `ProviderUnavailable`; the caller inspects `status()` for
+    /// the 401 (stale crumb) and other non-2xx cases.
+    async fn send_authenticated(&self, url: &str) -> Result<reqwest::Response> {
+        let request = self.authenticated_request(url).await?;
+        request.send().await
+            .map_err(|_| TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable))
+    }
+
     /// Get quote data from Yahoo Finance
     async fn fetch_quote(&self, symbol: &str) -> Result<YahooQuoteResponse> {
         {
@@ -67,19 +146,28 @@ impl YahooFinanceProvider {
         debug!("Fetching quote for symbol: {} from URL: {}", symbol, url);
 
         let response = self.retry_policy.execute_with_retry(|| {
-            let client = &self.client;
             let url = url.clone();
             async move {
-                let response = client.get(&url).send().await
-                    .map_err(|e| TradingPlatformError::MarketData(
-                        MarketDataError::ProviderUnavailable
-                    ))?;
+                let mut response = self.send_authenticated(&url).await?;
+
+                // A cached crumb/cookie can go stale mid-session; give it
+                // exactly one refresh-and-retry before treating it like any
+                // other failure.
+                if response.status().as_u16() == 401 {
+                    warn!("Yahoo crumb rejected for {}, refreshing and retrying once", symbol);
+                    self.crumb_manager.lock().await.invalidate();
+                    response = self.send_authenticated(&url).await?;
+                }
 
                 if !response.status().is_success() {
                     let status = response.status();
+                    if status.as_u16() == 429 {
+                        let retry_after = parse_retry_after(&response);
+                        self.rate_limiter.lock().await.record_retry_after(retry_after);
+                    }
                     let error_text = response.text().await.unwrap_or_default();
                     error!("Yahoo Finance API error: {} - {}", status, error_text);
-                    
+
                     return match status.as_u16() {
                         404 => Err(TradingPlatformError::MarketData(
                             MarketDataError::SymbolNotFound(symbol.to_string())
@@ -107,6 +195,104 @@ impl YahooFinanceProvider {
         Ok(quote_response)
     }
 
+    /// Fetch quotes for up to `BATCH_QUOTE_CHUNK_SIZE` symbols in one request
+    /// via `v7/finance/quote?symbols=...`. Symbols Yahoo didn't return a
+    /// result for (delisted, typo'd, etc.) are simply absent from the map;
+    /// the caller decides whether to retry them individually.
+    async fn fetch_quotes_chunk(&self, symbols: &[String]) -> Result<HashMap<String, MarketData>> {
+        {
+            let mut limiter = self.rate_limiter.lock().await;
+            limiter.wait_if_needed().await;
+        }
+
+        let url = format!(
+            "{}/v7/finance/quote?symbols={}",
+            self.config.base_url,
+            symbols.join(",")
+        );
+
+        debug!("Fetching batch quote for {} symbols from URL: {}", symbols.len(), url);
+
+        let response = self.retry_policy.execute_with_retry(|| {
+            let url = url.clone();
+            async move {
+                let mut response = self.send_authenticated(&url).await?;
+
+                if response.status().as_u16() == 401 {
+                    warn!("Yahoo crumb rejected for batch quote, refreshing and retrying once");
+                    self.crumb_manager.lock().await.invalidate();
+                    response = self.send_authenticated(&url).await?;
+                }
+
+                if !response.status().is_success() {
+                    let status = response.status();
+                    if status.as_u16() == 429 {
+                        let retry_after = parse_retry_after(&response);
+                        self.rate_limiter.lock().await.record_retry_after(retry_after);
+                    }
+                    return match status.as_u16() {
+                        429 => Err(TradingPlatformError::MarketData(
+                            MarketDataError::RateLimitExceeded
+                        )),
+                        _ => Err(TradingPlatformError::MarketData(
+                            MarketDataError::ProviderUnavailable
+                        )),
+                    };
+                }
+
+                Ok(response)
+            }
+        }).await?;
+
+        let batch_response: YahooBatchQuoteResponse = response.json().await
+            .map_err(|e| {
+                error!("Failed to parse Yahoo Finance batch quote response: {}", e);
+                TradingPlatformError::MarketData(MarketDataError::InvalidFormat)
+            })?;
+
+        let mut results = HashMap::new();
+        for quote in &batch_response.quote_response.result {
+            let current_price = match quote.regular_market_price {
+                Some(price) => price,
+                None => continue,
+            };
+
+            let volume = quote.regular_market_volume.unwrap_or(0);
+            let previous_close = quote.regular_market_previous_close.unwrap_or(current_price);
+
+            let mut market_data = MarketData::new(quote.symbol.clone(), current_price, volume);
+            if let (Some(high), Some(low)) = (quote.regular_market_day_high, quote.regular_market_day_low) {
+                market_data = market_data.with_day_range(high, low);
+            }
+            market_data = market_data.with_change(previous_close);
+            if let Some(market_cap) = quote.market_cap {
+                market_data.market_cap = Some(market_cap as u64);
+            }
+
+            results.insert(quote.symbol.clone(), market_data);
+        }
+
+        Ok(results)
+    }
+
+    /// Batch `symbols` through `fetch_quotes_chunk` in groups of
+    /// `BATCH_QUOTE_CHUNK_SIZE`, turning an N-request operation into
+    /// ceil(N/`BATCH_QUOTE_CHUNK_SIZE`) requests. Any symbol missing from a
+    /// chunk's result (Yahoo silently drops unknown symbols rather than
+    /// erroring) is left out of the returned map for the caller to retry.
+    async fn fetch_quotes_batch(&self, symbols: &[String]) -> Result<HashMap<String, MarketData>> {
+        let mut results = HashMap::new();
+
+        for chunk in symbols.chunks(BATCH_QUOTE_CHUNK_SIZE) {
+            match self.fetch_quotes_chunk(chunk).await {
+                Ok(chunk_results) => results.extend(chunk_results),
+                Err(e) => warn!("Batch quote request failed for {:?}: {}", chunk, e),
+            }
+        }
+
+        Ok(results)
+    }
+
     /// Convert Yahoo Finance response to MarketData
     fn convert_to_market_data(&self, symbol: &str, response: &YahooQuoteResponse) -> Result<MarketData> {
         let chart = response.chart.result.first()
@@ -177,16 +363,22 @@ impl YahooFinanceProvider {
         debug!("Fetching historical data for symbol: {} from URL: {}", symbol, url);
 
         let response = self.retry_policy.execute_with_retry(|| {
-            let client = &self.client;
             let url = url.clone();
             async move {
-                let response = client.get(&url).send().await
-                    .map_err(|e| TradingPlatformError::MarketData(
-                        MarketDataError::ProviderUnavailable
-                    ))?;
+                let mut response = self.send_authenticated(&url).await?;
+
+                if response.status().as_u16() == 401 {
+                    warn!("Yahoo crumb rejected for {}, refreshing and retrying once", symbol);
+                    self.crumb_manager.lock().await.invalidate();
+                    response = self.send_authenticated(&url).await?;
+                }
 
                 if !response.status().is_success() {
                     let status = response.status();
+                    if status.as_u16() == 429 {
+                        let retry_after = parse_retry_after(&response);
+                        self.rate_limiter.lock().await.record_retry_after(retry_after);
+                    }
                     return match status.as_u16() {
                         404 => Err(TradingPlatformError::MarketData(
                             MarketDataError::SymbolNotFound(symbol.to_string())
@@ -215,7 +407,7 @@ impl YahooFinanceProvider {
     }
 
     /// Convert Yahoo Finance historical response to HistoricalData
-    fn convert_to_historical_data(&self, symbol: &str, response: &YahooHistoricalResponse, period: &TimePeriod) -> Result<HistoricalData> {
+    fn convert_to_historical_data(&self, symbol: &str, response: &YahooHistoricalResponse, period: &TimePeriod, adjust: AdjustType) -> Result<HistoricalData> {
         let chart = response.chart.result.first()
             .ok_or_else(|| TradingPlatformError::MarketData(
                 MarketDataError::NoDataAvailable(symbol.to_string())
@@ -232,6 +424,7 @@ impl YahooFinanceProvider {
         let lows = &indicators.low;
         let closes = &indicators.close;
         let volumes = &indicators.volume;
+        let adjcloses = chart.indicators.adjclose.first().map(|a| &a.adjclose);
 
         let mut historical_data = HistoricalData::new(symbol.to_string(), *period);
 
@@ -246,6 +439,22 @@ impl YahooFinanceProvider {
                 let datetime = Utc.timestamp_opt(timestamp as i64, 0).single()
                     .ok_or_else(|| TradingPlatformError::internal("Invalid timestamp"))?;
 
+                // Forward-adjust the bar using `adjclose/close`; a null/missing
+                // adjclose for this bar (or no adjclose series at all) falls
+                // back to the raw close, leaving the bar unadjusted.
+                let adjclose = adjust
+                    .is_forward_adjust()
+                    .then(|| adjcloses.and_then(|series| series.get(i)).and_then(|v| v.as_ref()).copied())
+                    .flatten();
+
+                let (open, high, low, close) = match adjclose {
+                    Some(adjclose) if close != 0.0 => {
+                        let ratio = adjclose / close;
+                        (open * ratio, high * ratio, low * ratio, adjclose)
+                    }
+                    _ => (open, high, low, close),
+                };
+
                 if let Ok(price_point) = PricePoint::new(datetime, open, high, low, close, volume) {
                     historical_data.add_price_point(price_point);
                 }
@@ -260,39 +469,180 @@ impl YahooFinanceProvider {
 
         Ok(historical_data)
     }
+
+    /// Like the `MarketDataProvider::get_historical_data` trait method, but
+    /// lets the caller request split/dividend-adjusted bars via `adjust`.
+    /// Unadjusted series have spurious gaps on split dates, which throws off
+    /// multi-year backtests.
+    pub async fn get_historical_data_adjusted(&self, symbol: &str, period: TimePeriod, adjust: AdjustType) -> Result<HistoricalData> {
+        if let Some(cached) = self.cache.get_historical(symbol, &period, adjust) {
+            debug!("Serving historical data for {} ({:?}) from cache", symbol, period);
+            return Ok(cached);
+        }
+
+        info!("Getting historical data for symbol: {} (period: {:?}, adjust: {:?})", symbol, period, adjust);
+
+        let response = self.fetch_historical_data(symbol, &period).await?;
+        let historical_data = self.convert_to_historical_data(symbol, &response, &period, adjust)?;
+
+        info!("Successfully retrieved {} historical data points for {}",
+              historical_data.data_points.len(), symbol);
+        self.cache.put_historical(symbol, &period, adjust, historical_data.clone());
+        Ok(historical_data)
+    }
+
+    async fn fetch_latest_quotes(&self, symbol: &str, interval: QuoteInterval) -> Result<YahooHistoricalResponse> {
+        {
+            let mut limiter = self.rate_limiter.lock().await;
+            limiter.wait_if_needed().await;
+        }
+
+        let (range, yahoo_interval) = match interval {
+            QuoteInterval::OneMinute => ("1d", "1m"),
+            QuoteInterval::FiveMinute => ("5d", "5m"),
+            QuoteInterval::FifteenMinute => ("5d", "15m"),
+            QuoteInterval::OneHour => ("1mo", "1h"),
+            QuoteInterval::OneDay => ("3mo", "1d"),
+        };
+
+        let url = format!(
+            "{}/v8/finance/chart/{}?period1=0&period2=9999999999&interval={}&range={}",
+            self.config.base_url,
+            symbol,
+            yahoo_interval,
+            range
+        );
+
+        debug!("Fetching latest {:?} quotes for symbol: {} from URL: {}", interval, symbol, url);
+
+        let response = self.retry_policy.execute_with_retry(|| {
+            let url = url.clone();
+            async move {
+                let mut response = self.send_authenticated(&url).await?;
+
+                if response.status().as_u16() == 401 {
+                    warn!("Yahoo crumb rejected for {}, refreshing and retrying once", symbol);
+                    self.crumb_manager.lock().await.invalidate();
+                    response = self.send_authenticated(&url).await?;
+                }
+
+                if !response.status().is_success() {
+                    let status = response.status();
+                    if status.as_u16() == 429 {
+                        let retry_after = parse_retry_after(&response);
+                        self.rate_limiter.lock().await.record_retry_after(retry_after);
+                    }
+                    return match status.as_u16() {
+                        404 => Err(TradingPlatformError::MarketData(
+                            MarketDataError::SymbolNotFound(symbol.to_string())
+                        )),
+                        429 => Err(TradingPlatformError::MarketData(
+                            MarketDataError::RateLimitExceeded
+                        )),
+                        _ => Err(TradingPlatformError::MarketData(
+                            MarketDataError::ProviderUnavailable
+                        )),
+                    };
+                }
+
+                Ok(response)
+            }
+        }).await?;
+
+        let historical_response: YahooHistoricalResponse = response.json().await
+            .map_err(|e| {
+                error!("Failed to parse Yahoo Finance latest-quotes response: {}", e);
+                TradingPlatformError::MarketData(MarketDataError::InvalidFormat)
+            })?;
+
+        debug!("Successfully fetched latest {:?} quotes for symbol: {}", interval, symbol);
+        Ok(historical_response)
+    }
+
+    /// Like the `MarketDataProvider::get_latest_quotes` trait method, implemented
+    /// against the same chart endpoint as historical data, just with a narrower
+    /// range tuned to return fresh intraday bars at `interval` granularity.
+    async fn fetch_and_convert_latest_quotes(&self, symbol: &str, interval: QuoteInterval) -> Result<HistoricalData> {
+        info!("Getting latest {:?} quotes for symbol: {}", interval, symbol);
+
+        let response = self.fetch_latest_quotes(symbol, interval).await?;
+        let historical_data = self.convert_to_historical_data(symbol, &response, &TimePeriod::OneDay, AdjustType::None)?;
+
+        info!("Successfully retrieved {} latest quote bars for {}", historical_data.data_points.len(), symbol);
+        Ok(historical_data)
+    }
+}
+
+/// Whether `YahooFinanceProvider::get_historical_data_adjusted` should
+/// forward-adjust bars for stock splits and dividends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AdjustType {
+    /// Raw, unadjusted OHLC as Yahoo reports it bar-by-bar. Matches the
+    /// behavior of the `MarketDataProvider::get_historical_data` trait method.
+    #[default]
+    None,
+    /// Scale open/high/low by `adjclose/close` and replace `close` with
+    /// `adjclose`, so split/dividend events don't show up as price gaps.
+    ForwardAdjust,
+}
+
+impl AdjustType {
+    fn is_forward_adjust(self) -> bool {
+        matches!(self, AdjustType::ForwardAdjust)
+    }
 }
 
 #[async_trait]
 impl MarketDataProvider for YahooFinanceProvider {
     async fn get_current_price(&self, symbol: &str) -> Result<MarketData> {
+        if let Some(cached) = self.cache.get_quote(symbol) {
+            debug!("Serving current price for {} from cache", symbol);
+            return Ok(cached);
+        }
+
         info!("Getting current price for symbol: {}", symbol);
-        
+
         let response = self.fetch_quote(symbol).await?;
         let market_data = self.convert_to_market_data(symbol, &response)?;
-        
-        info!("Successfully retrieved current price for {}: ${:.2}", symbol, market_data.price);
+
+        info!("Successfully retrieved current price for {}: ${:.2}", symbol, market_data.price.to_f64());
+        self.cache.put_quote(symbol.to_string(), market_data.clone());
         Ok(market_data)
     }
 
     async fn get_historical_data(&self, symbol: &str, period: TimePeriod) -> Result<HistoricalData> {
-        info!("Getting historical data for symbol: {} (period: {:?})", symbol, period);
-        
-        let response = self.fetch_historical_data(symbol, &period).await?;
-        let historical_data = self.convert_to_historical_data(symbol, &response, &period)?;
-        
-        info!("Successfully retrieved {} historical data points for {}", 
-              historical_data.data_points.len(), symbol);
-        Ok(historical_data)
+        self.get_historical_data_adjusted(symbol, period, AdjustType::None).await
+    }
+
+    async fn get_latest_quotes(&self, symbol: &str, interval: QuoteInterval) -> Result<HistoricalData> {
+        self.fetch_and_convert_latest_quotes(symbol, interval).await
     }
 
     async fn get_multiple_prices(&self, symbols: &[String]) -> Result<HashMap<String, MarketData>> {
         info!("Getting current prices for {} symbols", symbols.len());
-        
-        let mut results = HashMap::new();
-        let mut errors = Vec::new();
 
-        // Process symbols in batches to respect rate limits
+        let mut results: HashMap<String, MarketData> = HashMap::new();
+        let mut uncached: Vec<String> = Vec::new();
         for symbol in symbols {
+            match self.cache.get_quote(symbol) {
+                Some(cached) => { results.insert(symbol.clone(), cached); }
+                None => uncached.push(symbol.clone()),
+            }
+        }
+
+        let batch_results = self.fetch_quotes_batch(&uncached).await?;
+        for (symbol, market_data) in &batch_results {
+            self.cache.put_quote(symbol.clone(), market_data.clone());
+        }
+        results.extend(batch_results);
+
+        let missing: Vec<String> = symbols.iter()
+            .filter(|symbol| !results.contains_key(symbol.as_str()))
+            .cloned()
+            .collect();
+
+        let mut errors = Vec::new();
+        for symbol in &missing {
             match self.get_current_price(symbol).await {
                 Ok(market_data) => {
                     results.insert(symbol.clone(), market_data);
@@ -302,7 +652,7 @@ impl MarketDataProvider for YahooFinanceProvider {
                     errors.push((symbol.clone(), e));
                 }
             }
-            
+
             // Small delay between requests to avoid rate limiting
             sleep(Duration::from_millis(self.config.rate_limit_delay_ms)).await;
         }
@@ -336,45 +686,415 @@ impl MarketDataProvider for YahooFinanceProvider {
     }
 
     fn rate_limit_info(&self) -> RateLimitInfo {
-        RateLimitInfo {
-            requests_per_minute: 60,
-            requests_per_hour: 2000,
-            current_usage: 0, // Would need to track this in a real implementation
-            reset_time: None,
+        match self.rate_limiter.try_lock() {
+            Ok(mut limiter) => RateLimitInfo {
+                requests_per_minute: YAHOO_REQUESTS_PER_MINUTE,
+                requests_per_hour: YAHOO_REQUESTS_PER_HOUR,
+                current_usage: limiter.current_usage(),
+                reset_time: limiter.reset_time(),
+            },
+            // A request is mid-flight and holding the lock; report the
+            // advertised limits with no live usage rather than block.
+            Err(_) => RateLimitInfo {
+                requests_per_minute: YAHOO_REQUESTS_PER_MINUTE,
+                requests_per_hour: YAHOO_REQUESTS_PER_HOUR,
+                current_usage: 0,
+                reset_time: None,
+            },
         }
     }
+
+    /// Subscribe to `YAHOO_STREAM_URL` over a persistent WebSocket. If the
+    /// feed can't be reached (or keeps dropping) after `retry_policy`'s
+    /// reconnect budget is exhausted, falls back to polling
+    /// `get_current_price` so callers still get ticks, just less promptly.
+    async fn subscribe_quotes(&self, symbols: &[String]) -> Result<Pin<Box<dyn Stream<Item = MarketData> + Send>>> {
+        let (tx, rx) = mpsc::channel(64);
+        let provider = self.clone();
+        let symbols = symbols.to_vec();
+
+        tokio::spawn(async move {
+            if Self::stream_quotes(&provider, &symbols, &tx).await.is_err() {
+                warn!("Yahoo streaming unavailable for {:?}, falling back to polling", symbols);
+                Self::poll_quotes(&provider, &symbols, &tx).await;
+            }
+        });
+
+        Ok(Box::pin(ReceiverStream::new(rx)))
+    }
 }
 
-/// Rate limiter to prevent API abuse
+impl YahooFinanceProvider {
+    /// Hold a WebSocket to `YAHOO_STREAM_URL` open, sending a `subscribe`
+    /// control frame for `symbols` on connect and a matching `unsubscribe`
+    /// frame when the receiver goes away. Reconnects (per `retry_policy`)
+    /// whenever the socket drops; gives up and returns `Err` after
+    /// `retry_policy.max_retries + 1` connections in total, where a connect
+    /// that failed and a connection that opened and later dropped count
+    /// alike, so the caller can fall back to polling instead.
+    async fn stream_quotes(provider: &Self, symbols: &[String], tx: &mpsc::Sender<MarketData>) -> Result<()> {
+        for attempt in 0..=provider.retry_policy.max_retries {
+            if tx.is_closed() {
+                return Ok(());
+            }
+
+            if attempt > 0 {
+                sleep(provider.retry_policy.calculate_delay(attempt)).await;
+            }
+
+            let mut ws_stream = match connect_async(YAHOO_STREAM_URL).await {
+                Ok((ws_stream, _response)) => ws_stream,
+                Err(e) => {
+                    warn!("Yahoo streaming connect failed (attempt {}): {}", attempt + 1, e);
+                    continue;
+                }
+            };
+
+            let subscribe_frame = serde_json::json!({ "subscribe": symbols }).to_string();
+            if ws_stream.send(Message::Text(subscribe_frame)).await.is_err() {
+                continue;
+            }
+
+            while let Some(frame) = ws_stream.next().await {
+                let text = match frame {
+                    Ok(Message::Text(text)) => text,
+                    Ok(Message::Close(_)) | Err(_) => break,
+                    Ok(_) => continue,
+                };
+
+                match serde_json::from_str::<YahooStreamTick>(&text) {
+                    Ok(tick) => {
+                        let market_data = MarketData::new(tick.symbol, tick.price, tick.volume);
+                        if tx.send(market_data).await.is_err() {
+                            let unsubscribe_frame = serde_json::json!({ "unsubscribe": symbols }).to_string();
+                            let _ = ws_stream.send(Message::Text(unsubscribe_frame)).await;
+                            return Ok(());
+                        }
+                    }
+                    Err(e) => warn!("Failed to decode Yahoo stream tick: {}", e),
+                }
+            }
+
+            warn!("Yahoo streaming connection for {:?} dropped, reconnecting", symbols);
+        }
+
+        Err(TradingPlatformError::internal(
+            "Yahoo streaming unavailable after exhausting reconnect attempts",
+        ))
+    }
+
+    /// Poll `get_current_price` every `rate_limit_delay_ms` (at least once a
+    /// second apart) and
+    /// republish ticks as a stream. Used when the WebSocket feed in
+    /// `stream_quotes` is unreachable.
+    async fn poll_quotes(provider: &Self, symbols: &[String], tx: &mpsc::Sender<MarketData>) {
+        let tick_interval = Duration::from_millis(provider.config.rate_limit_delay_ms.max(1000));
+        let mut interval = tokio::time::interval(tick_interval);
+        loop {
+            interval.tick().await;
+
+            for symbol in symbols {
+                match provider.get_current_price(symbol).await {
+                    Ok(market_data) => {
+                        if tx.send(market_data).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => warn!("subscribe_quotes polling failed for {}: {}", symbol, e),
+                }
+            }
+        }
+    }
+}
+
+/// A single cached value with its own expiry, used for both the quote and
+/// historical-data caches.
+struct CacheEntry<T> {
+    value: T,
+    expires_at: SystemTime,
+}
+
+impl<T> CacheEntry<T> {
+    fn is_expired(&self, now: SystemTime) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// In-memory cache fronting `get_current_price`/`get_historical_data`,
+/// modeled on the Longbridge SDK's per-query-type TTL cache. Quotes get a
+/// short TTL (prices move second to second); historical bars get a long one
+/// (a day-old bar doesn't change). Stale entries are evicted lazily, on the
+/// next lookup that would otherwise have returned them.
+struct Cache {
+    quote_ttl: Duration,
+    historical_ttl: Duration,
+    quotes: std::sync::Mutex<HashMap<String, CacheEntry<MarketData>>>,
+    historical: std::sync::Mutex<HashMap<(String, String, AdjustType), CacheEntry<HistoricalData>>>,
+}
+
+impl Cache {
+    fn new(quote_ttl: Duration, historical_ttl: Duration) -> Self {
+        Self {
+            quote_ttl,
+            historical_ttl,
+            quotes: std::sync::Mutex::new(HashMap::new()),
+            historical: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get_quote(&self, symbol: &str) -> Option<MarketData> {
+        let mut quotes = self.quotes.lock().unwrap();
+        let now = SystemTime::now();
+        match quotes.get(symbol) {
+            Some(entry) if !entry.is_expired(now) => Some(entry.value.clone()),
+            Some(_) => {
+                quotes.remove(symbol);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn put_quote(&self, symbol: String, value: MarketData) {
+        let mut quotes = self.quotes.lock().unwrap();
+        quotes.insert(symbol, CacheEntry { value, expires_at: SystemTime::now() + self.quote_ttl });
+    }
+
+    fn historical_key(symbol: &str, period: &TimePeriod, adjust: AdjustType) -> (String, String, AdjustType) {
+        (symbol.to_string(), format!("{:?}", period), adjust)
+    }
+
+    fn get_historical(&self, symbol: &str, period: &TimePeriod, adjust: AdjustType) -> Option<HistoricalData> {
+        let key = Self::historical_key(symbol, period, adjust);
+        let mut historical = self.historical.lock().unwrap();
+        let now = SystemTime::now();
+        match historical.get(&key) {
+            Some(entry) if !entry.is_expired(now) => Some(entry.value.clone()),
+            Some(_) => {
+                historical.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn put_historical(&self, symbol: &str, period: &TimePeriod, adjust: AdjustType, value: HistoricalData) {
+        let key = Self::historical_key(symbol, period, adjust);
+        let mut historical = self.historical.lock().unwrap();
+        historical.insert(key, CacheEntry { value, expires_at: SystemTime::now() + self.historical_ttl });
+    }
+
+    /// Drop every cached entry for `symbol`, across both the quote and
+    /// historical caches.
+    fn invalidate(&self, symbol: &str) {
+        self.quotes.lock().unwrap().remove(symbol);
+        self.historical.lock().unwrap().retain(|(sym, _, _), _| sym != symbol);
+    }
+
+    fn clear(&self) {
+        self.quotes.lock().unwrap().clear();
+        self.historical.lock().unwrap().clear();
+    }
+}
+
+/// Parse a `Retry-After` header (seconds form) off a 429 response, falling
+/// back to a conservative default when Yahoo omits it.
+fn parse_retry_after(response: &reqwest::Response) -> Duration {
+    const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+    response.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RETRY_AFTER)
+}
+
+/// Dual-window token-bucket rate limiter enforcing both a per-minute and a
+/// per-hour cap, matching the two limits Yahoo actually advertises. Keeps a
+/// single sliding window of recent request timestamps (pruned to an hour)
+/// and derives the minute count by filtering within it, so only one deque
+/// needs maintaining for both windows.
 struct RateLimiter {
-    delay_ms: u64,
-    last_request: Option<SystemTime>,
+    requests_per_minute: u32,
+    requests_per_hour: u32,
+    timestamps: VecDeque<SystemTime>,
+    /// Set from a `Retry-After` on a 429; `wait_if_needed` sleeps past this
+    /// before even looking at the window, so a server-side rejection
+    /// throttles every subsequent caller, not just the one that got it.
+    blocked_until: Option<SystemTime>,
 }
 
 impl RateLimiter {
-    fn new(delay_ms: u64) -> Self {
+    fn new(requests_per_minute: u32, requests_per_hour: u32) -> Self {
         Self {
-            delay_ms,
-            last_request: None,
+            requests_per_minute,
+            requests_per_hour,
+            timestamps: VecDeque::new(),
+            blocked_until: None,
+        }
+    }
+
+    fn prune(&mut self, now: SystemTime) {
+        while let Some(&front) = self.timestamps.front() {
+            if now.duration_since(front).unwrap_or_default() > Duration::from_secs(3600) {
+                self.timestamps.pop_front();
+            } else {
+                break;
+            }
         }
     }
 
+    /// Feed a server-reported `Retry-After` into the bucket: every caller
+    /// blocks until it elapses, regardless of how much headroom the window
+    /// thinks is free.
+    fn record_retry_after(&mut self, retry_after: Duration) {
+        let until = SystemTime::now() + retry_after;
+        self.blocked_until = Some(self.blocked_until.map_or(until, |existing| existing.max(until)));
+    }
+
     async fn wait_if_needed(&mut self) {
-        if let Some(last_request) = self.last_request {
-            let elapsed = last_request.elapsed().unwrap_or(Duration::from_secs(0));
-            let required_delay = Duration::from_millis(self.delay_ms);
-            
-            if elapsed < required_delay {
-                let wait_time = required_delay - elapsed;
-                sleep(wait_time).await;
+        if let Some(blocked_until) = self.blocked_until.take() {
+            if let Ok(remaining) = blocked_until.duration_since(SystemTime::now()) {
+                sleep(remaining).await;
             }
         }
-        
-        self.last_request = Some(SystemTime::now());
+
+        loop {
+            let now = SystemTime::now();
+            self.prune(now);
+
+            let minute_ago = now.checked_sub(Duration::from_secs(60)).unwrap_or(now);
+            let minute_count = self.timestamps.iter().filter(|&&t| t >= minute_ago).count() as u32;
+
+            if minute_count >= self.requests_per_minute {
+                let oldest_in_minute = self.timestamps.iter().find(|&&t| t >= minute_ago).copied().unwrap_or(now);
+                let wait_until = oldest_in_minute + Duration::from_secs(60);
+                sleep(wait_until.duration_since(now).unwrap_or_default()).await;
+                continue;
+            }
+
+            if self.timestamps.len() as u32 >= self.requests_per_hour {
+                let oldest = *self.timestamps.front().unwrap();
+                let wait_until = oldest + Duration::from_secs(3600);
+                sleep(wait_until.duration_since(now).unwrap_or_default()).await;
+                continue;
+            }
+
+            break;
+        }
+
+        self.timestamps.push_back(SystemTime::now());
+    }
+
+    /// Requests counted against the per-minute window right now.
+    fn current_usage(&mut self) -> u32 {
+        let now = SystemTime::now();
+        self.prune(now);
+        let minute_ago = now.checked_sub(Duration::from_secs(60)).unwrap_or(now);
+        self.timestamps.iter().filter(|&&t| t >= minute_ago).count() as u32
+    }
+
+    /// Earliest moment the oldest tracked request ages out of the hour
+    /// window, freeing up a slot.
+    fn reset_time(&mut self) -> Option<DateTime<Utc>> {
+        let now = SystemTime::now();
+        self.prune(now);
+        self.timestamps.front().map(|&t| DateTime::<Utc>::from(t + Duration::from_secs(3600)))
+    }
+}
+
+/// Yahoo's chart/quote endpoints reject requests that don't carry a valid
+/// session cookie plus a matching `crumb` token. Caches both, fetched via a
+/// GET to the Yahoo homepage (to capture the `A3`/consent cookie) followed by
+/// a call to `v1/test/getcrumb`, and re-fetches once the cached pair expires
+/// or is explicitly invalidated after a 401.
+struct CrumbManager {
+    cookie: Option<String>,
+    crumb: Option<String>,
+    expires_at: Option<SystemTime>,
+}
+
+const CRUMB_TTL: Duration = Duration::from_secs(3600);
+
+impl CrumbManager {
+    fn new() -> Self {
+        Self {
+            cookie: None,
+            crumb: None,
+            expires_at: None,
+        }
+    }
+
+    /// Drop the cached cookie/crumb so the next `get_or_refresh` fetches a
+    /// fresh pair. Called after a 401 from a chart/quote request.
+    fn invalidate(&mut self) {
+        self.cookie = None;
+        self.crumb = None;
+        self.expires_at = None;
+    }
+
+    async fn get_or_refresh(&mut self, client: &Client) -> Result<(String, String)> {
+        let is_expired = self.expires_at.map(|exp| SystemTime::now() >= exp).unwrap_or(true);
+        if self.cookie.is_none() || self.crumb.is_none() || is_expired {
+            self.refresh(client).await?;
+        }
+
+        match (&self.cookie, &self.crumb) {
+            (Some(cookie), Some(crumb)) => Ok((cookie.clone(), crumb.clone())),
+            _ => Err(TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable)),
+        }
+    }
+
+    async fn refresh(&mut self, client: &Client) -> Result<()> {
+        debug!("Refreshing Yahoo session cookie and crumb");
+
+        let homepage_response = client.get("https://fc.yahoo.com")
+            .send().await
+            .map_err(|_| TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable))?;
+
+        let cookie = homepage_response.headers()
+            .get_all(reqwest::header::SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .map(|value| value.split(';').next().unwrap_or_default().to_string())
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if cookie.is_empty() {
+            error!("Yahoo homepage returned no session cookie");
+            return Err(TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable));
+        }
+
+        let crumb = client.get("https://query1.finance.yahoo.com/v1/test/getcrumb")
+            .header(reqwest::header::COOKIE, &cookie)
+            .send().await
+            .map_err(|_| TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable))?
+            .text().await
+            .map_err(|_| TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable))?;
+
+        if crumb.is_empty() {
+            error!("Yahoo getcrumb endpoint returned an empty crumb");
+            return Err(TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable));
+        }
+
+        self.cookie = Some(cookie);
+        self.crumb = Some(crumb);
+        self.expires_at = Some(SystemTime::now() + CRUMB_TTL);
+        Ok(())
     }
 }
 
 // Yahoo Finance API response structures
+
+/// A single tick frame off `YAHOO_STREAM_URL`.
+#[derive(Debug, Deserialize)]
+struct YahooStreamTick {
+    symbol: String,
+    price: f64,
+    volume: u64,
+}
+
 #[derive(Debug, Deserialize)]
 struct YahooQuoteResponse {
     chart: YahooChart,
@@ -385,6 +1105,30 @@ struct YahooHistoricalResponse {
     chart: YahooHistoricalChart,
 }
 
+/// `v7/finance/quote?symbols=...` response shape.
+#[derive(Debug, Deserialize)]
+struct YahooBatchQuoteResponse {
+    #[serde(rename = "quoteResponse")]
+    quote_response: YahooBatchQuoteBody,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooBatchQuoteBody {
+    result: Vec<YahooBatchQuote>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct YahooBatchQuote {
+    symbol: String,
+    regular_market_price: Option<f64>,
+    regular_market_previous_close: Option<f64>,
+    regular_market_volume: Option<u64>,
+    regular_market_day_high: Option<f64>,
+    regular_market_day_low: Option<f64>,
+    market_cap: Option<f64>,
+}
+
 #[derive(Debug, Deserialize)]
 struct YahooChart {
     result: Vec<YahooQuoteResult>,
@@ -447,6 +1191,15 @@ struct YahooIndicators {
 #[derive(Debug, Deserialize)]
 struct YahooHistoricalIndicators {
     quote: Vec<YahooHistoricalQuote>,
+    #[serde(default)]
+    adjclose: Vec<YahooAdjClose>,
+}
+
+/// Split/dividend-adjusted closes, parallel to `timestamp` the same way
+/// `YahooHistoricalQuote`'s fields are.
+#[derive(Debug, Deserialize)]
+struct YahooAdjClose {
+    adjclose: Vec<Option<f64>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -495,18 +1248,134 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_rate_limiter() {
-        let mut limiter = RateLimiter::new(100); // 100ms delay
-        
+    async fn test_rate_limiter_allows_requests_within_budget() {
+        let mut limiter = RateLimiter::new(5, 100);
+
         let start = SystemTime::now();
-        limiter.wait_if_needed().await; // First call should not wait
-        let first_elapsed = start.elapsed().unwrap();
-        
-        limiter.wait_if_needed().await; // Second call should wait
-        let second_elapsed = start.elapsed().unwrap();
-        
-        // Second call should take at least 100ms more than first
-        assert!(second_elapsed.as_millis() >= first_elapsed.as_millis() + 100);
+        for _ in 0..5 {
+            limiter.wait_if_needed().await;
+        }
+
+        // All 5 requests fit in the per-minute budget, so none of them block.
+        assert!(start.elapsed().unwrap().as_millis() < 500);
+        assert_eq!(limiter.current_usage(), 5);
+    }
+
+    #[test]
+    fn test_rate_limiter_record_retry_after_blocks_next_wait() {
+        let mut limiter = RateLimiter::new(60, 2000);
+        limiter.record_retry_after(Duration::from_secs(30));
+        assert!(limiter.blocked_until.is_some());
+    }
+
+    fn sample_historical_response() -> YahooHistoricalResponse {
+        YahooHistoricalResponse {
+            chart: YahooHistoricalChart {
+                result: vec![YahooHistoricalResult {
+                    meta: serde_json::from_value(serde_json::json!({
+                        "currency": "USD",
+                        "symbol": "AAPL",
+                        "exchangeName": "NMS",
+                        "instrumentType": "EQUITY",
+                        "timezone": "EST",
+                        "exchangeTimezoneName": "America/New_York",
+                        "dataGranularity": "1d",
+                        "range": "5d",
+                        "validRanges": ["5d"],
+                    })).unwrap(),
+                    timestamp: vec![1_700_000_000, 1_700_086_400],
+                    indicators: YahooHistoricalIndicators {
+                        quote: vec![YahooHistoricalQuote {
+                            open: vec![Some(100.0), Some(100.0)],
+                            high: vec![Some(110.0), Some(110.0)],
+                            low: vec![Some(90.0), Some(90.0)],
+                            close: vec![Some(100.0), Some(100.0)],
+                            volume: vec![Some(1_000), Some(2_000)],
+                        }],
+                        adjclose: vec![YahooAdjClose {
+                            // First bar unadjusted; second simulates a 2:1
+                            // split day (adjclose is half of the raw close).
+                            adjclose: vec![Some(100.0), Some(50.0)],
+                        }],
+                    },
+                }],
+                error: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_convert_to_historical_data_unadjusted_ignores_adjclose() {
+        let provider = YahooFinanceProvider::new(MarketDataConfig::default()).unwrap();
+        let response = sample_historical_response();
+
+        let historical_data = provider
+            .convert_to_historical_data("AAPL", &response, &TimePeriod::OneWeek, AdjustType::None)
+            .unwrap();
+
+        assert_eq!(historical_data.data_points[0].close.to_f64(), 100.0);
+        assert_eq!(historical_data.data_points[0].open.to_f64(), 100.0);
+    }
+
+    #[test]
+    fn test_convert_to_historical_data_forward_adjust_scales_ohlc() {
+        let provider = YahooFinanceProvider::new(MarketDataConfig::default()).unwrap();
+        let response = sample_historical_response();
+
+        let historical_data = provider
+            .convert_to_historical_data("AAPL", &response, &TimePeriod::OneWeek, AdjustType::ForwardAdjust)
+            .unwrap();
+
+        // First bar is unadjusted (ratio 1.0); the second simulates a 2:1
+        // split, so close halves and open/high/low scale by the same ratio.
+        assert_eq!(historical_data.data_points[0].close.to_f64(), 100.0);
+        assert_eq!(historical_data.data_points[1].close.to_f64(), 50.0);
+        assert_eq!(historical_data.data_points[1].open.to_f64(), 50.0);
+        assert_eq!(historical_data.data_points[1].high.to_f64(), 55.0);
+        assert_eq!(historical_data.data_points[1].low.to_f64(), 45.0);
+    }
+
+    fn sample_market_data(symbol: &str) -> MarketData {
+        MarketData::new(symbol.to_string(), 100.0, 1_000)
+    }
+
+    #[test]
+    fn test_cache_quote_expires_after_ttl() {
+        let cache = Cache::new(Duration::from_millis(0), Duration::from_secs(3600));
+        cache.put_quote("AAPL".to_string(), sample_market_data("AAPL"));
+
+        // TTL of zero means the entry is already stale the moment it's read.
+        assert!(cache.get_quote("AAPL").is_none());
+    }
+
+    #[test]
+    fn test_cache_quote_hit_within_ttl() {
+        let cache = Cache::new(Duration::from_secs(60), Duration::from_secs(3600));
+        cache.put_quote("AAPL".to_string(), sample_market_data("AAPL"));
+
+        assert_eq!(cache.get_quote("AAPL").unwrap().symbol, "AAPL");
+    }
+
+    #[test]
+    fn test_cache_invalidate_clears_only_that_symbol() {
+        let cache = Cache::new(Duration::from_secs(60), Duration::from_secs(3600));
+        cache.put_quote("AAPL".to_string(), sample_market_data("AAPL"));
+        cache.put_quote("MSFT".to_string(), sample_market_data("MSFT"));
+
+        cache.invalidate("AAPL");
+
+        assert!(cache.get_quote("AAPL").is_none());
+        assert!(cache.get_quote("MSFT").is_some());
+    }
+
+    #[test]
+    fn test_cache_clear_drops_everything() {
+        let cache = Cache::new(Duration::from_secs(60), Duration::from_secs(3600));
+        cache.put_quote("AAPL".to_string(), sample_market_data("AAPL"));
+
+        cache.clear();
+
+        assert!(cache.get_quote("AAPL").is_none());
     }
 
     // Integration tests would go here, but they require network access
@@ -0,0 +1,272 @@
+// Live Yahoo Finance market data provider backed by the `yahoo_finance_api` crate.
+//
+// Unlike `YahooFinanceProvider` (which talks to Yahoo's chart endpoint directly
+// over `reqwest`), this provider delegates the HTTP/JSON handling to
+// `yahoo_finance_api::YahooConnector` and focuses on mapping its responses onto
+// our own `MarketData`/`PricePoint` types.
+
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use futures::Stream;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, error, warn};
+use yahoo_finance_api as yahoo;
+
+use crate::data::{HistoricalData, MarketData, PricePoint, QuoteInterval, TimePeriod};
+use crate::error::{MarketDataError, Result, TradingPlatformError};
+use super::{MarketDataProvider, RateLimitInfo};
+
+/// Market data provider that fetches live quotes via the `yahoo_finance_api` crate.
+#[derive(Clone)]
+pub struct YahooMarketDataProvider {
+    connector: Arc<yahoo::YahooConnector>,
+}
+
+impl YahooMarketDataProvider {
+    /// Create a new live Yahoo Finance provider.
+    pub fn new() -> Result<Self> {
+        let connector = yahoo::YahooConnector::new().map_err(|e| {
+            TradingPlatformError::internal(format!("Failed to create Yahoo connector: {}", e))
+        })?;
+
+        Ok(Self {
+            connector: Arc::new(connector),
+        })
+    }
+
+    /// Translate a `QuoteInterval` into Yahoo's `(range, interval)` query
+    /// parameters, picking just enough lookback to return fresh bars at that
+    /// granularity.
+    fn quote_interval_to_range_interval(interval: QuoteInterval) -> (&'static str, &'static str) {
+        match interval {
+            QuoteInterval::OneMinute => ("1d", "1m"),
+            QuoteInterval::FiveMinute => ("5d", "5m"),
+            QuoteInterval::FifteenMinute => ("5d", "15m"),
+            QuoteInterval::OneHour => ("1mo", "1h"),
+            QuoteInterval::OneDay => ("3mo", "1d"),
+        }
+    }
+
+    /// Translate a `TimePeriod` into Yahoo's `(range, interval)` query parameters.
+    fn period_to_range_interval(period: TimePeriod) -> (&'static str, &'static str) {
+        match period {
+            TimePeriod::OneDay => ("1d", "5m"),
+            TimePeriod::OneWeek => ("5d", "15m"),
+            TimePeriod::OneMonth => ("1mo", "1d"),
+            TimePeriod::ThreeMonths => ("3mo", "1d"),
+            TimePeriod::SixMonths => ("6mo", "1d"),
+            TimePeriod::OneYear => ("1y", "1d"),
+            TimePeriod::TwoYears => ("2y", "1wk"),
+            TimePeriod::FiveYears => ("5y", "1mo"),
+            TimePeriod::Custom { days } if days <= 7 => ("5d", "1h"),
+            TimePeriod::Custom { days } if days <= 30 => ("1mo", "1d"),
+            TimePeriod::Custom { .. } => ("1y", "1d"),
+        }
+    }
+
+    /// Map a `yahoo_finance_api` error onto one of our `MarketDataError` variants.
+    fn map_error(symbol: &str, error: yahoo::YahooError) -> TradingPlatformError {
+        match error {
+            yahoo::YahooError::NoResult | yahoo::YahooError::EmptyDataSet => {
+                TradingPlatformError::MarketData(MarketDataError::SymbolNotFound(symbol.to_string()))
+            }
+            yahoo::YahooError::DeserializeFailed(msg) => {
+                error!("Failed to parse Yahoo Finance response for {}: {}", symbol, msg);
+                TradingPlatformError::MarketData(MarketDataError::InvalidFormat)
+            }
+            other => {
+                error!("Yahoo Finance API error for {}: {}", symbol, other);
+                TradingPlatformError::MarketData(MarketDataError::ProviderUnavailable)
+            }
+        }
+    }
+
+    async fn fetch_market_data(&self, symbol: &str) -> Result<MarketData> {
+        let response = self
+            .connector
+            .get_latest_quotes(symbol, "1d")
+            .await
+            .map_err(|e| Self::map_error(symbol, e))?;
+
+        let quote = response
+            .last_quote()
+            .map_err(|e| Self::map_error(symbol, e))?;
+
+        let metadata = response
+            .metadata()
+            .map_err(|e| Self::map_error(symbol, e))?;
+
+        let previous_close = metadata.chart_previous_close;
+        let volume = quote.volume;
+
+        let mut market_data = MarketData::new(symbol.to_string(), quote.close, volume);
+        market_data = market_data.with_day_range(quote.high, quote.low);
+        market_data = market_data.with_change(previous_close);
+
+        Ok(market_data)
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for YahooMarketDataProvider {
+    async fn get_current_price(&self, symbol: &str) -> Result<MarketData> {
+        debug!("Fetching live quote for {} via yahoo_finance_api", symbol);
+        self.fetch_market_data(symbol).await
+    }
+
+    async fn get_historical_data(&self, symbol: &str, period: TimePeriod) -> Result<HistoricalData> {
+        let (range, interval) = Self::period_to_range_interval(period);
+
+        let response = self
+            .connector
+            .get_quote_range(symbol, interval, range)
+            .await
+            .map_err(|e| Self::map_error(symbol, e))?;
+
+        let quotes = response
+            .quotes()
+            .map_err(|e| Self::map_error(symbol, e))?;
+
+        let mut historical_data = HistoricalData::new(symbol.to_string(), period);
+
+        for quote in quotes {
+            let timestamp = Utc.timestamp_opt(quote.timestamp as i64, 0).single();
+            let timestamp = match timestamp {
+                Some(ts) => ts,
+                None => continue,
+            };
+
+            if let Ok(price_point) = PricePoint::new(
+                timestamp,
+                quote.open,
+                quote.high,
+                quote.low,
+                quote.close,
+                quote.volume,
+            ) {
+                historical_data.add_price_point(price_point.with_adjusted_close(quote.adjclose));
+            }
+        }
+
+        if historical_data.data_points.is_empty() {
+            return Err(TradingPlatformError::MarketData(
+                MarketDataError::InsufficientHistoricalData(symbol.to_string()),
+            ));
+        }
+
+        Ok(historical_data)
+    }
+
+    async fn get_latest_quotes(&self, symbol: &str, interval: QuoteInterval) -> Result<HistoricalData> {
+        let (range, yahoo_interval) = Self::quote_interval_to_range_interval(interval);
+
+        let response = self
+            .connector
+            .get_quote_range(symbol, yahoo_interval, range)
+            .await
+            .map_err(|e| Self::map_error(symbol, e))?;
+
+        let quotes = response
+            .quotes()
+            .map_err(|e| Self::map_error(symbol, e))?;
+
+        let mut historical_data = HistoricalData::new(symbol.to_string(), TimePeriod::OneDay);
+
+        for quote in quotes {
+            let timestamp = Utc.timestamp_opt(quote.timestamp as i64, 0).single();
+            let timestamp = match timestamp {
+                Some(ts) => ts,
+                None => continue,
+            };
+
+            if let Ok(price_point) = PricePoint::new(
+                timestamp,
+                quote.open,
+                quote.high,
+                quote.low,
+                quote.close,
+                quote.volume,
+            ) {
+                historical_data.add_price_point(price_point.with_adjusted_close(quote.adjclose));
+            }
+        }
+
+        if historical_data.data_points.is_empty() {
+            return Err(TradingPlatformError::MarketData(
+                MarketDataError::InsufficientHistoricalData(symbol.to_string()),
+            ));
+        }
+
+        Ok(historical_data)
+    }
+
+    async fn get_multiple_prices(&self, symbols: &[String]) -> Result<HashMap<String, MarketData>> {
+        let mut results = HashMap::new();
+        let mut errors = Vec::new();
+
+        for symbol in symbols {
+            match self.fetch_market_data(symbol).await {
+                Ok(market_data) => {
+                    results.insert(symbol.clone(), market_data);
+                }
+                Err(e) => {
+                    warn!("Failed to get live price for symbol {}: {}", symbol, e);
+                    errors.push(e);
+                }
+            }
+        }
+
+        if results.is_empty() && !errors.is_empty() {
+            return Err(errors.into_iter().next().unwrap());
+        }
+
+        Ok(results)
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        self.fetch_market_data("AAPL").await.map(|_| ())
+    }
+
+    fn provider_name(&self) -> &str {
+        "Yahoo Finance (live)"
+    }
+
+    fn rate_limit_info(&self) -> RateLimitInfo {
+        RateLimitInfo {
+            requests_per_minute: 60,
+            requests_per_hour: 2000,
+            current_usage: 0,
+            reset_time: None,
+        }
+    }
+
+    async fn subscribe_quotes(&self, symbols: &[String]) -> Result<Pin<Box<dyn Stream<Item = MarketData> + Send>>> {
+        let (tx, rx) = tokio::sync::mpsc::channel(64);
+        let provider = self.clone();
+        let symbols = symbols.to_vec();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(5));
+            loop {
+                interval.tick().await;
+
+                for symbol in &symbols {
+                    match provider.fetch_market_data(symbol).await {
+                        Ok(market_data) => {
+                            if tx.send(market_data).await.is_err() {
+                                return;
+                            }
+                        }
+                        Err(e) => warn!("subscribe_quotes polling failed for {}: {}", symbol, e),
+                    }
+                }
+            }
+        });
+
+        Ok(Box::pin(ReceiverStream::new(rx)))
+    }
+}
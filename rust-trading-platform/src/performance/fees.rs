@@ -0,0 +1,106 @@
+// Pluggable, composable fee schedules.
+//
+// `CommissionModel` already covers flat/per-share/percentage/tiered
+// schedules as a single serializable enum wired straight into
+// `Portfolio::execute_trade`. `FeeModel` complements it with a trait so
+// ad-hoc combinations -- a per-trade ticket charge plus a percentage
+// brokerage fee plus a platform creator fee, say -- can be summed via
+// `CompositeFee` without growing `CommissionModel` into a combinatorial
+// set of variants.
+
+use crate::error::{Result, TradingPlatformError};
+use crate::performance::{CommissionModel, Money};
+
+/// Prices a trade's commission from its quantity, notional value, and (for
+/// schedules that care) the account's trailing volume. `CommissionModel`
+/// implements this too, so existing schedules compose with `CompositeFee`
+/// alongside the newer fee types.
+pub trait FeeModel: Send + Sync {
+    fn calculate(&self, quantity: f64, trade_value: f64, trailing_volume: f64) -> Result<Money>;
+}
+
+/// A flat amount per trade, regardless of size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlatFee(pub f64);
+
+impl FeeModel for FlatFee {
+    fn calculate(&self, _quantity: f64, _trade_value: f64, _trailing_volume: f64) -> Result<Money> {
+        if !self.0.is_finite() {
+            return Err(TradingPlatformError::internal("flat fee is not finite"));
+        }
+        Ok(Money::from_f64(self.0))
+    }
+}
+
+/// `bps` of the trade's notional value, uncapped. Pair with a `FlatFee` (or
+/// a `max_fee_bps`-bounded caller) inside a `CompositeFee` to build a
+/// schedule with both a base charge and a capped percentage component.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PercentageFee {
+    pub bps: f64,
+}
+
+impl FeeModel for PercentageFee {
+    fn calculate(&self, _quantity: f64, trade_value: f64, _trailing_volume: f64) -> Result<Money> {
+        let raw = trade_value.abs() * self.bps / 10_000.0;
+        if !raw.is_finite() {
+            return Err(TradingPlatformError::internal("percentage fee did not produce a finite amount"));
+        }
+        Ok(Money::from_f64(raw))
+    }
+}
+
+/// Sums several fee components, e.g. a flat ticket charge plus a
+/// percentage brokerage fee plus a platform creator fee.
+#[derive(Default)]
+pub struct CompositeFee(pub Vec<Box<dyn FeeModel>>);
+
+impl FeeModel for CompositeFee {
+    fn calculate(&self, quantity: f64, trade_value: f64, trailing_volume: f64) -> Result<Money> {
+        let mut total = Money::ZERO;
+        for component in &self.0 {
+            total = total.checked_add(component.calculate(quantity, trade_value, trailing_volume)?)?;
+        }
+        Ok(total)
+    }
+}
+
+impl FeeModel for CommissionModel {
+    fn calculate(&self, quantity: f64, trade_value: f64, trailing_volume: f64) -> Result<Money> {
+        self.calculate(quantity, trade_value, trailing_volume)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_fee_ignores_trade_size() {
+        let fee = FlatFee(2.5);
+        assert_eq!(fee.calculate(1000.0, 50_000.0, 0.0).unwrap().to_f64(), 2.5);
+    }
+
+    #[test]
+    fn test_percentage_fee_scales_with_notional() {
+        let fee = PercentageFee { bps: 10.0 };
+        assert_eq!(fee.calculate(10.0, 1_000.0, 0.0).unwrap().to_f64(), 1.0);
+    }
+
+    #[test]
+    fn test_composite_fee_sums_components() {
+        let fee = CompositeFee(vec![
+            Box::new(FlatFee(1.0)),
+            Box::new(PercentageFee { bps: 10.0 }),
+        ]);
+
+        let total = fee.calculate(10.0, 1_000.0, 0.0).unwrap();
+        assert_eq!(total.to_f64(), 2.0); // 1.0 flat + 1.0 (10bps of 1000)
+    }
+
+    #[test]
+    fn test_commission_model_implements_fee_model() {
+        let fee: Box<dyn FeeModel> = Box::new(CommissionModel::Fixed(3.0));
+        assert_eq!(fee.calculate(1.0, 100.0, 0.0).unwrap().to_f64(), 3.0);
+    }
+}
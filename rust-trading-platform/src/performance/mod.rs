@@ -7,24 +7,30 @@ use uuid::Uuid;
 use crate::strategy::Action;
 use crate::error::{Result, TradingPlatformError};
 
+mod money;
+pub use money::Money;
+
+pub mod fees;
+pub use fees::{FeeModel, FlatFee, PercentageFee, CompositeFee};
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PerformanceMetrics {
     pub strategy_id: String,
-    pub total_return: f64,
+    pub total_return: Money,
     pub total_return_percent: f64,
     pub total_trades: u32,
     pub winning_trades: u32,
     pub losing_trades: u32,
     pub current_position: Position,
-    pub max_drawdown: f64,
+    pub max_drawdown: Money,
     pub max_drawdown_percent: f64,
     pub sharpe_ratio: Option<f64>,
     pub win_rate: f64,
-    pub average_win: f64,
-    pub average_loss: f64,
+    pub average_win: Money,
+    pub average_loss: Money,
     pub profit_factor: f64,
-    pub initial_capital: f64,
-    pub current_capital: f64,
+    pub initial_capital: Money,
+    pub current_capital: Money,
     pub last_updated: DateTime<Utc>,
 }
 
@@ -32,13 +38,56 @@ pub struct PerformanceMetrics {
 pub struct Position {
     pub symbol: String,
     pub shares: f64,
-    pub average_price: f64,
-    pub current_price: f64,
-    pub current_value: f64,
-    pub unrealized_pnl: f64,
+    pub average_price: Money,
+    pub current_price: Money,
+    pub current_value: Money,
+    pub unrealized_pnl: Money,
     pub unrealized_pnl_percent: f64,
-    pub cost_basis: f64,
+    pub cost_basis: Money,
     pub last_updated: DateTime<Utc>,
+    /// Open tax lots, oldest first, as appended by `add_shares`. Consulted by
+    /// `remove_shares` when the caller asks for `Fifo`/`Lifo` cost basis
+    /// instead of the blended `average_price`.
+    pub lots: Vec<Lot>,
+}
+
+/// A single acquisition of shares at a specific price and time, tracked so
+/// `remove_shares` can close positions out in FIFO/LIFO order instead of
+/// always against the blended `average_price`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Lot {
+    pub shares: f64,
+    pub price: Money,
+    pub acquired_at: DateTime<Utc>,
+}
+
+/// Which open lots a `remove_shares` call drew against, and the realized
+/// P&L attributable to each one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClosedLot {
+    pub shares: f64,
+    pub price: Money,
+    pub acquired_at: DateTime<Utc>,
+    pub realized_pnl: Money,
+}
+
+/// The result of closing out shares via `remove_shares`: the total realized
+/// P&L plus a per-lot breakdown for tax reporting (short- vs. long-term
+/// holding periods can be derived from each `ClosedLot::acquired_at`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LotDisposal {
+    pub realized_pnl: Money,
+    pub lots_closed: Vec<ClosedLot>,
+}
+
+/// Which open lots `remove_shares` draws against first. `AverageCost` is the
+/// default and matches the platform's original blended-cost behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CostBasisMethod {
+    #[default]
+    AverageCost,
+    Fifo,
+    Lifo,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -48,36 +97,163 @@ pub struct Trade {
     pub symbol: String,
     pub action: Action,
     pub quantity: f64,
-    pub price: f64,
+    pub price: Money,
     pub timestamp: DateTime<Utc>,
     pub explanation: String,
-    pub commission: f64,
-    pub realized_pnl: Option<f64>,
-    pub trade_value: f64,
+    pub commission: Money,
+    pub realized_pnl: Option<Money>,
+    pub trade_value: Money,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Portfolio {
     pub id: String,
     pub strategy_id: String,
-    pub initial_capital: f64,
-    pub current_capital: f64,
+    pub initial_capital: Money,
+    pub current_capital: Money,
     pub positions: HashMap<String, Position>,
     pub trade_history: Vec<Trade>,
     pub performance_snapshots: Vec<PerformanceSnapshot>,
     pub created_at: DateTime<Utc>,
     pub last_updated: DateTime<Utc>,
+    /// How far `current_capital` may go negative to fund a buy or short
+    /// cover; zero (the `new()` default) means no margin borrowing at all.
+    pub margin_limit: Money,
+    /// Equity must stay at or above this fraction of `gross_exposure()`, or
+    /// `check_margin_call` fails. Only meaningful once `margin_limit` > 0.
+    pub maintenance_margin_fraction: f64,
+    /// Daily interest rate charged on borrowed notional (negative cash plus
+    /// short-position notional), applied each `create_snapshot`.
+    pub daily_borrow_rate: f64,
+    /// Total borrow interest accrued so far across all `create_snapshot` calls.
+    pub cumulative_borrow_interest: Money,
+    /// How `execute_trade` prices the commission on every trade it books;
+    /// the `commission` a caller set on the `Trade` itself is overwritten.
+    pub commission_model: CommissionModel,
+    /// Running notional (sum of `trade_value`) fed to `CommissionModel::Tiered`
+    /// as its volume input. Not reset automatically -- call
+    /// `reset_trailing_volume` at whatever cadence the tiers are keyed to
+    /// (e.g. monthly).
+    pub trailing_volume: Money,
+    /// Total commission paid across every trade this portfolio has executed.
+    pub total_commission_paid: Money,
+    /// Hard ceiling, in basis points of trade notional, on the commission
+    /// `commission_model` can charge a single trade. `None` means no
+    /// ceiling -- the model is trusted as configured.
+    pub max_fee_bps: Option<f64>,
+    /// Per-symbol collateral weights for `health`. Symbols not present here
+    /// use `default_risk_weights`.
+    pub risk_weights: HashMap<String, RiskWeights>,
+    /// Collateral weights applied to any symbol without an entry in
+    /// `risk_weights`.
+    pub default_risk_weights: RiskWeights,
+}
+
+/// Broker fee schedules `execute_trade` can price a trade's commission
+/// against, keyed by the trade's quantity, notional value, and (for
+/// `Tiered`) the portfolio's `trailing_volume`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum CommissionModel {
+    /// A flat amount per trade, regardless of size.
+    Fixed(f64),
+    /// A flat amount per share traded.
+    PerShare(f64),
+    /// `bps` of the trade's notional value, clamped to `[min, max]`.
+    Percentage { bps: f64, min: f64, max: f64 },
+    /// `bps` of notional, with the rate selected by the highest
+    /// `min_volume` tier the portfolio's `trailing_volume` has reached.
+    Tiered(Vec<VolumeTier>),
+}
+
+/// One rung of a `CommissionModel::Tiered` schedule: once `trailing_volume`
+/// reaches `min_volume`, trades are charged `rate_bps` of their notional.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct VolumeTier {
+    pub min_volume: f64,
+    pub rate_bps: f64,
+}
+
+impl Default for CommissionModel {
+    fn default() -> Self {
+        CommissionModel::Fixed(0.0)
+    }
+}
+
+/// Per-symbol collateral weights consulted by `Portfolio::health`: how much
+/// of a long position's value counts toward equity (`asset_weight`, < 1.0,
+/// a "haircut") versus how much a short position's notional counts against
+/// it (`liab_weight`, > 1.0). Symbols with no explicit entry in
+/// `Portfolio::risk_weights` fall back to `Portfolio::default_risk_weights`.
+/// Maintenance weights are looser (closer to 1.0) than initial weights,
+/// mirroring how margin systems allow an existing position more room than
+/// they'd require to open it fresh.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct RiskWeights {
+    pub initial_asset_weight: f64,
+    pub initial_liab_weight: f64,
+    pub maintenance_asset_weight: f64,
+    pub maintenance_liab_weight: f64,
+}
+
+impl Default for RiskWeights {
+    fn default() -> Self {
+        RiskWeights {
+            initial_asset_weight: 0.8,
+            initial_liab_weight: 1.2,
+            maintenance_asset_weight: 0.9,
+            maintenance_liab_weight: 1.1,
+        }
+    }
+}
+
+/// Which `RiskWeights` a `Portfolio::health` call should use: `Initial` is
+/// the stricter set an account must clear to open new exposure, while
+/// `Maintenance` is the looser set it must stay above to avoid
+/// `is_liquidatable`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthType {
+    Initial,
+    Maintenance,
+}
+
+impl CommissionModel {
+    /// Price the commission for a trade of `quantity` shares worth
+    /// `trade_value`, given the portfolio's `trailing_volume`.
+    pub fn calculate(&self, quantity: f64, trade_value: f64, trailing_volume: f64) -> Result<Money> {
+        let raw = match self {
+            CommissionModel::Fixed(amount) => *amount,
+            CommissionModel::PerShare(rate) => rate * quantity.abs(),
+            CommissionModel::Percentage { bps, min, max } => {
+                (trade_value.abs() * bps / 10_000.0).clamp(*min, *max)
+            }
+            CommissionModel::Tiered(tiers) => {
+                let rate_bps = tiers
+                    .iter()
+                    .filter(|tier| trailing_volume >= tier.min_volume)
+                    .max_by(|a, b| a.min_volume.total_cmp(&b.min_volume))
+                    .map(|tier| tier.rate_bps)
+                    .unwrap_or(0.0);
+                trade_value.abs() * rate_bps / 10_000.0
+            }
+        };
+
+        if !raw.is_finite() {
+            return Err(TradingPlatformError::internal("commission calculation did not produce a finite amount"));
+        }
+
+        Ok(Money::from_f64(raw))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PerformanceSnapshot {
     pub timestamp: DateTime<Utc>,
-    pub total_value: f64,
-    pub cash_balance: f64,
-    pub positions_value: f64,
-    pub total_return: f64,
+    pub total_value: Money,
+    pub cash_balance: Money,
+    pub positions_value: Money,
+    pub total_return: Money,
     pub daily_return: f64,
-    pub drawdown: f64,
+    pub drawdown: Money,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -93,23 +269,139 @@ pub struct RiskMetrics {
     pub calculated_at: DateTime<Utc>,
 }
 
+impl RiskMetrics {
+    /// Compute every risk field from a strategy's return series against a
+    /// market benchmark's. `portfolio_returns` and `market_returns` must be
+    /// the same length (one return per aligned period) with at least two
+    /// points each, or this returns `Err`.
+    ///
+    /// - Historical VaR: linear-interpolated 5%/1% quantile of the sorted
+    ///   portfolio returns, reported as a positive loss magnitude.
+    /// - Expected shortfall: mean of the returns at or below the 95% VaR cutoff.
+    /// - Volatility: sample standard deviation of `portfolio_returns`.
+    /// - Beta/alpha/correlation are `None` when `Var(market)` is zero, since
+    ///   they're undefined for a benchmark with no variance.
+    pub fn calculate(
+        strategy_id: String,
+        portfolio_returns: &[f64],
+        market_returns: &[f64],
+        risk_free_rate: f64,
+    ) -> Result<Self> {
+        if portfolio_returns.len() != market_returns.len() {
+            return Err(TradingPlatformError::invalid_parameters(
+                "portfolio_returns and market_returns must have the same length",
+            ));
+        }
+        if portfolio_returns.len() < 2 {
+            return Err(TradingPlatformError::invalid_parameters(
+                "need at least two returns to calculate risk metrics",
+            ));
+        }
+
+        let mut sorted_portfolio = portfolio_returns.to_vec();
+        sorted_portfolio.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let var_95 = -quantile(&sorted_portfolio, 0.05);
+        let var_99 = -quantile(&sorted_portfolio, 0.01);
+
+        let cutoff = -var_95;
+        let tail: Vec<f64> = sorted_portfolio.iter().copied().filter(|r| *r <= cutoff).collect();
+        let expected_shortfall = if tail.is_empty() {
+            var_95
+        } else {
+            -(tail.iter().sum::<f64>() / tail.len() as f64)
+        };
+
+        let mean_portfolio = mean(portfolio_returns);
+        let mean_market = mean(market_returns);
+        let volatility = std_dev(portfolio_returns, mean_portfolio);
+        let var_market = variance(market_returns, mean_market);
+        let std_market = var_market.sqrt();
+
+        let (beta, alpha, correlation_to_market) = if var_market > 0.0 {
+            let cov = covariance(portfolio_returns, market_returns, mean_portfolio, mean_market);
+            let beta = cov / var_market;
+            let alpha = mean_portfolio - (risk_free_rate + beta * (mean_market - risk_free_rate));
+            let correlation = if volatility > 0.0 && std_market > 0.0 {
+                Some(cov / (volatility * std_market))
+            } else {
+                None
+            };
+            (Some(beta), Some(alpha), correlation)
+        } else {
+            (None, None, None)
+        };
+
+        Ok(RiskMetrics {
+            strategy_id,
+            value_at_risk_95: var_95,
+            value_at_risk_99: var_99,
+            expected_shortfall,
+            beta,
+            alpha,
+            volatility,
+            correlation_to_market,
+            calculated_at: Utc::now(),
+        })
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn variance(values: &[f64], mean_value: f64) -> f64 {
+    values.iter().map(|v| (v - mean_value).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+}
+
+fn std_dev(values: &[f64], mean_value: f64) -> f64 {
+    variance(values, mean_value).sqrt()
+}
+
+fn covariance(a: &[f64], b: &[f64], mean_a: f64, mean_b: f64) -> f64 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| (x - mean_a) * (y - mean_b))
+        .sum::<f64>()
+        / (a.len() - 1) as f64
+}
+
+/// Value at `quantile` (0..=1) of an ascending-sorted series, linearly
+/// interpolating between the two adjacent ranks when it falls between them.
+fn quantile(sorted: &[f64], quantile: f64) -> f64 {
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+
+    let position = quantile * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    if lower == upper {
+        return sorted[lower];
+    }
+
+    let weight = position - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
 // Implementation methods
 impl PerformanceMetrics {
     pub fn new(strategy_id: String, initial_capital: f64) -> Self {
+        let initial_capital = Money::from_f64(initial_capital);
         PerformanceMetrics {
             strategy_id: strategy_id.clone(),
-            total_return: 0.0,
+            total_return: Money::ZERO,
             total_return_percent: 0.0,
             total_trades: 0,
             winning_trades: 0,
             losing_trades: 0,
             current_position: Position::empty("".to_string()),
-            max_drawdown: 0.0,
+            max_drawdown: Money::ZERO,
             max_drawdown_percent: 0.0,
             sharpe_ratio: None,
             win_rate: 0.0,
-            average_win: 0.0,
-            average_loss: 0.0,
+            average_win: Money::ZERO,
+            average_loss: Money::ZERO,
             profit_factor: 0.0,
             initial_capital,
             current_capital: initial_capital,
@@ -117,37 +409,45 @@ impl PerformanceMetrics {
         }
     }
 
-    pub fn update_from_trades(&mut self, trades: &[Trade]) {
+    pub fn update_from_trades(&mut self, trades: &[Trade]) -> Result<()> {
         self.total_trades = trades.len() as u32;
-        
-        let mut total_pnl = 0.0;
+
+        let mut total_pnl = Money::ZERO;
+        let mut total_commission = Money::ZERO;
         let mut wins = 0;
         let mut losses = 0;
-        let mut win_sum = 0.0;
-        let mut loss_sum = 0.0;
+        let mut win_sum = Money::ZERO;
+        let mut loss_sum = Money::ZERO;
 
         for trade in trades {
+            total_commission = total_commission.checked_add(trade.commission)?;
+
             if let Some(pnl) = trade.realized_pnl {
-                total_pnl += pnl;
-                if pnl > 0.0 {
-                    wins += 1;
-                    win_sum += pnl;
-                } else if pnl < 0.0 {
+                total_pnl = total_pnl.checked_add(pnl)?;
+                if pnl.is_negative() {
                     losses += 1;
-                    loss_sum += pnl.abs();
+                    loss_sum = loss_sum.checked_add(pnl.abs())?;
+                } else if !pnl.is_zero() {
+                    wins += 1;
+                    win_sum = win_sum.checked_add(pnl)?;
                 }
             }
         }
 
+        // Net of the commission paid on every trade, not just the ones with
+        // a realized P&L (a Buy pays commission too, even though its P&L is
+        // still unrealized).
+        let net_total_pnl = total_pnl.checked_sub(total_commission)?;
+
         self.winning_trades = wins;
         self.losing_trades = losses;
-        self.total_return = total_pnl;
-        self.total_return_percent = if self.initial_capital > 0.0 {
-            (total_pnl / self.initial_capital) * 100.0
+        self.total_return = net_total_pnl;
+        self.total_return_percent = if self.initial_capital.to_f64() > 0.0 {
+            (net_total_pnl.to_f64() / self.initial_capital.to_f64()) * 100.0
         } else {
             0.0
         };
-        self.current_capital = self.initial_capital + total_pnl;
+        self.current_capital = self.initial_capital.checked_add(net_total_pnl)?;
 
         // Calculate derived metrics
         if self.total_trades > 0 {
@@ -155,18 +455,22 @@ impl PerformanceMetrics {
         }
 
         if wins > 0 {
-            self.average_win = win_sum / wins as f64;
+            self.average_win = Money::from_f64(win_sum.to_f64() / wins as f64);
         }
 
         if losses > 0 {
-            self.average_loss = loss_sum / losses as f64;
+            self.average_loss = Money::from_f64(loss_sum.to_f64() / losses as f64);
         }
 
-        if self.average_loss > 0.0 {
-            self.profit_factor = self.average_win / self.average_loss;
+        if !self.average_loss.is_zero() {
+            // Net gross profit against commissions before dividing, so a
+            // high-turnover, fee-heavy strategy doesn't look better than it is.
+            let net_win_sum = (win_sum.checked_sub(total_commission)?).to_f64().max(0.0);
+            self.profit_factor = net_win_sum / loss_sum.to_f64();
         }
 
         self.last_updated = Utc::now();
+        Ok(())
     }
 
     pub fn calculate_sharpe_ratio(&mut self, returns: &[f64], risk_free_rate: f64) {
@@ -189,9 +493,9 @@ impl PerformanceMetrics {
     }
 
     pub fn update_drawdown(&mut self, current_value: f64, peak_value: f64) {
-        let drawdown = peak_value - current_value;
+        let drawdown = Money::from_f64(peak_value - current_value);
         let drawdown_percent = if peak_value > 0.0 {
-            (drawdown / peak_value) * 100.0
+            (drawdown.to_f64() / peak_value) * 100.0
         } else {
             0.0
         };
@@ -211,73 +515,195 @@ impl Position {
         Position {
             symbol,
             shares: 0.0,
-            average_price: 0.0,
-            current_price: 0.0,
-            current_value: 0.0,
-            unrealized_pnl: 0.0,
+            average_price: Money::ZERO,
+            current_price: Money::ZERO,
+            current_value: Money::ZERO,
+            unrealized_pnl: Money::ZERO,
             unrealized_pnl_percent: 0.0,
-            cost_basis: 0.0,
+            cost_basis: Money::ZERO,
             last_updated: Utc::now(),
+            lots: Vec::new(),
         }
     }
 
     pub fn new(symbol: String, shares: f64, price: f64) -> Self {
-        let cost_basis = shares * price;
+        let price = Money::from_f64(price);
+        let cost_basis = price.checked_mul_scalar(shares).expect("cost basis overflow");
+        let acquired_at = Utc::now();
         Position {
             symbol,
             shares,
             average_price: price,
             current_price: price,
             current_value: cost_basis,
-            unrealized_pnl: 0.0,
+            unrealized_pnl: Money::ZERO,
             unrealized_pnl_percent: 0.0,
             cost_basis,
-            last_updated: Utc::now(),
+            last_updated: acquired_at,
+            lots: vec![Lot { shares, price, acquired_at }],
         }
     }
 
-    pub fn update_price(&mut self, new_price: f64) {
+    pub fn update_price(&mut self, new_price: f64) -> Result<()> {
+        let new_price = Money::from_f64(new_price);
         self.current_price = new_price;
-        self.current_value = self.shares * new_price;
-        self.unrealized_pnl = self.current_value - self.cost_basis;
-        self.unrealized_pnl_percent = if self.cost_basis > 0.0 {
-            (self.unrealized_pnl / self.cost_basis) * 100.0
+        self.current_value = new_price.checked_mul_scalar(self.shares)?;
+        self.unrealized_pnl = self.current_value.checked_sub(self.cost_basis)?;
+        self.unrealized_pnl_percent = if self.cost_basis.to_f64() > 0.0 {
+            (self.unrealized_pnl.to_f64() / self.cost_basis.to_f64()) * 100.0
         } else {
             0.0
         };
         self.last_updated = Utc::now();
+        Ok(())
     }
 
-    pub fn add_shares(&mut self, additional_shares: f64, price: f64) {
-        let additional_cost = additional_shares * price;
-        let total_cost = self.cost_basis + additional_cost;
+    pub fn add_shares(&mut self, additional_shares: f64, price: f64) -> Result<()> {
+        let price_money = Money::from_f64(price);
+        let additional_cost = price_money.checked_mul_scalar(additional_shares)?;
+        let total_cost = self.cost_basis.checked_add(additional_cost)?;
         let total_shares = self.shares + additional_shares;
 
         if total_shares > 0.0 {
-            self.average_price = total_cost / total_shares;
+            self.average_price = Money::from_f64(total_cost.to_f64() / total_shares);
         }
 
         self.shares = total_shares;
         self.cost_basis = total_cost;
-        self.update_price(price);
+        self.lots.push(Lot { shares: additional_shares, price: price_money, acquired_at: Utc::now() });
+        self.update_price(price)
     }
 
-    pub fn remove_shares(&mut self, shares_to_remove: f64, price: f64) -> Result<f64> {
+    /// Close out `shares_to_remove` at `price`, drawing against open lots in
+    /// the order `method` specifies, and return the realized P&L together
+    /// with a per-lot breakdown. `AverageCost` reproduces the platform's
+    /// original behavior (a single disposal priced against the blended
+    /// `average_price`) without touching `self.lots`; `Fifo`/`Lifo` consume
+    /// (and shrink) the matching lots from `self.lots` instead.
+    pub fn remove_shares(&mut self, shares_to_remove: f64, price: f64, method: CostBasisMethod) -> Result<LotDisposal> {
         if shares_to_remove > self.shares {
             return Err(TradingPlatformError::internal("Cannot remove more shares than owned"));
         }
 
-        let realized_pnl = shares_to_remove * (price - self.average_price);
+        let price_money = Money::from_f64(price);
+        let disposal = match method {
+            CostBasisMethod::AverageCost => {
+                let realized_pnl = price_money.checked_sub(self.average_price)?.checked_mul_scalar(shares_to_remove)?;
+                LotDisposal {
+                    realized_pnl,
+                    lots_closed: vec![ClosedLot {
+                        shares: shares_to_remove,
+                        price: self.average_price,
+                        acquired_at: self.last_updated,
+                        realized_pnl,
+                    }],
+                }
+            }
+            CostBasisMethod::Fifo => self.consume_lots(shares_to_remove, price_money, false)?,
+            CostBasisMethod::Lifo => self.consume_lots(shares_to_remove, price_money, true)?,
+        };
+
         self.shares -= shares_to_remove;
-        self.cost_basis = self.shares * self.average_price;
-        self.update_price(price);
+        match method {
+            CostBasisMethod::AverageCost => {
+                self.cost_basis = self.average_price.checked_mul_scalar(self.shares)?;
+            }
+            CostBasisMethod::Fifo | CostBasisMethod::Lifo => {
+                // FIFO/LIFO closed specific lots rather than a blended
+                // average, so re-derive cost_basis/average_price from the
+                // lots that actually survived instead of reusing the
+                // (now stale) blended average.
+                let mut remaining_cost = Money::ZERO;
+                for lot in &self.lots {
+                    remaining_cost = remaining_cost.checked_add(lot.price.checked_mul_scalar(lot.shares)?)?;
+                }
+                self.cost_basis = remaining_cost;
+                self.average_price = if self.shares > 0.0 {
+                    Money::from_f64(remaining_cost.to_f64() / self.shares)
+                } else {
+                    Money::ZERO
+                };
+            }
+        }
+        self.update_price(price)?;
 
-        Ok(realized_pnl)
+        Ok(disposal)
+    }
+
+    /// Walk `self.lots` oldest-first (`reverse = false`, FIFO) or
+    /// newest-first (`reverse = true`, LIFO), closing whole or partial lots
+    /// until `shares_to_remove` is exhausted, and drop any lot left at zero.
+    fn consume_lots(&mut self, mut shares_to_remove: f64, price: Money, reverse: bool) -> Result<LotDisposal> {
+        let mut realized_pnl = Money::ZERO;
+        let mut lots_closed = Vec::new();
+
+        let indices: Vec<usize> = if reverse {
+            (0..self.lots.len()).rev().collect()
+        } else {
+            (0..self.lots.len()).collect()
+        };
+
+        for idx in indices {
+            if shares_to_remove <= 0.0 {
+                break;
+            }
+
+            let lot = &mut self.lots[idx];
+            if lot.shares <= 0.0 {
+                continue;
+            }
+
+            let taken = lot.shares.min(shares_to_remove);
+            let lot_pnl = price.checked_sub(lot.price)?.checked_mul_scalar(taken)?;
+            realized_pnl = realized_pnl.checked_add(lot_pnl)?;
+            lots_closed.push(ClosedLot { shares: taken, price: lot.price, acquired_at: lot.acquired_at, realized_pnl: lot_pnl });
+
+            lot.shares -= taken;
+            shares_to_remove -= taken;
+        }
+
+        self.lots.retain(|lot| lot.shares > 0.0);
+
+        Ok(LotDisposal { realized_pnl, lots_closed })
     }
 
     pub fn is_empty(&self) -> bool {
         self.shares == 0.0
     }
+
+    /// Open or add to a short position. `shares` is the positive quantity
+    /// borrowed and sold at `price`; internally tracked as negative `shares`
+    /// so the existing mark-to-market math in `update_price` applies as-is
+    /// (a falling price then correctly yields a positive `unrealized_pnl`).
+    pub fn open_short(&mut self, shares: f64, price: f64) -> Result<()> {
+        let additional_proceeds = Money::from_f64(price).checked_mul_scalar(shares)?;
+        let total_cost = self.cost_basis.checked_sub(additional_proceeds)?;
+        let total_shares = self.shares - shares;
+
+        if total_shares != 0.0 {
+            self.average_price = Money::from_f64(total_cost.to_f64() / total_shares);
+        }
+
+        self.shares = total_shares;
+        self.cost_basis = total_cost;
+        self.update_price(price)
+    }
+
+    /// Cover (buy back) `shares` of an open short position at `price`,
+    /// returning the realized P&L.
+    pub fn close_short(&mut self, shares: f64, price: f64) -> Result<Money> {
+        if shares > -self.shares {
+            return Err(TradingPlatformError::internal("Cannot cover more shares than are short"));
+        }
+
+        let price_money = Money::from_f64(price);
+        let realized_pnl = self.average_price.checked_sub(price_money)?.checked_mul_scalar(shares)?;
+        self.shares += shares;
+        self.cost_basis = self.average_price.checked_mul_scalar(self.shares)?;
+        self.update_price(price)?;
+
+        Ok(realized_pnl)
+    }
 }
 
 impl Trade {
@@ -290,8 +716,9 @@ impl Trade {
         explanation: String,
         commission: f64,
     ) -> Self {
-        let trade_value = quantity * price;
-        
+        let price = Money::from_f64(price);
+        let trade_value = price.checked_mul_scalar(quantity).expect("trade value overflow");
+
         Trade {
             id: Uuid::new_v4().to_string(),
             strategy_id,
@@ -301,28 +728,31 @@ impl Trade {
             price,
             timestamp: Utc::now(),
             explanation,
-            commission,
+            commission: Money::from_f64(commission),
             realized_pnl: None,
             trade_value,
         }
     }
 
     pub fn with_realized_pnl(mut self, pnl: f64) -> Self {
-        self.realized_pnl = Some(pnl);
+        self.realized_pnl = Some(Money::from_f64(pnl));
         self
     }
 
-    pub fn net_value(&self) -> f64 {
+    pub fn net_value(&self) -> Result<Money> {
         match self.action {
-            Action::Buy => -(self.trade_value + self.commission),
-            Action::Sell => self.trade_value - self.commission,
-            Action::Hold => 0.0,
+            Action::Buy => Ok(-(self.trade_value.checked_add(self.commission)?)),
+            Action::Sell => self.trade_value.checked_sub(self.commission),
+            Action::Hold => Ok(Money::ZERO),
+            Action::ShortSell => self.trade_value.checked_sub(self.commission),
+            Action::ExitShort => Ok(-(self.trade_value.checked_add(self.commission)?)),
         }
     }
 }
 
 impl Portfolio {
     pub fn new(strategy_id: String, initial_capital: f64) -> Self {
+        let initial_capital = Money::from_f64(initial_capital);
         Portfolio {
             id: Uuid::new_v4().to_string(),
             strategy_id,
@@ -333,39 +763,214 @@ impl Portfolio {
             performance_snapshots: Vec::new(),
             created_at: Utc::now(),
             last_updated: Utc::now(),
+            margin_limit: Money::ZERO,
+            maintenance_margin_fraction: 0.25,
+            daily_borrow_rate: 0.0,
+            cumulative_borrow_interest: Money::ZERO,
+            commission_model: CommissionModel::default(),
+            trailing_volume: Money::ZERO,
+            total_commission_paid: Money::ZERO,
+            max_fee_bps: None,
+            risk_weights: HashMap::new(),
+            default_risk_weights: RiskWeights::default(),
         }
     }
 
+    /// Price every future trade's commission with `model` instead of the
+    /// zero-fee default.
+    pub fn with_commission_model(mut self, model: CommissionModel) -> Self {
+        self.commission_model = model;
+        self
+    }
+
+    /// Cap the commission any single trade can be charged at `max_fee_bps`
+    /// of its notional, regardless of what `commission_model` computes --
+    /// a backstop against a misconfigured or malicious fee schedule.
+    pub fn with_max_fee_bps(mut self, max_fee_bps: f64) -> Self {
+        self.max_fee_bps = Some(max_fee_bps);
+        self
+    }
+
+    /// Use `weights` instead of `default_risk_weights` for `symbol` in
+    /// `health` calculations.
+    pub fn with_risk_weights(mut self, symbol: String, weights: RiskWeights) -> Self {
+        self.risk_weights.insert(symbol, weights);
+        self
+    }
+
+    /// Zero `trailing_volume`, e.g. at the start of a new billing month for
+    /// a `CommissionModel::Tiered` schedule.
+    pub fn reset_trailing_volume(&mut self) {
+        self.trailing_volume = Money::ZERO;
+    }
+
+    /// Enable margin borrowing: `current_capital` may go as low as
+    /// `-margin_limit`, `daily_borrow_rate` accrues interest on borrowed
+    /// notional each `create_snapshot`, and `check_margin_call` enforces
+    /// `maintenance_margin_fraction` of gross exposure on every trade.
+    pub fn with_margin(mut self, margin_limit: f64, maintenance_margin_fraction: f64, daily_borrow_rate: f64) -> Self {
+        self.margin_limit = Money::from_f64(margin_limit);
+        self.maintenance_margin_fraction = maintenance_margin_fraction;
+        self.daily_borrow_rate = daily_borrow_rate;
+        self
+    }
+
+    /// Total absolute notional across all open positions (long and short),
+    /// i.e. exposure regardless of direction -- the base that
+    /// `maintenance_margin_fraction` and borrow interest are sized against.
+    pub fn gross_exposure(&self) -> Result<Money> {
+        let mut total = Money::ZERO;
+        for position in self.positions.values() {
+            total = total.checked_add(position.current_value.abs())?;
+        }
+        Ok(total)
+    }
+
+    /// Err if equity (`total_value`) has fallen below
+    /// `maintenance_margin_fraction` of `gross_exposure`, i.e. a margin call.
+    pub fn check_margin_call(&self) -> Result<()> {
+        let exposure = self.gross_exposure()?;
+        if exposure.is_zero() {
+            return Ok(());
+        }
+
+        let equity = self.total_value()?;
+        let required = exposure.checked_mul_scalar(self.maintenance_margin_fraction)?;
+        if equity < required {
+            return Err(TradingPlatformError::internal(format!(
+                "Margin call: equity {:.2} is below the {:.0}% maintenance requirement on {:.2} gross exposure",
+                equity.to_f64(),
+                self.maintenance_margin_fraction * 100.0,
+                exposure.to_f64()
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Notional currently financed by the broker: negative cash plus the
+    /// notional of any short positions.
+    fn borrowed_notional(&self) -> Result<Money> {
+        let mut borrowed = if self.current_capital.is_negative() { self.current_capital.abs() } else { Money::ZERO };
+        for position in self.positions.values() {
+            if position.shares < 0.0 {
+                borrowed = borrowed.checked_add(position.current_value.abs())?;
+            }
+        }
+        Ok(borrowed)
+    }
+
+    /// Weighted account health under `health_type`'s collateral weights:
+    /// cash, plus each long position's value haircut by `asset_weight`,
+    /// minus each short position's notional inflated by `liab_weight`.
+    /// Weights come from `risk_weights`, falling back to
+    /// `default_risk_weights` for symbols with no explicit entry.
+    pub fn health(&self, health_type: HealthType) -> Result<Money> {
+        let mut health = self.current_capital;
+        for position in self.positions.values() {
+            let weights = self.risk_weights.get(&position.symbol).copied().unwrap_or(self.default_risk_weights);
+            let (asset_weight, liab_weight) = match health_type {
+                HealthType::Initial => (weights.initial_asset_weight, weights.initial_liab_weight),
+                HealthType::Maintenance => (weights.maintenance_asset_weight, weights.maintenance_liab_weight),
+            };
+
+            if position.shares >= 0.0 {
+                let weighted_asset = position.current_value.checked_mul_scalar(asset_weight)?;
+                health = health.checked_add(weighted_asset)?;
+            } else {
+                let weighted_liab = position.current_value.abs().checked_mul_scalar(liab_weight)?;
+                health = health.checked_sub(weighted_liab)?;
+            }
+        }
+        Ok(health)
+    }
+
+    /// True once `health(Maintenance)` has fallen below zero -- the account
+    /// no longer covers its weighted liabilities and is a candidate for a
+    /// forced exit.
+    pub fn is_liquidatable(&self) -> Result<bool> {
+        Ok(self.health(HealthType::Maintenance)?.is_negative())
+    }
+
     pub fn execute_trade(&mut self, trade: Trade) -> Result<()> {
         let symbol = trade.symbol.clone();
-        
+        let mut trade = trade;
+
+        // Price the trade's commission from `commission_model` rather than
+        // trusting whatever the caller put on `Trade::commission`.
+        if trade.action != Action::Hold {
+            let mut commission = self.commission_model.calculate(trade.quantity, trade.trade_value.to_f64(), self.trailing_volume.to_f64())?;
+            if let Some(max_fee_bps) = self.max_fee_bps {
+                let ceiling = trade.trade_value.abs().checked_mul_scalar(max_fee_bps / 10_000.0)?;
+                commission = commission.min(ceiling);
+            }
+            trade.commission = commission;
+            self.trailing_volume = self.trailing_volume.checked_add(trade.trade_value.abs())?;
+            self.total_commission_paid = self.total_commission_paid.checked_add(commission)?;
+        }
+
         match trade.action {
             Action::Buy => {
-                if self.current_capital < trade.trade_value + trade.commission {
+                let cost = trade.trade_value.checked_add(trade.commission)?;
+                let projected_capital = self.current_capital.checked_sub(cost)?;
+                if projected_capital < Money::ZERO.checked_sub(self.margin_limit)? {
                     return Err(TradingPlatformError::internal("Insufficient capital for trade"));
                 }
 
-                self.current_capital -= trade.trade_value + trade.commission;
-                
+                self.current_capital = projected_capital;
+
                 let position = self.positions.entry(symbol.clone()).or_insert_with(|| Position::empty(symbol));
-                position.add_shares(trade.quantity, trade.price);
+                position.add_shares(trade.quantity, trade.price.to_f64())?;
             }
             Action::Sell => {
                 if let Some(position) = self.positions.get_mut(&symbol) {
-                    let realized_pnl = position.remove_shares(trade.quantity, trade.price)?;
-                    self.current_capital += trade.trade_value - trade.commission;
-                    
+                    let disposal = position.remove_shares(trade.quantity, trade.price.to_f64(), CostBasisMethod::AverageCost)?;
+                    let realized_pnl = disposal.realized_pnl;
+                    let proceeds = trade.trade_value.checked_sub(trade.commission)?;
+                    self.current_capital = self.current_capital.checked_add(proceeds)?;
+
                     let mut updated_trade = trade;
                     updated_trade.realized_pnl = Some(realized_pnl);
                     self.trade_history.push(updated_trade);
-                    
+
                     if position.is_empty() {
                         self.positions.remove(&symbol);
                     }
                 } else {
                     return Err(TradingPlatformError::internal("Cannot sell shares not owned"));
                 }
-                return Ok(());
+                self.last_updated = Utc::now();
+                return self.check_margin_call();
+            }
+            Action::ShortSell => {
+                let proceeds = trade.trade_value.checked_sub(trade.commission)?;
+                self.current_capital = self.current_capital.checked_add(proceeds)?;
+
+                let position = self.positions.entry(symbol.clone()).or_insert_with(|| Position::empty(symbol));
+                position.open_short(trade.quantity, trade.price.to_f64())?;
+            }
+            Action::ExitShort => {
+                if let Some(position) = self.positions.get_mut(&symbol) {
+                    let realized_pnl = position.close_short(trade.quantity, trade.price.to_f64())?;
+                    let cost = trade.trade_value.checked_add(trade.commission)?;
+                    let projected_capital = self.current_capital.checked_sub(cost)?;
+                    if projected_capital < Money::ZERO.checked_sub(self.margin_limit)? {
+                        return Err(TradingPlatformError::internal("Insufficient capital to cover short"));
+                    }
+                    self.current_capital = projected_capital;
+
+                    let mut updated_trade = trade;
+                    updated_trade.realized_pnl = Some(realized_pnl);
+                    self.trade_history.push(updated_trade);
+
+                    if position.is_empty() {
+                        self.positions.remove(&symbol);
+                    }
+                } else {
+                    return Err(TradingPlatformError::internal("Cannot exit short without an open short position"));
+                }
+                self.last_updated = Utc::now();
+                return self.check_margin_call();
             }
             Action::Hold => {
                 // No action needed for hold
@@ -374,40 +979,502 @@ impl Portfolio {
 
         self.trade_history.push(trade);
         self.last_updated = Utc::now();
-        Ok(())
+        self.check_margin_call()
     }
 
-    pub fn update_position_prices(&mut self, prices: &HashMap<String, f64>) {
+    pub fn update_position_prices(&mut self, prices: &HashMap<String, f64>) -> Result<()> {
         for (symbol, position) in &mut self.positions {
             if let Some(&new_price) = prices.get(symbol) {
-                position.update_price(new_price);
+                position.update_price(new_price)?;
             }
         }
         self.last_updated = Utc::now();
+        Ok(())
+    }
+
+    pub fn total_value(&self) -> Result<Money> {
+        let mut positions_value = Money::ZERO;
+        for position in self.positions.values() {
+            positions_value = positions_value.checked_add(position.current_value)?;
+        }
+        self.current_capital.checked_add(positions_value)
     }
 
-    pub fn total_value(&self) -> f64 {
-        let positions_value: f64 = self.positions.values().map(|p| p.current_value).sum();
-        self.current_capital + positions_value
+    pub fn total_unrealized_pnl(&self) -> Result<Money> {
+        let mut total = Money::ZERO;
+        for position in self.positions.values() {
+            total = total.checked_add(position.unrealized_pnl)?;
+        }
+        Ok(total)
+    }
+
+    /// Plan the trades that move this portfolio toward `target_weights`
+    /// (symbol -> fraction of `total_value()`, each weight in `0.0..=1.0`
+    /// and summing to at most `1.0`; any remainder is left as cash).
+    ///
+    /// Two passes, mirroring how portfolio-management tools rebalance:
+    /// 1. Snapshot each symbol's current value (from the positions as of
+    ///    the last `update_position_prices` call) against the portfolio's
+    ///    current `total_value()`.
+    /// 2. For every symbol mentioned either in `target_weights` or in the
+    ///    current positions, compute `target_value = weight * total_value`
+    ///    (a weight of `0.0` for a held symbol not in `target_weights`
+    ///    liquidates it) and derive the share delta at `prices[symbol]`.
+    ///
+    /// Trades whose notional is smaller than `min_trade_volume` are
+    /// dropped to avoid churning on rounding noise. Sells are planned
+    /// before buys so the proceeds are available to fund them, and no
+    /// buy is sized past the cash actually on hand afterward. The result
+    /// is a plan of `Trade`s with `realized_pnl` left unset, ready to be
+    /// handed one at a time to `execute_trade`.
+    pub fn rebalance(
+        &self,
+        strategy_id: String,
+        target_weights: &HashMap<String, f64>,
+        prices: &HashMap<String, f64>,
+        min_trade_volume: f64,
+    ) -> Result<Vec<Trade>> {
+        let weight_sum: f64 = target_weights.values().sum();
+        if weight_sum > 1.0 + 1e-9 {
+            return Err(TradingPlatformError::invalid_parameters(
+                "target_weights must sum to at most 1.0",
+            ));
+        }
+        for &weight in target_weights.values() {
+            if weight < 0.0 {
+                return Err(TradingPlatformError::invalid_parameters(
+                    "target_weights cannot contain a negative weight",
+                ));
+            }
+        }
+
+        let total_value = self.total_value()?.to_f64();
+
+        let mut symbols: Vec<&String> = target_weights.keys().chain(self.positions.keys()).collect();
+        symbols.sort();
+        symbols.dedup();
+
+        let mut sells: Vec<(String, f64, f64, f64)> = Vec::new(); // (symbol, shares, price, weight)
+        let mut buys: Vec<(String, f64, f64, f64)> = Vec::new(); // (symbol, target_notional, price, weight)
+
+        for symbol in symbols {
+            let weight = target_weights.get(symbol).copied().unwrap_or(0.0);
+            let position = self.positions.get(symbol);
+            let current_value = position.map(|p| p.current_value.to_f64()).unwrap_or(0.0);
+            let current_shares = position.map(|p| p.shares).unwrap_or(0.0);
+
+            let price = match prices.get(symbol).copied().or_else(|| position.map(|p| p.current_price.to_f64())) {
+                Some(price) if price > 0.0 => price,
+                _ => {
+                    return Err(TradingPlatformError::invalid_parameters(format!(
+                        "no price available to rebalance {}",
+                        symbol
+                    )))
+                }
+            };
+
+            let target_value = weight * total_value;
+            let delta_value = target_value - current_value;
+            if delta_value.abs() < min_trade_volume {
+                continue;
+            }
+
+            if delta_value < 0.0 {
+                let shares = (-delta_value / price).min(current_shares.max(0.0));
+                if shares > 0.0 {
+                    sells.push((symbol.clone(), shares, price, weight));
+                }
+            } else {
+                buys.push((symbol.clone(), delta_value, price, weight));
+            }
+        }
+
+        let mut trades = Vec::with_capacity(sells.len() + buys.len());
+        let mut available_cash = self.current_capital.to_f64();
+
+        for (symbol, shares, price, weight) in sells {
+            let explanation = format!(
+                "Rebalance: sell {:.4} shares of {} to move toward {:.2}% target allocation",
+                shares,
+                symbol,
+                weight * 100.0
+            );
+            trades.push(Trade::new(strategy_id.clone(), symbol, Action::Sell, shares, price, explanation, 0.0));
+            available_cash += shares * price;
+        }
+
+        for (symbol, target_notional, price, weight) in buys {
+            let notional = target_notional.min(available_cash);
+            if notional < min_trade_volume {
+                continue;
+            }
+
+            let shares = notional / price;
+            let explanation = format!(
+                "Rebalance: buy {:.4} shares of {} to move toward {:.2}% target allocation",
+                shares,
+                symbol,
+                weight * 100.0
+            );
+            trades.push(Trade::new(strategy_id.clone(), symbol, Action::Buy, shares, price, explanation, 0.0));
+            available_cash -= notional;
+        }
+
+        Ok(trades)
     }
 
-    pub fn total_unrealized_pnl(&self) -> f64 {
-        self.positions.values().map(|p| p.unrealized_pnl).sum()
+    /// Charge a day's interest on `borrowed_notional()` at `daily_borrow_rate`
+    /// against `current_capital`, and add it to `cumulative_borrow_interest`.
+    /// A no-op while `daily_borrow_rate` is `0.0` (the `new()` default).
+    fn accrue_borrow_interest(&mut self) -> Result<()> {
+        if self.daily_borrow_rate == 0.0 {
+            return Ok(());
+        }
+
+        let interest = self.borrowed_notional()?.checked_mul_scalar(self.daily_borrow_rate)?;
+        self.current_capital = self.current_capital.checked_sub(interest)?;
+        self.cumulative_borrow_interest = self.cumulative_borrow_interest.checked_add(interest)?;
+        Ok(())
     }
 
-    pub fn create_snapshot(&mut self) {
+    pub fn create_snapshot(&mut self) -> Result<()> {
+        self.accrue_borrow_interest()?;
+
+        let total_value = self.total_value()?;
+        let mut positions_value = Money::ZERO;
+        for position in self.positions.values() {
+            positions_value = positions_value.checked_add(position.current_value)?;
+        }
+
         let snapshot = PerformanceSnapshot {
             timestamp: Utc::now(),
-            total_value: self.total_value(),
+            total_value,
             cash_balance: self.current_capital,
-            positions_value: self.positions.values().map(|p| p.current_value).sum(),
-            total_return: self.total_value() - self.initial_capital,
+            positions_value,
+            total_return: total_value.checked_sub(self.initial_capital)?,
             daily_return: 0.0, // Would be calculated based on previous snapshot
-            drawdown: 0.0, // Would be calculated based on peak value
+            drawdown: Money::ZERO, // Would be calculated based on peak value
         };
 
         self.performance_snapshots.push(snapshot);
         self.last_updated = Utc::now();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_rejects_mismatched_lengths() {
+        let result = RiskMetrics::calculate("s1".to_string(), &[0.01, 0.02], &[0.01], 0.0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_calculate_rejects_fewer_than_two_points() {
+        let result = RiskMetrics::calculate("s1".to_string(), &[0.01], &[0.01], 0.0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_calculate_var_and_expected_shortfall() {
+        let portfolio_returns = vec![-0.05, -0.03, -0.01, 0.0, 0.01, 0.02, 0.03, 0.04, 0.05, 0.06];
+        let market_returns = vec![-0.04, -0.02, -0.01, 0.0, 0.01, 0.02, 0.02, 0.03, 0.04, 0.05];
+
+        let metrics = RiskMetrics::calculate("s1".to_string(), &portfolio_returns, &market_returns, 0.0).unwrap();
+
+        assert!(metrics.value_at_risk_95 > 0.0);
+        assert!(metrics.value_at_risk_99 >= metrics.value_at_risk_95);
+        assert!(metrics.expected_shortfall >= metrics.value_at_risk_95);
+        assert!(metrics.volatility > 0.0);
+        assert!(metrics.beta.is_some());
+        assert!(metrics.alpha.is_some());
+        assert!(metrics.correlation_to_market.is_some());
+    }
+
+    #[test]
+    fn test_calculate_sets_beta_alpha_correlation_none_when_market_has_no_variance() {
+        let portfolio_returns = vec![0.01, 0.02, -0.01, 0.03];
+        let market_returns = vec![0.0, 0.0, 0.0, 0.0];
+
+        let metrics = RiskMetrics::calculate("s1".to_string(), &portfolio_returns, &market_returns, 0.0).unwrap();
+
+        assert!(metrics.beta.is_none());
+        assert!(metrics.alpha.is_none());
+        assert!(metrics.correlation_to_market.is_none());
+    }
+
+    #[test]
+    fn test_calculate_beta_one_for_identical_series() {
+        let returns = vec![0.01, -0.02, 0.03, 0.015, -0.005];
+
+        let metrics = RiskMetrics::calculate("s1".to_string(), &returns, &returns, 0.0).unwrap();
+
+        assert!((metrics.beta.unwrap() - 1.0).abs() < 1e-9);
+        assert!((metrics.alpha.unwrap() - 0.0).abs() < 1e-9);
+        assert!((metrics.correlation_to_market.unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rebalance_rejects_weights_over_one() {
+        let portfolio = Portfolio::new("s1".to_string(), 10000.0);
+        let mut target_weights = HashMap::new();
+        target_weights.insert("AAPL".to_string(), 0.6);
+        target_weights.insert("MSFT".to_string(), 0.6);
+
+        let result = portfolio.rebalance("s1".to_string(), &target_weights, &HashMap::new(), 1.0);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rebalance_buys_into_empty_portfolio() {
+        let portfolio = Portfolio::new("s1".to_string(), 10000.0);
+        let mut target_weights = HashMap::new();
+        target_weights.insert("AAPL".to_string(), 0.5);
+        target_weights.insert("MSFT".to_string(), 0.3);
+
+        let mut prices = HashMap::new();
+        prices.insert("AAPL".to_string(), 100.0);
+        prices.insert("MSFT".to_string(), 200.0);
+
+        let trades = portfolio.rebalance("s1".to_string(), &target_weights, &prices, 1.0).unwrap();
+
+        assert_eq!(trades.len(), 2);
+        assert!(trades.iter().all(|t| t.action == Action::Buy));
+        let aapl_trade = trades.iter().find(|t| t.symbol == "AAPL").unwrap();
+        assert!((aapl_trade.quantity - 50.0).abs() < 1e-9);
+        let msft_trade = trades.iter().find(|t| t.symbol == "MSFT").unwrap();
+        assert!((msft_trade.quantity - 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rebalance_sells_position_dropped_from_target() {
+        let mut portfolio = Portfolio::new("s1".to_string(), 0.0);
+        portfolio.positions.insert("AAPL".to_string(), Position::new("AAPL".to_string(), 100.0, 100.0));
+
+        let trades = portfolio
+            .rebalance("s1".to_string(), &HashMap::new(), &HashMap::new(), 1.0)
+            .unwrap();
+
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].action, Action::Sell);
+        assert!((trades[0].quantity - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rebalance_skips_trades_below_min_volume() {
+        // Total value 10100 puts AAPL's 50% target at 5050: a 50 delta, under the 100 minimum.
+        let mut portfolio = Portfolio::new("s1".to_string(), 5100.0);
+        portfolio.positions.insert("AAPL".to_string(), Position::new("AAPL".to_string(), 50.0, 100.0));
+
+        let mut target_weights = HashMap::new();
+        target_weights.insert("AAPL".to_string(), 0.5);
+
+        let mut prices = HashMap::new();
+        prices.insert("AAPL".to_string(), 100.0);
+
+        let trades = portfolio.rebalance("s1".to_string(), &target_weights, &prices, 100.0).unwrap();
+
+        assert!(trades.is_empty());
+    }
+
+    #[test]
+    fn test_remove_shares_average_cost_uses_blended_price() {
+        let mut position = Position::new("AAPL".to_string(), 10.0, 10.0);
+        position.add_shares(10.0, 20.0).unwrap();
+
+        let disposal = position.remove_shares(5.0, 30.0, CostBasisMethod::AverageCost).unwrap();
+
+        assert!((disposal.realized_pnl.to_f64() - 75.0).abs() < 1e-6);
+        assert_eq!(disposal.lots_closed.len(), 1);
+    }
+
+    #[test]
+    fn test_remove_shares_fifo_closes_oldest_lot_first() {
+        let mut position = Position::new("AAPL".to_string(), 10.0, 10.0);
+        position.add_shares(10.0, 20.0).unwrap();
+
+        let disposal = position.remove_shares(10.0, 30.0, CostBasisMethod::Fifo).unwrap();
+
+        assert_eq!(disposal.lots_closed.len(), 1);
+        assert!((disposal.lots_closed[0].price.to_f64() - 10.0).abs() < 1e-6);
+        assert!((disposal.realized_pnl.to_f64() - 200.0).abs() < 1e-6);
+        assert_eq!(position.lots.len(), 1);
+        assert!((position.lots[0].price.to_f64() - 20.0).abs() < 1e-6);
+        assert!((position.cost_basis.to_f64() - 200.0).abs() < 1e-6);
+        assert!((position.average_price.to_f64() - 20.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_remove_shares_lifo_closes_newest_lot_first() {
+        let mut position = Position::new("AAPL".to_string(), 10.0, 10.0);
+        position.add_shares(10.0, 20.0).unwrap();
+
+        let disposal = position.remove_shares(10.0, 30.0, CostBasisMethod::Lifo).unwrap();
+
+        assert_eq!(disposal.lots_closed.len(), 1);
+        assert!((disposal.lots_closed[0].price.to_f64() - 20.0).abs() < 1e-6);
+        assert!((disposal.realized_pnl.to_f64() - 100.0).abs() < 1e-6);
+        assert_eq!(position.lots.len(), 1);
+        assert!((position.lots[0].price.to_f64() - 10.0).abs() < 1e-6);
+        assert!((position.cost_basis.to_f64() - 100.0).abs() < 1e-6);
+        assert!((position.average_price.to_f64() - 10.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_remove_shares_fifo_splits_across_lots() {
+        let mut position = Position::new("AAPL".to_string(), 10.0, 10.0);
+        position.add_shares(10.0, 20.0).unwrap();
+
+        let disposal = position.remove_shares(15.0, 30.0, CostBasisMethod::Fifo).unwrap();
+
+        assert_eq!(disposal.lots_closed.len(), 2);
+        assert!((disposal.lots_closed[0].shares - 10.0).abs() < 1e-6);
+        assert!((disposal.lots_closed[1].shares - 5.0).abs() < 1e-6);
+        assert_eq!(position.lots.len(), 1);
+        assert!((position.lots[0].shares - 5.0).abs() < 1e-6);
+        assert!((position.cost_basis.to_f64() - 100.0).abs() < 1e-6);
+        assert!((position.average_price.to_f64() - 20.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_buy_without_margin_rejects_insufficient_capital() {
+        let mut portfolio = Portfolio::new("s1".to_string(), 100.0);
+        let buy = Trade::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 10.0, 100.0, "".to_string(), 0.0);
+
+        assert!(portfolio.execute_trade(buy).is_err());
+    }
+
+    #[test]
+    fn test_with_margin_allows_capital_to_go_negative_up_to_limit() {
+        let mut portfolio = Portfolio::new("s1".to_string(), 100.0).with_margin(900.0, 0.1, 0.0);
+        let buy = Trade::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 10.0, 100.0, "".to_string(), 0.0);
+
+        portfolio.execute_trade(buy).unwrap();
+
+        assert_eq!(portfolio.current_capital.to_f64(), -900.0);
+    }
+
+    #[test]
+    fn test_create_snapshot_accrues_borrow_interest_on_negative_cash() {
+        let mut portfolio = Portfolio::new("s1".to_string(), 100.0).with_margin(900.0, 0.1, 0.001);
+        let buy = Trade::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 10.0, 100.0, "".to_string(), 0.0);
+        portfolio.execute_trade(buy).unwrap();
+
+        let capital_before = portfolio.current_capital;
+        portfolio.create_snapshot().unwrap();
+
+        assert!(portfolio.current_capital < capital_before);
+        assert!(portfolio.cumulative_borrow_interest.to_f64() > 0.0);
+    }
+
+    #[test]
+    fn test_check_margin_call_fails_when_equity_below_maintenance_fraction() {
+        let mut portfolio = Portfolio::new("s1".to_string(), 100.0).with_margin(900.0, 0.5, 0.0);
+        portfolio.positions.insert("AAPL".to_string(), Position::new("AAPL".to_string(), 10.0, 100.0));
+        portfolio.current_capital = Money::from_f64(-900.0);
+
+        assert!(portfolio.check_margin_call().is_err());
+    }
+
+    #[test]
+    fn test_health_weights_long_and_short_positions_with_defaults() {
+        let mut portfolio = Portfolio::new("s1".to_string(), 1000.0);
+        portfolio.positions.insert("AAPL".to_string(), Position::new("AAPL".to_string(), 10.0, 100.0));
+        portfolio.positions.insert("TSLA".to_string(), Position::new("TSLA".to_string(), -5.0, 100.0));
+
+        // cash 1000 + 0.8 * 1000 (long) - 1.2 * 500 (short) = 1200
+        assert_eq!(portfolio.health(HealthType::Initial).unwrap().to_f64(), 1200.0);
+        // cash 1000 + 0.9 * 1000 (long) - 1.1 * 500 (short) = 1350
+        assert_eq!(portfolio.health(HealthType::Maintenance).unwrap().to_f64(), 1350.0);
+    }
+
+    #[test]
+    fn test_health_uses_per_symbol_risk_weights_override() {
+        let mut portfolio = Portfolio::new("s1".to_string(), 0.0).with_risk_weights(
+            "AAPL".to_string(),
+            RiskWeights { initial_asset_weight: 0.5, initial_liab_weight: 1.5, maintenance_asset_weight: 0.5, maintenance_liab_weight: 1.5 },
+        );
+        portfolio.positions.insert("AAPL".to_string(), Position::new("AAPL".to_string(), 10.0, 100.0));
+
+        assert_eq!(portfolio.health(HealthType::Initial).unwrap().to_f64(), 500.0); // 0.5 * 1000, not the 0.8 default
+    }
+
+    #[test]
+    fn test_is_liquidatable_true_once_maintenance_health_goes_negative() {
+        let mut portfolio = Portfolio::new("s1".to_string(), 100.0);
+        portfolio.positions.insert("TSLA".to_string(), Position::new("TSLA".to_string(), -10.0, 100.0));
+
+        // cash 100 - 1.1 * 1000 = -1000
+        assert!(portfolio.is_liquidatable().unwrap());
+    }
+
+    #[test]
+    fn test_is_liquidatable_false_when_well_collateralized() {
+        let portfolio = Portfolio::new("s1".to_string(), 1000.0);
+        assert!(!portfolio.is_liquidatable().unwrap());
+    }
+
+    #[test]
+    fn test_commission_model_percentage_clamps_to_min_and_max() {
+        let model = CommissionModel::Percentage { bps: 10.0, min: 1.0, max: 50.0 };
+
+        assert_eq!(model.calculate(1.0, 100.0, 0.0).unwrap().to_f64(), 1.0); // 0.10 floored to min
+        assert_eq!(model.calculate(1.0, 1_000_000.0, 0.0).unwrap().to_f64(), 50.0); // capped at max
+    }
+
+    #[test]
+    fn test_commission_model_tiered_picks_highest_reached_tier() {
+        let model = CommissionModel::Tiered(vec![
+            VolumeTier { min_volume: 0.0, rate_bps: 10.0 },
+            VolumeTier { min_volume: 100_000.0, rate_bps: 5.0 },
+        ]);
+
+        assert_eq!(model.calculate(1.0, 1000.0, 0.0).unwrap().to_f64(), 1.0); // 10 bps
+        assert_eq!(model.calculate(1.0, 1000.0, 150_000.0).unwrap().to_f64(), 0.5); // 5 bps
+    }
+
+    #[test]
+    fn test_execute_trade_prices_commission_from_model_and_accumulates_it() {
+        let mut portfolio = Portfolio::new("s1".to_string(), 10000.0)
+            .with_commission_model(CommissionModel::PerShare(0.01));
+        let buy = Trade::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 10.0, 100.0, "".to_string(), 999.0);
+
+        portfolio.execute_trade(buy).unwrap();
+
+        assert_eq!(portfolio.trade_history[0].commission.to_f64(), 0.1); // 10 shares * 0.01, not the caller's 999.0
+        assert_eq!(portfolio.total_commission_paid.to_f64(), 0.1);
+        assert_eq!(portfolio.current_capital.to_f64(), 10000.0 - 1000.0 - 0.1);
+    }
+
+    #[test]
+    fn test_execute_trade_clamps_commission_to_max_fee_bps() {
+        let mut portfolio = Portfolio::new("s1".to_string(), 10000.0)
+            .with_commission_model(CommissionModel::PerShare(1.0))
+            .with_max_fee_bps(50.0);
+        let buy = Trade::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 10.0, 100.0, "".to_string(), 999.0);
+
+        portfolio.execute_trade(buy).unwrap();
+
+        // Uncapped commission would be 10 shares * $1.0 = $10, but 50 bps of the
+        // $1000 notional is only $5, so the ceiling wins.
+        assert_eq!(portfolio.trade_history[0].commission.to_f64(), 5.0);
+        assert_eq!(portfolio.total_commission_paid.to_f64(), 5.0);
+    }
+
+    #[test]
+    fn test_execute_trade_leaves_commission_unclamped_when_under_max_fee_bps() {
+        let mut portfolio = Portfolio::new("s1".to_string(), 10000.0)
+            .with_commission_model(CommissionModel::PerShare(0.01))
+            .with_max_fee_bps(50.0);
+        let buy = Trade::new("s1".to_string(), "AAPL".to_string(), Action::Buy, 10.0, 100.0, "".to_string(), 999.0);
+
+        portfolio.execute_trade(buy).unwrap();
+
+        assert_eq!(portfolio.trade_history[0].commission.to_f64(), 0.1);
     }
 }
 
@@ -0,0 +1,168 @@
+// Fixed-point decimal type for cash, prices, and P&L.
+//
+// `f64` money drifts: summing many small `add_shares`/`remove_shares`
+// adjustments accumulates rounding error, and nothing stops a sell from
+// silently driving `current_capital` negative by a fraction of a cent.
+// `Money` stores an `i128` scaled by `SCALE` and only exposes checked
+// arithmetic, so overflow is a `TradingPlatformError` instead of a number
+// that quietly drifted. `checked_sub` may go below zero (margin balances
+// do); `checked_sub_nonnegative` turns a below-zero result into an error.
+
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+use crate::error::{Result, TradingPlatformError};
+
+/// Decimal places of precision kept internally (enough for fractional
+/// cents on any realistic share price or cash balance).
+const SCALE: i128 = 100_000_000; // 1e8
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Money(i128);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    /// Build from a floating-point amount, rounding to `Money`'s precision.
+    /// This is the one lossy edge of the type: it exists so market data and
+    /// user input (both naturally `f64`) can enter the ledger; every
+    /// computation from that point on is exact fixed-point arithmetic.
+    pub fn from_f64(amount: f64) -> Self {
+        Money((amount * SCALE as f64).round() as i128)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / SCALE as f64
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn abs(self) -> Money {
+        Money(self.0.abs())
+    }
+
+    pub fn checked_add(self, other: Money) -> Result<Money> {
+        self.0
+            .checked_add(other.0)
+            .map(Money)
+            .ok_or_else(|| TradingPlatformError::internal("money overflow on add"))
+    }
+
+    pub fn checked_sub(self, other: Money) -> Result<Money> {
+        self.0
+            .checked_sub(other.0)
+            .map(Money)
+            .ok_or_else(|| TradingPlatformError::internal("money overflow on subtract"))
+    }
+
+    /// Multiply by a plain scalar (e.g. a share count), checked against
+    /// overflow. Used for `shares * price` style computations where one
+    /// side of the multiplication is a quantity rather than money.
+    pub fn checked_mul_scalar(self, scalar: f64) -> Result<Money> {
+        let result = self.0 as f64 * scalar;
+        if !result.is_finite() || result.abs() >= i128::MAX as f64 {
+            return Err(TradingPlatformError::internal("money overflow on multiply"));
+        }
+        Ok(Money(result.round() as i128))
+    }
+
+    /// Subtract, failing instead of going negative. Used wherever a sell or
+    /// withdrawal must not be allowed to drive a balance below zero.
+    pub fn checked_sub_nonnegative(self, other: Money) -> Result<Money> {
+        let result = self.checked_sub(other)?;
+        if result.is_negative() {
+            return Err(TradingPlatformError::internal(
+                "money subtraction would go negative",
+            ));
+        }
+        Ok(result)
+    }
+}
+
+impl std::ops::Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2}", self.to_f64())
+    }
+}
+
+impl From<f64> for Money {
+    fn from(amount: f64) -> Self {
+        Money::from_f64(amount)
+    }
+}
+
+impl From<Money> for f64 {
+    fn from(money: Money) -> Self {
+        money.to_f64()
+    }
+}
+
+// Serialize as the scaled integer string so round-tripping through JSON
+// never touches a float and can't introduce new rounding error.
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        raw.parse::<i128>().map(Money).map_err(DeError::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_f64_round_trips_through_to_f64() {
+        let money = Money::from_f64(1234.56);
+        assert!((money.to_f64() - 1234.56).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_checked_add_and_sub() {
+        let a = Money::from_f64(100.0);
+        let b = Money::from_f64(40.0);
+        assert!((a.checked_add(b).unwrap().to_f64() - 140.0).abs() < 1e-9);
+        assert!((a.checked_sub(b).unwrap().to_f64() - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_checked_sub_nonnegative_rejects_negative_result() {
+        let a = Money::from_f64(10.0);
+        let b = Money::from_f64(20.0);
+        assert!(a.checked_sub_nonnegative(b).is_err());
+    }
+
+    #[test]
+    fn test_checked_mul_scalar() {
+        let price = Money::from_f64(50.0);
+        let value = price.checked_mul_scalar(3.0).unwrap();
+        assert!((value.to_f64() - 150.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_serde_round_trip_is_exact() {
+        let money = Money::from_f64(9999.99);
+        let json = serde_json::to_string(&money).unwrap();
+        let back: Money = serde_json::from_str(&json).unwrap();
+        assert_eq!(money, back);
+    }
+}
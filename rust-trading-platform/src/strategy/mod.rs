@@ -5,9 +5,16 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
-use crate::data::{MarketData, PricePoint};
+use crate::data::{MarketData, Price, PricePoint};
 use crate::error::{Result, StrategyError};
 
+pub mod position_manager;
+
+pub use position_manager::PositionManager;
+
+/// Default lookback period used when `StrategyType::RSI` doesn't specify one.
+const DEFAULT_RSI_PERIOD: usize = 14;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Strategy {
     pub id: String,
@@ -18,17 +25,75 @@ pub struct Strategy {
     pub is_active: bool,
 }
 
+/// How a `Composite` strategy reconciles its children's signals.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum CombineMode {
+    /// Only emit Buy/Sell when every child agrees on the same action.
+    All,
+    /// Emit the highest-confidence non-Hold action any child produces.
+    Any,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum StrategyType {
     PriceDrop { threshold: f64 },
     MovingAverage { short_period: usize, long_period: usize },
-    RSI { oversold: f64, overbought: f64 },
+    RSI { oversold: f64, overbought: f64, period: Option<usize> },
+    Confluence {
+        short_period: usize,
+        long_period: usize,
+        rsi_oversold: f64,
+        rsi_overbought: f64,
+        stoch_period: usize,
+        stoch_oversold: f64,
+        stoch_overbought: f64,
+    },
+    MACD {
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    },
+    StochasticRSI {
+        rsi_period: usize,
+        stoch_period: usize,
+        k_smoothing: usize,
+        d_smoothing: usize,
+        oversold: f64,
+        overbought: f64,
+    },
+    CCI { oversold: f64, overbought: f64, period: usize },
+    StochRSI {
+        rsi_period: usize,
+        stoch_period: usize,
+        k_smooth: usize,
+        d_smooth: usize,
+        oversold: f64,
+        overbought: f64,
+    },
+    Composite {
+        children: Vec<StrategyType>,
+        mode: CombineMode,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct StrategyParameters {
     pub symbol: String,
     pub additional_params: HashMap<String, serde_json::Value>,
+    /// Percentage gain from entry at which `PositionManager` forces a Sell.
+    pub take_profit_percent: Option<f64>,
+    /// Percentage loss from entry at which `PositionManager` forces a Sell.
+    pub stop_loss_percent: Option<f64>,
+    /// Percentage drop from the peak price since entry at which
+    /// `PositionManager` forces a Sell.
+    pub trailing_stop_percent: Option<f64>,
+    /// Leverage multiplier applied to short and long positions, e.g. `2.0`
+    /// for 2x. `None` means unleveraged (1x).
+    pub leverage: Option<f64>,
+    /// Minimum bar volume a Buy/Sell/ShortSell/ExitShort signal requires to
+    /// pass through `StrategyEngine`'s volume guard. `None` means only a
+    /// literal zero-volume bar is guarded.
+    pub min_volume: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -55,6 +120,10 @@ pub enum Action {
     Buy,
     Sell,
     Hold,
+    /// Opens a short position (borrow and sell, expecting the price to fall).
+    ShortSell,
+    /// Closes a previously opened short position (buy back to cover).
+    ExitShort,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -103,7 +172,7 @@ impl Strategy {
                     return Err(StrategyError::invalid_parameters("Long period should not exceed 200").into());
                 }
             }
-            StrategyType::RSI { oversold, overbought } => {
+            StrategyType::RSI { oversold, overbought, period } => {
                 if *oversold <= 0.0 || *oversold >= 100.0 {
                     return Err(StrategyError::invalid_parameters("RSI oversold level must be between 0 and 100").into());
                 }
@@ -113,6 +182,111 @@ impl Strategy {
                 if *oversold >= *overbought {
                     return Err(StrategyError::invalid_parameters("RSI oversold level must be less than overbought level").into());
                 }
+                if let Some(period) = period {
+                    if *period < 2 {
+                        return Err(StrategyError::invalid_parameters("RSI period must be at least 2").into());
+                    }
+                }
+            }
+            StrategyType::Confluence {
+                short_period,
+                long_period,
+                rsi_oversold,
+                rsi_overbought,
+                stoch_period,
+                stoch_oversold,
+                stoch_overbought,
+            } => {
+                if *short_period == 0 || *long_period == 0 || *stoch_period == 0 {
+                    return Err(StrategyError::invalid_parameters("Confluence periods must be greater than 0").into());
+                }
+                if *short_period >= *long_period {
+                    return Err(StrategyError::invalid_parameters("Short period must be less than long period").into());
+                }
+                if *long_period > 200 {
+                    return Err(StrategyError::invalid_parameters("Long period should not exceed 200").into());
+                }
+                if *rsi_oversold <= 0.0 || *rsi_oversold >= 100.0 || *rsi_overbought <= 0.0 || *rsi_overbought >= 100.0 {
+                    return Err(StrategyError::invalid_parameters("RSI levels must be between 0 and 100").into());
+                }
+                if *rsi_oversold >= *rsi_overbought {
+                    return Err(StrategyError::invalid_parameters("RSI oversold level must be less than overbought level").into());
+                }
+                if *stoch_oversold <= 0.0 || *stoch_oversold >= 100.0 || *stoch_overbought <= 0.0 || *stoch_overbought >= 100.0 {
+                    return Err(StrategyError::invalid_parameters("Stochastic levels must be between 0 and 100").into());
+                }
+                if *stoch_oversold >= *stoch_overbought {
+                    return Err(StrategyError::invalid_parameters("Stochastic oversold level must be less than overbought level").into());
+                }
+            }
+            StrategyType::MACD { fast_period, slow_period, signal_period } => {
+                if *fast_period == 0 || *slow_period == 0 || *signal_period == 0 {
+                    return Err(StrategyError::invalid_parameters("MACD periods must be greater than 0").into());
+                }
+                if *fast_period >= *slow_period {
+                    return Err(StrategyError::invalid_parameters("MACD fast period must be less than slow period").into());
+                }
+                if *slow_period > 200 {
+                    return Err(StrategyError::invalid_parameters("MACD slow period should not exceed 200").into());
+                }
+            }
+            StrategyType::StochasticRSI {
+                rsi_period,
+                stoch_period,
+                k_smoothing,
+                d_smoothing,
+                oversold,
+                overbought,
+            } => {
+                if *rsi_period == 0 || *stoch_period == 0 || *k_smoothing == 0 || *d_smoothing == 0 {
+                    return Err(StrategyError::invalid_parameters("Stochastic RSI periods must be greater than 0").into());
+                }
+                if *oversold <= 0.0 || *oversold >= 100.0 || *overbought <= 0.0 || *overbought >= 100.0 {
+                    return Err(StrategyError::invalid_parameters("Stochastic RSI levels must be between 0 and 100").into());
+                }
+                if *oversold >= *overbought {
+                    return Err(StrategyError::invalid_parameters("Stochastic RSI oversold level must be less than overbought level").into());
+                }
+            }
+            StrategyType::CCI { oversold, overbought, period } => {
+                if *period == 0 {
+                    return Err(StrategyError::invalid_parameters("CCI period must be greater than 0").into());
+                }
+                if *oversold >= *overbought {
+                    return Err(StrategyError::invalid_parameters("CCI oversold level must be less than overbought level").into());
+                }
+            }
+            StrategyType::StochRSI {
+                rsi_period,
+                stoch_period,
+                k_smooth,
+                d_smooth,
+                oversold,
+                overbought,
+            } => {
+                if *rsi_period == 0 || *stoch_period == 0 || *k_smooth == 0 || *d_smooth == 0 {
+                    return Err(StrategyError::invalid_parameters("StochRSI periods must be greater than 0").into());
+                }
+                if *oversold <= 0.0 || *oversold >= 100.0 || *overbought <= 0.0 || *overbought >= 100.0 {
+                    return Err(StrategyError::invalid_parameters("StochRSI levels must be between 0 and 100").into());
+                }
+                if *oversold >= *overbought {
+                    return Err(StrategyError::invalid_parameters("StochRSI oversold level must be less than overbought level").into());
+                }
+            }
+            StrategyType::Composite { children, .. } => {
+                if children.is_empty() {
+                    return Err(StrategyError::invalid_parameters("Composite strategy must have at least one child").into());
+                }
+                for child in children {
+                    Strategy::new(child.clone(), self.symbol.clone())?.validate()?;
+                }
+            }
+        }
+
+        if let Some(leverage) = self.parameters.leverage {
+            if !(1.0..=10.0).contains(&leverage) {
+                return Err(StrategyError::invalid_parameters("Leverage must be between 1.0 and 10.0").into());
             }
         }
 
@@ -126,6 +300,12 @@ impl Strategy {
     pub fn activate(&mut self) {
         self.is_active = true;
     }
+
+    /// Leverage multiplier for this strategy's positions, defaulting to 1x
+    /// (unleveraged) when `parameters.leverage` isn't set.
+    pub fn leverage(&self) -> f64 {
+        self.parameters.leverage.unwrap_or(1.0)
+    }
 }
 
 impl StrategyParameters {
@@ -140,17 +320,229 @@ impl StrategyParameters {
                 additional_params.insert("short_period".to_string(), serde_json::Value::Number((*short_period as u64).into()));
                 additional_params.insert("long_period".to_string(), serde_json::Value::Number((*long_period as u64).into()));
             }
-            StrategyType::RSI { oversold, overbought } => {
+            StrategyType::RSI { oversold, overbought, period } => {
+                additional_params.insert("oversold".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*oversold).unwrap()));
+                additional_params.insert("overbought".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*overbought).unwrap()));
+                additional_params.insert("period".to_string(), serde_json::Value::Number((period.unwrap_or(DEFAULT_RSI_PERIOD) as u64).into()));
+            }
+            StrategyType::Confluence {
+                short_period,
+                long_period,
+                rsi_oversold,
+                rsi_overbought,
+                stoch_period,
+                stoch_oversold,
+                stoch_overbought,
+            } => {
+                additional_params.insert("short_period".to_string(), serde_json::Value::Number((*short_period as u64).into()));
+                additional_params.insert("long_period".to_string(), serde_json::Value::Number((*long_period as u64).into()));
+                additional_params.insert("rsi_oversold".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*rsi_oversold).unwrap()));
+                additional_params.insert("rsi_overbought".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*rsi_overbought).unwrap()));
+                additional_params.insert("stoch_period".to_string(), serde_json::Value::Number((*stoch_period as u64).into()));
+                additional_params.insert("stoch_oversold".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*stoch_oversold).unwrap()));
+                additional_params.insert("stoch_overbought".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*stoch_overbought).unwrap()));
+            }
+            StrategyType::MACD { fast_period, slow_period, signal_period } => {
+                additional_params.insert("fast_period".to_string(), serde_json::Value::Number((*fast_period as u64).into()));
+                additional_params.insert("slow_period".to_string(), serde_json::Value::Number((*slow_period as u64).into()));
+                additional_params.insert("signal_period".to_string(), serde_json::Value::Number((*signal_period as u64).into()));
+            }
+            StrategyType::StochasticRSI {
+                rsi_period,
+                stoch_period,
+                k_smoothing,
+                d_smoothing,
+                oversold,
+                overbought,
+            } => {
+                additional_params.insert("rsi_period".to_string(), serde_json::Value::Number((*rsi_period as u64).into()));
+                additional_params.insert("stoch_period".to_string(), serde_json::Value::Number((*stoch_period as u64).into()));
+                additional_params.insert("k_smoothing".to_string(), serde_json::Value::Number((*k_smoothing as u64).into()));
+                additional_params.insert("d_smoothing".to_string(), serde_json::Value::Number((*d_smoothing as u64).into()));
+                additional_params.insert("oversold".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*oversold).unwrap()));
+                additional_params.insert("overbought".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*overbought).unwrap()));
+            }
+            StrategyType::CCI { oversold, overbought, period } => {
+                additional_params.insert("oversold".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*oversold).unwrap()));
+                additional_params.insert("overbought".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*overbought).unwrap()));
+                additional_params.insert("period".to_string(), serde_json::Value::Number((*period as u64).into()));
+            }
+            StrategyType::StochRSI {
+                rsi_period,
+                stoch_period,
+                k_smooth,
+                d_smooth,
+                oversold,
+                overbought,
+            } => {
+                additional_params.insert("rsi_period".to_string(), serde_json::Value::Number((*rsi_period as u64).into()));
+                additional_params.insert("stoch_period".to_string(), serde_json::Value::Number((*stoch_period as u64).into()));
+                additional_params.insert("k_smooth".to_string(), serde_json::Value::Number((*k_smooth as u64).into()));
+                additional_params.insert("d_smooth".to_string(), serde_json::Value::Number((*d_smooth as u64).into()));
                 additional_params.insert("oversold".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*oversold).unwrap()));
                 additional_params.insert("overbought".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(*overbought).unwrap()));
             }
+            StrategyType::Composite { children, mode } => {
+                additional_params.insert("child_count".to_string(), serde_json::Value::Number((children.len() as u64).into()));
+                let mode_str = match mode {
+                    CombineMode::All => "all",
+                    CombineMode::Any => "any",
+                };
+                additional_params.insert("mode".to_string(), serde_json::Value::String(mode_str.to_string()));
+            }
         }
 
         Ok(StrategyParameters {
             symbol: symbol.to_string(),
             additional_params,
+            take_profit_percent: None,
+            stop_loss_percent: None,
+            trailing_stop_percent: None,
+            leverage: None,
+            min_volume: None,
         })
     }
+
+    /// Attach position-exit thresholds consumed by `PositionManager`.
+    pub fn with_exit_thresholds(
+        mut self,
+        take_profit_percent: Option<f64>,
+        stop_loss_percent: Option<f64>,
+        trailing_stop_percent: Option<f64>,
+    ) -> Self {
+        self.take_profit_percent = take_profit_percent;
+        self.stop_loss_percent = stop_loss_percent;
+        self.trailing_stop_percent = trailing_stop_percent;
+        self
+    }
+
+    /// Attach a leverage multiplier (e.g. `2.0` for 2x), validated by
+    /// `Strategy::validate` to fall within 1.0–10.0.
+    pub fn with_leverage(mut self, leverage: f64) -> Self {
+        self.leverage = Some(leverage);
+        self
+    }
+
+    /// Set the volume floor consumed by `StrategyEngine`'s volume guard.
+    pub fn with_min_volume(mut self, min_volume: u64) -> Self {
+        self.min_volume = Some(min_volume);
+        self
+    }
+}
+
+/// Fluent builder for `Strategy`. Unlike `Strategy::new`, which can
+/// construct a `Strategy` with nonsensical parameters that only fail later
+/// at `validate()`, `build()` runs every validation check at construction
+/// time so an invalid strategy can never escape the builder.
+#[derive(Debug, Clone, Default)]
+pub struct StrategyBuilder {
+    symbol: Option<String>,
+    strategy_type: Option<StrategyType>,
+    initial_cash: Option<f64>,
+    is_active: bool,
+    take_profit_percent: Option<f64>,
+    stop_loss_percent: Option<f64>,
+    trailing_stop_percent: Option<f64>,
+    leverage: Option<f64>,
+    min_volume: Option<u64>,
+}
+
+impl StrategyBuilder {
+    pub fn new() -> Self {
+        StrategyBuilder {
+            is_active: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn symbol(mut self, symbol: impl Into<String>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    pub fn strategy_type(mut self, strategy_type: StrategyType) -> Self {
+        self.strategy_type = Some(strategy_type);
+        self
+    }
+
+    pub fn initial_cash(mut self, initial_cash: f64) -> Self {
+        self.initial_cash = Some(initial_cash);
+        self
+    }
+
+    pub fn active(mut self, is_active: bool) -> Self {
+        self.is_active = is_active;
+        self
+    }
+
+    pub fn exit_thresholds(
+        mut self,
+        take_profit_percent: Option<f64>,
+        stop_loss_percent: Option<f64>,
+        trailing_stop_percent: Option<f64>,
+    ) -> Self {
+        self.take_profit_percent = take_profit_percent;
+        self.stop_loss_percent = stop_loss_percent;
+        self.trailing_stop_percent = trailing_stop_percent;
+        self
+    }
+
+    pub fn leverage(mut self, leverage: f64) -> Self {
+        self.leverage = Some(leverage);
+        self
+    }
+
+    pub fn min_volume(mut self, min_volume: u64) -> Self {
+        self.min_volume = Some(min_volume);
+        self
+    }
+
+    /// Validate and assemble the accumulated fields into a `Strategy`.
+    /// Errors on a missing symbol/strategy type, a non-positive
+    /// `initial_cash`, or any of `Strategy::validate`'s checks (e.g.
+    /// `short_period >= long_period`, `oversold >= overbought`).
+    pub fn build(self) -> Result<Strategy> {
+        let symbol = self
+            .symbol
+            .ok_or_else(|| StrategyError::invalid_parameters("Symbol is required"))?;
+        let strategy_type = self
+            .strategy_type
+            .ok_or_else(|| StrategyError::invalid_parameters("Strategy type is required"))?;
+
+        if let Some(initial_cash) = self.initial_cash {
+            if initial_cash <= 0.0 {
+                return Err(StrategyError::invalid_parameters("Initial cash must be greater than 0").into());
+            }
+        }
+
+        let mut parameters = StrategyParameters::from_strategy_type(&strategy_type, &symbol)?
+            .with_exit_thresholds(self.take_profit_percent, self.stop_loss_percent, self.trailing_stop_percent);
+
+        if let Some(leverage) = self.leverage {
+            parameters = parameters.with_leverage(leverage);
+        }
+        if let Some(min_volume) = self.min_volume {
+            parameters = parameters.with_min_volume(min_volume);
+        }
+        if let Some(initial_cash) = self.initial_cash {
+            parameters.additional_params.insert(
+                "initial_cash".to_string(),
+                serde_json::Value::Number(serde_json::Number::from_f64(initial_cash).unwrap()),
+            );
+        }
+
+        let strategy = Strategy {
+            id: Uuid::new_v4().to_string(),
+            strategy_type,
+            symbol,
+            parameters,
+            created_at: Utc::now(),
+            is_active: self.is_active,
+        };
+
+        strategy.validate()?;
+        Ok(strategy)
+    }
 }
 
 impl TradingSignal {
@@ -196,6 +588,11 @@ impl Default for StrategyParameters {
         StrategyParameters {
             symbol: "AAPL".to_string(),
             additional_params: HashMap::new(),
+            take_profit_percent: None,
+            stop_loss_percent: None,
+            trailing_stop_percent: None,
+            leverage: None,
+            min_volume: None,
         }
     }
 }
@@ -207,6 +604,8 @@ impl std::fmt::Display for Action {
             Action::Buy => write!(f, "BUY"),
             Action::Sell => write!(f, "SELL"),
             Action::Hold => write!(f, "HOLD"),
+            Action::ShortSell => write!(f, "SHORT_SELL"),
+            Action::ExitShort => write!(f, "EXIT_SHORT"),
         }
     }
 }
@@ -218,13 +617,65 @@ impl std::fmt::Display for StrategyType {
             StrategyType::MovingAverage { short_period, long_period } => {
                 write!(f, "MovingAverage({}/{})", short_period, long_period)
             }
-            StrategyType::RSI { oversold, overbought } => {
-                write!(f, "RSI({}/{})", oversold, overbought)
+            StrategyType::RSI { oversold, overbought, period } => {
+                write!(f, "RSI({}/{}, period={})", oversold, overbought, period.unwrap_or(DEFAULT_RSI_PERIOD))
+            }
+            StrategyType::Confluence { short_period, long_period, .. } => {
+                write!(f, "Confluence({}/{})", short_period, long_period)
+            }
+            StrategyType::MACD { fast_period, slow_period, signal_period } => {
+                write!(f, "MACD({}/{}/{})", fast_period, slow_period, signal_period)
+            }
+            StrategyType::StochasticRSI { oversold, overbought, .. } => {
+                write!(f, "StochasticRSI({}/{})", oversold, overbought)
+            }
+            StrategyType::CCI { oversold, overbought, period } => {
+                write!(f, "CCI({}, {}/{})", period, oversold, overbought)
+            }
+            StrategyType::StochRSI { oversold, overbought, .. } => {
+                write!(f, "StochRSI({}/{})", oversold, overbought)
+            }
+            StrategyType::Composite { children, mode } => {
+                let mode_str = match mode {
+                    CombineMode::All => "All",
+                    CombineMode::Any => "Any",
+                };
+                write!(f, "Composite({}, {} children)", mode_str, children.len())
             }
         }
     }
 }
 
+/// Direction in which one series crossed another between two consecutive bars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cross {
+    Above,
+    Below,
+}
+
+/// Detects whether series `a` crossed series `b` between the previous and
+/// current bar, freqtrade `crossed_above`/`crossed_below` style. `None` means
+/// no cross (including both staying on the same side or an exact tie).
+pub fn series_cross(prev_a: f64, curr_a: f64, prev_b: f64, curr_b: f64) -> Option<Cross> {
+    if prev_a <= prev_b && curr_a > curr_b {
+        Some(Cross::Above)
+    } else if prev_a >= prev_b && curr_a < curr_b {
+        Some(Cross::Below)
+    } else {
+        None
+    }
+}
+
+/// True when `curr` has risen from at-or-below `level` to strictly above it.
+pub fn crossed_above(prev: f64, curr: f64, level: f64) -> bool {
+    prev <= level && curr > level
+}
+
+/// True when `curr` has fallen from at-or-above `level` to strictly below it.
+pub fn crossed_below(prev: f64, curr: f64, level: f64) -> bool {
+    prev >= level && curr < level
+}
+
 // Strategy Execution Engine
 
 /// Trait for executing trading strategies
@@ -251,6 +702,32 @@ impl StrategyEngine {
         engine.validate_parameters()?;
         Ok(engine)
     }
+
+    /// When the current bar's volume is at or below `parameters.min_volume`
+    /// (zero volume is always guarded, even with no floor configured),
+    /// downgrade a Buy/Sell/ShortSell/ExitShort signal to Hold, since a
+    /// signal on an illiquid bar could not actually be filled.
+    fn apply_volume_guard(&self, signal: TradingSignal, market_data: &MarketData) -> TradingSignal {
+        let floor = self.strategy.parameters.min_volume.unwrap_or(0);
+        if signal.action == Action::Hold || market_data.volume > floor {
+            return signal;
+        }
+
+        let explanation = format!(
+            "{} downgraded to HOLD: bar volume ({}) is at or below the illiquid-bar floor ({}), so the signal could not actually be filled.",
+            signal.action, market_data.volume, floor
+        );
+
+        TradingSignal::new(
+            signal.strategy_id,
+            signal.symbol,
+            Action::Hold,
+            signal.price,
+            explanation,
+            signal.confidence,
+            signal.metadata.strategy_data,
+        )
+    }
 }
 
 #[async_trait]
@@ -261,24 +738,94 @@ impl StrategyExecutor for StrategyEngine {
                 self.strategy.id.clone(),
                 self.strategy.symbol.clone(),
                 Action::Hold,
-                market_data.price,
+                market_data.price.to_f64(),
                 "Strategy is inactive".to_string(),
                 0.0,
                 HashMap::new(),
             ));
         }
 
-        match &self.strategy.strategy_type {
+        let signal = match &self.strategy.strategy_type {
             StrategyType::PriceDrop { threshold } => {
                 self.execute_price_drop_strategy(market_data, historical_data, *threshold).await
             }
             StrategyType::MovingAverage { short_period, long_period } => {
                 self.execute_moving_average_strategy(market_data, historical_data, *short_period, *long_period).await
             }
-            StrategyType::RSI { oversold, overbought } => {
-                self.execute_rsi_strategy(market_data, historical_data, *oversold, *overbought).await
+            StrategyType::RSI { oversold, overbought, period } => {
+                self.execute_rsi_strategy(market_data, historical_data, *oversold, *overbought, period.unwrap_or(DEFAULT_RSI_PERIOD)).await
             }
-        }
+            StrategyType::Confluence {
+                short_period,
+                long_period,
+                rsi_oversold,
+                rsi_overbought,
+                stoch_period,
+                stoch_oversold,
+                stoch_overbought,
+            } => {
+                self.execute_confluence_strategy(
+                    market_data,
+                    historical_data,
+                    *short_period,
+                    *long_period,
+                    *rsi_oversold,
+                    *rsi_overbought,
+                    *stoch_period,
+                    *stoch_oversold,
+                    *stoch_overbought,
+                ).await
+            }
+            StrategyType::MACD { fast_period, slow_period, signal_period } => {
+                self.execute_macd_strategy(market_data, historical_data, *fast_period, *slow_period, *signal_period).await
+            }
+            StrategyType::StochasticRSI {
+                rsi_period,
+                stoch_period,
+                k_smoothing,
+                d_smoothing,
+                oversold,
+                overbought,
+            } => {
+                self.execute_stochastic_rsi_strategy(
+                    market_data,
+                    historical_data,
+                    *rsi_period,
+                    *stoch_period,
+                    *k_smoothing,
+                    *d_smoothing,
+                    *oversold,
+                    *overbought,
+                ).await
+            }
+            StrategyType::CCI { oversold, overbought, period } => {
+                self.execute_cci_strategy(market_data, historical_data, *oversold, *overbought, *period).await
+            }
+            StrategyType::StochRSI {
+                rsi_period,
+                stoch_period,
+                k_smooth,
+                d_smooth,
+                oversold,
+                overbought,
+            } => {
+                self.execute_stoch_rsi_strategy(
+                    market_data,
+                    historical_data,
+                    *rsi_period,
+                    *stoch_period,
+                    *k_smooth,
+                    *d_smooth,
+                    *oversold,
+                    *overbought,
+                ).await
+            }
+            StrategyType::Composite { children, mode } => {
+                self.execute_composite_strategy(market_data, historical_data, children, mode).await
+            }
+        }?;
+
+        Ok(self.apply_volume_guard(signal, market_data))
     }
 
     fn validate_parameters(&self) -> Result<()> {
@@ -303,8 +850,8 @@ impl StrategyEngine {
         }
 
         // Get the most recent closing price for comparison
-        let previous_close = historical_data.last().unwrap().close;
-        let current_price = market_data.price;
+        let previous_close = historical_data.last().unwrap().close.to_f64();
+        let current_price = market_data.price.to_f64();
         
         // Calculate percentage change
         let price_change_percent = ((current_price - previous_close) / previous_close) * 100.0;
@@ -386,7 +933,7 @@ impl StrategyEngine {
             long_ma
         };
 
-        let current_price = market_data.price;
+        let current_price = market_data.price.to_f64();
         
         let mut strategy_data = HashMap::new();
         strategy_data.insert("short_ma".to_string(), short_ma);
@@ -395,20 +942,21 @@ impl StrategyEngine {
         strategy_data.insert("prev_long_ma".to_string(), prev_long_ma);
         strategy_data.insert("current_price".to_string(), current_price);
 
-        let (action, explanation, confidence) = if prev_short_ma <= prev_long_ma && short_ma > long_ma {
+        let ma_cross = series_cross(prev_short_ma, short_ma, prev_long_ma, long_ma);
+        let (action, explanation, confidence) = if ma_cross == Some(Cross::Above) {
             // Bullish crossover - BUY signal
             let explanation = format!(
                 "Bullish crossover detected: {}-period MA (${:.2}) crossed above {}-period MA (${:.2}). Current price: ${:.2}. This suggests upward momentum.",
                 short_period, short_ma, long_period, long_ma, current_price
             );
             (Action::Buy, explanation, 0.85)
-        } else if prev_short_ma >= prev_long_ma && short_ma < long_ma {
-            // Bearish crossover - SELL signal
+        } else if ma_cross == Some(Cross::Below) {
+            // Bearish crossover - open a short
             let explanation = format!(
                 "Bearish crossover detected: {}-period MA (${:.2}) crossed below {}-period MA (${:.2}). Current price: ${:.2}. This suggests downward momentum.",
                 short_period, short_ma, long_period, long_ma, current_price
             );
-            (Action::Sell, explanation, 0.85)
+            (Action::ShortSell, explanation, 0.85)
         } else {
             // No crossover - HOLD
             let trend = if short_ma > long_ma { "bullish" } else { "bearish" };
@@ -437,15 +985,14 @@ impl StrategyEngine {
         historical_data: &[PricePoint],
         oversold: f64,
         overbought: f64,
+        period: usize,
     ) -> Result<TradingSignal> {
-        const RSI_PERIOD: usize = 14;
-        
-        if historical_data.len() < RSI_PERIOD + 1 {
+        if historical_data.len() < period + 1 {
             return Err(StrategyError::InsufficientData.into());
         }
 
-        let rsi = self.calculate_rsi(historical_data, RSI_PERIOD)?;
-        let current_price = market_data.price;
+        let rsi = self.calculate_rsi(historical_data, period)?;
+        let current_price = market_data.price.to_f64();
         
         let mut strategy_data = HashMap::new();
         strategy_data.insert("rsi".to_string(), rsi);
@@ -461,12 +1008,12 @@ impl StrategyEngine {
             );
             (Action::Buy, explanation, 0.8)
         } else if rsi >= overbought {
-            // RSI indicates overbought condition - SELL signal
+            // RSI indicates overbought condition - open a short
             let explanation = format!(
                 "RSI at {:.1} indicates overbought condition (above {:.1}). Current price: ${:.2}. This suggests taking profits as the stock may be overvalued.",
                 rsi, overbought, current_price
             );
-            (Action::Sell, explanation, 0.8)
+            (Action::ShortSell, explanation, 0.8)
         } else {
             // RSI in neutral zone - HOLD
             let zone = if rsi > 50.0 { "bullish" } else { "bearish" };
@@ -488,6 +1035,141 @@ impl StrategyEngine {
         ))
     }
 
+    /// Execute the multi-indicator confluence strategy: a BUY/SELL only fires
+    /// when the MA crossover, RSI, and Stochastic %K all agree on direction.
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_confluence_strategy(
+        &self,
+        market_data: &MarketData,
+        historical_data: &[PricePoint],
+        short_period: usize,
+        long_period: usize,
+        rsi_oversold: f64,
+        rsi_overbought: f64,
+        stoch_period: usize,
+        stoch_oversold: f64,
+        stoch_overbought: f64,
+    ) -> Result<TradingSignal> {
+        const RSI_PERIOD: usize = 14;
+        let min_required = long_period.max(RSI_PERIOD + 1).max(stoch_period + 2);
+
+        if historical_data.len() < min_required {
+            return Err(StrategyError::InsufficientData.into());
+        }
+
+        let short_ma = self.calculate_simple_moving_average(historical_data, short_period)?;
+        let long_ma = self.calculate_simple_moving_average(historical_data, long_period)?;
+
+        let prev_short_ma = if historical_data.len() > short_period {
+            self.calculate_simple_moving_average(&historical_data[..historical_data.len() - 1], short_period)?
+        } else {
+            short_ma
+        };
+        let prev_long_ma = if historical_data.len() > long_period {
+            self.calculate_simple_moving_average(&historical_data[..historical_data.len() - 1], long_period)?
+        } else {
+            long_ma
+        };
+
+        let rsi = self.calculate_rsi(historical_data, RSI_PERIOD)?;
+        let (stoch_k, stoch_d) = self.calculate_stochastic(historical_data, stoch_period)?;
+        let current_price = market_data.price.to_f64();
+
+        let mut strategy_data = HashMap::new();
+        strategy_data.insert("short_ma".to_string(), short_ma);
+        strategy_data.insert("long_ma".to_string(), long_ma);
+        strategy_data.insert("rsi".to_string(), rsi);
+        strategy_data.insert("stoch_k".to_string(), stoch_k);
+        strategy_data.insert("stoch_d".to_string(), stoch_d);
+        strategy_data.insert("current_price".to_string(), current_price);
+
+        let ma_cross = series_cross(prev_short_ma, short_ma, prev_long_ma, long_ma);
+        let bullish_crossover = ma_cross == Some(Cross::Above);
+        let bearish_crossover = ma_cross == Some(Cross::Below);
+        let rsi_confirms_buy = rsi <= rsi_oversold;
+        let rsi_confirms_sell = rsi >= rsi_overbought;
+        let stoch_confirms_buy = stoch_k <= stoch_oversold;
+        let stoch_confirms_sell = stoch_k >= stoch_overbought;
+
+        let (action, explanation, confidence) = if bullish_crossover && rsi_confirms_buy && stoch_confirms_buy {
+            let explanation = format!(
+                "Confluence BUY: {}-period MA crossed above {}-period MA (${:.2} > ${:.2}), RSI {:.1} is oversold (<= {:.1}), and Stochastic %K {:.1} is oversold (<= {:.1}). All three indicators confirm.",
+                short_period, long_period, short_ma, long_ma, rsi, rsi_oversold, stoch_k, stoch_oversold
+            );
+            (Action::Buy, explanation, 0.9)
+        } else if bearish_crossover && rsi_confirms_sell && stoch_confirms_sell {
+            let explanation = format!(
+                "Confluence SELL: {}-period MA crossed below {}-period MA (${:.2} < ${:.2}), RSI {:.1} is overbought (>= {:.1}), and Stochastic %K {:.1} is overbought (>= {:.1}). All three indicators confirm.",
+                short_period, long_period, short_ma, long_ma, rsi, rsi_overbought, stoch_k, stoch_overbought
+            );
+            (Action::Sell, explanation, 0.9)
+        } else {
+            let mut failed_conditions = Vec::new();
+            if !bullish_crossover && !bearish_crossover {
+                failed_conditions.push("no MA crossover".to_string());
+            }
+            if !rsi_confirms_buy && !rsi_confirms_sell {
+                failed_conditions.push(format!("RSI {:.1} is neutral", rsi));
+            }
+            if !stoch_confirms_buy && !stoch_confirms_sell {
+                failed_conditions.push(format!("Stochastic %K {:.1} is neutral", stoch_k));
+            }
+            let reason = if failed_conditions.is_empty() {
+                "indicators disagree on direction".to_string()
+            } else {
+                failed_conditions.join(", ")
+            };
+
+            let explanation = format!(
+                "No confluence signal ({}). {}-period MA: ${:.2}, {}-period MA: ${:.2}, RSI: {:.1}, Stochastic %K/%D: {:.1}/{:.1}.",
+                reason, short_period, short_ma, long_period, long_ma, rsi, stoch_k, stoch_d
+            );
+            (Action::Hold, explanation, 0.5)
+        };
+
+        Ok(TradingSignal::new(
+            self.strategy.id.clone(),
+            self.strategy.symbol.clone(),
+            action,
+            current_price,
+            explanation,
+            confidence,
+            strategy_data,
+        ))
+    }
+
+    /// Calculate Stochastic %K (position of the latest close within the
+    /// period's high/low range) and %D (a 3-period SMA of %K).
+    fn calculate_stochastic(&self, data: &[PricePoint], period: usize) -> Result<(f64, f64)> {
+        if data.len() < period + 2 {
+            return Err(StrategyError::InsufficientData.into());
+        }
+
+        let percent_k = |window: &[PricePoint]| -> f64 {
+            let highest_high = window.iter().map(|p| p.high.to_f64()).fold(f64::MIN, f64::max);
+            let lowest_low = window.iter().map(|p| p.low.to_f64()).fold(f64::MAX, f64::min);
+            let close = window.last().unwrap().close.to_f64();
+
+            if (highest_high - lowest_low).abs() < f64::EPSILON {
+                50.0
+            } else {
+                (close - lowest_low) / (highest_high - lowest_low) * 100.0
+            }
+        };
+
+        let k = percent_k(&data[data.len() - period..]);
+
+        let d_samples = 3.min(data.len() - period + 1);
+        let d = (0..d_samples)
+            .map(|i| {
+                let end = data.len() - i;
+                percent_k(&data[end - period..end])
+            })
+            .sum::<f64>() / d_samples as f64;
+
+        Ok((k, d))
+    }
+
     /// Calculate Simple Moving Average
     fn calculate_simple_moving_average(&self, data: &[PricePoint], period: usize) -> Result<f64> {
         if data.len() < period {
@@ -497,13 +1179,16 @@ impl StrategyEngine {
         let sum: f64 = data.iter()
             .rev()
             .take(period)
-            .map(|point| point.close)
+            .map(|point| point.close.to_f64())
             .sum();
 
         Ok(sum / period as f64)
     }
 
-    /// Calculate RSI (Relative Strength Index)
+    /// Calculate RSI (Relative Strength Index) using Wilder's smoothing: the
+    /// average gain/loss is seeded with a simple mean over the first `period`
+    /// changes, then smoothed forward through the rest of the history with
+    /// `avg = (prev_avg * (period - 1) + current) / period`.
     fn calculate_rsi(&self, data: &[PricePoint], period: usize) -> Result<f64> {
         if data.len() < period + 1 {
             return Err(StrategyError::InsufficientData.into());
@@ -514,7 +1199,7 @@ impl StrategyEngine {
         let mut losses = Vec::new();
 
         for i in 1..data.len() {
-            let change = data[i].close - data[i-1].close;
+            let change = (data[i].close - data[i-1].close).to_f64();
             if change > 0.0 {
                 gains.push(change);
                 losses.push(0.0);
@@ -528,54 +1213,588 @@ impl StrategyEngine {
             return Err(StrategyError::InsufficientData.into());
         }
 
-        // Calculate average gains and losses for the period
-        let avg_gain: f64 = gains.iter().rev().take(period).sum::<f64>() / period as f64;
-        let avg_loss: f64 = losses.iter().rev().take(period).sum::<f64>() / period as f64;
+        // Seed with the simple mean of the first `period` changes, then walk
+        // forward applying Wilder's exponential smoothing.
+        let mut avg_gain: f64 = gains[..period].iter().sum::<f64>() / period as f64;
+        let mut avg_loss: f64 = losses[..period].iter().sum::<f64>() / period as f64;
+
+        for i in period..gains.len() {
+            avg_gain = (avg_gain * (period - 1) as f64 + gains[i]) / period as f64;
+            avg_loss = (avg_loss * (period - 1) as f64 + losses[i]) / period as f64;
+        }
 
         // Calculate RSI
         if avg_loss == 0.0 {
             return Ok(100.0); // All gains, RSI = 100
         }
+        if avg_gain == 0.0 {
+            return Ok(0.0); // All losses, RSI = 0
+        }
 
         let rs = avg_gain / avg_loss;
         let rsi = 100.0 - (100.0 / (1.0 + rs));
 
         Ok(rsi)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use chrono::Utc;
+    /// Execute MACD strategy: buys on a bullish MACD/signal-line crossover and
+    /// shorts on a bearish one.
+    async fn execute_macd_strategy(
+        &self,
+        market_data: &MarketData,
+        historical_data: &[PricePoint],
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    ) -> Result<TradingSignal> {
+        let min_required = slow_period + signal_period;
+        if historical_data.len() < min_required {
+            return Err(StrategyError::InsufficientData.into());
+        }
 
-    fn create_test_price_points(prices: &[f64]) -> Vec<PricePoint> {
-        prices.iter().enumerate().map(|(i, &price)| {
-            PricePoint {
-                timestamp: Utc::now() - chrono::Duration::days((prices.len() - i - 1) as i64),
-                open: price,
-                high: price * 1.02,
-                low: price * 0.98,
-                close: price,
-                volume: 1000000,
-                adjusted_close: Some(price),
-            }
-        }).collect()
-    }
+        let closes: Vec<f64> = historical_data.iter().map(|p| p.close.to_f64()).collect();
+        let fast_ema = self.calculate_ema_series(&closes, fast_period)?;
+        let slow_ema = self.calculate_ema_series(&closes, slow_period)?;
 
-    fn create_test_market_data(symbol: &str, price: f64) -> MarketData {
-        MarketData {
-            symbol: symbol.to_string(),
-            price,
-            volume: 1000000,
-            timestamp: Utc::now(),
-            change: 0.0,
-            change_percent: 0.0,
-            market_cap: Some(1000000000),
-            day_high: Some(price * 1.05),
-            day_low: Some(price * 0.95),
-            previous_close: Some(price),
-        }
+        // fast_ema starts earlier than slow_ema since it needs fewer seed
+        // points; offset aligns the two series on the same trailing dates.
+        let offset = fast_ema.len() - slow_ema.len();
+        let macd_line: Vec<f64> = slow_ema
+            .iter()
+            .enumerate()
+            .map(|(i, slow)| fast_ema[i + offset] - slow)
+            .collect();
+
+        if macd_line.len() < signal_period {
+            return Err(StrategyError::InsufficientData.into());
+        }
+        let signal_line = self.calculate_ema_series(&macd_line, signal_period)?;
+
+        let macd = *macd_line.last().unwrap();
+        let signal_value = *signal_line.last().unwrap();
+        let prev_macd = macd_line[macd_line.len() - 2];
+        let prev_signal = signal_line[signal_line.len() - 2];
+        let histogram = macd - signal_value;
+        let current_price = market_data.price.to_f64();
+
+        let mut strategy_data = HashMap::new();
+        strategy_data.insert("macd".to_string(), macd);
+        strategy_data.insert("signal".to_string(), signal_value);
+        strategy_data.insert("histogram".to_string(), histogram);
+        strategy_data.insert("current_price".to_string(), current_price);
+
+        let prev_histogram = prev_macd - prev_signal;
+        let bullish_cross = crossed_above(prev_histogram, histogram, 0.0);
+        let bearish_cross = crossed_below(prev_histogram, histogram, 0.0);
+        // Scale confidence with how sharply the histogram swung through zero
+        // this bar, relative to the current price, so a decisive crossover
+        // reads as more confident than a borderline one.
+        let swing_confidence = (0.6 + ((histogram - prev_histogram).abs() / current_price * 10.0)).min(0.95);
+
+        let (action, explanation, confidence) = if bullish_cross {
+            let explanation = format!(
+                "MACD bullish crossover: histogram swung from {:.3} to {:.3} as MACD ({:.3}) crossed above its signal line ({:.3}). Current price: ${:.2}.",
+                prev_histogram, histogram, macd, signal_value, current_price
+            );
+            (Action::Buy, explanation, swing_confidence)
+        } else if bearish_cross {
+            let explanation = format!(
+                "MACD bearish crossover: histogram swung from {:.3} to {:.3} as MACD ({:.3}) crossed below its signal line ({:.3}). Current price: ${:.2}.",
+                prev_histogram, histogram, macd, signal_value, current_price
+            );
+            (Action::ShortSell, explanation, swing_confidence)
+        } else {
+            let trend = if histogram > 0.0 { "bullish" } else { "bearish" };
+            let explanation = format!(
+                "No MACD crossover. MACD: {:.3}, Signal: {:.3}, Histogram: {:.3}. Current trend: {}. Current price: ${:.2}.",
+                macd, signal_value, histogram, trend, current_price
+            );
+            (Action::Hold, explanation, 0.6)
+        };
+
+        Ok(TradingSignal::new(
+            self.strategy.id.clone(),
+            self.strategy.symbol.clone(),
+            action,
+            current_price,
+            explanation,
+            confidence,
+            strategy_data,
+        ))
+    }
+
+    /// Execute Stochastic RSI strategy: buys when %K crosses up out of the
+    /// oversold zone and shorts when it crosses down out of the overbought
+    /// zone.
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_stochastic_rsi_strategy(
+        &self,
+        market_data: &MarketData,
+        historical_data: &[PricePoint],
+        rsi_period: usize,
+        stoch_period: usize,
+        k_smoothing: usize,
+        d_smoothing: usize,
+        oversold: f64,
+        overbought: f64,
+    ) -> Result<TradingSignal> {
+        let min_required = (rsi_period + stoch_period + k_smoothing + d_smoothing).saturating_sub(2);
+        if historical_data.len() < min_required {
+            return Err(StrategyError::InsufficientData.into());
+        }
+
+        let (k_series, d_series) = self.calculate_stochastic_rsi(
+            historical_data,
+            rsi_period,
+            stoch_period,
+            k_smoothing,
+            d_smoothing,
+        )?;
+
+        let current_k = *k_series.last().unwrap();
+        let current_d = *d_series.last().unwrap();
+        let prev_k = k_series[k_series.len() - 2];
+        let current_price = market_data.price.to_f64();
+
+        let mut strategy_data = HashMap::new();
+        strategy_data.insert("stoch_rsi_k".to_string(), current_k);
+        strategy_data.insert("stoch_rsi_d".to_string(), current_d);
+        strategy_data.insert("oversold_level".to_string(), oversold);
+        strategy_data.insert("overbought_level".to_string(), overbought);
+        strategy_data.insert("current_price".to_string(), current_price);
+
+        let bullish_cross = crossed_above(prev_k, current_k, oversold);
+        let bearish_cross = crossed_below(prev_k, current_k, overbought);
+
+        let (action, explanation, confidence) = if bullish_cross {
+            let explanation = format!(
+                "Stochastic RSI %K ({:.1}) crossed back above the oversold level ({:.1}). Current price: ${:.2}. This suggests a potential buying opportunity.",
+                current_k, oversold, current_price
+            );
+            (Action::Buy, explanation, 0.8)
+        } else if bearish_cross {
+            let explanation = format!(
+                "Stochastic RSI %K ({:.1}) crossed back below the overbought level ({:.1}). Current price: ${:.2}. This suggests taking profits.",
+                current_k, overbought, current_price
+            );
+            (Action::ShortSell, explanation, 0.8)
+        } else {
+            let zone = if current_k <= oversold {
+                "oversold"
+            } else if current_k >= overbought {
+                "overbought"
+            } else {
+                "neutral"
+            };
+            let explanation = format!(
+                "No Stochastic RSI crossover. %K: {:.1}, %D: {:.1}. Zone: {}. Current price: ${:.2}.",
+                current_k, current_d, zone, current_price
+            );
+            (Action::Hold, explanation, 0.6)
+        };
+
+        Ok(TradingSignal::new(
+            self.strategy.id.clone(),
+            self.strategy.symbol.clone(),
+            action,
+            current_price,
+            explanation,
+            confidence,
+            strategy_data,
+        ))
+    }
+
+    /// Calculate an EMA series (seeded with the SMA of the first `period`
+    /// values, then smoothed forward with alpha = 2 / (period + 1)). Returns
+    /// one value per input value from `period - 1` onward.
+    fn calculate_ema_series(&self, values: &[f64], period: usize) -> Result<Vec<f64>> {
+        if values.len() < period {
+            return Err(StrategyError::InsufficientData.into());
+        }
+
+        let alpha = 2.0 / (period as f64 + 1.0);
+        let seed = values[..period].iter().sum::<f64>() / period as f64;
+
+        let mut ema_series = Vec::with_capacity(values.len() - period + 1);
+        ema_series.push(seed);
+
+        for value in &values[period..] {
+            let prev = *ema_series.last().unwrap();
+            ema_series.push(value * alpha + prev * (1.0 - alpha));
+        }
+
+        Ok(ema_series)
+    }
+
+    /// Calculate a full RSI series using Wilder's smoothing, returning one
+    /// value per input point from `period` onward.
+    fn calculate_rsi_series(&self, data: &[PricePoint], period: usize) -> Result<Vec<f64>> {
+        if data.len() < period + 1 {
+            return Err(StrategyError::InsufficientData.into());
+        }
+
+        let mut gains = Vec::new();
+        let mut losses = Vec::new();
+        for i in 1..data.len() {
+            let change = (data[i].close - data[i - 1].close).to_f64();
+            if change > 0.0 {
+                gains.push(change);
+                losses.push(0.0);
+            } else {
+                gains.push(0.0);
+                losses.push(-change);
+            }
+        }
+
+        let mut avg_gain: f64 = gains[..period].iter().sum::<f64>() / period as f64;
+        let mut avg_loss: f64 = losses[..period].iter().sum::<f64>() / period as f64;
+
+        let rsi_of = |avg_gain: f64, avg_loss: f64| -> f64 {
+            if avg_loss == 0.0 {
+                100.0
+            } else if avg_gain == 0.0 {
+                0.0
+            } else {
+                let rs = avg_gain / avg_loss;
+                100.0 - (100.0 / (1.0 + rs))
+            }
+        };
+
+        let mut rsi_series = Vec::with_capacity(gains.len() - period + 1);
+        rsi_series.push(rsi_of(avg_gain, avg_loss));
+
+        for i in period..gains.len() {
+            avg_gain = (avg_gain * (period - 1) as f64 + gains[i]) / period as f64;
+            avg_loss = (avg_loss * (period - 1) as f64 + losses[i]) / period as f64;
+            rsi_series.push(rsi_of(avg_gain, avg_loss));
+        }
+
+        Ok(rsi_series)
+    }
+
+    /// Calculate the Stochastic RSI %K and %D series: RSI is rescaled into a
+    /// 0-100 stochastic range over `stoch_period`, %K is a `k_smoothing`-period
+    /// SMA of that raw value, and %D is a `d_smoothing`-period SMA of %K.
+    fn calculate_stochastic_rsi(
+        &self,
+        data: &[PricePoint],
+        rsi_period: usize,
+        stoch_period: usize,
+        k_smoothing: usize,
+        d_smoothing: usize,
+    ) -> Result<(Vec<f64>, Vec<f64>)> {
+        let rsi_series = self.calculate_rsi_series(data, rsi_period)?;
+
+        if rsi_series.len() < stoch_period {
+            return Err(StrategyError::InsufficientData.into());
+        }
+
+        let raw_stoch_rsi: Vec<f64> = (stoch_period - 1..rsi_series.len())
+            .map(|i| {
+                let window = &rsi_series[i + 1 - stoch_period..=i];
+                let highest = window.iter().cloned().fold(f64::MIN, f64::max);
+                let lowest = window.iter().cloned().fold(f64::MAX, f64::min);
+                let current = rsi_series[i];
+                if (highest - lowest).abs() < f64::EPSILON {
+                    50.0
+                } else {
+                    (current - lowest) / (highest - lowest) * 100.0
+                }
+            })
+            .collect();
+
+        if raw_stoch_rsi.len() < k_smoothing {
+            return Err(StrategyError::InsufficientData.into());
+        }
+        let k_series: Vec<f64> = (k_smoothing - 1..raw_stoch_rsi.len())
+            .map(|i| raw_stoch_rsi[i + 1 - k_smoothing..=i].iter().sum::<f64>() / k_smoothing as f64)
+            .collect();
+
+        if k_series.len() < d_smoothing {
+            return Err(StrategyError::InsufficientData.into());
+        }
+        let d_series: Vec<f64> = (d_smoothing - 1..k_series.len())
+            .map(|i| k_series[i + 1 - d_smoothing..=i].iter().sum::<f64>() / d_smoothing as f64)
+            .collect();
+
+        if k_series.len() < 2 {
+            return Err(StrategyError::InsufficientData.into());
+        }
+
+        Ok((k_series, d_series))
+    }
+
+    /// Execute CCI (Commodity Channel Index) strategy: buys when CCI rises
+    /// back above the oversold level and sells when it falls back below the
+    /// overbought level.
+    async fn execute_cci_strategy(
+        &self,
+        market_data: &MarketData,
+        historical_data: &[PricePoint],
+        oversold: f64,
+        overbought: f64,
+        period: usize,
+    ) -> Result<TradingSignal> {
+        if historical_data.len() < period + 1 {
+            return Err(StrategyError::InsufficientData.into());
+        }
+
+        let cci_series = self.calculate_cci_series(historical_data, period)?;
+        let current_cci = *cci_series.last().unwrap();
+        let prev_cci = cci_series[cci_series.len() - 2];
+        let current_price = market_data.price.to_f64();
+
+        let mut strategy_data = HashMap::new();
+        strategy_data.insert("cci".to_string(), current_cci);
+        strategy_data.insert("oversold_level".to_string(), oversold);
+        strategy_data.insert("overbought_level".to_string(), overbought);
+        strategy_data.insert("current_price".to_string(), current_price);
+
+        let bullish_cross = crossed_above(prev_cci, current_cci, oversold);
+        let bearish_cross = crossed_below(prev_cci, current_cci, overbought);
+
+        let (action, explanation, confidence) = if bullish_cross {
+            let explanation = format!(
+                "CCI ({:.1}) rose back above the oversold level ({:.1}). Current price: ${:.2}. This suggests a potential buying opportunity.",
+                current_cci, oversold, current_price
+            );
+            (Action::Buy, explanation, 0.75)
+        } else if bearish_cross {
+            let explanation = format!(
+                "CCI ({:.1}) fell back below the overbought level ({:.1}). Current price: ${:.2}. This suggests taking profits.",
+                current_cci, overbought, current_price
+            );
+            (Action::Sell, explanation, 0.75)
+        } else {
+            let explanation = format!(
+                "No CCI crossover. CCI: {:.1}, oversold: {:.1}, overbought: {:.1}. Current price: ${:.2}.",
+                current_cci, oversold, overbought, current_price
+            );
+            (Action::Hold, explanation, 0.6)
+        };
+
+        Ok(TradingSignal::new(
+            self.strategy.id.clone(),
+            self.strategy.symbol.clone(),
+            action,
+            current_price,
+            explanation,
+            confidence,
+            strategy_data,
+        ))
+    }
+
+    /// Calculate a CCI series: for each bar from `period - 1` onward,
+    /// `(TP - SMA(TP, period)) / (0.015 * mean_deviation)` where the typical
+    /// price `TP = (high + low + close) / 3`.
+    fn calculate_cci_series(&self, data: &[PricePoint], period: usize) -> Result<Vec<f64>> {
+        if data.len() < period {
+            return Err(StrategyError::InsufficientData.into());
+        }
+
+        let typical_prices: Vec<f64> = data
+            .iter()
+            .map(|p| (p.high + p.low + p.close).to_f64() / 3.0)
+            .collect();
+
+        let cci_series = (period - 1..typical_prices.len())
+            .map(|i| {
+                let window = &typical_prices[i + 1 - period..=i];
+                let sma = window.iter().sum::<f64>() / period as f64;
+                let mean_deviation = window.iter().map(|tp| (tp - sma).abs()).sum::<f64>() / period as f64;
+                if mean_deviation.abs() < f64::EPSILON {
+                    0.0
+                } else {
+                    (typical_prices[i] - sma) / (0.015 * mean_deviation)
+                }
+            })
+            .collect();
+
+        Ok(cci_series)
+    }
+
+    /// Execute StochRSI strategy: buys when %K crosses above %D while both
+    /// are in the oversold zone, and sells when %K crosses below %D while
+    /// both are in the overbought zone. A flat RSI window (zero denominator)
+    /// falls through to Hold via `calculate_stochastic_rsi`'s 50.0 default.
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_stoch_rsi_strategy(
+        &self,
+        market_data: &MarketData,
+        historical_data: &[PricePoint],
+        rsi_period: usize,
+        stoch_period: usize,
+        k_smooth: usize,
+        d_smooth: usize,
+        oversold: f64,
+        overbought: f64,
+    ) -> Result<TradingSignal> {
+        let min_required = (rsi_period + stoch_period + k_smooth + d_smooth).saturating_sub(2);
+        if historical_data.len() < min_required {
+            return Err(StrategyError::InsufficientData.into());
+        }
+
+        let (k_series, d_series) = self.calculate_stochastic_rsi(
+            historical_data,
+            rsi_period,
+            stoch_period,
+            k_smooth,
+            d_smooth,
+        )?;
+
+        if d_series.len() < 2 || k_series.len() < d_series.len() + 1 {
+            return Err(StrategyError::InsufficientData.into());
+        }
+
+        let k_offset = k_series.len() - d_series.len();
+        let current_k = *k_series.last().unwrap();
+        let current_d = *d_series.last().unwrap();
+        let prev_k = k_series[k_offset + d_series.len() - 2];
+        let prev_d = d_series[d_series.len() - 2];
+        let current_price = market_data.price.to_f64();
+
+        let mut strategy_data = HashMap::new();
+        strategy_data.insert("stoch_rsi_k".to_string(), current_k);
+        strategy_data.insert("stoch_rsi_d".to_string(), current_d);
+        strategy_data.insert("oversold_level".to_string(), oversold);
+        strategy_data.insert("overbought_level".to_string(), overbought);
+        strategy_data.insert("current_price".to_string(), current_price);
+
+        let kd_cross = series_cross(prev_k, current_k, prev_d, current_d);
+        let bullish_cross = kd_cross == Some(Cross::Above) && current_k <= oversold;
+        let bearish_cross = kd_cross == Some(Cross::Below) && current_k >= overbought;
+
+        let (action, explanation, confidence) = if bullish_cross {
+            let explanation = format!(
+                "StochRSI %K ({:.1}) crossed above %D ({:.1}) inside the oversold zone (<= {:.1}). Current price: ${:.2}. This suggests a potential buying opportunity.",
+                current_k, current_d, oversold, current_price
+            );
+            (Action::Buy, explanation, 0.8)
+        } else if bearish_cross {
+            let explanation = format!(
+                "StochRSI %K ({:.1}) crossed below %D ({:.1}) inside the overbought zone (>= {:.1}). Current price: ${:.2}. This suggests taking profits.",
+                current_k, current_d, overbought, current_price
+            );
+            (Action::Sell, explanation, 0.8)
+        } else {
+            let explanation = format!(
+                "No StochRSI %K/%D crossover in a trigger zone. %K: {:.1}, %D: {:.1}. Current price: ${:.2}.",
+                current_k, current_d, current_price
+            );
+            (Action::Hold, explanation, 0.6)
+        };
+
+        Ok(TradingSignal::new(
+            self.strategy.id.clone(),
+            self.strategy.symbol.clone(),
+            action,
+            current_price,
+            explanation,
+            confidence,
+            strategy_data,
+        ))
+    }
+
+    /// Execute a composite strategy: run every child against the same data
+    /// and reconcile their actions per `CombineMode`. `All` only emits a
+    /// signal when every child agrees on the same non-Hold action, averaging
+    /// their confidences; `Any` takes the highest-confidence non-Hold signal.
+    /// Either mode holds when no child agreement is reached.
+    async fn execute_composite_strategy(
+        &self,
+        market_data: &MarketData,
+        historical_data: &[PricePoint],
+        children: &[StrategyType],
+        mode: &CombineMode,
+    ) -> Result<TradingSignal> {
+        let mut child_signals = Vec::with_capacity(children.len());
+        for child in children {
+            let child_strategy = Strategy::new(child.clone(), self.strategy.symbol.clone())?;
+            let child_engine = StrategyEngine::new(child_strategy)?;
+            child_signals.push(child_engine.execute(market_data, historical_data).await?);
+        }
+
+        let current_price = market_data.price.to_f64();
+        let explanation = child_signals
+            .iter()
+            .enumerate()
+            .map(|(i, signal)| format!("[{}] {}: {}", i + 1, signal.action, signal.explanation))
+            .collect::<Vec<_>>()
+            .join(" | ");
+
+        let (action, confidence) = match mode {
+            CombineMode::All => {
+                let non_hold: Vec<&TradingSignal> = child_signals
+                    .iter()
+                    .filter(|signal| signal.action != Action::Hold)
+                    .collect();
+                let all_agree = !non_hold.is_empty()
+                    && non_hold.len() == child_signals.len()
+                    && non_hold.iter().all(|signal| signal.action == non_hold[0].action);
+
+                if all_agree {
+                    let avg_confidence =
+                        non_hold.iter().map(|signal| signal.confidence).sum::<f64>() / non_hold.len() as f64;
+                    (non_hold[0].action.clone(), avg_confidence)
+                } else {
+                    (Action::Hold, 0.5)
+                }
+            }
+            CombineMode::Any => child_signals
+                .iter()
+                .filter(|signal| signal.action != Action::Hold)
+                .max_by(|a, b| a.confidence.partial_cmp(&b.confidence).unwrap())
+                .map(|signal| (signal.action.clone(), signal.confidence))
+                .unwrap_or((Action::Hold, 0.5)),
+        };
+
+        Ok(TradingSignal::new(
+            self.strategy.id.clone(),
+            self.strategy.symbol.clone(),
+            action,
+            current_price,
+            explanation,
+            confidence,
+            HashMap::new(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn create_test_price_points(prices: &[f64]) -> Vec<PricePoint> {
+        prices.iter().enumerate().map(|(i, &price)| {
+            PricePoint {
+                timestamp: Utc::now() - chrono::Duration::days((prices.len() - i - 1) as i64),
+                open: Price::from_f64(price),
+                high: Price::from_f64(price * 1.02),
+                low: Price::from_f64(price * 0.98),
+                close: Price::from_f64(price),
+                volume: 1000000,
+                adjusted_close: Some(Price::from_f64(price)),
+                market_status: None,
+            }
+        }).collect()
+    }
+
+    fn create_test_market_data(symbol: &str, price: f64) -> MarketData {
+        MarketData {
+            symbol: symbol.to_string(),
+            price: Price::from_f64(price),
+            volume: 1000000,
+            timestamp: Utc::now(),
+            change: Price::ZERO,
+            change_percent: 0.0,
+            market_cap: Some(1000000000),
+            day_high: Some(Price::from_f64(price * 1.05)),
+            day_low: Some(Price::from_f64(price * 0.95)),
+            previous_close: Some(Price::from_f64(price)),
+            confidence: 0.0,
+            publish_time: Utc::now(),
+        }
     }
 
     #[tokio::test]
@@ -682,7 +1901,7 @@ mod tests {
 
         let signal = engine.execute(&market_data, &historical_data).await.unwrap();
         
-        assert_eq!(signal.action, Action::Sell);
+        assert_eq!(signal.action, Action::ShortSell);
         assert!(signal.confidence > 0.8);
         assert!(signal.explanation.contains("Bearish crossover"));
     }
@@ -690,7 +1909,7 @@ mod tests {
     #[tokio::test]
     async fn test_rsi_strategy_oversold_buy_signal() {
         let strategy = Strategy::new(
-            StrategyType::RSI { oversold: 30.0, overbought: 70.0 },
+            StrategyType::RSI { oversold: 30.0, overbought: 70.0, period: None },
             "AAPL".to_string(),
         ).unwrap();
 
@@ -709,9 +1928,9 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_rsi_strategy_overbought_sell_signal() {
+    async fn test_rsi_strategy_overbought_short_sell_signal() {
         let strategy = Strategy::new(
-            StrategyType::RSI { oversold: 30.0, overbought: 70.0 },
+            StrategyType::RSI { oversold: 30.0, overbought: 70.0, period: None },
             "AAPL".to_string(),
         ).unwrap();
 
@@ -724,13 +1943,98 @@ mod tests {
 
         let signal = engine.execute(&market_data, &historical_data).await.unwrap();
         
-        assert_eq!(signal.action, Action::Sell);
+        assert_eq!(signal.action, Action::ShortSell);
         assert!(signal.confidence > 0.7);
         assert!(signal.explanation.contains("overbought"));
     }
 
     #[tokio::test]
-    async fn test_inactive_strategy_returns_hold() {
+    async fn test_rsi_strategy_respects_custom_period() {
+        let strategy = Strategy::new(
+            StrategyType::RSI { oversold: 30.0, overbought: 70.0, period: Some(5) },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        // Only 6 points: too few for the default 14-period RSI, but enough
+        // for the configured 5-period RSI.
+        let prices: Vec<f64> = (0..6).map(|i| 100.0 - (i as f64 * 2.0)).collect();
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 90.0);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Buy);
+        assert!(signal.explanation.contains("oversold"));
+    }
+
+    #[tokio::test]
+    async fn test_confluence_strategy_buy_requires_all_three_confirmations() {
+        let strategy = Strategy::new(
+            StrategyType::Confluence {
+                short_period: 2,
+                long_period: 5,
+                rsi_oversold: 35.0,
+                rsi_overbought: 70.0,
+                stoch_period: 10,
+                stoch_oversold: 30.0,
+                stoch_overbought: 70.0,
+            },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        // A decline that slows to 1-point steps, then a 4.5-point uptick: the
+        // 2-bar MA crosses above the 5-bar MA while RSI (about 13.8) and the
+        // 10-bar Stochastic %K (25) are both still in oversold territory.
+        let prices: Vec<f64> = vec![
+            88.0, 86.0, 84.0, 82.0, 81.0, 80.0, 75.0, 70.0, 66.0, 64.0, 63.0, 62.0, 61.0, 60.0, 64.5,
+        ];
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 64.5);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Buy);
+        assert_eq!(signal.confidence, 0.9);
+        assert!(signal.explanation.contains("Confluence BUY"));
+    }
+
+    #[tokio::test]
+    async fn test_confluence_strategy_holds_when_only_crossover_confirms() {
+        let strategy = Strategy::new(
+            StrategyType::Confluence {
+                short_period: 2,
+                long_period: 5,
+                rsi_oversold: 10.0,
+                rsi_overbought: 90.0,
+                stoch_period: 10,
+                stoch_oversold: 10.0,
+                stoch_overbought: 90.0,
+            },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        // Same bars as the buy test: the MA crossover confirms, but RSI (about
+        // 13.8) and %K (25) sit above the 10 oversold levels.
+        let prices: Vec<f64> = vec![
+            88.0, 86.0, 84.0, 82.0, 81.0, 80.0, 75.0, 70.0, 66.0, 64.0, 63.0, 62.0, 61.0, 60.0, 64.5,
+        ];
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 64.5);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Hold);
+        assert!(signal.explanation.contains("No confluence signal"));
+    }
+
+    #[tokio::test]
+    async fn test_inactive_strategy_returns_hold() {
         let mut strategy = Strategy::new(
             StrategyType::PriceDrop { threshold: 5.0 },
             "AAPL".to_string(),
@@ -771,4 +2075,415 @@ mod tests {
             other => panic!("Expected InsufficientData error, got: {:?}", other),
         }
     }
+
+    #[test]
+    fn test_leverage_validation() {
+        let mut strategy = Strategy::new(
+            StrategyType::PriceDrop { threshold: 5.0 },
+            "AAPL".to_string(),
+        ).unwrap();
+        assert!(strategy.validate().is_ok());
+        assert_eq!(strategy.leverage(), 1.0);
+
+        strategy.parameters = strategy.parameters.with_leverage(5.0);
+        assert!(strategy.validate().is_ok());
+        assert_eq!(strategy.leverage(), 5.0);
+
+        strategy.parameters = strategy.parameters.with_leverage(15.0);
+        assert!(strategy.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_macd_strategy_bullish_cross_buy_signal() {
+        let strategy = Strategy::new(
+            StrategyType::MACD { fast_period: 3, slow_period: 6, signal_period: 3 },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        // A choppy run whose histogram dips negative then swings back
+        // through zero on the final bar.
+        let prices: Vec<f64> = vec![
+            100.0, 103.49345978649976, 104.54957738586897, 101.69286983139789,
+            102.63199685972415, 101.68048895338842, 98.76701788187107, 98.00966023688363,
+            101.20942143607282,
+        ];
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 101.20942143607282);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Buy);
+        assert!(signal.confidence > 0.6 && signal.confidence <= 0.95);
+        assert!(signal.explanation.contains("MACD bullish crossover"));
+    }
+
+    #[tokio::test]
+    async fn test_macd_strategy_bearish_cross_short_sell_signal() {
+        let strategy = Strategy::new(
+            StrategyType::MACD { fast_period: 3, slow_period: 6, signal_period: 3 },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        // A choppy rally whose histogram runs positive then swings back
+        // through zero on the final bar.
+        let prices: Vec<f64> = vec![
+            100.0, 98.78693616532041, 99.89995966314531, 103.65744828127234,
+            106.72848938625546, 106.08079194135438, 107.82253520017166, 109.25495081334515,
+            108.23182776770759, 106.1115352676953, 102.7385470928251, 105.76981858790046,
+            102.22709229517483, 102.37666129322602, 102.67695231327153, 99.84606618256439,
+            101.09351991818127, 104.42010546677258, 106.62916922536488, 105.74324335831328,
+            102.75500302898334,
+        ];
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 102.75500302898334);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::ShortSell);
+        assert!(signal.confidence > 0.6 && signal.confidence <= 0.95);
+        assert!(signal.explanation.contains("MACD bearish crossover"));
+    }
+
+    #[tokio::test]
+    async fn test_stochastic_rsi_strategy_bullish_cross_buy_signal() {
+        let strategy = Strategy::new(
+            StrategyType::StochasticRSI {
+                rsi_period: 5,
+                stoch_period: 5,
+                k_smoothing: 3,
+                d_smoothing: 3,
+                oversold: 20.0,
+                overbought: 80.0,
+            },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        let prices: Vec<f64> = vec![
+            100.0, 97.67, 99.12, 97.59, 95.42, 93.04, 94.48, 94.76, 95.30, 92.49, 90.05, 88.45,
+            89.06, 89.43,
+        ];
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 89.43);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Buy);
+        assert_eq!(signal.confidence, 0.8);
+        assert!(signal.explanation.contains("crossed back above the oversold level"));
+    }
+
+    #[tokio::test]
+    async fn test_stochastic_rsi_strategy_bearish_cross_short_sell_signal() {
+        let strategy = Strategy::new(
+            StrategyType::StochasticRSI {
+                rsi_period: 5,
+                stoch_period: 5,
+                k_smoothing: 3,
+                d_smoothing: 3,
+                oversold: 20.0,
+                overbought: 80.0,
+            },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        let prices: Vec<f64> = vec![
+            100.0, 102.69, 102.06, 99.35, 101.27, 98.84, 99.34, 101.79, 100.08, 97.60, 97.11,
+            95.55, 95.86, 93.21, 93.60, 96.29, 97.07, 97.57, 94.94,
+        ];
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 94.94);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::ShortSell);
+        assert_eq!(signal.confidence, 0.8);
+        assert!(signal.explanation.contains("crossed back below the overbought level"));
+    }
+
+    #[tokio::test]
+    async fn test_cci_strategy_bullish_cross_buy_signal() {
+        let strategy = Strategy::new(
+            StrategyType::CCI { oversold: -100.0, overbought: 100.0, period: 5 },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        let prices: Vec<f64> = vec![100.0, 100.55, 102.97, 99.48, 96.42, 98.51];
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 98.51);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Buy);
+        assert_eq!(signal.confidence, 0.75);
+        assert!(signal.explanation.contains("rose back above the oversold level"));
+    }
+
+    #[tokio::test]
+    async fn test_cci_strategy_bearish_cross_sell_signal() {
+        let strategy = Strategy::new(
+            StrategyType::CCI { oversold: -100.0, overbought: 100.0, period: 5 },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        let prices: Vec<f64> = vec![100.0, 100.74, 97.78, 101.11, 100.90, 101.55, 102.40, 105.67, 105.42];
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 105.42);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Sell);
+        assert_eq!(signal.confidence, 0.75);
+        assert!(signal.explanation.contains("fell back below the overbought level"));
+    }
+
+    #[tokio::test]
+    async fn test_stoch_rsi_strategy_bullish_cross_buy_signal() {
+        let strategy = Strategy::new(
+            StrategyType::StochRSI {
+                rsi_period: 5,
+                stoch_period: 5,
+                k_smooth: 3,
+                d_smooth: 3,
+                oversold: 20.0,
+                overbought: 80.0,
+            },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        let prices: Vec<f64> = vec![
+            100.0, 98.26, 97.63, 99.76, 100.61, 98.21, 101.14, 99.42, 97.97, 99.61, 98.58, 97.36,
+            94.80, 92.34, 92.84,
+        ];
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 92.84);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Buy);
+        assert_eq!(signal.confidence, 0.8);
+        assert!(signal.explanation.contains("crossed above %D inside the oversold zone"));
+    }
+
+    #[tokio::test]
+    async fn test_stoch_rsi_strategy_bearish_cross_sell_signal() {
+        let strategy = Strategy::new(
+            StrategyType::StochRSI {
+                rsi_period: 5,
+                stoch_period: 5,
+                k_smooth: 3,
+                d_smooth: 3,
+                oversold: 20.0,
+                overbought: 80.0,
+            },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        let prices: Vec<f64> = vec![
+            100.0, 102.35, 99.86, 100.41, 99.95, 100.13, 97.91, 96.06, 95.73, 94.06, 93.79, 90.94,
+            88.45, 89.71, 89.24, 89.32, 90.72, 89.87,
+        ];
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 89.87);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Sell);
+        assert_eq!(signal.confidence, 0.8);
+        assert!(signal.explanation.contains("crossed below %D inside the overbought zone"));
+    }
+
+    #[tokio::test]
+    async fn test_composite_strategy_all_mode_agreement_emits_buy() {
+        let strategy = Strategy::new(
+            StrategyType::Composite {
+                children: vec![
+                    StrategyType::PriceDrop { threshold: 2.0 },
+                    StrategyType::PriceDrop { threshold: 1.0 },
+                ],
+                mode: CombineMode::All,
+            },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        let historical_data = create_test_price_points(&[100.0]);
+        let market_data = create_test_market_data("AAPL", 90.0);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Buy);
+        assert_eq!(signal.confidence, 0.8);
+        assert!(signal.explanation.contains("[1]"));
+        assert!(signal.explanation.contains("[2]"));
+    }
+
+    #[tokio::test]
+    async fn test_composite_strategy_all_mode_disagreement_holds() {
+        let strategy = Strategy::new(
+            StrategyType::Composite {
+                children: vec![
+                    StrategyType::PriceDrop { threshold: 2.0 },
+                    StrategyType::RSI { oversold: 30.0, overbought: 70.0, period: None },
+                ],
+                mode: CombineMode::All,
+            },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        // Rising historical data pushes RSI into overbought (ShortSell), while
+        // the current price sits well below the last close (Buy for PriceDrop).
+        let prices: Vec<f64> = (0..20).map(|i| 100.0 + (i as f64 * 2.0)).collect();
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 130.0);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Hold);
+        assert_eq!(signal.confidence, 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_composite_strategy_any_mode_takes_first_non_hold() {
+        let strategy = Strategy::new(
+            StrategyType::Composite {
+                children: vec![
+                    StrategyType::PriceDrop { threshold: 50.0 },
+                    StrategyType::RSI { oversold: 30.0, overbought: 70.0, period: None },
+                ],
+                mode: CombineMode::Any,
+            },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        let prices: Vec<f64> = (0..20).map(|i| 100.0 + (i as f64 * 2.0)).collect();
+        let historical_data = create_test_price_points(&prices);
+        let market_data = create_test_market_data("AAPL", 138.0);
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::ShortSell);
+        assert!(signal.confidence > 0.7);
+    }
+
+    #[tokio::test]
+    async fn test_volume_guard_downgrades_buy_on_zero_volume_bar() {
+        let strategy = Strategy::new(
+            StrategyType::PriceDrop { threshold: 5.0 },
+            "AAPL".to_string(),
+        ).unwrap();
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        let historical_data = create_test_price_points(&[100.0]);
+        let mut market_data = create_test_market_data("AAPL", 94.0);
+        market_data.volume = 0;
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Hold);
+        assert!(signal.explanation.contains("downgraded to HOLD"));
+        assert!(signal.explanation.contains("illiquid-bar floor"));
+    }
+
+    #[tokio::test]
+    async fn test_volume_guard_respects_configured_floor() {
+        let mut strategy = Strategy::new(
+            StrategyType::PriceDrop { threshold: 5.0 },
+            "AAPL".to_string(),
+        ).unwrap();
+        strategy.parameters = strategy.parameters.with_min_volume(500);
+
+        let engine = StrategyEngine::new(strategy).unwrap();
+
+        let historical_data = create_test_price_points(&[100.0]);
+        let mut market_data = create_test_market_data("AAPL", 94.0);
+        market_data.volume = 200;
+
+        let signal = engine.execute(&market_data, &historical_data).await.unwrap();
+
+        assert_eq!(signal.action, Action::Hold);
+        assert!(signal.explanation.contains("downgraded to HOLD"));
+    }
+
+    #[test]
+    fn test_series_cross_detects_direction() {
+        assert_eq!(series_cross(1.0, 3.0, 2.0, 2.0), Some(Cross::Above));
+        assert_eq!(series_cross(3.0, 1.0, 2.0, 2.0), Some(Cross::Below));
+        assert_eq!(series_cross(1.0, 1.5, 2.0, 2.5), None);
+    }
+
+    #[test]
+    fn test_strategy_builder_builds_valid_strategy() {
+        let strategy = StrategyBuilder::new()
+            .symbol("AAPL")
+            .strategy_type(StrategyType::PriceDrop { threshold: 5.0 })
+            .initial_cash(10000.0)
+            .active(false)
+            .leverage(2.0)
+            .build()
+            .unwrap();
+
+        assert_eq!(strategy.symbol, "AAPL");
+        assert!(!strategy.is_active);
+        assert_eq!(strategy.leverage(), 2.0);
+    }
+
+    #[test]
+    fn test_strategy_builder_rejects_missing_symbol() {
+        let result = StrategyBuilder::new()
+            .strategy_type(StrategyType::PriceDrop { threshold: 5.0 })
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_strategy_builder_rejects_invalid_parameters() {
+        let result = StrategyBuilder::new()
+            .symbol("AAPL")
+            .strategy_type(StrategyType::MovingAverage { short_period: 50, long_period: 10 })
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_strategy_builder_rejects_non_positive_initial_cash() {
+        let result = StrategyBuilder::new()
+            .symbol("AAPL")
+            .strategy_type(StrategyType::PriceDrop { threshold: 5.0 })
+            .initial_cash(-100.0)
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_crossed_above_and_below() {
+        assert!(crossed_above(29.0, 31.0, 30.0));
+        assert!(!crossed_above(31.0, 32.0, 30.0));
+        assert!(crossed_below(71.0, 69.0, 70.0));
+        assert!(!crossed_below(69.0, 68.0, 70.0));
+    }
 }
\ No newline at end of file
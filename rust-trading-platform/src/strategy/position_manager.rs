@@ -0,0 +1,194 @@
+// Position-exit management layered on top of raw strategy signals
+
+use crate::data::MarketData;
+use crate::strategy::{Action, TradingSignal};
+
+/// Tracks an open long position and overrides a strategy's raw action with a
+/// forced Sell when a take-profit, stop-loss, or trailing-stop threshold
+/// trips. The trailing stop ratchets up with the highest price seen since
+/// entry and fires when price falls that percentage below the peak.
+#[derive(Debug, Clone)]
+pub struct PositionManager {
+    take_profit_percent: Option<f64>,
+    stop_loss_percent: Option<f64>,
+    trailing_stop_percent: Option<f64>,
+    entry_price: Option<f64>,
+    peak_price: Option<f64>,
+}
+
+impl PositionManager {
+    pub fn new(
+        take_profit_percent: Option<f64>,
+        stop_loss_percent: Option<f64>,
+        trailing_stop_percent: Option<f64>,
+    ) -> Self {
+        PositionManager {
+            take_profit_percent,
+            stop_loss_percent,
+            trailing_stop_percent,
+            entry_price: None,
+            peak_price: None,
+        }
+    }
+
+    pub fn is_in_position(&self) -> bool {
+        self.entry_price.is_some()
+    }
+
+    /// Apply exit management on top of a raw signal. Enters position tracking
+    /// on a Buy, then on every later call checks take-profit and stop-loss
+    /// against the entry price and the trailing stop against the peak price
+    /// seen since entry, and overrides the action with a forced Sell (and
+    /// explanation) when one trips.
+    pub fn apply(&mut self, mut signal: TradingSignal, market_data: &MarketData) -> TradingSignal {
+        let current_price = market_data.price.to_f64();
+
+        let (entry_price, peak_price) = match (self.entry_price, self.peak_price) {
+            (Some(entry_price), Some(peak_price)) => (entry_price, peak_price.max(current_price)),
+            _ => {
+                if signal.action == Action::Buy {
+                    self.entry_price = Some(current_price);
+                    self.peak_price = Some(current_price);
+                }
+                return signal;
+            }
+        };
+        self.peak_price = Some(peak_price);
+
+        let gain_percent = (current_price - entry_price) / entry_price * 100.0;
+        let drop_from_peak_percent = (peak_price - current_price) / peak_price * 100.0;
+
+        let exit_reason = self.check_take_profit(gain_percent, current_price, entry_price)
+            .or_else(|| self.check_stop_loss(gain_percent, current_price, entry_price))
+            .or_else(|| self.check_trailing_stop(drop_from_peak_percent, current_price, peak_price));
+
+        if let Some(reason) = exit_reason {
+            signal.action = Action::Sell;
+            signal.explanation = reason;
+            signal.confidence = 0.95;
+            signal.metadata.strategy_data.insert("entry_price".to_string(), entry_price);
+            signal.metadata.strategy_data.insert("peak_price".to_string(), peak_price);
+            self.entry_price = None;
+            self.peak_price = None;
+            return signal;
+        }
+
+        if signal.action == Action::Sell {
+            self.entry_price = None;
+            self.peak_price = None;
+        }
+
+        signal
+    }
+
+    fn check_take_profit(&self, gain_percent: f64, current_price: f64, entry_price: f64) -> Option<String> {
+        let take_profit = self.take_profit_percent?;
+        if gain_percent >= take_profit {
+            Some(format!(
+                "take-profit hit at ${:.2}, entry was ${:.2} ({:.2}% gain)",
+                current_price, entry_price, gain_percent
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn check_stop_loss(&self, gain_percent: f64, current_price: f64, entry_price: f64) -> Option<String> {
+        let stop_loss = self.stop_loss_percent?;
+        if gain_percent <= -stop_loss {
+            Some(format!(
+                "stop-loss hit at ${:.2}, entry was ${:.2} ({:.2}% loss)",
+                current_price, entry_price, gain_percent.abs()
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn check_trailing_stop(&self, drop_from_peak_percent: f64, current_price: f64, peak_price: f64) -> Option<String> {
+        let trailing_stop = self.trailing_stop_percent?;
+        if drop_from_peak_percent >= trailing_stop {
+            Some(format!(
+                "trailing stop hit at ${:.2}, peak was ${:.2}",
+                current_price, peak_price
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::{Action, TradingSignal};
+    use std::collections::HashMap;
+
+    fn signal(action: Action, price: f64) -> TradingSignal {
+        TradingSignal::new(
+            "strategy-1".to_string(),
+            "AAPL".to_string(),
+            action,
+            price,
+            "raw signal".to_string(),
+            0.7,
+            HashMap::new(),
+        )
+    }
+
+    fn market_data(price: f64) -> MarketData {
+        MarketData::new("AAPL".to_string(), price, 1_000_000)
+    }
+
+    #[test]
+    fn test_take_profit_forces_sell() {
+        let mut manager = PositionManager::new(Some(10.0), Some(5.0), None);
+
+        let entry = manager.apply(signal(Action::Buy, 100.0), &market_data(100.0));
+        assert_eq!(entry.action, Action::Buy);
+        assert!(manager.is_in_position());
+
+        let exit = manager.apply(signal(Action::Hold, 111.0), &market_data(111.0));
+        assert_eq!(exit.action, Action::Sell);
+        assert!(exit.explanation.contains("take-profit hit"));
+        assert!(!manager.is_in_position());
+    }
+
+    #[test]
+    fn test_stop_loss_forces_sell() {
+        let mut manager = PositionManager::new(Some(10.0), Some(5.0), None);
+
+        manager.apply(signal(Action::Buy, 100.0), &market_data(100.0));
+        let exit = manager.apply(signal(Action::Hold, 94.0), &market_data(94.0));
+
+        assert_eq!(exit.action, Action::Sell);
+        assert!(exit.explanation.contains("stop-loss hit"));
+    }
+
+    #[test]
+    fn test_trailing_stop_ratchets_up_with_peak() {
+        let mut manager = PositionManager::new(None, None, Some(5.0));
+
+        manager.apply(signal(Action::Buy, 100.0), &market_data(100.0));
+        // Price rallies to a new peak; no exit yet since it hasn't pulled back.
+        let rally = manager.apply(signal(Action::Hold, 120.0), &market_data(120.0));
+        assert_eq!(rally.action, Action::Hold);
+
+        // Pulls back more than 5% from the $120 peak (not from the $100 entry).
+        let exit = manager.apply(signal(Action::Hold, 113.0), &market_data(113.0));
+        assert_eq!(exit.action, Action::Sell);
+        assert!(exit.explanation.contains("trailing stop hit"));
+        assert!(exit.explanation.contains("peak was $120.00"));
+    }
+
+    #[test]
+    fn test_no_exit_when_within_thresholds() {
+        let mut manager = PositionManager::new(Some(10.0), Some(5.0), Some(5.0));
+
+        manager.apply(signal(Action::Buy, 100.0), &market_data(100.0));
+        let hold = manager.apply(signal(Action::Hold, 102.0), &market_data(102.0));
+
+        assert_eq!(hold.action, Action::Hold);
+        assert!(manager.is_in_position());
+    }
+}
@@ -2,10 +2,39 @@
 
 use crate::strategy::{Action, StrategyType, RiskLevel};
 use crate::data::MarketData;
+use crate::error::Result;
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Default lookback for `MarketContext::analyze_support_resistance`.
+pub const DEFAULT_DONCHIAN_PERIOD: usize = 20;
+
+/// Default lookback for `MarketContext::analyze_sentiment`'s Wilder's RSI.
+pub const DEFAULT_RSI_PERIOD: usize = 14;
+
+/// Default tradeable annualized log-return volatility band: below this, an
+/// instrument is too quiet to profit from; above it, too wild to risk.
+pub const DEFAULT_MIN_TRADEABLE_VOLATILITY: f64 = 0.10;
+pub const DEFAULT_MAX_TRADEABLE_VOLATILITY: f64 = 0.80;
+
+/// EMA period `analyze_vsa` uses to establish the "average" volume a bar is
+/// compared against.
+pub const DEFAULT_VSA_EMA_PERIOD: usize = 20;
+
+/// Defaults for `ScenarioEngine::build_scenarios`'s three-indicator
+/// confluence model.
+pub const DEFAULT_ATR_PERIOD: usize = 14;
+pub const DEFAULT_BOLLINGER_PERIOD: usize = 20;
+pub const DEFAULT_BOLLINGER_K: f64 = 2.0;
+pub const DEFAULT_KELTNER_ATR_MULTIPLIER: f64 = 1.5;
+pub const DEFAULT_CHANDELIER_ATR_MULTIPLIER: f64 = 3.0;
+
+/// Fraction of the support/resistance band width treated as "near" a level
+/// when deriving `PricePosition`.
+const SUPPORT_RESISTANCE_TOLERANCE: f64 = 0.05;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ExplanationContext {
     pub strategy_type: StrategyType,
@@ -36,6 +65,9 @@ pub struct MarketContext {
     pub volume_analysis: VolumeAnalysis,
     pub support_resistance: Option<SupportResistance>,
     pub market_sentiment: MarketSentiment,
+    /// Annualized log-return volatility set by `analyze_volatility_log_returns`.
+    #[serde(default)]
+    pub annualized_volatility: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -199,6 +231,7 @@ impl MarketContext {
             volume_analysis: VolumeAnalysis::Normal,
             support_resistance: None,
             market_sentiment: MarketSentiment::Neutral,
+            annualized_volatility: None,
         }
     }
 
@@ -243,6 +276,114 @@ impl MarketContext {
         };
     }
 
+    /// Computes annualized log-return volatility from a price series:
+    /// `r_t = ln(p_t / p_{t-1})`, standard deviation of the returns,
+    /// annualized by `sqrt(periods_per_year)` (e.g. 252 for daily bars).
+    /// Unlike `analyze_volatility`, this is scale-independent and
+    /// comparable across instruments. Sets `volatility_level` and
+    /// `annualized_volatility`, and returns the annualized figure.
+    pub fn analyze_volatility_log_returns(&mut self, prices: &[f64], periods_per_year: f64) -> f64 {
+        if prices.len() < 2 {
+            return 0.0;
+        }
+
+        let log_returns: Vec<f64> = prices.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+        let mean = log_returns.iter().sum::<f64>() / log_returns.len() as f64;
+        let variance = log_returns.iter()
+            .map(|r| (r - mean).powi(2))
+            .sum::<f64>() / log_returns.len() as f64;
+        let annualized = variance.sqrt() * periods_per_year.sqrt();
+
+        self.volatility_level = match annualized {
+            v if v < 0.10 => VolatilityLevel::VeryLow,
+            v if v < 0.20 => VolatilityLevel::Low,
+            v if v < 0.40 => VolatilityLevel::Normal,
+            v if v < 0.80 => VolatilityLevel::High,
+            _ => VolatilityLevel::VeryHigh,
+        };
+        self.annualized_volatility = Some(annualized);
+
+        annualized
+    }
+
+    /// Whether the last computed annualized volatility falls within
+    /// `[min_vol, max_vol]`. Returns `true` if volatility hasn't been
+    /// computed via `analyze_volatility_log_returns` yet, since there's
+    /// nothing to gate on.
+    pub fn is_tradeable(&self, min_vol: f64, max_vol: f64) -> bool {
+        self.annualized_volatility
+            .map(|v| v >= min_vol && v <= max_vol)
+            .unwrap_or(true)
+    }
+
+    /// Volume Spread Analysis on `bars` (`open, high, low, close, volume`).
+    /// Classifies the last bar's spread (`high - low`) as narrow
+    /// (< 0.7x average) or wide (> 1.5x average), its volume against an
+    /// EMA of volume as ultra-high (> 2x), above-average (> 1.5x), or low,
+    /// and its close position within the bar (`(close-low)/spread`).
+    /// Flags a wide-spread/ultra-high-volume/low-close bar as likely
+    /// distribution (no-demand), and a narrow-spread/above-average-volume
+    /// down bar that closes high as likely absorption (no-supply),
+    /// pushing each as a `KeyIndicator` and setting
+    /// `volume_analysis = Unusual` when either fires.
+    pub fn analyze_vsa(&mut self, bars: &[(f64, f64, f64, f64, f64)]) -> Vec<KeyIndicator> {
+        if bars.len() < 2 {
+            return Vec::new();
+        }
+
+        let avg_spread = bars.iter().map(|(_, h, l, _, _)| h - l).sum::<f64>() / bars.len() as f64;
+
+        let volumes: Vec<f64> = bars.iter().map(|(_, _, _, _, v)| *v).collect();
+        let ema_period = DEFAULT_VSA_EMA_PERIOD.min(volumes.len());
+        let alpha = 2.0 / (ema_period as f64 + 1.0);
+        let ema_volume = volumes[1..]
+            .iter()
+            .fold(volumes[0], |ema, v| alpha * v + (1.0 - alpha) * ema);
+
+        let (open, high, low, close, volume) = *bars.last().unwrap();
+        let spread = (high - low).max(f64::EPSILON);
+        let close_position = (close - low) / spread;
+
+        let is_narrow = spread < 0.7 * avg_spread;
+        let is_wide = spread > 1.5 * avg_spread;
+        let is_ultra_high_volume = volume > 2.0 * ema_volume;
+        let is_above_average_volume = volume > 1.5 * ema_volume;
+        let is_down_move = close < open;
+
+        let mut indicators = Vec::new();
+        let mut unusual = false;
+
+        if is_wide && is_ultra_high_volume && close_position <= 0.25 {
+            indicators.push(KeyIndicator::critical(
+                "VSA Distribution".to_string(),
+                volume,
+                format!(
+                    "Wide spread ({:.2}) on ultra-high volume with close in the lower quarter ({:.0}% of range) — possible distribution / no-demand",
+                    spread, close_position * 100.0
+                ),
+            ));
+            unusual = true;
+        }
+
+        if is_narrow && is_above_average_volume && is_down_move && close_position >= 0.75 {
+            indicators.push(KeyIndicator::important(
+                "VSA Absorption".to_string(),
+                volume,
+                format!(
+                    "Narrow spread ({:.2}) on above-average volume during a down move, closing in the upper quarter ({:.0}% of range) — possible absorption / no-supply",
+                    spread, close_position * 100.0
+                ),
+            ));
+            unusual = true;
+        }
+
+        if unusual {
+            self.volume_analysis = VolumeAnalysis::Unusual;
+        }
+
+        indicators
+    }
+
     pub fn analyze_volume(&mut self, current_volume: u64, average_volume: u64) {
         let volume_ratio = current_volume as f64 / average_volume.max(1) as f64;
 
@@ -255,6 +396,120 @@ impl MarketContext {
             _ => VolumeAnalysis::Unusual,
         };
     }
+
+    /// Computes a Donchian price channel (highest high / lowest low) over
+    /// the last `period` bars of `ohlcv` (each entry is `(high, low,
+    /// close)`), sets `support_resistance`, and returns `KeyIndicator`s
+    /// citing the concrete levels. Uses `sigma = 1.0` (no shrink).
+    pub fn analyze_support_resistance(&mut self, ohlcv: &[(f64, f64, f64)], period: usize) -> Vec<KeyIndicator> {
+        self.analyze_support_resistance_with_sigma(ohlcv, period, 1.0)
+    }
+
+    /// As `analyze_support_resistance`, but shrinks both bounds toward the
+    /// channel midpoint by `sigma` (clamped to `(0, 1]`); `sigma < 1.0`
+    /// tightens the channel, e.g. to avoid over-wide levels after a spike.
+    pub fn analyze_support_resistance_with_sigma(
+        &mut self,
+        ohlcv: &[(f64, f64, f64)],
+        period: usize,
+        sigma: f64,
+    ) -> Vec<KeyIndicator> {
+        if ohlcv.is_empty() {
+            return Vec::new();
+        }
+
+        let period = period.clamp(1, ohlcv.len());
+        let window = &ohlcv[ohlcv.len() - period..];
+        let sigma = sigma.clamp(f64::EPSILON, 1.0);
+
+        let highest_high = window.iter().map(|(h, _, _)| *h).fold(f64::MIN, f64::max);
+        let lowest_low = window.iter().map(|(_, l, _)| *l).fold(f64::MAX, f64::min);
+        let midpoint = (highest_high + lowest_low) / 2.0;
+
+        let resistance_level = midpoint + (highest_high - midpoint) * sigma;
+        let support_level = midpoint - (midpoint - lowest_low) * sigma;
+
+        let latest_close = window.last().map(|(_, _, c)| *c).unwrap_or(midpoint);
+        let band = (resistance_level - support_level).max(f64::EPSILON) * SUPPORT_RESISTANCE_TOLERANCE;
+
+        let current_position = if latest_close > resistance_level {
+            PricePosition::AboveResistance
+        } else if latest_close >= resistance_level - band {
+            PricePosition::NearResistance
+        } else if latest_close < support_level {
+            PricePosition::BelowSupport
+        } else if latest_close <= support_level + band {
+            PricePosition::NearSupport
+        } else {
+            PricePosition::BetweenLevels
+        };
+
+        self.support_resistance = Some(SupportResistance {
+            support_level,
+            resistance_level,
+            current_position: current_position.clone(),
+        });
+
+        vec![
+            KeyIndicator::important(
+                "Resistance Level".to_string(),
+                resistance_level,
+                format!("{}-bar Donchian high; price is {:?}", period, current_position),
+            ),
+            KeyIndicator::important(
+                "Support Level".to_string(),
+                support_level,
+                format!("{}-bar Donchian low; price is {:?}", period, current_position),
+            ),
+        ]
+    }
+
+    /// Computes Wilder's RSI over `price_changes` (seeding average gain/loss
+    /// as the mean over the first `period` values, then smoothing each
+    /// subsequent step), sets `market_sentiment` from the final RSI, and
+    /// returns a `Critical` `KeyIndicator` when RSI is in an overbought
+    /// (>70) or oversold (<30) zone.
+    pub fn analyze_sentiment(&mut self, price_changes: &[f64], period: usize) -> Vec<KeyIndicator> {
+        if price_changes.len() <= period || period == 0 {
+            return Vec::new();
+        }
+
+        let gain = |change: f64| change.max(0.0);
+        let loss = |change: f64| (-change).max(0.0);
+
+        let mut avg_gain: f64 = price_changes[..period].iter().map(|c| gain(*c)).sum::<f64>() / period as f64;
+        let mut avg_loss: f64 = price_changes[..period].iter().map(|c| loss(*c)).sum::<f64>() / period as f64;
+
+        for change in &price_changes[period..] {
+            avg_gain = (avg_gain * (period - 1) as f64 + gain(*change)) / period as f64;
+            avg_loss = (avg_loss * (period - 1) as f64 + loss(*change)) / period as f64;
+        }
+
+        let rsi = if avg_loss == 0.0 {
+            100.0
+        } else {
+            let rs = avg_gain / avg_loss;
+            100.0 - 100.0 / (1.0 + rs)
+        };
+
+        self.market_sentiment = match rsi {
+            r if r > 70.0 => MarketSentiment::VeryBullish,
+            r if r >= 55.0 => MarketSentiment::Bullish,
+            r if r >= 45.0 => MarketSentiment::Neutral,
+            r if r >= 30.0 => MarketSentiment::Bearish,
+            _ => MarketSentiment::VeryBearish,
+        };
+
+        if rsi > 70.0 || rsi < 30.0 {
+            vec![KeyIndicator::critical(
+                "RSI".to_string(),
+                rsi,
+                format!("RSI at {:.1} is in an over-{} zone", rsi, if rsi > 70.0 { "bought" } else { "sold" }),
+            )]
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 impl KeyIndicator {
@@ -293,6 +548,447 @@ impl Default for MarketContext {
     }
 }
 
+/// Builds an `Explanation` from an `ExplanationContext`. The default,
+/// `RuleBasedRenderer`, produces deterministic template-based text; an
+/// `LlmExplanationRenderer` can be swapped in for human-grade narrative
+/// output, falling back to the rule-based renderer if the LLM call fails
+/// or its response can't be parsed.
+#[async_trait]
+pub trait ExplanationRenderer: Send + Sync {
+    async fn render(&self, ctx: &ExplanationContext) -> Result<Explanation>;
+}
+
+/// Deterministic, template-based renderer. No external dependencies, so
+/// this is the default used when no `LlmService` is configured.
+#[derive(Debug, Clone, Default)]
+pub struct RuleBasedRenderer;
+
+impl RuleBasedRenderer {
+    pub fn new() -> Self {
+        RuleBasedRenderer
+    }
+
+    fn summary(ctx: &ExplanationContext) -> String {
+        format!(
+            "{:?} strategy signals {:?} on {} at ${:.2}",
+            ctx.strategy_type, ctx.action, ctx.market_data.symbol, ctx.market_data.price.to_f64()
+        )
+    }
+
+    fn detailed_reasoning(ctx: &ExplanationContext) -> String {
+        if ctx.strategy_data.is_empty() {
+            return format!(
+                "No strategy indicators were recorded alongside this {:?} signal.",
+                ctx.action
+            );
+        }
+
+        let mut keys: Vec<&String> = ctx.strategy_data.keys().collect();
+        keys.sort();
+        let parts: Vec<String> = keys
+            .into_iter()
+            .map(|k| format!("{} = {:.4}", k, ctx.strategy_data[k]))
+            .collect();
+        format!(
+            "Signal derived from: {}.",
+            parts.join(", ")
+        )
+    }
+
+    fn risk_factors(ctx: &ExplanationContext) -> Vec<String> {
+        match &ctx.historical_context {
+            Some(mc) => {
+                let mut factors = Vec::new();
+                if matches!(mc.volatility_level, VolatilityLevel::High | VolatilityLevel::VeryHigh) {
+                    factors.push(format!("Elevated volatility ({})", mc.volatility_level));
+                }
+                if matches!(mc.volume_analysis, VolumeAnalysis::Unusual) {
+                    factors.push("Unusual volume pattern".to_string());
+                }
+                if let Some(vol) = mc.annualized_volatility {
+                    let tradeable = mc.is_tradeable(DEFAULT_MIN_TRADEABLE_VOLATILITY, DEFAULT_MAX_TRADEABLE_VOLATILITY);
+                    factors.push(format!(
+                        "Annualized volatility {:.1}% ({})",
+                        vol * 100.0,
+                        if tradeable { "within tradeable band" } else { "outside tradeable band" }
+                    ));
+                }
+                factors
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn alternative_scenarios(ctx: &ExplanationContext) -> Vec<AlternativeScenario> {
+        match &ctx.historical_context {
+            Some(mc) if matches!(mc.trend_direction, TrendDirection::Sideways) => {
+                vec![AlternativeScenario::new(
+                    "Range-bound continuation".to_string(),
+                    0.4,
+                    "Price has shown no clear trend recently".to_string(),
+                    "Signal may not follow through".to_string(),
+                )]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl ExplanationRenderer for RuleBasedRenderer {
+    async fn render(&self, ctx: &ExplanationContext) -> Result<Explanation> {
+        let mut explanation = Explanation::new(Self::summary(ctx), Self::detailed_reasoning(ctx))
+            .with_risk_factors(Self::risk_factors(ctx));
+
+        if let Some(mc) = &ctx.historical_context {
+            explanation = explanation.with_market_context(format!(
+                "{} trend, {} volatility, {} sentiment",
+                mc.trend_direction, mc.volatility_level, mc.market_sentiment
+            ));
+        }
+
+        for scenario in Self::alternative_scenarios(ctx) {
+            explanation.add_alternative_scenario(scenario);
+        }
+
+        Ok(explanation)
+    }
+}
+
+/// A backend capable of completing a text prompt, e.g. a wrapper around the
+/// OpenAI API or a locally hosted model. Kept to a single method so it's
+/// trivial to implement against any provider.
+#[async_trait]
+pub trait LlmService: Send + Sync {
+    async fn complete(&self, prompt: String) -> Result<String>;
+}
+
+/// Renders explanations by asking an `LlmService` for a narrative, falling
+/// back to `RuleBasedRenderer` if the call fails or the response doesn't
+/// contain the expected sections.
+pub struct LlmExplanationRenderer<L: LlmService> {
+    llm: L,
+    fallback: RuleBasedRenderer,
+}
+
+impl<L: LlmService> LlmExplanationRenderer<L> {
+    pub fn new(llm: L) -> Self {
+        LlmExplanationRenderer {
+            llm,
+            fallback: RuleBasedRenderer::new(),
+        }
+    }
+
+    fn build_prompt(ctx: &ExplanationContext) -> String {
+        let mut prompt = String::new();
+        prompt.push_str("You are a trading assistant explaining why a strategy fired a signal.\n");
+        prompt.push_str(&format!("Strategy: {:?}\n", ctx.strategy_type));
+        prompt.push_str(&format!("Action: {:?}\n", ctx.action));
+        prompt.push_str(&format!(
+            "Market data: symbol={} price={:.2} volume={} change={:.2}%\n",
+            ctx.market_data.symbol, ctx.market_data.price.to_f64(), ctx.market_data.volume, ctx.market_data.change_percent
+        ));
+
+        let mut keys: Vec<&String> = ctx.strategy_data.keys().collect();
+        keys.sort();
+        if !keys.is_empty() {
+            let data = keys
+                .into_iter()
+                .map(|k| format!("{}={:.4}", k, ctx.strategy_data[k]))
+                .collect::<Vec<_>>()
+                .join(", ");
+            prompt.push_str(&format!("Strategy data: {}\n", data));
+        }
+
+        if let Some(mc) = &ctx.historical_context {
+            prompt.push_str(&format!(
+                "Historical context: trend={}, volatility={}, volume={:?}, sentiment={}\n",
+                mc.trend_direction, mc.volatility_level, mc.volume_analysis, mc.market_sentiment
+            ));
+        }
+
+        prompt.push_str(
+            "Respond with exactly these four labeled sections, each on its own line:\n\
+             SUMMARY: <one sentence>\n\
+             REASONING: <a short paragraph>\n\
+             RISKS: <comma-separated risk factors, or 'none'>\n\
+             SCENARIOS: <semicolon-separated 'name|probability|description|outcome' entries, or 'none'>\n",
+        );
+        prompt
+    }
+
+    fn parse_response(raw: &str) -> Option<ParsedExplanation> {
+        let mut summary = None;
+        let mut reasoning = None;
+        let mut risks = Vec::new();
+        let mut scenarios = Vec::new();
+
+        for line in raw.lines() {
+            let line = line.trim();
+            if let Some(rest) = line.strip_prefix("SUMMARY:") {
+                summary = Some(rest.trim().to_string());
+            } else if let Some(rest) = line.strip_prefix("REASONING:") {
+                reasoning = Some(rest.trim().to_string());
+            } else if let Some(rest) = line.strip_prefix("RISKS:") {
+                let rest = rest.trim();
+                if !rest.eq_ignore_ascii_case("none") && !rest.is_empty() {
+                    risks = rest.split(',').map(|s| s.trim().to_string()).collect();
+                }
+            } else if let Some(rest) = line.strip_prefix("SCENARIOS:") {
+                let rest = rest.trim();
+                if !rest.eq_ignore_ascii_case("none") && !rest.is_empty() {
+                    for entry in rest.split(';') {
+                        let fields: Vec<&str> = entry.split('|').map(|s| s.trim()).collect();
+                        if fields.len() == 4 {
+                            if let Ok(probability) = fields[1].parse::<f64>() {
+                                scenarios.push(AlternativeScenario::new(
+                                    fields[0].to_string(),
+                                    probability,
+                                    fields[2].to_string(),
+                                    fields[3].to_string(),
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        match (summary, reasoning) {
+            (Some(summary), Some(reasoning)) => Some(ParsedExplanation {
+                summary,
+                reasoning,
+                risks,
+                scenarios,
+            }),
+            _ => None,
+        }
+    }
+}
+
+struct ParsedExplanation {
+    summary: String,
+    reasoning: String,
+    risks: Vec<String>,
+    scenarios: Vec<AlternativeScenario>,
+}
+
+#[async_trait]
+impl<L: LlmService + Send + Sync> ExplanationRenderer for LlmExplanationRenderer<L> {
+    async fn render(&self, ctx: &ExplanationContext) -> Result<Explanation> {
+        let prompt = Self::build_prompt(ctx);
+
+        let raw = match self.llm.complete(prompt).await {
+            Ok(raw) => raw,
+            Err(_) => return self.fallback.render(ctx).await,
+        };
+
+        match Self::parse_response(&raw) {
+            Some(parsed) => {
+                let mut explanation = Explanation::new(parsed.summary, parsed.reasoning)
+                    .with_risk_factors(parsed.risks);
+                for scenario in parsed.scenarios {
+                    explanation.add_alternative_scenario(scenario);
+                }
+                Ok(explanation)
+            }
+            None => self.fallback.render(ctx).await,
+        }
+    }
+}
+
+/// Derives `AlternativeScenario`s for an `ExplanationContext` from the
+/// confluence of three signals computed over a bar series (`open, high,
+/// low, close, volume`): a Chandelier-exit style ATR trailing stop for
+/// trend direction, a Bollinger-inside-Keltner volatility squeeze for
+/// compression/expansion, and cumulative volume delta for buy/sell
+/// pressure. When the three agree, this favors a single high-probability
+/// "continuation" scenario; when they disagree, it splits probability
+/// across competing "reversal" and "range-bound" scenarios.
+pub struct ScenarioEngine {
+    bars: Vec<(f64, f64, f64, f64, f64)>,
+}
+
+impl ScenarioEngine {
+    pub fn new(bars: Vec<(f64, f64, f64, f64, f64)>) -> Self {
+        ScenarioEngine { bars }
+    }
+
+    fn true_ranges(&self) -> Vec<f64> {
+        self.bars
+            .windows(2)
+            .map(|w| {
+                let (_, high, low, _, _) = w[1];
+                let (_, _, _, prev_close, _) = w[0];
+                (high - low)
+                    .max((high - prev_close).abs())
+                    .max((low - prev_close).abs())
+            })
+            .collect()
+    }
+
+    /// Wilder-smoothed average true range: seeded with the mean of the first
+    /// `period` true ranges, then smoothed over every later one.
+    fn atr(&self, period: usize) -> Option<f64> {
+        let true_ranges = self.true_ranges();
+        if true_ranges.len() < period {
+            return None;
+        }
+
+        let mut atr = true_ranges[..period].iter().sum::<f64>() / period as f64;
+        for tr in &true_ranges[period..] {
+            atr = (atr * (period - 1) as f64 + tr) / period as f64;
+        }
+        Some(atr)
+    }
+
+    fn closes(&self) -> Vec<f64> {
+        self.bars.iter().map(|(_, _, _, c, _)| *c).collect()
+    }
+
+    /// `(lower, mid, upper)` Bollinger Bands over the last `period` closes.
+    fn bollinger_bands(&self, period: usize, k: f64) -> Option<(f64, f64, f64)> {
+        let closes = self.closes();
+        if closes.len() < period {
+            return None;
+        }
+
+        let window = &closes[closes.len() - period..];
+        let mid = window.iter().sum::<f64>() / period as f64;
+        let variance = window.iter().map(|c| (c - mid).powi(2)).sum::<f64>() / period as f64;
+        let std_dev = variance.sqrt();
+
+        Some((mid - k * std_dev, mid, mid + k * std_dev))
+    }
+
+    /// `(lower, mid, upper)` Keltner Channels: an EMA midline offset by
+    /// `atr_multiplier` times the ATR.
+    fn keltner_channels(&self, period: usize, atr_multiplier: f64) -> Option<(f64, f64, f64)> {
+        let closes = self.closes();
+        if closes.len() < period {
+            return None;
+        }
+
+        let atr = self.atr(period)?;
+        let window = &closes[closes.len() - period..];
+        let alpha = 2.0 / (period as f64 + 1.0);
+        let mid = window[1..]
+            .iter()
+            .fold(window[0], |ema, c| alpha * c + (1.0 - alpha) * ema);
+
+        Some((mid - atr_multiplier * atr, mid, mid + atr_multiplier * atr))
+    }
+
+    /// `true` when the latest close sits above a Chandelier-exit style ATR
+    /// trailing stop anchored to the recent highest high.
+    fn trend_bullish(&self, period: usize) -> Option<bool> {
+        let atr = self.atr(period)?;
+        if self.bars.len() < period {
+            return None;
+        }
+
+        let window = &self.bars[self.bars.len() - period..];
+        let highest_high = window.iter().map(|(_, h, _, _, _)| *h).fold(f64::MIN, f64::max);
+        let trailing_stop = highest_high - DEFAULT_CHANDELIER_ATR_MULTIPLIER * atr;
+        let latest_close = self.bars.last().map(|(_, _, _, c, _)| *c)?;
+
+        Some(latest_close > trailing_stop)
+    }
+
+    /// Net buy volume minus sell volume, signed by each bar's direction.
+    fn volume_delta(&self) -> f64 {
+        self.bars
+            .iter()
+            .map(|(open, _, _, close, volume)| {
+                if close > open {
+                    *volume
+                } else if close < open {
+                    -*volume
+                } else {
+                    0.0
+                }
+            })
+            .sum()
+    }
+
+    pub fn build_scenarios(&self, ctx: &ExplanationContext) -> Vec<AlternativeScenario> {
+        let trend_bullish = match self.trend_bullish(DEFAULT_ATR_PERIOD) {
+            Some(v) => v,
+            None => return Vec::new(),
+        };
+        let (bb_lower, _, bb_upper) = match self.bollinger_bands(DEFAULT_BOLLINGER_PERIOD, DEFAULT_BOLLINGER_K) {
+            Some(v) => v,
+            None => return Vec::new(),
+        };
+        let (kc_lower, _, kc_upper) = match self.keltner_channels(DEFAULT_BOLLINGER_PERIOD, DEFAULT_KELTNER_ATR_MULTIPLIER) {
+            Some(v) => v,
+            None => return Vec::new(),
+        };
+
+        let squeeze_building = bb_upper < kc_upper && bb_lower > kc_lower;
+        let volume_bullish = self.volume_delta() > 0.0;
+        let squeeze_range = kc_upper - kc_lower;
+        let price = ctx.market_data.price.to_f64();
+
+        let aligned = trend_bullish == volume_bullish && !squeeze_building;
+
+        if aligned {
+            let direction = if trend_bullish { "upward" } else { "downward" };
+            let target = if trend_bullish { price + squeeze_range } else { price - squeeze_range };
+            vec![AlternativeScenario::new(
+                "Continuation".to_string(),
+                0.75,
+                format!(
+                    "Trend, volatility expansion, and volume delta all confirm a {} move",
+                    direction
+                ),
+                format!("Price extends toward {:.2} (measured move from the recent range)", target),
+            )]
+        } else if squeeze_building {
+            vec![
+                AlternativeScenario::new(
+                    "Range-bound".to_string(),
+                    0.6,
+                    "Bollinger Bands remain inside the Keltner Channel, so volatility is still compressing".to_string(),
+                    format!("Price chops between roughly {:.2} and {:.2} until the squeeze releases", kc_lower, kc_upper),
+                ),
+                AlternativeScenario::new(
+                    "Reversal".to_string(),
+                    0.4,
+                    "Volatility is still compressing, so the squeeze could release against the current trend".to_string(),
+                    format!(
+                        "A release against the current {} trend targets {:.2}",
+                        if trend_bullish { "up" } else { "down" },
+                        if trend_bullish { price - squeeze_range } else { price + squeeze_range }
+                    ),
+                ),
+            ]
+        } else {
+            vec![
+                AlternativeScenario::new(
+                    "Reversal".to_string(),
+                    0.5,
+                    format!(
+                        "Volume delta is {} while price trend is {}, a divergence that often precedes a turn",
+                        if volume_bullish { "bullish" } else { "bearish" },
+                        if trend_bullish { "bullish" } else { "bearish" }
+                    ),
+                    format!(
+                        "Price reverses toward {:.2}",
+                        if trend_bullish { price - squeeze_range } else { price + squeeze_range }
+                    ),
+                ),
+                AlternativeScenario::new(
+                    "Range-bound".to_string(),
+                    0.5,
+                    "Conflicting signals leave no clear directional edge".to_string(),
+                    format!("Price oscillates near {:.2} pending confirmation", price),
+                ),
+            ]
+        }
+    }
+}
+
 // Display implementations
 impl std::fmt::Display for TrendDirection {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {